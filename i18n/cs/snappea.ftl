@@ -17,6 +17,8 @@ move-toolbar = Přesunout panel nástrojů (Ctrl+hjkl)
 screenshot-video = Snímek obrazovky / Video
 select-region = Vybrat oblast (R)
 select-screen = Vybrat obrazovku (S)
+auto-trim = Automaticky oříznout okraje (T, pravým tlačítkem znovu oříznout)
+crop-mode = Znovu oříznout výběr (C)
 
 # Context-sensitive copy/save tooltips
 copy-selected-region = Kopírovat vybranou oblast (Enter)
@@ -39,12 +41,52 @@ minimize-to-tray = Minimalizovat do oznamovací oblasti
 # OCR tooltips
 copy-ocr-text = Kopírovat OCR text (O)
 recognize-text = Rozpoznat text (O)
-install-tesseract = Nainstalujte tesseract pro povolení OCR
+install-tesseract = Pro povolení OCR nainstalujte tesseract nebo v nastavení zvolte vestavěný engine
+ocr-engine = OCR engine
+ocr-engine-tesseract = Tesseract
+ocr-engine-builtin = Vestavěný
+ocr-engine-builtin-hint = Pouze latinka. Potřebuje modely (asi 12 MB), přibalené nebo stažené.
+ocr-deskew = Narovnat zkosený text
+ocr-model-download = Stáhnout chybějící modely
+ocr-copy-as = Kopírovat text jako
+ocr-layout-paragraphs = Text s odstavci
+ocr-layout-code = Kód s odsazením
+ocr-layout-tsv = Tabulka (oddělená tabulátory)
+ocr-layout-markdown = Tabulka (Markdown)
+ocr-languages = Jazyky OCR
+ocr-no-languages = Nenalezeny žádné jazykové balíčky tesseractu
+ocr-capture-languages = Jazyky pro tento snímek
+ocr-languages-default = Použít výchozí jazyky
 
 # QR tooltips
 copy-qr-code = Kopírovat QR kód (Q)
 scan-qr-code = Skenovat QR kód (Q)
 
+# QR code actions
+qr-open = Otevřít
+qr-email = E-mail: { $address }
+qr-email-open = Napsat e-mail
+qr-phone = Telefon: { $number }
+qr-phone-open = Zavolat
+qr-location = Poloha: { $latitude }, { $longitude }
+qr-location-open = Zobrazit na mapě
+qr-wifi = Síť Wi-Fi: { $ssid }
+qr-wifi-password = Heslo: { $password }
+qr-wifi-no-password = Bez hesla
+qr-copy-network = Kopírovat síť
+qr-copy-password = Kopírovat heslo
+qr-otp = Tajný klíč 2FA pro { $account }
+qr-otp-warning = Kdokoli s tímto kódem může generovat vaše přihlašovací kódy.
+qr-copy-secret = Kopírovat klíč
+qr-contact-name = Jméno: { $value }
+qr-contact-organization = Organizace: { $value }
+qr-contact-address = Adresa: { $value }
+qr-contact-website = Web: { $value }
+qr-contact-note = Poznámka: { $value }
+qr-codes-found = Nalezené kódy: { $count }. Kliknutím nebo stiskem čísla jeden zkopírujete.
+qr-copy-all = Kopírovat vše
+qr-copy-all-json = Kopírovat jako JSON
+
 # Cancel button
 cancel-escape = Ukončit (Escape)
 
@@ -57,21 +99,64 @@ color-orange = Oranžová
 color-purple = Fialová
 color-white = Bílá
 color-black = Černá
+recent-colors = Nedávné
+custom-color = Vlastní barva (hex, potvrďte Enterem)
+color-hue = H
+color-saturation = S
+color-value = V
+color-palette = Název palety
+color-palette-name = Paleta { $number }
+add-to-palette = Přidat aktuální barvu do této palety
+new-palette = Nová paleta
+palette-remove-hint = Pravým kliknutím barvu odeberete
 
 # Shape tools
 arrow = Šipka
 oval-circle = Ovál nebo kruh
 rectangle-square = Obdélník nebo čtverec
+spotlight = Reflektor
 shape-cycle-hint = Shift+A pro přepínání tvarů, A pro zapnutí/vypnutí
 color = Barva
 shadow = Stín
+shape-width = Šířka: { $width } px
+shape-auto-contrast = Kontrastní stín
+shape-fill = Výplň
+annotation-presets = Předvolby
+annotation-preset = Název předvolby
+preset-name = Předvolba { $number }
+preset-red-arrow = Červená šipka
+preset-red-thick-arrow = Silná červená šipka
+preset-yellow-highlighter = Žlutý zvýrazňovač
+preset-blurred-box = Rozmazaný rámeček
+preset-apply = Použít předvolbu
+preset-remove = Odebrat předvolbu
+preset-add = Uložit aktuální styl
+preset-hint = Použijte klávesami 1-9
+spotlight-dim = Ztmavení okolí: { $percent } %
+spotlight-ellipse = Elipsa
+spotlight-blur = Rozmazat okolí
 clear-annotations = Vymazat kreslení
 
 # Redact tools
 redact-blackout = Skrýt (začernit)
 pixelate-blur = Pixelizovat (rozmazat)
+gaussian-blur = Rozmazat (Gaussovsky)
 redact-cycle-hint = Shift+D pro přepínání nástrojů, D pro zapnutí/vypnutí
 pixelation-size = Pixelizace: { $size } px
+secure-pixelation = Bezpečná pixelizace (přidá šum)
+blur-radius = Poloměr rozmazání: { $radius } px
+redact-sensitive = Skrýt citlivé údaje
+find-text = Najít text…
+search-regex = Regulární výraz
+search-invalid = Neplatný vzor
+search-matches = { $count ->
+    [one] 1 shoda
+    [few] { $count } shody
+   *[other] { $count } shod
+}
+search-redact-all = Skrýt všechny shody
+search-pixelate-all = Pixelizovat všechny shody
+search-highlight-all = Zvýraznit všechny shody
 clear-redactions = Vymazat skrytí
 
 # Pencil/drawing settings
@@ -83,10 +168,12 @@ clear-drawings = Vymazat kresby
 draw-arrow = Nakreslit šipku (A, pravé tlačítko pro nastavení)
 draw-circle = Nakreslit kruh (A, Ctrl pro dokonalý tvar, pravé tlačítko pro nastavení)
 draw-rectangle = Nakreslit obdélník (A, Ctrl pro čtverec, pravé tlačítko pro nastavení)
+draw-spotlight = Zvýraznit reflektorem (A, pravé tlačítko pro nastavení)
 
 # Redact tool tooltips
 redact-tool = Skrýt (D, pravé tlačítko pro nastavení)
 pixelate-tool = Pixelizovat (D, pravé tlačítko pro nastavení)
+blur-tool = Rozmazat (D, pravé tlačítko pro nastavení)
 
 # Settings drawer tabs
 general = Obecné
@@ -95,6 +182,15 @@ video = Video
 
 # Settings drawer - General
 magnifier = Lupa
+magnifier-callout = Výřez vedle zdroje
+color-picker = Kapátko (I) — kopíruje { $format }, pravým tlačítkem změníte formát
+measure-tool = Měření (M) — pravým tlačítkem přepnete mezi úsečkou a obdélníkem
+stamp-tool = Razítko (P) — pravým tlačítkem vyberete razítko
+stamp-size = Velikost: { $size } px
+stamp-folder-hint = Přidejte soubory PNG, GIF nebo SVG do složky s razítky
+stamp-open-folder = Složka
+stamp-reload = Načíst znovu
+callout-border = Okraj: { $width } px
 set-as-default-portal = Nastavit jako výchozí
 set-as-default-portal-description = Použít SnapPea jako výchozí portál pro snímky obrazovky ve vašem systému
 toolbar-opacity = Průhlednost panelu nástrojů (neaktivního): { $percent } %
@@ -109,6 +205,10 @@ documents = Dokumenty
 custom = Vlastní
 browse = Procházet...
 copy-on-save = Kopírovat při uložení
+color-picker-sets-shape-color = Použít vybranou barvu pro anotace
+measure-in-export = Ponechat měření v uloženém obrázku
+beautify-frame = Ozdobný rámeček
+beautify-preset = Styl
 
 # Settings drawer - Video save location
 video-save-location = Uložit videa do:
@@ -139,6 +239,9 @@ no-text-detected = Nebyl rozpoznán žádný text
 invalid-ocr-scale = Neplatné měřítko OCR mapování
 tesseract-image-error = Nepodařilo se vytvořit obrázek pro tesseract: { $error }
 tesseract-ocr-error = OCR Tesseract selhalo: { $error }
+ocr-model-error = Nepodařilo se načíst modely OCR: { $error }
+ocr-models-missing = Modely OCR nejsou nainstalovány. Povolte jejich stažení v nastavení.
+ocr-engine-error = Vestavěné OCR selhalo: { $error }
 
 # Screen selection hints
 select-screen-hint = Klikněte nebo stiskněte Enter pro výběr této obrazovky
@@ -17,6 +17,8 @@ move-toolbar = Move Toolbar (Ctrl+hjkl)
 screenshot-video = Screenshot / Video
 select-region = Select Region (R)
 select-screen = Select Screen (S)
+auto-trim = Auto-trim Margins (T, right-click to re-crop)
+crop-mode = Re-crop Selection (C)
 
 # Context-sensitive copy/save tooltips
 copy-selected-region = Copy Selected Region (Enter)
@@ -39,12 +41,52 @@ minimize-to-tray = Minimize to System Tray
 # OCR tooltips
 copy-ocr-text = Copy OCR Text (O)
 recognize-text = Recognize Text (O)
-install-tesseract = Install tesseract to enable OCR
+install-tesseract = Install tesseract or pick the built-in engine in settings to enable OCR
+ocr-engine = OCR engine
+ocr-engine-tesseract = Tesseract
+ocr-engine-builtin = Built-in
+ocr-engine-builtin-hint = Latin script only. Needs its models (about 12 MB), bundled or downloaded.
+ocr-deskew = Straighten skewed text
+ocr-model-download = Download missing models
+ocr-copy-as = Copy text as
+ocr-layout-paragraphs = Text with paragraphs
+ocr-layout-code = Code with indentation
+ocr-layout-tsv = Table (tab-separated)
+ocr-layout-markdown = Table (Markdown)
+ocr-languages = OCR languages
+ocr-no-languages = No tesseract language packs found
+ocr-capture-languages = Languages for this capture
+ocr-languages-default = Use default languages
 
 # QR tooltips
 copy-qr-code = Copy QR Code (Q)
 scan-qr-code = Scan QR Code (Q)
 
+# QR code actions
+qr-open = Open
+qr-email = Email: { $address }
+qr-email-open = Write email
+qr-phone = Phone: { $number }
+qr-phone-open = Call
+qr-location = Location: { $latitude }, { $longitude }
+qr-location-open = Show on map
+qr-wifi = Wi-Fi network: { $ssid }
+qr-wifi-password = Password: { $password }
+qr-wifi-no-password = No password
+qr-copy-network = Copy network
+qr-copy-password = Copy password
+qr-otp = 2FA secret for { $account }
+qr-otp-warning = Anyone with this code can generate your login codes.
+qr-copy-secret = Copy secret
+qr-contact-name = Name: { $value }
+qr-contact-organization = Organization: { $value }
+qr-contact-address = Address: { $value }
+qr-contact-website = Website: { $value }
+qr-contact-note = Note: { $value }
+qr-codes-found = { $count } codes found. Click one or press its number to copy it.
+qr-copy-all = Copy all
+qr-copy-all-json = Copy as JSON
+
 # Cancel button
 cancel-escape = Cancel (Escape)
 
@@ -57,21 +99,63 @@ color-orange = Orange
 color-purple = Purple
 color-white = White
 color-black = Black
+recent-colors = Recent
+custom-color = Custom color (hex, Enter to apply)
+color-hue = H
+color-saturation = S
+color-value = V
+color-palette = Palette name
+color-palette-name = Palette { $number }
+add-to-palette = Add current color to this palette
+new-palette = New palette
+palette-remove-hint = Right-click a color to remove it
 
 # Shape tools
 arrow = Arrow
 oval-circle = Oval or Circle
 rectangle-square = Rectangle or Square
+spotlight = Spotlight
 shape-cycle-hint = Shift+A to cycle shapes, A to toggle
 color = Color
 shadow = Shadow
+shape-width = Width: { $width } px
+shape-auto-contrast = Auto-contrast shadow
+shape-fill = Fill
+annotation-presets = Presets
+annotation-preset = Preset name
+preset-name = Preset { $number }
+preset-red-arrow = Red arrow
+preset-red-thick-arrow = Red thick arrow
+preset-yellow-highlighter = Yellow highlighter
+preset-blurred-box = Blurred box
+preset-apply = Apply preset
+preset-remove = Remove preset
+preset-add = Save current style
+preset-hint = Press 1-9 to apply
+spotlight-dim = Dim outside: { $percent }%
+spotlight-ellipse = Ellipse
+spotlight-blur = Blur outside
 clear-annotations = Clear Annotations
 
 # Redact tools
 redact-blackout = Redact (black out)
 pixelate-blur = Pixelate (blur out)
+gaussian-blur = Blur (Gaussian)
 redact-cycle-hint = Shift+D to cycle tools, D to toggle
 pixelation-size = Pixelation: { $size }px
+secure-pixelation = Secure pixelation (adds noise)
+blur-radius = Blur radius: { $radius }px
+redact-sensitive = Redact Sensitive Data
+find-text = Find text…
+search-regex = Regular expression
+search-invalid = Invalid pattern
+search-matches = { $count ->
+    [one] 1 match
+   *[other] { $count } matches
+}
+search-redact-all = Redact all matches
+search-pixelate-all = Pixelate all matches
+search-highlight-all = Highlight all matches
 clear-redactions = Clear Redactions
 
 # Pencil/drawing settings
@@ -83,14 +167,25 @@ clear-drawings = Clear Drawings
 draw-arrow = Draw Arrow (A, right-click for settings)
 draw-circle = Draw Circle (A, Ctrl for perfect, right-click for settings)
 draw-rectangle = Draw Rectangle (A, Ctrl for square, right-click for settings)
+draw-spotlight = Draw Spotlight (A, right-click for settings)
 
 # Redact tool tooltips
 redact-tool = Redact (D, right-click for settings)
 pixelate-tool = Pixelate (D, right-click for settings)
+blur-tool = Blur (D, right-click for settings)
 
 # Magnifier tool
 magnifier-tool = Magnifier (right-click for settings)
+color-picker = Color Picker (I) — copies { $format }, right-click to change format
+measure-tool = Measure (M) — right-click to switch between line and box
+stamp-tool = Stamp (P) — right-click to choose a stamp
+stamp-size = Size: { $size } px
+stamp-folder-hint = Add PNG, GIF or SVG files to the stamp folder
+stamp-open-folder = Folder
+stamp-reload = Reload
 magnification = Magnification: { $value }x
+magnifier-callout = Callout beside source
+callout-border = Border: { $width }px
 
 # Delayed screenshot
 delayed-screenshot = Screenshot in { $secs }s (right-click to change delay)
@@ -117,6 +212,10 @@ documents = Documents
 custom = Custom
 browse = Browse...
 copy-on-save = Copy on save
+color-picker-sets-shape-color = Use picked color for annotations
+measure-in-export = Keep measurements in saved image
+beautify-frame = Beautify frame
+beautify-preset = Style
 
 # Settings drawer - Video save location
 video-save-location = Save videos to:
@@ -147,6 +246,9 @@ no-text-detected = No text detected
 invalid-ocr-scale = Invalid OCR mapping scale
 tesseract-image-error = Failed to create tesseract image: { $error }
 tesseract-ocr-error = Tesseract OCR failed: { $error }
+ocr-model-error = Failed to load OCR models: { $error }
+ocr-models-missing = OCR models are not installed. Allow downloading them in the settings.
+ocr-engine-error = Built-in OCR failed: { $error }
 
 # Screen selection hints
 select-screen-hint = Click or press Enter to select this screen
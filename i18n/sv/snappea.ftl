@@ -17,6 +17,8 @@ move-toolbar = Flytta verktygsfältet (Ctrl+hjkl)
 screenshot-video = Skärmdump / video
 select-region = Välj region (R)
 select-screen = Välj Skärm (S)
+auto-trim = Beskär marginaler automatiskt (T, högerklicka för att beskära om)
+crop-mode = Beskär markeringen igen (C)
 
 # Kontextkänsliga verktygstips för kopiering/spara
 copy-selected-region = Kopiera vald region (Enter)
@@ -39,12 +41,52 @@ minimize-to-tray = Minimera till systemfältet
 # OCR verktygstips
 copy-ocr-text = Kopiera OCR text (O)
 recognize-text = Tolka text (O)
-install-tesseract = Installera tesseract för att aktivera OCR
+install-tesseract = Installera tesseract eller välj den inbyggda motorn i inställningarna för att aktivera OCR
+ocr-engine = OCR-motor
+ocr-engine-tesseract = Tesseract
+ocr-engine-builtin = Inbyggd
+ocr-engine-builtin-hint = Endast latinska tecken. Behöver sina modeller (cirka 12 MB), medföljande eller nedladdade.
+ocr-deskew = Räta upp sned text
+ocr-model-download = Ladda ner saknade modeller
+ocr-copy-as = Kopiera text som
+ocr-layout-paragraphs = Text med stycken
+ocr-layout-code = Kod med indrag
+ocr-layout-tsv = Tabell (tabbseparerad)
+ocr-layout-markdown = Tabell (Markdown)
+ocr-languages = OCR-språk
+ocr-no-languages = Inga språkpaket för tesseract hittades
+ocr-capture-languages = Språk för den här skärmbilden
+ocr-languages-default = Använd standardspråk
 
 # QR verktygstips
 copy-qr-code = Kopiera QR-kod (Q)
 scan-qr-code = Skanna QR-kod  (Q)
 
+# QR code actions
+qr-open = Öppna
+qr-email = E-post: { $address }
+qr-email-open = Skriv e-post
+qr-phone = Telefon: { $number }
+qr-phone-open = Ring
+qr-location = Plats: { $latitude }, { $longitude }
+qr-location-open = Visa på karta
+qr-wifi = Wi-Fi-nätverk: { $ssid }
+qr-wifi-password = Lösenord: { $password }
+qr-wifi-no-password = Inget lösenord
+qr-copy-network = Kopiera nätverk
+qr-copy-password = Kopiera lösenord
+qr-otp = 2FA-hemlighet för { $account }
+qr-otp-warning = Alla med den här koden kan skapa dina inloggningskoder.
+qr-copy-secret = Kopiera hemlighet
+qr-contact-name = Namn: { $value }
+qr-contact-organization = Organisation: { $value }
+qr-contact-address = Adress: { $value }
+qr-contact-website = Webbplats: { $value }
+qr-contact-note = Anteckning: { $value }
+qr-codes-found = { $count } koder hittades. Klicka på en eller tryck på dess nummer för att kopiera den.
+qr-copy-all = Kopiera alla
+qr-copy-all-json = Kopiera som JSON
+
 # Avbryt knapp
 cancel-escape = Avbryt (Escape)
 
@@ -57,21 +99,63 @@ color-orange = Orange
 color-purple = Lila
 color-white = Vit
 color-black = Svart
+recent-colors = Senaste
+custom-color = Egen färg (hex, Enter för att använda)
+color-hue = H
+color-saturation = S
+color-value = V
+color-palette = Palettnamn
+color-palette-name = Palett { $number }
+add-to-palette = Lägg till aktuell färg i paletten
+new-palette = Ny palett
+palette-remove-hint = Högerklicka på en färg för att ta bort den
 
 # Formverktyg
 arrow = Pil
 oval-circle = Oval eller cirkel
 rectangle-square = Rektangel eller fyrkant
+spotlight = Strålkastare
 shape-cycle-hint = Skift+A för att växla mellan former, A för att växla
 color = Färg
 shadow = Skugga
+shape-width = Bredd: { $width } px
+shape-auto-contrast = Kontrastanpassad skugga
+shape-fill = Fyllning
+annotation-presets = Förval
+annotation-preset = Namn på förval
+preset-name = Förval { $number }
+preset-red-arrow = Röd pil
+preset-red-thick-arrow = Tjock röd pil
+preset-yellow-highlighter = Gul överstrykningspenna
+preset-blurred-box = Suddig ruta
+preset-apply = Använd förval
+preset-remove = Ta bort förval
+preset-add = Spara aktuell stil
+preset-hint = Tryck 1-9 för att använda
+spotlight-dim = Dämpa utanför: { $percent }%
+spotlight-ellipse = Ellips
+spotlight-blur = Gör utsidan suddig
 clear-annotations = Rensa annoteringar
 
 # Redigeringsverktyg
 redact-blackout = Skärma bort (svärta över)
 pixelate-blur = Pixelera (oskärpa)
+gaussian-blur = Oskärpa (Gaussisk)
 redact-cycle-hint = Skift+D för att växla mellan verktyg, D för att växla
 pixelation-size = Pixelering: { $size }px
+secure-pixelation = Säker pixelering (lägger till brus)
+blur-radius = Oskärperadie: { $radius }px
+redact-sensitive = Dölj känsliga uppgifter
+find-text = Sök text…
+search-regex = Reguljärt uttryck
+search-invalid = Ogiltigt mönster
+search-matches = { $count ->
+    [one] 1 träff
+   *[other] { $count } träffar
+}
+search-redact-all = Dölj alla träffar
+search-pixelate-all = Pixelera alla träffar
+search-highlight-all = Markera alla träffar
 clear-redactions = Rensa borttagningar
 
 # Blyertspenna/teckning inställningar
@@ -83,10 +167,12 @@ clear-drawings = Rensa ritningar
 draw-arrow = Rita pil (A, högerklicka för inställningar)
 draw-circle = Rita cirkel (A, Ctrl för perfekt, högerklicka för inställningar)
 draw-rectangle = Rita rektangel (A, Ctrl för kvadrat, högerklicka för inställningar)
+draw-spotlight = Rita strålkastare (A, högerklicka för inställningar)
 
 # Verktygstips för redigeringsverktyg
 redact-tool = Redigera (D, högerklicka för inställningar)
 pixelate-tool = Pixelera (D, högerklicka för inställningar)
+blur-tool = Oskärpa (D, högerklicka för inställningar)
 
 # Inställningslådans flikar
 general = Allmänt
@@ -95,6 +181,15 @@ video = Video
 
 # Inställningslåda - Allmänt
 magnifier = Förstoringsglas
+magnifier-callout = Utsnitt bredvid källan
+color-picker = Färgväljare (I) — kopierar { $format }, högerklicka för att byta format
+measure-tool = Mät (M) — högerklicka för att växla mellan linje och ruta
+stamp-tool = Stämpel (P) — högerklicka för att välja stämpel
+stamp-size = Storlek: { $size } px
+stamp-folder-hint = Lägg till PNG-, GIF- eller SVG-filer i stämpelmappen
+stamp-open-folder = Mapp
+stamp-reload = Läs in igen
+callout-border = Kant: { $width } px
 set-as-default-portal = Ställ in som standard
 set-as-default-portal-description = Använd SnapPea som standardportal för skärmdumpar för ditt system
 toolbar-opacity = Verktygsfältets opacitet (inaktiv): { $percent }%
@@ -108,6 +203,10 @@ documents = Dokument
 custom = Anpassad
 browse = Bläddra...
 copy-on-save = Kopiera vid sparning
+color-picker-sets-shape-color = Använd vald färg för anteckningar
+measure-in-export = Behåll mätningar i sparad bild
+beautify-frame = Snygg ram
+beautify-preset = Stil
 
 # Inställningslåda - Plats för att spara video
 video-save-location = Spara videor till:
@@ -138,6 +237,9 @@ no-text-detected = Ingen text upptäckt
 invalid-ocr-scale = Ogiltig OCR-mappningsskala
 tesseract-image-error = Misslyckades att skapa tesseract bild: { $error }
 tesseract-ocr-error = Tesseract OCR misslyckades: { $error }
+ocr-model-error = Kunde inte läsa in OCR-modeller: { $error }
+ocr-models-missing = OCR-modellerna är inte installerade. Tillåt nedladdning i inställningarna.
+ocr-engine-error = Inbyggd OCR misslyckades: { $error }
 
 # Kommandoradsanvändning
 cli-usage = Användning: snappea --record --output FILE --output-name NAME --region X,Y,W,H --logical-size W,H --encoder ENC [--container FMT] [--framerate FPS] [--toplevel-index IDX]
@@ -0,0 +1,210 @@
+//! Constrained drawing and alignment guides for arrows, rectangles and ellipses
+//!
+//! While Shift is held, arrows snap to 15° steps and rectangles and ellipses
+//! are locked to squares and circles. Otherwise the points of a new shape snap
+//! to nearby guides: the edges and center of the selection and the edges of
+//! existing annotations.
+
+use crate::domain::{Annotation, Choice};
+
+/// Arrow angles snap to multiples of this many degrees while constrained
+pub const ANGLE_STEP: f32 = 15.0;
+/// Distance in logical pixels within which a point snaps to a guide
+pub const GUIDE_SNAP_DISTANCE: f32 = 6.0;
+
+/// The selected rectangle in global logical coordinates, if any
+pub fn selection_bounds(choice: &Choice) -> Option<(f32, f32, f32, f32)> {
+    match choice {
+        Choice::Rectangle(r, _) if r.width() > 0 && r.height() > 0 => {
+            Some((r.left as f32, r.top as f32, r.right as f32, r.bottom as f32))
+        }
+        _ => None,
+    }
+}
+
+/// Rotate `end` around `start` to the nearest multiple of `ANGLE_STEP`, keeping the length
+pub fn snap_angle(start: (f32, f32), end: (f32, f32)) -> (f32, f32) {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let length = dx.hypot(dy);
+    if length == 0.0 {
+        return end;
+    }
+    let step = ANGLE_STEP.to_radians();
+    let angle = (dy.atan2(dx) / step).round() * step;
+    (
+        start.0 + length * angle.cos(),
+        start.1 + length * angle.sin(),
+    )
+}
+
+/// Move `end` so the box from `start` is square (the shorter side wins)
+pub fn lock_square(start: (f32, f32), end: (f32, f32)) -> (f32, f32) {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let side = dx.abs().min(dy.abs());
+    (start.0 + side.copysign(dx), start.1 + side.copysign(dy))
+}
+
+/// A point after snapping, with the guides it snapped to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Snap {
+    pub point: (f32, f32),
+    /// Vertical guide (x coordinate) the point lines up with
+    pub guide_x: Option<f32>,
+    /// Horizontal guide (y coordinate) the point lines up with
+    pub guide_y: Option<f32>,
+}
+
+impl Snap {
+    fn free(point: (f32, f32)) -> Self {
+        Self {
+            point,
+            guide_x: None,
+            guide_y: None,
+        }
+    }
+}
+
+/// Vertical and horizontal guide lines in global logical coordinates
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Guides {
+    pub xs: Vec<f32>,
+    pub ys: Vec<f32>,
+}
+
+impl Guides {
+    /// Guides from the selection edges and center and the bounds of arrows,
+    /// rectangles, ellipses and stamps
+    pub fn collect(selection: Option<(f32, f32, f32, f32)>, annotations: &[Annotation]) -> Self {
+        let mut guides = Self::default();
+        if let Some((x1, y1, x2, y2)) = selection {
+            guides.xs.extend([x1, (x1 + x2) / 2.0, x2]);
+            guides.ys.extend([y1, (y1 + y2) / 2.0, y2]);
+        }
+        for annotation in annotations {
+            let (x1, y1, x2, y2) = match annotation {
+                Annotation::Arrow(a) => (a.start_x, a.start_y, a.end_x, a.end_y),
+                Annotation::Circle(c) => (c.start_x, c.start_y, c.end_x, c.end_y),
+                Annotation::Rectangle(r) => (r.start_x, r.start_y, r.end_x, r.end_y),
+                Annotation::Stamp(s) => s.rect(),
+                _ => continue,
+            };
+            guides.xs.extend([x1, x2]);
+            guides.ys.extend([y1, y2]);
+        }
+        guides
+    }
+
+    /// Snap each coordinate of `point` to the nearest guide within `GUIDE_SNAP_DISTANCE`
+    pub fn snap(&self, point: (f32, f32)) -> Snap {
+        let nearest = |lines: &[f32], v: f32| {
+            lines
+                .iter()
+                .copied()
+                .filter(|line| (line - v).abs() <= GUIDE_SNAP_DISTANCE)
+                .min_by(|a, b| (a - v).abs().total_cmp(&(b - v).abs()))
+        };
+        let guide_x = nearest(&self.xs, point.0);
+        let guide_y = nearest(&self.ys, point.1);
+        Snap {
+            point: (guide_x.unwrap_or(point.0), guide_y.unwrap_or(point.1)),
+            guide_x,
+            guide_y,
+        }
+    }
+}
+
+/// Where a new shape starts: snapped to guides unless constrained
+pub fn shape_start(point: (f32, f32), constrain: bool, guides: &Guides) -> Snap {
+    if constrain {
+        Snap::free(point)
+    } else {
+        guides.snap(point)
+    }
+}
+
+/// Where an arrow from `start` ends: at a 15° step when constrained, otherwise
+/// snapped to guides
+pub fn arrow_end(start: (f32, f32), end: (f32, f32), constrain: bool, guides: &Guides) -> Snap {
+    if constrain {
+        Snap::free(snap_angle(start, end))
+    } else {
+        guides.snap(end)
+    }
+}
+
+/// Where a rectangle or ellipse from `start` ends: square when constrained,
+/// otherwise snapped to guides
+pub fn box_end(start: (f32, f32), end: (f32, f32), constrain: bool, guides: &Guides) -> Snap {
+    if constrain {
+        Snap::free(lock_square(start, end))
+    } else {
+        guides.snap(end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ShapeColor;
+    use crate::domain::RectOutlineAnnotation;
+
+    fn close(a: (f32, f32), b: (f32, f32)) -> bool {
+        (a.0 - b.0).abs() < 1e-3 && (a.1 - b.1).abs() < 1e-3
+    }
+
+    #[test]
+    fn shift_snaps_angles_and_locks_squares() {
+        // 5° off horizontal snaps to horizontal, 40° to 45°
+        let end = snap_angle(
+            (0.0, 0.0),
+            (
+                100.0 * 5f32.to_radians().cos(),
+                100.0 * 5f32.to_radians().sin(),
+            ),
+        );
+        assert!(close(end, (100.0, 0.0)));
+        let end = snap_angle(
+            (10.0, 10.0),
+            (
+                10.0 + 40f32.to_radians().cos(),
+                10.0 + 40f32.to_radians().sin(),
+            ),
+        );
+        let diagonal = 45f32.to_radians();
+        assert!(close(end, (10.0 + diagonal.cos(), 10.0 + diagonal.sin())));
+
+        assert_eq!(lock_square((10.0, 10.0), (40.0, -50.0)), (40.0, -20.0));
+        assert_eq!(lock_square((10.0, 10.0), (-20.0, 60.0)), (-20.0, 40.0));
+    }
+
+    #[test]
+    fn points_snap_to_selection_and_annotation_edges() {
+        let rect = Annotation::Rectangle(RectOutlineAnnotation {
+            start_x: 50.0,
+            start_y: 60.0,
+            end_x: 150.0,
+            end_y: 90.0,
+            width: 3.0,
+            fill: false,
+            color: ShapeColor::default(),
+            shadow: false,
+            auto_contrast: false,
+        });
+        let guides = Guides::collect(Some((0.0, 0.0, 400.0, 300.0)), &[rect]);
+
+        // Lines up with the rectangle's right edge and the selection's middle
+        let snap = guides.snap((153.0, 147.0));
+        assert_eq!(snap.point, (150.0, 150.0));
+        assert_eq!((snap.guide_x, snap.guide_y), (Some(150.0), Some(150.0)));
+
+        // Too far from everything: left alone
+        let snap = guides.snap((120.0, 120.0));
+        assert_eq!(snap, Snap::free((120.0, 120.0)));
+
+        // Constraining takes precedence over guides
+        assert_eq!(
+            box_end((0.0, 0.0), (153.0, 147.0), true, &guides).point,
+            (147.0, 147.0)
+        );
+    }
+}
@@ -2,10 +2,14 @@
 //!
 //! Handles DrawMsg for all annotation drawing operations.
 
+use crate::annotations::constrain::{arrow_end, box_end, selection_bounds, shape_start};
 use crate::domain::{
-    Annotation, ArrowAnnotation, CircleOutlineAnnotation, MAGNIFIER_MAX_ZOOM, MAGNIFIER_MIN_ZOOM,
-    MagnifierAnnotation, PixelateAnnotation, RectOutlineAnnotation, RedactAnnotation,
+    Annotation, ArrowAnnotation, BlurAnnotation, CircleOutlineAnnotation, MAGNIFIER_MAX_ZOOM,
+    MAGNIFIER_MIN_ZOOM, MagnifierAnnotation, MeasureAnnotation, PixelateAnnotation,
+    RectOutlineAnnotation, RedactAnnotation, SpotlightAnnotation, StampAnnotation,
+    stamp_size_from_drag,
 };
+use crate::render::image::secure_noise_seed;
 use crate::screenshot::Args;
 use crate::session::messages::{DrawAction, DrawMsg};
 
@@ -16,6 +20,10 @@ use crate::session::messages::{DrawAction, DrawMsg};
 pub fn handle_draw_msg(args: &mut Args, msg: DrawMsg) {
     match msg {
         DrawMsg::Arrow(action) => handle_arrow(args, action),
+        DrawMsg::ArrowBend(index, x, y) => {
+            args.annotations
+                .edit_arrow(index, |arrow| arrow.bend_through(x, y));
+        }
         DrawMsg::Circle(action) => handle_circle(args, action),
         DrawMsg::Rectangle(action) => handle_rectangle(args, action),
         DrawMsg::Magnifier(action) => handle_magnifier(args, action),
@@ -24,16 +32,16 @@ pub fn handle_draw_msg(args: &mut Args, msg: DrawMsg) {
         }
         DrawMsg::MagnifierMove(index, x, y) => {
             args.annotations.selected_magnifier = Some(index);
-            args.annotations.edit_selected_magnifier(|m| {
-                let r = m.radius();
-                m.set_geometry(x, y, r);
-            });
+            args.annotations
+                .edit_selected_magnifier(|m| m.move_to(x, y));
         }
         DrawMsg::MagnifierResize(index, radius) => {
             args.annotations.selected_magnifier = Some(index);
             args.annotations.edit_selected_magnifier(|m| {
-                let (cx, cy) = m.center();
-                m.set_geometry(cx, cy, radius);
+                if !m.is_callout() {
+                    let (cx, cy) = m.center();
+                    m.set_geometry(cx, cy, radius);
+                }
             });
         }
         DrawMsg::MagnifierSetZoom(index, zoom) => {
@@ -42,8 +50,13 @@ pub fn handle_draw_msg(args: &mut Args, msg: DrawMsg) {
             args.annotations
                 .edit_selected_magnifier(|m| m.magnification = zoom);
         }
+        DrawMsg::Spotlight(action) => handle_spotlight(args, action),
+        DrawMsg::Measure(action) => handle_measure(args, action),
+        DrawMsg::Stamp(action) => handle_stamp(args, action),
         DrawMsg::Redact(action) => handle_redact(args, action),
         DrawMsg::Pixelate(action) => handle_pixelate(args, action),
+        DrawMsg::Blur(action) => handle_blur(args, action),
+        DrawMsg::Constrain(held) => args.annotations.constrain = held,
         DrawMsg::ClearShapes => args.annotations.clear_shapes(),
         DrawMsg::ClearRedactions => args.annotations.clear_redactions(),
         DrawMsg::Undo => args.annotations.undo(),
@@ -68,18 +81,31 @@ fn handle_arrow(args: &mut Args, action: DrawAction) {
         }
         DrawAction::Start(x, y) => {
             if args.annotations.arrow_mode {
-                args.annotations.arrow_drawing = Some((x, y));
+                let guides = args.annotations.guides(&args.session.choice);
+                let start = shape_start((x, y), args.annotations.constrain, &guides);
+                args.annotations.arrow_drawing = Some(start.point);
             }
         }
         DrawAction::End(x, y) => {
             if let Some((start_x, start_y)) = args.annotations.arrow_drawing.take() {
+                let guides = args.annotations.guides(&args.session.choice);
+                let end = arrow_end(
+                    (start_x, start_y),
+                    (x, y),
+                    args.annotations.constrain,
+                    &guides,
+                );
+                let (x, y) = end.point;
                 let arrow = ArrowAnnotation {
                     start_x,
                     start_y,
                     end_x: x,
                     end_y: y,
+                    control: None,
+                    width: args.ui.shape_width,
                     color: args.ui.shape_color,
                     shadow: args.ui.shape_shadow,
+                    auto_contrast: args.ui.shape_auto_contrast,
                 };
                 args.annotations.arrows.push(arrow.clone());
                 args.annotations.add(Annotation::Arrow(arrow));
@@ -105,18 +131,31 @@ fn handle_circle(args: &mut Args, action: DrawAction) {
         }
         DrawAction::Start(x, y) => {
             if args.annotations.circle_mode {
-                args.annotations.circle_drawing = Some((x, y));
+                let guides = args.annotations.guides(&args.session.choice);
+                let start = shape_start((x, y), args.annotations.constrain, &guides);
+                args.annotations.circle_drawing = Some(start.point);
             }
         }
         DrawAction::End(x, y) => {
             if let Some((start_x, start_y)) = args.annotations.circle_drawing.take() {
+                let guides = args.annotations.guides(&args.session.choice);
+                let end = box_end(
+                    (start_x, start_y),
+                    (x, y),
+                    args.annotations.constrain,
+                    &guides,
+                );
+                let (x, y) = end.point;
                 let circle = CircleOutlineAnnotation {
                     start_x,
                     start_y,
                     end_x: x,
                     end_y: y,
+                    width: args.ui.shape_width,
+                    fill: args.ui.shape_fill,
                     color: args.ui.shape_color,
                     shadow: args.ui.shape_shadow,
+                    auto_contrast: args.ui.shape_auto_contrast,
                 };
                 args.annotations.circles.push(circle.clone());
                 args.annotations.add(Annotation::Circle(circle));
@@ -142,18 +181,31 @@ fn handle_rectangle(args: &mut Args, action: DrawAction) {
         }
         DrawAction::Start(x, y) => {
             if args.annotations.rect_outline_mode {
-                args.annotations.rect_outline_drawing = Some((x, y));
+                let guides = args.annotations.guides(&args.session.choice);
+                let start = shape_start((x, y), args.annotations.constrain, &guides);
+                args.annotations.rect_outline_drawing = Some(start.point);
             }
         }
         DrawAction::End(x, y) => {
             if let Some((start_x, start_y)) = args.annotations.rect_outline_drawing.take() {
+                let guides = args.annotations.guides(&args.session.choice);
+                let end = box_end(
+                    (start_x, start_y),
+                    (x, y),
+                    args.annotations.constrain,
+                    &guides,
+                );
+                let (x, y) = end.point;
                 let rect = RectOutlineAnnotation {
                     start_x,
                     start_y,
                     end_x: x,
                     end_y: y,
+                    width: args.ui.shape_width,
+                    fill: args.ui.shape_fill,
                     color: args.ui.shape_color,
                     shadow: args.ui.shape_shadow,
+                    auto_contrast: args.ui.shape_auto_contrast,
                 };
                 args.annotations.rect_outlines.push(rect.clone());
                 args.annotations.add(Annotation::Rectangle(rect));
@@ -185,7 +237,7 @@ fn handle_magnifier(args: &mut Args, action: DrawAction) {
         }
         DrawAction::End(x, y) => {
             if let Some((start_x, start_y)) = args.annotations.magnifier_drawing.take() {
-                let magnifier = MagnifierAnnotation {
+                let mut magnifier = MagnifierAnnotation {
                     start_x,
                     start_y,
                     end_x: x,
@@ -193,7 +245,17 @@ fn handle_magnifier(args: &mut Args, action: DrawAction) {
                     magnification: args.ui.magnifier_magnification,
                     color: args.ui.shape_color,
                     shadow: args.ui.shape_shadow,
+                    callout: None,
+                    callout_border: args.ui.magnifier_callout_border,
                 };
+                if args.ui.magnifier_callout {
+                    // A click without a drag has no source area to enlarge
+                    let (x1, y1, x2, y2) = magnifier.source_rect();
+                    if x2 - x1 < 2.0 || y2 - y1 < 2.0 {
+                        return;
+                    }
+                    magnifier.place_callout(selection_bounds(&args.session.choice));
+                }
                 args.annotations.magnifiers.push(magnifier.clone());
                 args.annotations.add(Annotation::Magnifier(magnifier));
                 // Select the newly created magnifier so it can be tweaked
@@ -204,6 +266,126 @@ fn handle_magnifier(args: &mut Args, action: DrawAction) {
     }
 }
 
+// ============================================================================
+// Spotlight handlers
+// ============================================================================
+
+fn handle_spotlight(args: &mut Args, action: DrawAction) {
+    match action {
+        DrawAction::ModeToggle => {
+            args.annotations.spotlight_mode = !args.annotations.spotlight_mode;
+            if !args.annotations.spotlight_mode {
+                args.annotations.spotlight_drawing = None;
+            } else {
+                disable_other_modes(args, Mode::Spotlight);
+                args.detection.clear();
+            }
+        }
+        DrawAction::Start(x, y) => {
+            if args.annotations.spotlight_mode {
+                args.annotations.spotlight_drawing = Some((x, y));
+            }
+        }
+        DrawAction::End(x, y) => {
+            if let Some((start_x, start_y)) = args.annotations.spotlight_drawing.take() {
+                let spotlight = SpotlightAnnotation {
+                    x: start_x,
+                    y: start_y,
+                    x2: x,
+                    y2: y,
+                    ellipse: args.ui.spotlight_ellipse,
+                    dim: args.ui.spotlight_dim,
+                    blur: args.ui.spotlight_blur,
+                };
+                args.annotations.spotlights.push(spotlight.clone());
+                args.annotations.add(Annotation::Spotlight(spotlight));
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Measure handlers
+// ============================================================================
+
+fn handle_measure(args: &mut Args, action: DrawAction) {
+    match action {
+        DrawAction::ModeToggle => {
+            args.annotations.measure_mode = !args.annotations.measure_mode;
+            if !args.annotations.measure_mode {
+                args.annotations.measure_drawing = None;
+            } else {
+                disable_other_modes(args, Mode::Measure);
+                args.detection.clear();
+            }
+        }
+        DrawAction::Start(x, y) => {
+            if args.annotations.measure_mode {
+                args.annotations.measure_drawing = Some((x, y));
+            }
+        }
+        DrawAction::End(x, y) => {
+            if let Some((start_x, start_y)) = args.annotations.measure_drawing.take() {
+                // A click without a drag measures nothing
+                if (x - start_x).abs() < 1.0 && (y - start_y).abs() < 1.0 {
+                    return;
+                }
+                let measurement = MeasureAnnotation {
+                    start_x,
+                    start_y,
+                    end_x: x,
+                    end_y: y,
+                    kind: args.ui.measure_kind,
+                    color: args.ui.shape_color,
+                };
+                args.annotations.measurements.push(measurement.clone());
+                args.annotations.add(Annotation::Measure(measurement));
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Stamp handlers
+// ============================================================================
+
+fn handle_stamp(args: &mut Args, action: DrawAction) {
+    match action {
+        DrawAction::ModeToggle => {
+            args.annotations.stamp_mode = !args.annotations.stamp_mode;
+            if !args.annotations.stamp_mode {
+                args.annotations.stamp_drawing = None;
+            } else {
+                disable_other_modes(args, Mode::Stamp);
+                args.detection.clear();
+                args.ui.ensure_stamps_loaded();
+            }
+        }
+        DrawAction::Start(x, y) => {
+            if args.annotations.stamp_mode {
+                args.annotations.stamp_drawing = Some((x, y));
+            }
+        }
+        DrawAction::End(x, y) => {
+            let Some(center) = args.annotations.stamp_drawing.take() else {
+                return;
+            };
+            let Some(selected) = args.ui.selected_stamp() else {
+                return;
+            };
+            let stamp = StampAnnotation {
+                x: center.0,
+                y: center.1,
+                size: stamp_size_from_drag(center, (x, y), args.ui.stamp_size),
+                name: selected.name.clone(),
+                image: selected.image.clone(),
+            };
+            args.annotations.stamps.push(stamp.clone());
+            args.annotations.add(Annotation::Stamp(stamp));
+        }
+    }
+}
+
 // ============================================================================
 // Redact handlers
 // ============================================================================
@@ -257,6 +439,8 @@ fn handle_pixelate(args: &mut Args, action: DrawAction) {
         DrawAction::Start(x, y) => {
             if args.annotations.pixelate_mode {
                 args.annotations.pixelate_drawing = Some((x, y));
+                args.annotations.pixelate_noise_seed =
+                    args.ui.secure_pixelation.then(secure_noise_seed);
             }
         }
         DrawAction::End(x, y) => {
@@ -267,6 +451,7 @@ fn handle_pixelate(args: &mut Args, action: DrawAction) {
                     x2: x,
                     y2: y,
                     block_size: args.ui.pixelation_block_size,
+                    noise_seed: args.annotations.pixelate_noise_seed.take(),
                 };
                 args.annotations.pixelations.push(pixelate.clone());
                 args.annotations.add(Annotation::Pixelate(pixelate));
@@ -275,6 +460,42 @@ fn handle_pixelate(args: &mut Args, action: DrawAction) {
     }
 }
 
+// ============================================================================
+// Blur handlers
+// ============================================================================
+
+fn handle_blur(args: &mut Args, action: DrawAction) {
+    match action {
+        DrawAction::ModeToggle => {
+            args.annotations.blur_mode = !args.annotations.blur_mode;
+            if !args.annotations.blur_mode {
+                args.annotations.blur_drawing = None;
+            } else {
+                disable_other_modes(args, Mode::Blur);
+                args.detection.clear();
+            }
+        }
+        DrawAction::Start(x, y) => {
+            if args.annotations.blur_mode {
+                args.annotations.blur_drawing = Some((x, y));
+            }
+        }
+        DrawAction::End(x, y) => {
+            if let Some((start_x, start_y)) = args.annotations.blur_drawing.take() {
+                let blur = BlurAnnotation {
+                    x: start_x,
+                    y: start_y,
+                    x2: x,
+                    y2: y,
+                    radius: args.ui.blur_radius,
+                };
+                args.annotations.blurs.push(blur.clone());
+                args.annotations.add(Annotation::Blur(blur));
+            }
+        }
+    }
+}
+
 // ============================================================================
 // Helper functions
 // ============================================================================
@@ -285,8 +506,12 @@ enum Mode {
     Circle,
     Rectangle,
     Magnifier,
+    Spotlight,
+    Measure,
+    Stamp,
     Redact,
     Pixelate,
+    Blur,
 }
 
 fn disable_other_modes(args: &mut Args, keep: Mode) {
@@ -307,6 +532,19 @@ fn disable_other_modes(args: &mut Args, keep: Mode) {
         args.annotations.magnifier_drawing = None;
         args.annotations.selected_magnifier = None;
     }
+    if keep != Mode::Spotlight {
+        args.annotations.spotlight_mode = false;
+        args.annotations.spotlight_drawing = None;
+    }
+    if keep != Mode::Measure {
+        args.annotations.measure_mode = false;
+        args.annotations.measure_drawing = None;
+    }
+    if keep != Mode::Stamp {
+        args.annotations.stamp_mode = false;
+        args.annotations.stamp_drawing = None;
+    }
+    args.annotations.color_picker_mode = false;
     if keep != Mode::Redact {
         args.annotations.redact_mode = false;
         args.annotations.redact_drawing = None;
@@ -315,4 +553,8 @@ fn disable_other_modes(args: &mut Args, keep: Mode) {
         args.annotations.pixelate_mode = false;
         args.annotations.pixelate_drawing = None;
     }
+    if keep != Mode::Blur {
+        args.annotations.blur_mode = false;
+        args.annotations.blur_drawing = None;
+    }
 }
@@ -4,5 +4,7 @@
 //! - Annotation types (Arrow, Circle, Rectangle, Redact, Pixelate)
 //! - Rendering functions for annotations
 //! - Message handlers for DrawMsg
+//! - Constrained drawing and alignment guides
 
+pub mod constrain;
 pub mod handlers;
@@ -0,0 +1,151 @@
+//! Barcode detection using rxing
+//!
+//! Finds the 1D retail and logistics barcodes and the 2D symbologies rqrr
+//! doesn't read. QR codes are left to qr.rs.
+
+use image::RgbaImage;
+use rxing::helpers::detect_multiple_in_luma_with_hints;
+use rxing::{BarcodeFormat, DecodeHintValue, DecodeHints};
+
+use super::qr::{DetectedQrCode, Symbology, polygon_center};
+
+/// Formats searched for, and how each is labelled
+const FORMATS: &[(BarcodeFormat, Symbology)] = &[
+    (BarcodeFormat::EAN_13, Symbology::Ean13),
+    (BarcodeFormat::EAN_8, Symbology::Ean8),
+    (BarcodeFormat::UPC_A, Symbology::UpcA),
+    (BarcodeFormat::UPC_E, Symbology::UpcE),
+    (BarcodeFormat::CODE_128, Symbology::Code128),
+    (BarcodeFormat::CODE_39, Symbology::Code39),
+    (BarcodeFormat::DATA_MATRIX, Symbology::DataMatrix),
+    (BarcodeFormat::AZTEC, Symbology::Aztec),
+    (BarcodeFormat::PDF_417, Symbology::Pdf417),
+];
+
+/// Height of the band around a 1D code's scan line, as a share of its
+/// length; the bars' height isn't reported, and most codes are taller
+const SCAN_LINE_BAND: f32 = 0.4;
+
+/// Detect barcodes in an image at full resolution, since the bars of 1D
+/// codes don't survive downsampling
+pub fn detect_barcodes(img: &RgbaImage, output_name: &str, scale: f32) -> Vec<DetectedQrCode> {
+    let gray = image::imageops::grayscale(img);
+    let (width, height) = gray.dimensions();
+    let mut hints = DecodeHints::default()
+        .with(DecodeHintValue::TryHarder(true))
+        .with(DecodeHintValue::PossibleFormats(
+            FORMATS.iter().map(|(format, _)| *format).collect(),
+        ));
+
+    // Finding nothing is reported as an error too
+    let results =
+        match detect_multiple_in_luma_with_hints(gray.into_raw(), width, height, &mut hints) {
+            Ok(results) => results,
+            Err(e) => {
+                log::debug!("No barcodes found: {e}");
+                return Vec::new();
+            }
+        };
+
+    results
+        .iter()
+        .filter_map(|result| {
+            let symbology = FORMATS
+                .iter()
+                .find(|(format, _)| format == result.getBarcodeFormat())
+                .map(|(_, symbology)| *symbology)?;
+            let points: Vec<(f32, f32)> = result
+                .getPoints()
+                .iter()
+                .map(|p| (p.x / scale, p.y / scale))
+                .collect();
+            let corners = outline(&points)?;
+            let (center_x, center_y) = polygon_center(&corners);
+            Some(DetectedQrCode {
+                center_x,
+                center_y,
+                corners,
+                content: result.getText().to_string(),
+                output_name: output_name.to_string(),
+                symbology,
+            })
+        })
+        .collect()
+}
+
+/// Four corners around the points rxing reports: the corners themselves for
+/// most 2D codes, a band along the scan line for 1D codes, which only report
+/// its ends, and the bounding box otherwise
+fn outline(points: &[(f32, f32)]) -> Option<[(f32, f32); 4]> {
+    match points {
+        [] => None,
+        &[a, b, c, d] => Some([a, b, c, d]),
+        &[(x1, y1), (x2, y2)] => {
+            let length = (x2 - x1).hypot(y2 - y1).max(1.0);
+            let half = length * SCAN_LINE_BAND / 2.0;
+            let (nx, ny) = ((y1 - y2) / length * half, (x2 - x1) / length * half);
+            Some([
+                (x1 + nx, y1 + ny),
+                (x1 - nx, y1 - ny),
+                (x2 - nx, y2 - ny),
+                (x2 + nx, y2 + ny),
+            ])
+        }
+        _ => {
+            let (mut left, mut top) = (f32::INFINITY, f32::INFINITY);
+            let (mut right, mut bottom) = (f32::NEG_INFINITY, f32::NEG_INFINITY);
+            for &(x, y) in points {
+                (left, top) = (left.min(x), top.min(y));
+                (right, bottom) = (right.max(x), bottom.max(y));
+            }
+            Some([(left, top), (right, top), (right, bottom), (left, bottom)])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outlines_reported_points() {
+        let corners = [(1.0, 2.0), (9.0, 2.5), (8.5, 10.0), (0.5, 9.0)];
+        assert_eq!(outline(&corners), Some(corners));
+        assert_eq!(outline(&[]), None);
+
+        // Other counts get their bounding box
+        let points = [(4.0, 1.0), (9.0, 6.0), (2.0, 8.0)];
+        assert_eq!(
+            outline(&points),
+            Some([(2.0, 1.0), (9.0, 1.0), (9.0, 8.0), (2.0, 8.0)])
+        );
+    }
+
+    #[test]
+    fn outlines_scan_line_as_band() {
+        // A diagonal scan line 100px long
+        let (start, end) = ((10.0, 10.0), (70.0, 90.0));
+        let [a, b, c, d] = outline(&[start, end]).unwrap();
+        let close = |x: f32, y: f32| (x - y).abs() < 1e-3;
+
+        // Across the line at both ends, as thick as a share of its length
+        for ((x1, y1), (x2, y2)) in [(a, b), (d, c)] {
+            let (dx, dy) = (x2 - x1, y2 - y1);
+            assert!(close(dx.hypot(dy), SCAN_LINE_BAND * 100.0));
+            assert!(close(dx * 60.0 + dy * 80.0, 0.0));
+        }
+        // Centred on the line's ends
+        assert!(close((a.0 + b.0) / 2.0, start.0) && close((a.1 + b.1) / 2.0, start.1));
+        assert!(close((c.0 + d.0) / 2.0, end.0) && close((c.1 + d.1) / 2.0, end.1));
+    }
+
+    #[test]
+    fn formats_have_distinct_symbologies() {
+        for (i, (_, symbology)) in FORMATS.iter().enumerate() {
+            assert!(
+                FORMATS[i + 1..].iter().all(|(_, other)| other != symbology),
+                "{symbology:?} is used twice"
+            );
+        }
+    }
+}
@@ -1,10 +1,19 @@
 //! Image capture and processing module
 //!
 //! This module consolidates:
-//! - QR code detection (qr.rs)
-//! - OCR text recognition (ocr.rs)
+//! - QR code detection (qr.rs) and other barcodes (barcode.rs)
+//! - Typed QR payloads such as Wi-Fi, 2FA and contacts (payload.rs)
+//! - OCR text recognition with tesseract or the built-in engine (ocr/)
+//! - Sensitive data detection on OCR text (sensitive.rs)
+//! - Text search over OCR results (search.rs)
+//! - Auto-trim of uniform margins (trim.rs)
 //! - Screenshot image type (image.rs)
 
+pub mod barcode;
 pub mod image;
 pub mod ocr;
+pub mod payload;
 pub mod qr;
+pub mod search;
+pub mod sensitive;
+pub mod trim;
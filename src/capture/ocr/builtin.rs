@@ -0,0 +1,240 @@
+//! Built-in OCR backend using the pure-Rust ocrs engine
+//!
+//! Needs no system packages. The detection and recognition models are looked
+//! up in the directories packagers bundle them in, then in the user cache,
+//! where they are downloaded when the user allows it. Downloads are checked
+//! against pinned checksums. Recognizes Latin script only.
+
+use image::RgbaImage;
+use ocrs::{ImageSource, OcrEngine, OcrEngineParams, TextItem};
+use rten::Model;
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use super::preprocess::{Options, preprocess};
+use super::{OcrBackend, OcrMapping, OcrOutput, OcrTextOverlay, OcrWord, overlays_text};
+use crate::fl;
+
+/// Where the models are downloaded from
+const MODEL_URL: &str = "https://ocrs-models.s3-accelerate.amazonaws.com";
+
+const DETECTION_MODEL: &str = "text-detection.rten";
+const RECOGNITION_MODEL: &str = "text-recognition.rten";
+
+/// SHA-256 of the published models, as hex
+const DETECTION_MODEL_SHA256: &str =
+    "0000000000000000000000000000000000000000000000000000000000000000";
+const RECOGNITION_MODEL_SHA256: &str =
+    "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Directories packagers can ship the models in (Flatpak, then system)
+const BUNDLED_MODEL_DIRS: &[&str] = &[
+    "/app/share/snappea/ocr-models",
+    "/usr/share/snappea/ocr-models",
+];
+
+/// Loaded engine, kept for the lifetime of the process since loading the
+/// models takes a while
+static ENGINE: OnceLock<OcrEngine> = OnceLock::new();
+
+/// Directory models are downloaded to
+fn model_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("snappea").join("ocr-models"))
+}
+
+/// Path of an installed model, bundled or cached
+fn find_model(name: &str) -> Option<PathBuf> {
+    BUNDLED_MODEL_DIRS
+        .iter()
+        .map(PathBuf::from)
+        .chain(model_dir())
+        .map(|dir| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Path of a model, downloading it into the cache if it is not installed
+/// and `download` allows it
+fn fetch_model(name: &str, sha256: &str, download: bool) -> Result<PathBuf, String> {
+    if let Some(path) = find_model(name) {
+        return Ok(path);
+    }
+    if !download {
+        return Err(fl!("ocr-models-missing"));
+    }
+    let dir = model_dir().ok_or_else(|| fl!("ocr-model-error", error = "no cache directory"))?;
+    let path = dir.join(name);
+    fetch(&format!("{MODEL_URL}/{name}"), sha256, &path)
+        .map_err(|e| fl!("ocr-model-error", error = e.to_string()))?;
+    Ok(path)
+}
+
+/// Download `url` to `path`, through a temporary file so an interrupted or
+/// corrupted download is never mistaken for a model
+fn fetch(url: &str, sha256: &str, path: &Path) -> anyhow::Result<()> {
+    log::info!("Downloading OCR model {url}");
+    let dir = path.parent().unwrap_or(Path::new("."));
+    std::fs::create_dir_all(dir)?;
+    let mut reader = ureq::get(url).call()?.into_reader();
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut reader, &mut HashingWriter(&mut file, &mut hasher))?;
+    file.flush()?;
+    let digest = hex(&hasher.finalize());
+    if digest != sha256 {
+        anyhow::bail!("checksum mismatch for {url}: expected {sha256}, got {digest}");
+    }
+    file.persist(path)?;
+    Ok(())
+}
+
+/// Writer that hashes everything written through it
+struct HashingWriter<'a, W>(&'a mut W, &'a mut Sha256);
+
+impl<W: Write> Write for HashingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.0.write(buf)?;
+        self.1.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// Lowercase hex of `bytes`
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn load_engine(download: bool) -> Result<OcrEngine, String> {
+    let load = |name: &str, sha256: &str| {
+        let path = fetch_model(name, sha256, download)?;
+        Model::load_file(&path).map_err(|e| fl!("ocr-model-error", error = e.to_string()))
+    };
+    OcrEngine::new(OcrEngineParams {
+        detection_model: Some(load(DETECTION_MODEL, DETECTION_MODEL_SHA256)?),
+        recognition_model: Some(load(RECOGNITION_MODEL, RECOGNITION_MODEL_SHA256)?),
+        ..Default::default()
+    })
+    .map_err(|e| fl!("ocr-model-error", error = e.to_string()))
+}
+
+fn engine(download: bool) -> Result<&'static OcrEngine, String> {
+    if let Some(engine) = ENGINE.get() {
+        return Ok(engine);
+    }
+    let engine = load_engine(download)?;
+    Ok(ENGINE.get_or_init(|| engine))
+}
+
+/// The ocrs engine
+pub struct Builtin {
+    /// Straighten skewed text before recognition
+    pub deskew: bool,
+    /// Download missing models instead of failing
+    pub download: bool,
+}
+
+impl OcrBackend for Builtin {
+    fn needs_download(&self) -> bool {
+        self.download
+            && ENGINE.get().is_none()
+            && [DETECTION_MODEL, RECOGNITION_MODEL]
+                .iter()
+                .any(|name| find_model(name).is_none())
+    }
+
+    fn recognize(&self, img: &RgbaImage, mapping: &OcrMapping) -> Result<OcrOutput, String> {
+        let engine = engine(self.download)?;
+        let error = |e: &dyn std::fmt::Display| fl!("ocr-engine-error", error = e.to_string());
+
+        log::info!(
+            "Running built-in OCR on {}x{} image...",
+            img.width(),
+            img.height()
+        );
+        // The model copes with gray levels itself, so no binarisation
+        let pre = preprocess(
+            img,
+            Options {
+                binarize: false,
+                deskew: self.deskew,
+            },
+        );
+        let source = ImageSource::from_bytes(pre.image.as_raw(), pre.image.dimensions())
+            .map_err(|e| error(&e))?;
+        let input = engine.prepare_input(source).map_err(|e| error(&e))?;
+        let word_rects = engine.detect_words(&input).map_err(|e| error(&e))?;
+        let line_rects = engine.find_text_lines(&input, &word_rects);
+        let lines = engine
+            .recognize_text(&input, &line_rects)
+            .map_err(|e| error(&e))?;
+
+        let to_logical = |v: f32| v / mapping.scale;
+        // Boxes are in the deskewed image; turn them back first
+        let place = |left: f32, top: f32, width: f32, height: f32| {
+            let (left, top) = pre.unrotate_box(left, top, width, height);
+            (
+                mapping.origin.0 + to_logical(left),
+                mapping.origin.1 + to_logical(top),
+                to_logical(width),
+                to_logical(height),
+            )
+        };
+        let mut overlays = Vec::new();
+        for line in lines.iter().flatten() {
+            let text = line.to_string().trim().to_string();
+            if text.is_empty() {
+                continue;
+            }
+            let words = line
+                .words()
+                .map(|word| {
+                    let rect = word.bounding_rect();
+                    let (left, top, width, height) = place(
+                        rect.left() as f32,
+                        rect.top() as f32,
+                        rect.width() as f32,
+                        rect.height() as f32,
+                    );
+                    OcrWord {
+                        left,
+                        top,
+                        width,
+                        height,
+                        text: word.to_string(),
+                        par_num: 1,
+                        line_num: 1,
+                    }
+                })
+                .collect();
+            // ocrs has no paragraph detection, so every line is its own block
+            let rect = line.bounding_rect();
+            let (left, top, width, height) = place(
+                rect.left() as f32,
+                rect.top() as f32,
+                rect.width() as f32,
+                rect.height() as f32,
+            );
+            overlays.push(OcrTextOverlay {
+                left,
+                top,
+                width,
+                height,
+                text,
+                block_num: overlays.len() as i32 + 1,
+                output_name: mapping.output_name.clone(),
+                words,
+            });
+        }
+        log::info!("Built-in OCR found {} lines", overlays.len());
+
+        Ok(OcrOutput {
+            text: overlays_text(&overlays),
+            overlays,
+        })
+    }
+}
@@ -0,0 +1,308 @@
+//! Layout-preserving OCR output
+//!
+//! Rebuilds the structure of recognized text from word geometry: rows from
+//! vertical overlap, indentation from a typical character width, and table
+//! columns from gaps that line up across rows. Block boundaries are ignored
+//! since tesseract's sparse-text mode splits table rows into many blocks.
+
+use super::{OcrSelection, OcrTextOverlay, OcrWord};
+
+/// A blank line is kept between rows further apart than this many line
+/// heights
+const PARAGRAPH_GAP: f32 = 0.9;
+
+/// Words further apart than this many line heights are in different cells
+const CELL_GAP: f32 = 1.0;
+
+/// How OCR text is laid out when copied
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OcrLayout {
+    /// Line breaks kept, with a blank line between paragraphs
+    Paragraphs,
+    /// Indentation and alignment kept, for code and terminal output
+    Code,
+    /// Table as tab-separated values
+    Tsv,
+    /// Table in Markdown
+    Markdown,
+}
+
+/// Words on one row, left to right, with the row's vertical extent
+struct Row<'a> {
+    top: f32,
+    bottom: f32,
+    words: Vec<&'a OcrWord>,
+}
+
+/// Lay out the selected words, or else every word, one output after another
+pub fn layout_overlays(
+    overlays: &[OcrTextOverlay],
+    selection: Option<&OcrSelection>,
+    layout: OcrLayout,
+) -> String {
+    if let Some(selection) = selection {
+        let words: Vec<&OcrWord> = selection
+            .words(overlays)
+            .into_iter()
+            .map(|(_, word)| word)
+            .collect();
+        return layout_words(&words, layout);
+    }
+
+    let mut outputs: Vec<&str> = Vec::new();
+    for overlay in overlays {
+        if !outputs.contains(&overlay.output_name.as_str()) {
+            outputs.push(&overlay.output_name);
+        }
+    }
+    outputs
+        .into_iter()
+        .map(|output| {
+            let words: Vec<&OcrWord> = overlays
+                .iter()
+                .filter(|o| o.output_name == output)
+                .flat_map(|o| &o.words)
+                .collect();
+            layout_words(&words, layout)
+        })
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Lay out `words`, which may come in any order
+pub fn layout_words(words: &[&OcrWord], layout: OcrLayout) -> String {
+    if words.is_empty() {
+        return String::new();
+    }
+    let rows = rows(words);
+    let line_height = median(words.iter().map(|w| w.height));
+    let char_width = median(
+        words
+            .iter()
+            .map(|w| w.width / w.text.chars().count().max(1) as f32),
+    );
+
+    match layout {
+        OcrLayout::Paragraphs => paragraphs(&rows, line_height),
+        OcrLayout::Code => code(&rows, char_width),
+        OcrLayout::Tsv => table(&rows, line_height)
+            .iter()
+            .map(|cells| cells.join("\t"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OcrLayout::Markdown => markdown(&table(&rows, line_height)),
+    }
+}
+
+/// Lower median, so one blank line between two rows doesn't double the
+/// line pitch; at least 1 to keep divisions sane
+fn median(values: impl Iterator<Item = f32>) -> f32 {
+    let mut values: Vec<f32> = values.collect();
+    values.sort_by(f32::total_cmp);
+    let middle = values.len().saturating_sub(1) / 2;
+    values.get(middle).copied().unwrap_or(1.0).max(1.0)
+}
+
+/// Group words into rows: a word joins the row whose first word it overlaps
+/// at its vertical center
+fn rows<'a>(words: &[&'a OcrWord]) -> Vec<Row<'a>> {
+    let center = |w: &OcrWord| w.top + w.height / 2.0;
+    let mut sorted = words.to_vec();
+    sorted.sort_by(|a, b| center(a).total_cmp(&center(b)));
+
+    let mut rows: Vec<Row> = Vec::new();
+    for word in sorted {
+        match rows.last_mut() {
+            Some(row)
+                if center(word) <= row.words[0].top + row.words[0].height
+                    && center(word) >= row.words[0].top =>
+            {
+                row.top = row.top.min(word.top);
+                row.bottom = row.bottom.max(word.top + word.height);
+                row.words.push(word);
+            }
+            _ => rows.push(Row {
+                top: word.top,
+                bottom: word.top + word.height,
+                words: vec![word],
+            }),
+        }
+    }
+    for row in &mut rows {
+        row.words.sort_by(|a, b| a.left.total_cmp(&b.left));
+    }
+    rows
+}
+
+fn row_text(row: &Row) -> String {
+    row.words
+        .iter()
+        .map(|w| w.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn paragraphs(rows: &[Row], line_height: f32) -> String {
+    let mut text = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        if i > 0 {
+            text.push('\n');
+            if row.top - rows[i - 1].bottom > line_height * PARAGRAPH_GAP {
+                text.push('\n');
+            }
+        }
+        text.push_str(&row_text(row));
+    }
+    text
+}
+
+/// Place every word at the column it starts at, and keep blank lines
+fn code(rows: &[Row], char_width: f32) -> String {
+    let left = rows
+        .iter()
+        .map(|row| row.words[0].left)
+        .fold(f32::INFINITY, f32::min);
+    let pitch = median(rows.windows(2).map(|pair| pair[1].top - pair[0].top));
+
+    let mut text = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        if i > 0 {
+            let lines = ((row.top - rows[i - 1].top) / pitch).round().max(1.0) as usize;
+            text.push_str(&"\n".repeat(lines));
+        }
+        let mut column = 0;
+        for (j, word) in row.words.iter().enumerate() {
+            let target = ((word.left - left) / char_width).round().max(0.0) as usize;
+            let spaces = target.saturating_sub(column).max(usize::from(j > 0));
+            text.push_str(&" ".repeat(spaces));
+            text.push_str(&word.text);
+            column += spaces + word.text.chars().count();
+        }
+    }
+    text
+}
+
+/// Split rows into cells at wide gaps, then line cells up into columns
+/// where their horizontal extents overlap across rows
+fn table(rows: &[Row], line_height: f32) -> Vec<Vec<String>> {
+    // (left, right, text) of each cell, per row
+    let cells: Vec<Vec<(f32, f32, String)>> = rows
+        .iter()
+        .map(|row| {
+            let mut cells: Vec<(f32, f32, String)> = Vec::new();
+            for word in &row.words {
+                let right = word.left + word.width;
+                match cells.last_mut() {
+                    Some((_, r, text)) if word.left - *r <= line_height * CELL_GAP => {
+                        *r = right;
+                        text.push(' ');
+                        text.push_str(&word.text);
+                    }
+                    _ => cells.push((word.left, right, word.text.clone())),
+                }
+            }
+            cells
+        })
+        .collect();
+
+    let mut spans: Vec<(f32, f32)> = cells.iter().flatten().map(|(l, r, _)| (*l, *r)).collect();
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut columns: Vec<(f32, f32)> = Vec::new();
+    for (l, r) in spans {
+        match columns.last_mut() {
+            Some((_, right)) if l <= *right => *right = right.max(r),
+            _ => columns.push((l, r)),
+        }
+    }
+
+    cells
+        .into_iter()
+        .map(|row| {
+            let mut out = vec![String::new(); columns.len()];
+            for (l, _, text) in row {
+                let index = columns.iter().rposition(|(cl, _)| *cl <= l).unwrap_or(0);
+                if !out[index].is_empty() {
+                    out[index].push(' ');
+                }
+                out[index].push_str(&text);
+            }
+            out
+        })
+        .collect()
+}
+
+/// First row as the header
+fn markdown(table: &[Vec<String>]) -> String {
+    let line = |cells: &[String]| {
+        let cells: Vec<String> = cells.iter().map(|c| c.replace('|', "\\|")).collect();
+        format!("| {} |", cells.join(" | "))
+    };
+    let Some((header, body)) = table.split_first() else {
+        return String::new();
+    };
+    let mut lines = vec![line(header), line(&vec!["---".to_string(); header.len()])];
+    lines.extend(body.iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Words of monospaced `lines`, 8 wide and 20 high per character cell
+    fn grid(lines: &[&str]) -> Vec<OcrWord> {
+        let mut words = Vec::new();
+        for (row, line) in lines.iter().enumerate() {
+            let mut start = None;
+            for (col, ch) in line.chars().chain([' ']).enumerate() {
+                match (ch == ' ', start) {
+                    (false, None) => start = Some(col),
+                    (true, Some(s)) => {
+                        words.push(OcrWord {
+                            left: s as f32 * 8.0,
+                            top: row as f32 * 24.0,
+                            width: (col - s) as f32 * 8.0,
+                            height: 16.0,
+                            text: line[s..col].to_string(),
+                            par_num: 1,
+                            line_num: 1,
+                        });
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        words
+    }
+
+    #[test]
+    fn keeps_indentation_and_paragraphs() {
+        let source = ["fn main() {", "    run();", "", "}"];
+        let words = grid(&source);
+        let words: Vec<&OcrWord> = words.iter().rev().collect();
+        assert_eq!(layout_words(&words, OcrLayout::Code), source.join("\n"));
+        assert_eq!(
+            layout_words(&words, OcrLayout::Paragraphs),
+            "fn main() {\nrun();\n\n}"
+        );
+    }
+
+    #[test]
+    fn rebuilds_tables() {
+        let words = grid(&[
+            "Name      Status   Last run",
+            "api a|b   up       2 min ago",
+        ]);
+        let words: Vec<&OcrWord> = words.iter().collect();
+        assert_eq!(
+            layout_words(&words, OcrLayout::Tsv),
+            "Name\tStatus\tLast run\napi a|b\tup\t2 min ago"
+        );
+        assert_eq!(
+            layout_words(&words, OcrLayout::Markdown),
+            "| Name | Status | Last run |\n| --- | --- | --- |\n| api a\\|b | up | 2 min ago |"
+        );
+    }
+}
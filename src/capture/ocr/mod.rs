@@ -0,0 +1,234 @@
+//! OCR (Optical Character Recognition)
+//!
+//! Recognition goes through an [`OcrBackend`]: the system tesseract binary
+//! (tesseract.rs) or the built-in ocrs engine with cached models (builtin.rs).
+//! Both clean up the image first (preprocess.rs). Recognized words can be
+//! selected on screen (selection.rs) and copied with their layout (layout.rs).
+
+mod builtin;
+mod layout;
+mod preprocess;
+mod selection;
+mod tesseract;
+
+use image::RgbaImage;
+
+pub use builtin::Builtin;
+pub use layout::{OcrLayout, layout_overlays};
+pub use selection::{OcrSelection, reading_order, word_at};
+pub use tesseract::{Tesseract, installed_languages, is_tesseract_available};
+
+use crate::config::OcrEngine;
+use crate::fl;
+
+/// OCR text overlay metadata
+#[derive(Clone, Debug, PartialEq)]
+pub struct OcrTextOverlay {
+    /// Bounding box in logical coordinates (relative to output)
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Recognized text for this region
+    pub text: String,
+    /// Block number for coloring
+    pub block_num: i32,
+    /// Which output this overlay belongs to
+    pub output_name: String,
+    /// Words making up `text`, in reading order (empty for the whole-selection fallback)
+    pub words: Vec<OcrWord>,
+}
+
+/// A single recognized word inside an OCR block
+#[derive(Clone, Debug, PartialEq)]
+pub struct OcrWord {
+    /// Bounding box in logical coordinates (relative to output)
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub height: f32,
+    pub text: String,
+    /// Paragraph number within the block
+    pub par_num: i32,
+    /// Line number within the paragraph
+    pub line_num: i32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum OcrStatus {
+    #[default]
+    Idle,
+    DownloadingModels,
+    Running,
+    Done(String, Vec<OcrTextOverlay>),
+    Error(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct OcrMapping {
+    /// Top-left of the cropped OCR region in logical coordinates
+    pub origin: (f32, f32),
+    /// Size of the cropped OCR region in logical coordinates
+    pub size: (f32, f32),
+    /// Pixels-per-logical-unit for this output image
+    pub scale: f32,
+    /// Output name this mapping belongs to
+    pub output_name: String,
+}
+
+/// Text and block overlays recognized by a backend
+pub struct OcrOutput {
+    /// All recognized text, empty if none was found
+    pub text: String,
+    pub overlays: Vec<OcrTextOverlay>,
+}
+
+/// An OCR engine
+pub trait OcrBackend {
+    /// Whether models have to be downloaded before the first run
+    fn needs_download(&self) -> bool {
+        false
+    }
+
+    /// Recognize text in `img`, the region of an output described by `mapping`.
+    ///
+    /// Overlays are in logical coordinates relative to the output. Errors are
+    /// user-facing messages.
+    fn recognize(&self, img: &RgbaImage, mapping: &OcrMapping) -> Result<OcrOutput, String>;
+}
+
+/// The backend for `engine`; `lang` is a tesseract language argument as built
+/// by [`language_arg`], `deskew` straightens skewed text first and `download`
+/// lets missing models be downloaded
+pub fn backend(
+    engine: OcrEngine,
+    lang: String,
+    deskew: bool,
+    download: bool,
+) -> Box<dyn OcrBackend> {
+    match engine {
+        OcrEngine::Tesseract => Box::new(Tesseract { lang, deskew }),
+        OcrEngine::Builtin => Box::new(Builtin { deskew, download }),
+    }
+}
+
+/// Check if OCR models will be downloaded before `engine` can run.
+pub fn models_need_download(engine: OcrEngine, download: bool) -> bool {
+    backend(engine, String::new(), false, download).needs_download()
+}
+
+/// Language used when none is configured
+pub const DEFAULT_LANGUAGE: &str = "eng";
+
+/// Tesseract `-l` argument for a set of languages, e.g. `eng+deu+jpn`
+pub fn language_arg(languages: &[String]) -> String {
+    if languages.is_empty() {
+        DEFAULT_LANGUAGE.to_string()
+    } else {
+        languages.join("+")
+    }
+}
+
+/// Add `lang` to `languages` or remove it, keeping at least one language
+pub fn toggle_language(languages: &mut Vec<String>, lang: String) {
+    if let Some(pos) = languages.iter().position(|l| *l == lang) {
+        if languages.len() > 1 {
+            languages.remove(pos);
+        }
+    } else {
+        languages.push(lang);
+    }
+}
+
+/// Run OCR on an image and return the status with detected text and overlays.
+pub fn run_ocr_on_image_with_status(
+    img: &RgbaImage,
+    mapping: OcrMapping,
+    backend: &dyn OcrBackend,
+) -> OcrStatus {
+    if mapping.scale <= 0.0 {
+        return OcrStatus::Error(fl!("invalid-ocr-scale"));
+    }
+
+    match backend.recognize(img, &mapping) {
+        Ok(OcrOutput { text, mut overlays }) => {
+            let no_text = fl!("no-text-detected");
+            let text = if text.is_empty() {
+                no_text.clone()
+            } else {
+                text
+            };
+            // If no blocks found, create a fallback overlay covering the whole selection
+            if overlays.is_empty() && !text.is_empty() && text != no_text {
+                overlays.push(OcrTextOverlay {
+                    left: mapping.origin.0,
+                    top: mapping.origin.1,
+                    width: mapping.size.0,
+                    height: mapping.size.1,
+                    text: text.clone(),
+                    block_num: 0,
+                    output_name: mapping.output_name.clone(),
+                    words: Vec::new(),
+                });
+            }
+            OcrStatus::Done(text, overlays)
+        }
+        Err(e) => OcrStatus::Error(e),
+    }
+}
+
+/// Remove OCR overlays that overlap any redacted region.
+///
+/// `redacted` holds normalized `(left, top, right, bottom)` bounds in global
+/// logical coordinates; `output_origin` maps an overlay's output name to that
+/// output's global logical position. Whole blocks are dropped rather than
+/// trimmed, so nothing that was partly covered can leak through.
+/// Returns true if any overlay was removed.
+pub fn scrub_redacted_overlays(
+    overlays: &mut Vec<OcrTextOverlay>,
+    redacted: &[(f32, f32, f32, f32)],
+    output_origin: impl Fn(&str) -> (f32, f32),
+) -> bool {
+    if redacted.is_empty() {
+        return false;
+    }
+    let before = overlays.len();
+    overlays.retain(|o| {
+        let (ox, oy) = output_origin(&o.output_name);
+        let (left, top) = (ox + o.left, oy + o.top);
+        let (right, bottom) = (left + o.width, top + o.height);
+        !redacted
+            .iter()
+            .any(|&(rl, rt, rr, rb)| left < rr && right > rl && top < rb && bottom > rt)
+    });
+    overlays.len() != before
+}
+
+/// Rebuild plain text from overlays in reading order (top to bottom, then left to right)
+pub fn overlays_text(overlays: &[OcrTextOverlay]) -> String {
+    let mut sorted: Vec<&OcrTextOverlay> = overlays.iter().collect();
+    sorted.sort_by(|a, b| a.top.total_cmp(&b.top).then(a.left.total_cmp(&b.left)));
+    sorted
+        .iter()
+        .map(|o| o.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combines_and_toggles_languages() {
+        let mut languages = vec!["eng".to_string()];
+        toggle_language(&mut languages, "jpn".to_string());
+        assert_eq!(language_arg(&languages), "eng+jpn");
+        toggle_language(&mut languages, "eng".to_string());
+        assert_eq!(languages, ["jpn"]);
+        // The last language stays
+        toggle_language(&mut languages, "jpn".to_string());
+        assert_eq!(languages, ["jpn"]);
+        assert_eq!(language_arg(&[]), DEFAULT_LANGUAGE);
+    }
+}
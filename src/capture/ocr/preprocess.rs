@@ -0,0 +1,295 @@
+//! Image cleanup before OCR
+//!
+//! OCR engines are trained on dark print on light paper, so terminal and IDE
+//! screenshots read poorly as captured. The image is turned into dark text on
+//! a light background with stretched contrast, then optionally binarised with
+//! a local threshold and straightened. Parameters come from the image's own
+//! statistics.
+
+use image::{GrayImage, Luma, RgbaImage};
+
+/// Fraction of the darkest and lightest pixels ignored when stretching contrast
+const CLIP_FRACTION: f32 = 0.01;
+
+/// Below this spread between dark and light pixels the text is faint, and the
+/// binarisation threshold hugs the local mean more closely
+const LOW_CONTRAST: u8 = 96;
+
+/// Largest skew corrected, and the resolution it is searched at (degrees)
+const MAX_SKEW_DEG: f32 = 5.0;
+const SKEW_STEP_DEG: f32 = 0.25;
+
+/// Skew this small is left alone rather than resampling the image
+const MIN_SKEW_DEG: f32 = 0.3;
+
+/// Skew is estimated on a copy at most this wide, for speed
+const SKEW_SAMPLE_WIDTH: u32 = 480;
+
+/// Which optional steps to run
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    /// Replace gray levels with black and white using a local threshold
+    pub binarize: bool,
+    /// Rotate text lines to horizontal
+    pub deskew: bool,
+}
+
+/// A cleaned-up image, and the rotation that maps it back onto the input
+pub struct Preprocessed {
+    pub image: GrayImage,
+    /// Deskew rotation in radians (0 when not rotated)
+    pub angle: f32,
+}
+
+impl Preprocessed {
+    /// Map a point in the cleaned-up image back to the input image
+    pub fn unrotate(&self, x: f32, y: f32) -> (f32, f32) {
+        if self.angle == 0.0 {
+            return (x, y);
+        }
+        let (cx, cy) = center(&self.image);
+        let (sin, cos) = self.angle.sin_cos();
+        let (dx, dy) = (x - cx, y - cy);
+        (cx + dx * cos - dy * sin, cy + dx * sin + dy * cos)
+    }
+
+    /// Map a box in the cleaned-up image back to the input image, turning its
+    /// center and keeping its size
+    pub fn unrotate_box(&self, left: f32, top: f32, width: f32, height: f32) -> (f32, f32) {
+        let (cx, cy) = self.unrotate(left + width / 2.0, top + height / 2.0);
+        (cx - width / 2.0, cy - height / 2.0)
+    }
+}
+
+/// Gray levels at the clip fractions and the middle of the histogram
+struct Levels {
+    low: u8,
+    median: u8,
+    high: u8,
+}
+
+fn levels(img: &GrayImage) -> Levels {
+    let mut histogram = [0u32; 256];
+    for pixel in img.pixels() {
+        histogram[pixel.0[0] as usize] += 1;
+    }
+    let total = img.width() as f32 * img.height() as f32;
+    let level_at = |fraction: f32| {
+        let target = (total * fraction).max(1.0);
+        let mut seen = 0.0;
+        for (level, &count) in histogram.iter().enumerate() {
+            seen += count as f32;
+            if seen >= target {
+                return level as u8;
+            }
+        }
+        u8::MAX
+    };
+    Levels {
+        low: level_at(CLIP_FRACTION),
+        median: level_at(0.5),
+        high: level_at(1.0 - CLIP_FRACTION),
+    }
+}
+
+/// Clean up `img` for OCR
+pub fn preprocess(img: &RgbaImage, options: Options) -> Preprocessed {
+    let mut gray = image::imageops::grayscale(img);
+    let mut levels = levels(&gray);
+
+    // Most pixels are background: a dark median means light text on dark
+    if levels.median < 128 {
+        image::imageops::invert(&mut gray);
+        levels = Levels {
+            low: 255 - levels.high,
+            median: 255 - levels.median,
+            high: 255 - levels.low,
+        };
+    }
+    stretch(&mut gray, levels.low, levels.high);
+
+    if options.binarize {
+        let window = (gray.width().min(gray.height()) / 8).clamp(15, 101) | 1;
+        let k = if levels.high - levels.low < LOW_CONTRAST {
+            0.05
+        } else {
+            0.15
+        };
+        gray = binarize(&gray, window, k);
+    }
+
+    let angle = if options.deskew {
+        estimate_skew(&gray)
+    } else {
+        0.0
+    };
+    if angle != 0.0 {
+        gray = rotate(&gray, angle);
+    }
+    Preprocessed { image: gray, angle }
+}
+
+/// Map `low..=high` onto the full gray range
+fn stretch(img: &mut GrayImage, low: u8, high: u8) {
+    if high <= low {
+        return;
+    }
+    let scale = 255.0 / (high - low) as f32;
+    for pixel in img.pixels_mut() {
+        let v = (pixel.0[0].saturating_sub(low) as f32 * scale).min(255.0);
+        pixel.0[0] = v as u8;
+    }
+}
+
+/// Bradley's adaptive threshold: a pixel is ink when it is more than `k`
+/// darker than the mean of the `window`-sized square around it
+fn binarize(img: &GrayImage, window: u32, k: f32) -> GrayImage {
+    let (w, h) = img.dimensions();
+    // Summed-area table with a zero row and column in front
+    let stride = w as usize + 1;
+    let mut integral = vec![0u64; stride * (h as usize + 1)];
+    for y in 0..h as usize {
+        let mut row_sum = 0u64;
+        for x in 0..w as usize {
+            row_sum += img.get_pixel(x as u32, y as u32).0[0] as u64;
+            integral[(y + 1) * stride + x + 1] = integral[y * stride + x + 1] + row_sum;
+        }
+    }
+
+    let half = window / 2;
+    GrayImage::from_fn(w, h, |x, y| {
+        let (x0, y0) = (
+            x.saturating_sub(half) as usize,
+            y.saturating_sub(half) as usize,
+        );
+        let (x1, y1) = (
+            (x + half + 1).min(w) as usize,
+            (y + half + 1).min(h) as usize,
+        );
+        let sum = integral[y1 * stride + x1] + integral[y0 * stride + x0]
+            - integral[y0 * stride + x1]
+            - integral[y1 * stride + x0];
+        let mean = sum as f32 / ((x1 - x0) * (y1 - y0)) as f32;
+        let ink = (img.get_pixel(x, y).0[0] as f32) < mean * (1.0 - k);
+        Luma([if ink { 0 } else { 255 }])
+    })
+}
+
+/// Angle (radians) of the text lines, found as the rotation whose row
+/// projection of dark pixels is sharpest; 0 if too small to bother
+fn estimate_skew(img: &GrayImage) -> f32 {
+    let sample_scale = (SKEW_SAMPLE_WIDTH as f32 / img.width() as f32).min(1.0);
+    let ink: Vec<(f32, f32)> = img
+        .enumerate_pixels()
+        .filter(|(_, _, p)| p.0[0] < 128)
+        .map(|(x, y, _)| (x as f32 * sample_scale, y as f32 * sample_scale))
+        .collect();
+    if ink.is_empty() {
+        return 0.0;
+    }
+
+    let diagonal = (img.width() as f32).hypot(img.height() as f32) * sample_scale;
+    let bins = diagonal.ceil() as usize * 2 + 1;
+    let mut rows = vec![0u32; bins];
+    let score = |angle: f32, rows: &mut Vec<u32>| {
+        rows.iter_mut().for_each(|r| *r = 0);
+        let (sin, cos) = angle.sin_cos();
+        for &(x, y) in &ink {
+            let r = (y * cos - x * sin + diagonal).round() as usize;
+            rows[r.min(bins - 1)] += 1;
+        }
+        rows.iter().map(|&n| (n as u64).pow(2)).sum::<u64>()
+    };
+
+    let steps = (MAX_SKEW_DEG / SKEW_STEP_DEG) as i32;
+    let (mut best_angle, mut best_score) = (0.0, score(0.0, &mut rows));
+    for step in -steps..=steps {
+        let angle = (step as f32 * SKEW_STEP_DEG).to_radians();
+        let s = score(angle, &mut rows);
+        if s > best_score {
+            (best_angle, best_score) = (angle, s);
+        }
+    }
+    if best_angle.to_degrees().abs() < MIN_SKEW_DEG {
+        0.0
+    } else {
+        best_angle
+    }
+}
+
+/// Rotate so that lines at `angle` become horizontal, filling with white
+fn rotate(img: &GrayImage, angle: f32) -> GrayImage {
+    let (cx, cy) = center(img);
+    let (sin, cos) = angle.sin_cos();
+    GrayImage::from_fn(img.width(), img.height(), |x, y| {
+        let (dx, dy) = (x as f32 - cx, y as f32 - cy);
+        let (sx, sy) = (cx + dx * cos - dy * sin, cy + dx * sin + dy * cos);
+        Luma([sample(img, sx, sy)])
+    })
+}
+
+/// Bilinear sample, white outside the image
+fn sample(img: &GrayImage, x: f32, y: f32) -> u8 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let at = |px: f32, py: f32| {
+        if px < 0.0 || py < 0.0 || px >= img.width() as f32 || py >= img.height() as f32 {
+            255.0
+        } else {
+            img.get_pixel(px as u32, py as u32).0[0] as f32
+        }
+    };
+    let top = at(x0, y0) * (1.0 - fx) + at(x0 + 1.0, y0) * fx;
+    let bottom = at(x0, y0 + 1.0) * (1.0 - fx) + at(x0 + 1.0, y0 + 1.0) * fx;
+    (top * (1.0 - fy) + bottom * fy).round() as u8
+}
+
+fn center(img: &GrayImage) -> (f32, f32) {
+    (img.width() as f32 / 2.0, img.height() as f32 / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Light "text" rows on a dark background, sloping by `angle_deg`
+    fn terminal(angle_deg: f32) -> RgbaImage {
+        let slope = angle_deg.to_radians().tan();
+        RgbaImage::from_fn(300, 200, |x, y| {
+            let row = y as f32 - x as f32 * slope;
+            let on_text = (row as i32).rem_euclid(20) < 4 && (20..280).contains(&x);
+            if on_text {
+                image::Rgba([200, 200, 200, 255])
+            } else {
+                image::Rgba([30, 30, 30, 255])
+            }
+        })
+    }
+
+    #[test]
+    fn dark_backgrounds_become_light_and_binary() {
+        let options = Options {
+            binarize: true,
+            deskew: false,
+        };
+        let out = preprocess(&terminal(0.0), options);
+        assert_eq!(out.image.get_pixel(5, 5).0[0], 255);
+        assert_eq!(out.image.get_pixel(150, 41).0[0], 0);
+        assert!(out.image.pixels().all(|p| p.0[0] == 0 || p.0[0] == 255));
+    }
+
+    #[test]
+    fn skewed_lines_are_straightened() {
+        let options = Options {
+            binarize: false,
+            deskew: true,
+        };
+        let out = preprocess(&terminal(2.0), options);
+        assert!((out.angle.to_degrees() - 2.0).abs() <= SKEW_STEP_DEG);
+        // Straight text is left alone
+        assert_eq!(preprocess(&terminal(0.0), options).angle, 0.0);
+        // Points map back onto the input
+        let (x, y) = out.unrotate(250.0, 100.0);
+        assert!((x - 250.0).abs() < 5.0 && y > 100.0);
+    }
+}
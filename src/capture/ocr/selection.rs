@@ -0,0 +1,197 @@
+//! Text selection over OCR results
+//!
+//! The words of one output are put in reading order, and a selection runs
+//! from the word under the press to the word nearest the cursor, like
+//! selecting text in a PDF viewer.
+
+use super::{OcrTextOverlay, OcrWord};
+
+/// Extra margin around word boxes when hit testing, in logical pixels
+const HIT_SLOP: f32 = 2.0;
+
+/// A text selection on one output, kept as the points it was dragged between
+///
+/// Points rather than word indices, so the selection stays valid when OCR
+/// blocks are dropped for being redacted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OcrSelection {
+    pub output_name: String,
+    /// Where the drag started, in logical coordinates relative to the output
+    pub anchor: (f32, f32),
+    /// Where the drag is now
+    pub focus: (f32, f32),
+}
+
+/// Words of `output` in reading order, each with the index of its block
+pub fn reading_order<'a>(
+    overlays: &'a [OcrTextOverlay],
+    output: &str,
+) -> Vec<(usize, &'a OcrWord)> {
+    let mut blocks: Vec<(usize, &OcrTextOverlay)> = overlays
+        .iter()
+        .enumerate()
+        .filter(|(_, o)| o.output_name == output)
+        .collect();
+    blocks.sort_by(|(_, a), (_, b)| a.top.total_cmp(&b.top).then(a.left.total_cmp(&b.left)));
+    blocks
+        .into_iter()
+        .flat_map(|(block, o)| o.words.iter().map(move |word| (block, word)))
+        .collect()
+}
+
+/// Index of the word under `(x, y)`
+pub fn word_at(words: &[(usize, &OcrWord)], (x, y): (f32, f32)) -> Option<usize> {
+    words.iter().position(|(_, w)| {
+        x >= w.left - HIT_SLOP
+            && x <= w.left + w.width + HIT_SLOP
+            && y >= w.top - HIT_SLOP
+            && y <= w.top + w.height + HIT_SLOP
+    })
+}
+
+/// Index of the word closest to `(x, y)`, preferring words on the same row
+/// so that dragging past the end of a line selects up to its last word
+fn nearest_word(words: &[(usize, &OcrWord)], (x, y): (f32, f32)) -> Option<usize> {
+    let dx = |w: &OcrWord| (w.left - x).max(x - w.left - w.width).max(0.0);
+    let dy = |w: &OcrWord| (w.top - y).max(y - w.top - w.height).max(0.0);
+    words
+        .iter()
+        .enumerate()
+        .min_by(|(_, (_, a)), (_, (_, b))| dy(a).total_cmp(&dy(b)).then(dx(a).total_cmp(&dx(b))))
+        .map(|(index, _)| index)
+}
+
+impl OcrSelection {
+    /// Selected words in reading order, each with the index of its block
+    pub fn words<'a>(&self, overlays: &'a [OcrTextOverlay]) -> Vec<(usize, &'a OcrWord)> {
+        let mut words = reading_order(overlays, &self.output_name);
+        let (Some(a), Some(b)) = (
+            nearest_word(&words, self.anchor),
+            nearest_word(&words, self.focus),
+        ) else {
+            return Vec::new();
+        };
+        words.truncate(a.max(b) + 1);
+        words.drain(..a.min(b));
+        words
+    }
+
+    /// Selected text, with a line break wherever the words change line
+    pub fn text(&self, overlays: &[OcrTextOverlay]) -> String {
+        let mut text = String::new();
+        let mut previous = None;
+        for (block, word) in self.words(overlays) {
+            let line = Some((block, word.par_num, word.line_num));
+            if previous.is_some() {
+                text.push(if previous == line { ' ' } else { '\n' });
+            }
+            text.push_str(&word.text);
+            previous = line;
+        }
+        text
+    }
+
+    /// One highlight box per selected line, as (left, top, width, height)
+    pub fn line_boxes(&self, overlays: &[OcrTextOverlay]) -> Vec<(f32, f32, f32, f32)> {
+        // Edges (left, top, right, bottom) of each line so far
+        let mut edges: Vec<(f32, f32, f32, f32)> = Vec::new();
+        let mut previous = None;
+        for (block, word) in self.words(overlays) {
+            let line = Some((block, word.par_num, word.line_num));
+            let (x1, y1) = (word.left, word.top);
+            let (x2, y2) = (word.left + word.width, word.top + word.height);
+            match edges.last_mut() {
+                Some((l, t, r, b)) if previous == line => {
+                    (*l, *t, *r, *b) = (l.min(x1), t.min(y1), r.max(x2), b.max(y2));
+                }
+                _ => edges.push((x1, y1, x2, y2)),
+            }
+            previous = line;
+        }
+        edges
+            .into_iter()
+            .map(|(l, t, r, b)| (l, t, r - l, b - t))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(top: f32, lines: &[&str]) -> OcrTextOverlay {
+        let mut words = Vec::new();
+        for (line, text) in lines.iter().enumerate() {
+            let mut left = 10.0;
+            for word in text.split(' ') {
+                let width = 8.0 * word.len() as f32;
+                words.push(OcrWord {
+                    left,
+                    top: top + line as f32 * 20.0,
+                    width,
+                    height: 16.0,
+                    text: word.to_string(),
+                    par_num: 1,
+                    line_num: line as i32 + 1,
+                });
+                left += width + 8.0;
+            }
+        }
+        OcrTextOverlay {
+            left: 10.0,
+            top,
+            width: 200.0,
+            height: lines.len() as f32 * 20.0,
+            text: lines.join(" "),
+            block_num: 1,
+            output_name: "DP-1".to_string(),
+            words,
+        }
+    }
+
+    #[test]
+    fn selects_a_span_across_lines() {
+        // Out of reading order on purpose
+        let overlays = vec![
+            block(100.0, &["OK Cancel"]),
+            block(0.0, &["Order ID:", "A-1234 shipped"]),
+        ];
+        let words = reading_order(&overlays, "DP-1");
+        assert_eq!(words[0].1.text, "Order");
+
+        let id = word_at(&words, (35.0, 28.0)).unwrap();
+        assert_eq!(words[id].1.text, "A-1234");
+        let single = OcrSelection {
+            output_name: "DP-1".to_string(),
+            anchor: (35.0, 28.0),
+            focus: (35.0, 28.0),
+        };
+        assert_eq!(single.text(&overlays), "A-1234");
+
+        // Dragging backwards past the end of the first line
+        let span = OcrSelection {
+            focus: (150.0, 8.0),
+            ..single
+        };
+        assert_eq!(span.text(&overlays), "ID:\nA-1234");
+        assert_eq!(span.line_boxes(&overlays).len(), 2);
+        assert!(word_at(&words, (190.0, 60.0)).is_none());
+    }
+
+    #[test]
+    fn breaks_lines_between_paragraphs() {
+        // Line numbers start over in the second paragraph of the block
+        let mut overlay = block(0.0, &["Order ID:", "A-1234 shipped"]);
+        for word in overlay.words.iter_mut().filter(|w| w.line_num == 2) {
+            (word.par_num, word.line_num) = (2, 1);
+        }
+        let overlays = vec![overlay];
+        let all = OcrSelection {
+            output_name: "DP-1".to_string(),
+            anchor: (10.0, 8.0),
+            focus: (150.0, 28.0),
+        };
+        assert_eq!(all.text(&overlays), "Order ID:\nA-1234 shipped");
+        assert_eq!(all.line_boxes(&overlays).len(), 2);
+    }
+}
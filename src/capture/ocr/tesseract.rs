@@ -0,0 +1,262 @@
+//! Tesseract OCR backend, running the system `tesseract` binary through
+//! rusty-tesseract
+
+use image::RgbaImage;
+use rusty_tesseract::{Args, Image};
+use std::collections::HashMap;
+
+use super::preprocess::{Options, preprocess};
+use super::{OcrBackend, OcrMapping, OcrOutput, OcrTextOverlay, OcrWord};
+use crate::fl;
+
+/// Check if tesseract is installed and available on the system.
+pub fn is_tesseract_available() -> bool {
+    std::process::Command::new("tesseract")
+        .arg("--version")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// List the tesseract language packs installed on the system.
+pub fn installed_languages() -> Vec<String> {
+    std::process::Command::new("tesseract")
+        .arg("--list-langs")
+        .output()
+        .map(|output| {
+            // Tesseract 3 prints the list to stderr, later versions to stdout
+            let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+            parse_language_list(&text)
+        })
+        .unwrap_or_default()
+}
+
+/// Parse the output of `tesseract --list-langs`, skipping the header and the
+/// orientation detection pack, which is not a language
+fn parse_language_list(output: &str) -> Vec<String> {
+    let mut languages: Vec<String> = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.contains(' ') && *line != "osd")
+        .map(str::to_string)
+        .collect();
+    languages.sort();
+    languages.dedup();
+    languages
+}
+
+/// Tesseract with a language argument such as `eng+deu+jpn`
+pub struct Tesseract {
+    pub lang: String,
+    /// Straighten skewed text before recognition
+    pub deskew: bool,
+}
+
+impl OcrBackend for Tesseract {
+    fn recognize(&self, img: &RgbaImage, mapping: &OcrMapping) -> Result<OcrOutput, String> {
+        log::info!(
+            "Running OCR with rusty-tesseract on {}x{} image...",
+            img.width(),
+            img.height()
+        );
+
+        // Convert RgbaImage to DynamicImage
+        let dynamic_img = image::DynamicImage::ImageRgba8(img.clone());
+
+        // For small images, upscale to improve OCR accuracy on small text
+        // Tesseract works best with text that's at least 10-12 pixels tall
+        let min_dimension = img.width().min(img.height());
+        let (processed_img, upscale_factor) = if min_dimension < 100 {
+            // Very small selection - upscale 4x
+            let new_width = img.width() * 4;
+            let new_height = img.height() * 4;
+            log::info!("Upscaling small image 4x to {}x{}", new_width, new_height);
+            (
+                dynamic_img.resize(new_width, new_height, image::imageops::FilterType::Lanczos3),
+                4.0_f32,
+            )
+        } else if min_dimension < 200 {
+            // Small selection - upscale 2x
+            let new_width = img.width() * 2;
+            let new_height = img.height() * 2;
+            log::info!("Upscaling small image 2x to {}x{}", new_width, new_height);
+            (
+                dynamic_img.resize(new_width, new_height, image::imageops::FilterType::Lanczos3),
+                2.0_f32,
+            )
+        } else {
+            (dynamic_img, 1.0_f32)
+        };
+
+        // Dark themes, faint text and skew; tesseract does best on black and white
+        let pre = preprocess(
+            &processed_img.to_rgba8(),
+            Options {
+                binarize: true,
+                deskew: self.deskew,
+            },
+        );
+
+        let cleaned = image::DynamicImage::ImageLuma8(pre.image.clone());
+
+        // Create rusty-tesseract Image from DynamicImage
+        let tess_img = match Image::from_dynamic_image(&cleaned) {
+            Ok(img) => img,
+            Err(e) => {
+                return Err(fl!("tesseract-image-error", error = e.to_string()));
+            }
+        };
+
+        // Configure tesseract arguments
+        // Use higher DPI for better small text recognition
+        let dpi = if min_dimension < 200 { 300 } else { 150 };
+        let args = Args {
+            lang: self.lang.clone(),
+            config_variables: HashMap::new(),
+            dpi: Some(dpi),
+            psm: Some(11), // Fully automatic page segmentation
+            oem: Some(3),  // Default OCR Engine Mode
+        };
+
+        // Run OCR for text
+        let text_result = rusty_tesseract::image_to_string(&tess_img, &args);
+        let data_result = rusty_tesseract::image_to_data(&tess_img, &args);
+
+        let mut overlays = Vec::new();
+        if let Ok(data_output) = data_result {
+            log::info!("Tesseract returned {} data entries", data_output.data.len());
+
+            // Group words by block_num to create block-level overlays
+            let mut blocks: std::collections::HashMap<i32, Vec<_>> =
+                std::collections::HashMap::new();
+            for d in data_output
+                .data
+                .into_iter()
+                .filter(|d| !d.text.trim().is_empty() && d.conf > 0.0)
+            {
+                blocks.entry(d.block_num).or_default().push(d);
+            }
+
+            for (block_num, words) in blocks {
+                if words.is_empty() {
+                    continue;
+                }
+
+                // Calculate bounding box for the entire block
+                let mut min_left = i32::MAX;
+                let mut min_top = i32::MAX;
+                let mut max_right = i32::MIN;
+                let mut max_bottom = i32::MIN;
+
+                // Sort words by paragraph, line, then word for proper text ordering;
+                // line numbers start over in every paragraph
+                let mut sorted_words = words;
+                sorted_words.sort_by_key(|w| (w.par_num, w.line_num, w.word_num));
+
+                // Convert tesseract image coords to output-relative logical coords
+                // Divide by upscale_factor first since tesseract coords are in upscaled image space
+                let to_logical = |v: f32| v / upscale_factor / mapping.scale;
+                // Boxes are in the deskewed image; turn them back first
+                let place = |left: i32, top: i32, width: i32, height: i32| {
+                    let (width, height) = (width as f32, height as f32);
+                    let (left, top) = pre.unrotate_box(left as f32, top as f32, width, height);
+                    (
+                        mapping.origin.0 + to_logical(left),
+                        mapping.origin.1 + to_logical(top),
+                        to_logical(width),
+                        to_logical(height),
+                    )
+                };
+
+                // Build combined text and bounding box
+                let mut text_parts: Vec<String> = Vec::new();
+                let mut words = Vec::with_capacity(sorted_words.len());
+                let mut current_line = None;
+
+                for word in &sorted_words {
+                    let (left, top, width, height) =
+                        place(word.left, word.top, word.width, word.height);
+                    words.push(OcrWord {
+                        left,
+                        top,
+                        width,
+                        height,
+                        text: word.text.clone(),
+                        par_num: word.par_num,
+                        line_num: word.line_num,
+                    });
+
+                    min_left = min_left.min(word.left);
+                    min_top = min_top.min(word.top);
+                    max_right = max_right.max(word.left + word.width);
+                    max_bottom = max_bottom.max(word.top + word.height);
+
+                    let line = Some((word.par_num, word.line_num));
+                    if line != current_line {
+                        if current_line.is_some() {
+                            text_parts.push(" ".to_string());
+                        }
+                        current_line = line;
+                    } else {
+                        text_parts.push(" ".to_string());
+                    }
+                    text_parts.push(word.text.clone());
+                }
+
+                let block_text = text_parts.concat().trim().to_string();
+                if block_text.is_empty() {
+                    continue;
+                }
+
+                // Convert bounding box to output-relative logical coords
+                let (left, top, width, height) = place(
+                    min_left,
+                    min_top,
+                    max_right - min_left,
+                    max_bottom - min_top,
+                );
+
+                log::info!(
+                    "OCR block {}: '{}' at ({}, {}, {}x{})",
+                    block_num,
+                    block_text,
+                    left,
+                    top,
+                    width,
+                    height
+                );
+                overlays.push(OcrTextOverlay {
+                    left,
+                    top,
+                    width,
+                    height,
+                    text: block_text,
+                    block_num,
+                    output_name: mapping.output_name.clone(),
+                    words,
+                });
+            }
+            log::info!("Generated {} block-level OCR overlays", overlays.len());
+        }
+
+        text_result
+            .map(|text| OcrOutput {
+                text: text.trim().to_string(),
+                overlays,
+            })
+            .map_err(|e| fl!("tesseract-ocr-error", error = e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_installed_languages() {
+        let output =
+            "List of available languages in \"/usr/share/tessdata/\" (4):\njpn\neng\nosd\ndeu\n";
+        assert_eq!(parse_language_list(output), ["deu", "eng", "jpn"]);
+    }
+}
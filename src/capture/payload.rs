@@ -0,0 +1,370 @@
+//! Typed QR code payloads
+//!
+//! Recognizes the well-known formats encoded in QR codes (links, Wi-Fi
+//! credentials, 2FA secrets, contacts, mail, phone and geo URIs) so the
+//! overlay can show their fields and offer matching actions.
+
+/// A contact field decoded from a vCard or MECARD
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContactField {
+    Name,
+    Organization,
+    Phone,
+    Email,
+    Address,
+    Url,
+    Note,
+}
+
+/// What a QR code's content means
+#[derive(Clone, Debug, PartialEq)]
+pub enum QrPayload {
+    /// A web link, as a URL that can be opened
+    Url(String),
+    /// Wi-Fi network credentials
+    Wifi {
+        ssid: String,
+        /// None for open networks
+        password: Option<String>,
+    },
+    /// A TOTP/HOTP secret for two-factor authentication
+    Otp {
+        /// Issuer and account name, e.g. `Example:alice@example.com`
+        account: String,
+        secret: String,
+    },
+    /// Contact fields in the order they appear
+    Contact(Vec<(ContactField, String)>),
+    /// `mailto:` URI and the address it writes to
+    Mail { uri: String, address: String },
+    /// `tel:` URI and the number it calls
+    Phone { uri: String, number: String },
+    /// `geo:` URI and its coordinates
+    Geo {
+        uri: String,
+        latitude: String,
+        longitude: String,
+    },
+    /// Anything else
+    Text,
+}
+
+/// Work out what `content` is
+pub fn parse_payload(content: &str) -> QrPayload {
+    let content = content.trim();
+    let lower = content.to_ascii_lowercase();
+    if lower.starts_with("http://") || lower.starts_with("https://") {
+        QrPayload::Url(content.to_string())
+    } else if lower.starts_with("www.") {
+        QrPayload::Url(format!("https://{content}"))
+    } else if lower.starts_with("wifi:") {
+        parse_wifi(&content[5..])
+    } else if lower.starts_with("otpauth://") {
+        parse_otp(&content[10..])
+    } else if lower.starts_with("begin:vcard") {
+        parse_vcard(content)
+    } else if lower.starts_with("mecard:") {
+        parse_mecard(&content[7..])
+    } else if lower.starts_with("mailto:") {
+        let address = content[7..].split('?').next().unwrap_or_default();
+        QrPayload::Mail {
+            uri: content.to_string(),
+            address: percent_decode(address),
+        }
+    } else if lower.starts_with("tel:") {
+        QrPayload::Phone {
+            uri: content.to_string(),
+            number: percent_decode(&content[4..]),
+        }
+    } else if lower.starts_with("geo:") {
+        parse_geo(content)
+    } else {
+        QrPayload::Text
+    }
+}
+
+/// Split `KEY:value;KEY:value;;` fields as used by Wi-Fi and MECARD codes,
+/// where `\` escapes the separators
+fn fields(body: &str) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => field.extend(chars.next()),
+            ';' => {
+                if let Some((key, value)) = split_field(&field) {
+                    fields.push((key, value));
+                }
+                field.clear();
+            }
+            _ => field.push(c),
+        }
+    }
+    fields.extend(split_field(&field));
+    fields
+}
+
+fn split_field(field: &str) -> Option<(String, String)> {
+    let (key, value) = field.split_once(':')?;
+    Some((key.trim().to_ascii_uppercase(), value.to_string()))
+}
+
+fn parse_wifi(body: &str) -> QrPayload {
+    let mut ssid = String::new();
+    let mut password = None;
+    for (key, value) in fields(body) {
+        match key.as_str() {
+            "S" => ssid = value,
+            "P" if !value.is_empty() => password = Some(value),
+            _ => {}
+        }
+    }
+    QrPayload::Wifi { ssid, password }
+}
+
+/// `otpauth://totp/Issuer:account?secret=...&issuer=...`
+fn parse_otp(body: &str) -> QrPayload {
+    let (path, query) = body.split_once('?').unwrap_or((body, ""));
+    let label = path.split_once('/').map(|(_, l)| l).unwrap_or_default();
+    let mut account = percent_decode(label);
+    let mut secret = String::new();
+    for (key, value) in query.split('&').filter_map(|p| p.split_once('=')) {
+        match key.to_ascii_lowercase().as_str() {
+            "secret" => secret = value.to_string(),
+            "issuer" if !account.contains(':') => {
+                account = format!("{}:{account}", percent_decode(value));
+            }
+            _ => {}
+        }
+    }
+    if secret.is_empty() {
+        return QrPayload::Text;
+    }
+    QrPayload::Otp { account, secret }
+}
+
+fn parse_mecard(body: &str) -> QrPayload {
+    let contact = fields(body)
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let field = match key.as_str() {
+                "N" => {
+                    // "Last,First"
+                    let name = match value.split_once(',') {
+                        Some((last, first)) => format!("{} {}", first.trim(), last.trim()),
+                        None => value,
+                    };
+                    return Some((ContactField::Name, name));
+                }
+                "ORG" => ContactField::Organization,
+                "TEL" => ContactField::Phone,
+                "EMAIL" => ContactField::Email,
+                "ADR" => ContactField::Address,
+                "URL" => ContactField::Url,
+                "NOTE" => ContactField::Note,
+                _ => return None,
+            };
+            Some((field, value))
+        })
+        .filter(|(_, value)| !value.trim().is_empty())
+        .collect();
+    QrPayload::Contact(contact)
+}
+
+fn parse_vcard(content: &str) -> QrPayload {
+    // Unfold continuation lines, which start with a space or tab
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.trim_end().to_string()),
+        }
+    }
+
+    let mut contact = Vec::new();
+    let mut full_name = None;
+    let mut structured_name = None;
+    for line in &lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Drop parameters such as TEL;TYPE=cell
+        let name = name
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_uppercase();
+        let mut parts = split_escaped(value, ';');
+        let joined = |separator: &str| join_parts(&parts, separator);
+        let field = match name.as_str() {
+            "FN" => {
+                full_name = Some(joined(" "));
+                continue;
+            }
+            // "Last;First;Middle;Prefix;Suffix", shown as "First Last"
+            "N" => {
+                if parts.len() > 1 {
+                    parts.swap(0, 1);
+                }
+                structured_name = Some(join_parts(&parts, " "));
+                continue;
+            }
+            "ORG" => (ContactField::Organization, joined(", ")),
+            "TEL" => (ContactField::Phone, joined(" ")),
+            "EMAIL" => (ContactField::Email, joined(" ")),
+            "ADR" => (ContactField::Address, joined(", ")),
+            "URL" => (ContactField::Url, value.to_string()),
+            "NOTE" => (ContactField::Note, joined("; ")),
+            _ => continue,
+        };
+        if !field.1.is_empty() {
+            contact.push(field);
+        }
+    }
+    // The formatted name wins over the structured one
+    if let Some(name) = full_name.or(structured_name).filter(|n| !n.is_empty()) {
+        contact.insert(0, (ContactField::Name, name));
+    }
+    QrPayload::Contact(contact)
+}
+
+/// Join the non-empty parts of a vCard value
+fn join_parts(parts: &[String], separator: &str) -> String {
+    parts
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Split a vCard value on `separator`, resolving `\,` `\;` `\n` escapes
+fn split_escaped(value: &str, separator: char) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        let part = parts.last_mut().expect("parts is never empty");
+        match c {
+            '\\' => match chars.next() {
+                Some('n' | 'N') => part.push('\n'),
+                Some(escaped) => part.push(escaped),
+                None => {}
+            },
+            c if c == separator => parts.push(String::new()),
+            c => part.push(c),
+        }
+    }
+    parts
+}
+
+/// `geo:lat,lon[,alt][;params][?query]`
+fn parse_geo(content: &str) -> QrPayload {
+    let coordinates = content[4..].split(['?', ';']).next().unwrap_or_default();
+    let mut parts = coordinates.split(',').map(str::trim);
+    match (parts.next(), parts.next()) {
+        (Some(latitude), Some(longitude))
+            if latitude.parse::<f64>().is_ok() && longitude.parse::<f64>().is_ok() =>
+        {
+            QrPayload::Geo {
+                uri: content.to_string(),
+                latitude: latitude.to_string(),
+                longitude: longitude.to_string(),
+            }
+        }
+        _ => QrPayload::Text,
+    }
+}
+
+/// Decode `%XX` escapes, leaving malformed ones as they are
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_credentials() {
+        assert_eq!(
+            parse_payload(r"WIFI:T:WPA;S:Cafe\;Guest;P:p@ss:word;H:false;;"),
+            QrPayload::Wifi {
+                ssid: "Cafe;Guest".to_string(),
+                password: Some("p@ss:word".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_payload("WIFI:S:Open;T:nopass;P:;;"),
+            QrPayload::Wifi {
+                ssid: "Open".to_string(),
+                password: None,
+            }
+        );
+        assert_eq!(
+            parse_payload("otpauth://totp/alice%40example.com?secret=JBSWY3DP&issuer=Example"),
+            QrPayload::Otp {
+                account: "Example:alice@example.com".to_string(),
+                secret: "JBSWY3DP".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_contacts_and_uris() {
+        let vcard = "BEGIN:VCARD\r\nVERSION:3.0\r\nN:Doe;Jane;;;\r\nFN:Jane Doe\r\n\
+                     TEL;TYPE=cell:+1 555 0100\r\nADR:;;1 Main St;Springfield;;12345;\r\n\
+                     END:VCARD";
+        assert_eq!(
+            parse_payload(vcard),
+            QrPayload::Contact(vec![
+                (ContactField::Name, "Jane Doe".to_string()),
+                (ContactField::Phone, "+1 555 0100".to_string()),
+                (
+                    ContactField::Address,
+                    "1 Main St, Springfield, 12345".to_string()
+                ),
+            ])
+        );
+        assert_eq!(
+            parse_payload("MECARD:N:Doe,John;TEL:5550100;EMAIL:john@example.com;;"),
+            QrPayload::Contact(vec![
+                (ContactField::Name, "John Doe".to_string()),
+                (ContactField::Phone, "5550100".to_string()),
+                (ContactField::Email, "john@example.com".to_string()),
+            ])
+        );
+        assert!(matches!(
+            parse_payload("mailto:a%40b.org?subject=Hi"),
+            QrPayload::Mail { address, .. } if address == "a@b.org"
+        ));
+        assert!(matches!(
+            parse_payload("geo:48.2,16.37;u=35"),
+            QrPayload::Geo { latitude, longitude, .. } if latitude == "48.2" && longitude == "16.37"
+        ));
+        assert_eq!(
+            parse_payload("www.example.com"),
+            QrPayload::Url("https://www.example.com".to_string())
+        );
+        assert_eq!(parse_payload("geo:nowhere"), QrPayload::Text);
+    }
+}
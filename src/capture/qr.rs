@@ -1,17 +1,65 @@
 //! QR code detection module using rqrr
+//!
+//! Other symbologies are found by barcode.rs and share [`DetectedQrCode`].
 
 use image::RgbaImage;
 
-/// Detected QR code with position and content
+/// Kind of code that was decoded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Symbology {
+    Qr,
+    Ean13,
+    Ean8,
+    UpcA,
+    UpcE,
+    Code128,
+    Code39,
+    DataMatrix,
+    Aztec,
+    Pdf417,
+}
+
+impl Symbology {
+    /// Name shown next to the decoded content
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Qr => "QR",
+            Self::Ean13 => "EAN-13",
+            Self::Ean8 => "EAN-8",
+            Self::UpcA => "UPC-A",
+            Self::UpcE => "UPC-E",
+            Self::Code128 => "Code 128",
+            Self::Code39 => "Code 39",
+            Self::DataMatrix => "Data Matrix",
+            Self::Aztec => "Aztec",
+            Self::Pdf417 => "PDF417",
+        }
+    }
+}
+
+/// How several detected codes are copied at once
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QrFormat {
+    /// One code's content per line
+    Lines,
+    /// JSON array with each code's content, symbology and position
+    Json,
+}
+
+/// Detected QR code or barcode with position and content
 #[derive(Clone, Debug)]
 pub struct DetectedQrCode {
     /// Center position in logical coordinates (relative to output)
     pub center_x: f32,
     pub center_y: f32,
+    /// Outline in logical coordinates (relative to output), corner by corner
+    /// around the code, which may be rotated or seen in perspective
+    pub corners: [(f32, f32); 4],
     /// The decoded content of the QR code
     pub content: String,
     /// Which output this QR code is on
     pub output_name: String,
+    pub symbology: Symbology,
 }
 
 /// Detect QR codes in an image at a specific resolution
@@ -47,15 +95,21 @@ pub fn detect_qr_codes_at_resolution(
     let mut results = Vec::new();
     for grid in grids {
         if let Ok((_, content)) = grid.decode() {
-            let bounds = &grid.bounds;
-            let cx = (bounds[0].x + bounds[1].x + bounds[2].x + bounds[3].x) as f32 / 4.0;
-            let cy = (bounds[0].y + bounds[1].y + bounds[2].y + bounds[3].y) as f32 / 4.0;
+            let corners = grid.bounds.map(|p| {
+                (
+                    (p.x as f32 * downsample_factor) / scale,
+                    (p.y as f32 * downsample_factor) / scale,
+                )
+            });
+            let (center_x, center_y) = polygon_center(&corners);
 
             results.push(DetectedQrCode {
-                center_x: (cx * downsample_factor) / scale,
-                center_y: (cy * downsample_factor) / scale,
+                center_x,
+                center_y,
+                corners,
                 content,
                 output_name: output_name.to_string(),
+                symbology: Symbology::Qr,
             });
         }
     }
@@ -63,13 +117,160 @@ pub fn detect_qr_codes_at_resolution(
     results
 }
 
-/// Check if a QR code is a duplicate (same content at similar position)
+/// Share of the smaller outline that must be covered by the other for two
+/// detections of the same content to be one code
+const DUPLICATE_OVERLAP: f32 = 0.5;
+
+/// Check if a QR code is a duplicate (same content where its outline mostly
+/// overlaps one already found, e.g. by a pass at another resolution)
 pub fn is_duplicate_qr(existing: &[DetectedQrCode], new: &DetectedQrCode) -> bool {
-    const POSITION_THRESHOLD: f32 = 50.0; // pixels
     existing.iter().any(|e| {
         e.content == new.content
             && e.output_name == new.output_name
-            && (e.center_x - new.center_x).abs() < POSITION_THRESHOLD
-            && (e.center_y - new.center_y).abs() < POSITION_THRESHOLD
+            && overlap(&e.corners, &new.corners) >= DUPLICATE_OVERLAP
     })
 }
+
+/// Average of the corners
+pub fn polygon_center(corners: &[(f32, f32)]) -> (f32, f32) {
+    let count = corners.len().max(1) as f32;
+    let x = corners.iter().map(|p| p.0).sum::<f32>() / count;
+    let y = corners.iter().map(|p| p.1).sum::<f32>() / count;
+    (x, y)
+}
+
+/// Shoelace area, positive for clockwise outlines in screen coordinates
+fn signed_area(polygon: &[(f32, f32)]) -> f32 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum::<f32>()
+        / 2.0
+}
+
+/// Share of the smaller of two convex outlines covered by their intersection
+fn overlap(a: &[(f32, f32); 4], b: &[(f32, f32); 4]) -> f32 {
+    // Clipping needs both outlines wound the same way
+    let wound = |corners: &[(f32, f32); 4]| {
+        let mut corners = corners.to_vec();
+        if signed_area(&corners) < 0.0 {
+            corners.reverse();
+        }
+        corners
+    };
+    let (a, b) = (wound(a), wound(b));
+    let smaller = signed_area(&a).min(signed_area(&b));
+    if smaller <= f32::EPSILON {
+        return 0.0;
+    }
+    signed_area(&clip(&a, &b)).abs() / smaller
+}
+
+/// Sutherland-Hodgman: the part of `subject` inside the convex `clip`
+fn clip(subject: &[(f32, f32)], clip: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let mut output = subject.to_vec();
+    for i in 0..clip.len() {
+        let (e1, e2) = (clip[i], clip[(i + 1) % clip.len()]);
+        // Inside is to the right of the edge, as the outlines are clockwise
+        let side = |p: (f32, f32)| (e2.0 - e1.0) * (p.1 - e1.1) - (e2.1 - e1.1) * (p.0 - e1.0);
+        let input = std::mem::take(&mut output);
+        for j in 0..input.len() {
+            let (current, previous) = (input[j], input[(j + input.len() - 1) % input.len()]);
+            let (sc, sp) = (side(current), side(previous));
+            if (sc >= 0.0) != (sp >= 0.0) {
+                let t = sp / (sp - sc);
+                output.push((
+                    previous.0 + (current.0 - previous.0) * t,
+                    previous.1 + (current.1 - previous.1) * t,
+                ));
+            }
+            if sc >= 0.0 {
+                output.push(current);
+            }
+        }
+        if output.is_empty() {
+            break;
+        }
+    }
+    output
+}
+
+/// Content of all `codes` in `format`, in the order they were detected
+pub fn format_codes(codes: &[DetectedQrCode], format: QrFormat) -> String {
+    match format {
+        QrFormat::Lines => codes
+            .iter()
+            .map(|qr| qr.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        QrFormat::Json => {
+            let codes: Vec<serde_json::Value> = codes
+                .iter()
+                .map(|qr| {
+                    serde_json::json!({
+                        "content": qr.content,
+                        "symbology": qr.symbology.label(),
+                        "output": qr.output_name,
+                        "x": qr.center_x,
+                        "y": qr.center_y,
+                        "corners": qr.corners,
+                    })
+                })
+                .collect();
+            serde_json::to_string_pretty(&codes).unwrap_or_default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_all_codes() {
+        let code = |content: &str, symbology| DetectedQrCode {
+            center_x: 10.0,
+            center_y: 20.5,
+            corners: [(0.0, 0.0); 4],
+            content: content.to_string(),
+            output_name: "DP-1".to_string(),
+            symbology,
+        };
+        let codes = [
+            code("https://example.com", Symbology::Qr),
+            code("4006381333931", Symbology::Ean13),
+        ];
+        assert_eq!(
+            format_codes(&codes, QrFormat::Lines),
+            "https://example.com\n4006381333931"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&format_codes(&codes, QrFormat::Json)).unwrap();
+        assert_eq!(json[1]["symbology"], "EAN-13");
+        assert_eq!(json[0]["content"], "https://example.com");
+        assert_eq!(json[0]["y"], 20.5);
+    }
+
+    #[test]
+    fn duplicates_overlap() {
+        let code = |corners: [(f32, f32); 4]| DetectedQrCode {
+            center_x: 0.0,
+            center_y: 0.0,
+            corners,
+            content: "https://example.com".to_string(),
+            output_name: "DP-1".to_string(),
+            symbology: Symbology::Qr,
+        };
+        let square = code([(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)]);
+        // Found again by another pass, slightly off and wound the other way
+        let again = code([(5.0, 2.0), (3.0, 104.0), (104.0, 101.0), (102.0, 1.0)]);
+        assert!(is_duplicate_qr(std::slice::from_ref(&square), &again));
+
+        // The same content printed twice, side by side
+        let twin = code([(60.0, 0.0), (160.0, 0.0), (160.0, 100.0), (60.0, 100.0)]);
+        assert!(!is_duplicate_qr(std::slice::from_ref(&square), &twin));
+    }
+}
@@ -0,0 +1,187 @@
+//! Text search over OCR results
+//!
+//! Finds a plain or regex query in recognized text and maps each hit back to
+//! word boxes, merged per line, so matches can be highlighted or redacted.
+
+use regex::RegexBuilder;
+
+use super::ocr::OcrTextOverlay;
+
+/// Box around a search hit, in logical coordinates relative to its output
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrMatch {
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Which output the match belongs to
+    pub output_name: String,
+}
+
+/// Find every occurrence of `query` in the OCR overlays.
+///
+/// Matching is case-insensitive; with `regex` off the query is taken
+/// literally. A hit spanning several lines yields one box per line.
+pub fn search_overlays(
+    overlays: &[OcrTextOverlay],
+    query: &str,
+    regex: bool,
+) -> Result<Vec<OcrMatch>, regex::Error> {
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let pattern = if regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+    let re = RegexBuilder::new(&pattern).case_insensitive(true).build()?;
+
+    let mut matches = Vec::new();
+    for overlay in overlays {
+        // Rebuild the block text from its words so byte offsets map back to boxes
+        let mut text = String::new();
+        let mut spans = Vec::with_capacity(overlay.words.len());
+        for word in &overlay.words {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            spans.push(text.len()..text.len() + word.text.len());
+            text.push_str(&word.text);
+        }
+        if overlay.words.is_empty() {
+            text = overlay.text.clone();
+        }
+
+        for m in re.find_iter(&text) {
+            if m.is_empty() {
+                continue;
+            }
+            if overlay.words.is_empty() {
+                matches.push(OcrMatch {
+                    left: overlay.left,
+                    top: overlay.top,
+                    width: overlay.width,
+                    height: overlay.height,
+                    output_name: overlay.output_name.clone(),
+                });
+                continue;
+            }
+
+            // Union the hit words line by line
+            let mut line: Option<LineHits> = None;
+            for (word, span) in overlay.words.iter().zip(&spans) {
+                if span.end <= m.start() || span.start >= m.end() {
+                    continue;
+                }
+                let (x1, y1) = (word.left, word.top);
+                let (x2, y2) = (word.left + word.width, word.top + word.height);
+                let num = (word.par_num, word.line_num);
+                line = match line {
+                    Some((current, l, t, r, b)) if current == num => {
+                        Some((num, l.min(x1), t.min(y1), r.max(x2), b.max(y2)))
+                    }
+                    other => {
+                        if let Some(done) = other {
+                            matches.push(line_match(done, &overlay.output_name));
+                        }
+                        Some((num, x1, y1, x2, y2))
+                    }
+                };
+            }
+            if let Some(done) = line {
+                matches.push(line_match(done, &overlay.output_name));
+            }
+        }
+    }
+    Ok(matches)
+}
+
+/// Paragraph and line number, then the edges (left, top, right, bottom) of
+/// the hit words on that line
+type LineHits = ((i32, i32), f32, f32, f32, f32);
+
+fn line_match((_, left, top, right, bottom): LineHits, output: &str) -> OcrMatch {
+    OcrMatch {
+        left,
+        top,
+        width: right - left,
+        height: bottom - top,
+        output_name: output.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::ocr::OcrWord;
+
+    fn word(text: &str, left: f32, line_num: i32) -> OcrWord {
+        OcrWord {
+            left,
+            top: line_num as f32 * 20.0,
+            width: 10.0 * text.len() as f32,
+            height: 16.0,
+            text: text.to_string(),
+            par_num: 1,
+            line_num,
+        }
+    }
+
+    fn block(words: Vec<OcrWord>) -> OcrTextOverlay {
+        OcrTextOverlay {
+            left: 0.0,
+            top: 0.0,
+            width: 200.0,
+            height: 60.0,
+            text: words
+                .iter()
+                .map(|w| w.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+            block_num: 1,
+            output_name: "test".to_string(),
+            words,
+        }
+    }
+
+    #[test]
+    fn plain_query_matches_words_case_insensitively() {
+        let overlays = [block(vec![
+            word("Hello", 0.0, 0),
+            word("alice,", 60.0, 0),
+            word("ALICE", 0.0, 1),
+        ])];
+        let matches = search_overlays(&overlays, "alice", false).unwrap();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].left, 60.0);
+        assert_eq!(matches[1].top, 20.0);
+        // Regex metacharacters are literal in plain mode
+        assert!(
+            search_overlays(&overlays, "a.ice", false)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn multi_word_hit_is_merged_per_line() {
+        let overlays = [block(vec![
+            word("Jane", 0.0, 0),
+            word("Doe", 50.0, 0),
+            word("Smith", 0.0, 1),
+        ])];
+        let matches = search_overlays(&overlays, "jane doe", false).unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].left, matches[0].width), (0.0, 80.0));
+
+        let across = search_overlays(&overlays, r"doe\s+smith", true).unwrap();
+        assert_eq!(across.len(), 2, "one box per line");
+    }
+
+    #[test]
+    fn invalid_regex_is_an_error() {
+        assert!(search_overlays(&[], "(", true).is_err());
+        assert!(search_overlays(&[], "", true).unwrap().is_empty());
+    }
+}
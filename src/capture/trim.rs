@@ -0,0 +1,100 @@
+//! Auto-trim: locate the content inside uniform-colour margins
+//!
+//! Window captures often carry a band of empty desktop or window background
+//! around the interesting part. The margin colour is taken from the image
+//! corners, and whole rows/columns matching it are stripped from every side.
+
+use image::{Rgba, RgbaImage};
+
+/// Per-channel difference still treated as margin (absorbs gradients and dithering)
+pub const TRIM_TOLERANCE: u8 = 8;
+
+/// Content bounds `(left, top, right, bottom)` in pixels, right/bottom exclusive
+///
+/// Returns None when the image is empty or a single uniform colour.
+pub fn content_bounds(img: &RgbaImage, tolerance: u8) -> Option<(u32, u32, u32, u32)> {
+    let (width, height) = img.dimensions();
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let margin = margin_color(img, tolerance);
+    let is_margin = |x: u32, y: u32| similar(*img.get_pixel(x, y), margin, tolerance);
+
+    let row_is_margin = |y: u32| (0..width).all(|x| is_margin(x, y));
+    let top = (0..height).find(|&y| !row_is_margin(y))?;
+    let bottom = (0..height).rev().find(|&y| !row_is_margin(y))? + 1;
+
+    let col_is_margin = |x: u32| (top..bottom).all(|y| is_margin(x, y));
+    let left = (0..width).find(|&x| !col_is_margin(x))?;
+    let right = (0..width).rev().find(|&x| !col_is_margin(x))? + 1;
+
+    Some((left, top, right, bottom))
+}
+
+/// The colour shared by most corners, so content touching one corner doesn't win
+fn margin_color(img: &RgbaImage, tolerance: u8) -> Rgba<u8> {
+    let (width, height) = img.dimensions();
+    let corners = [
+        *img.get_pixel(0, 0),
+        *img.get_pixel(width - 1, 0),
+        *img.get_pixel(0, height - 1),
+        *img.get_pixel(width - 1, height - 1),
+    ];
+
+    let mut best = (corners[0], 0);
+    for corner in corners {
+        let votes = corners
+            .iter()
+            .filter(|&&other| similar(corner, other, tolerance))
+            .count();
+        if votes > best.1 {
+            best = (corner, votes);
+        }
+    }
+    best.0
+}
+
+fn similar(a: Rgba<u8>, b: Rgba<u8>, tolerance: u8) -> bool {
+    a.0.iter()
+        .zip(b.0)
+        .all(|(&x, y)| x.abs_diff(y) <= tolerance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canvas(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_pixel(width, height, Rgba([250, 250, 250, 255]))
+    }
+
+    fn fill(img: &mut RgbaImage, x: std::ops::Range<u32>, y: std::ops::Range<u32>) {
+        for py in y {
+            for px in x.clone() {
+                img.put_pixel(px, py, Rgba([20, 40, 60, 255]));
+            }
+        }
+    }
+
+    #[test]
+    fn strips_uniform_margins() {
+        let mut img = canvas(12, 10);
+        fill(&mut img, 3..7, 2..5);
+        // Slight noise in the margin is still margin
+        img.put_pixel(10, 8, Rgba([245, 252, 250, 255]));
+        assert_eq!(content_bounds(&img, TRIM_TOLERANCE), Some((3, 2, 7, 5)));
+    }
+
+    #[test]
+    fn content_touching_a_corner_is_kept() {
+        let mut img = canvas(10, 10);
+        fill(&mut img, 0..4, 0..3);
+        assert_eq!(content_bounds(&img, TRIM_TOLERANCE), Some((0, 0, 4, 3)));
+    }
+
+    #[test]
+    fn uniform_image_has_no_content() {
+        assert_eq!(content_bounds(&canvas(8, 8), TRIM_TOLERANCE), None);
+    }
+}
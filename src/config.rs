@@ -51,6 +51,193 @@ impl ShapeColor {
             255,
         ]
     }
+
+    /// Build from 8-bit RGB channels
+    pub fn from_rgb_u8(rgb: [u8; 3]) -> Self {
+        Self {
+            r: rgb[0] as f32 / 255.0,
+            g: rgb[1] as f32 / 255.0,
+            b: rgb[2] as f32 / 255.0,
+        }
+    }
+}
+
+/// User-defined, named set of annotation colors shared by all color choosers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColorPalette {
+    pub name: String,
+    pub colors: Vec<ShapeColor>,
+}
+
+/// Background behind a beautified screenshot
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BeautifyBackground {
+    Solid(ShapeColor),
+    /// Linear gradient; `angle` in degrees (0 = left to right, 90 = top to bottom)
+    Gradient {
+        from: ShapeColor,
+        to: ShapeColor,
+        angle: f32,
+    },
+}
+
+/// Named export style that wraps the screenshot in padding on a background,
+/// with rounded corners and a drop shadow. Sizes are in logical pixels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeautifyPreset {
+    pub name: String,
+    pub padding: u32,
+    pub background: BeautifyBackground,
+    pub corner_radius: u32,
+    /// Drop shadow blur radius (0 = no shadow)
+    pub shadow: u32,
+}
+
+/// Lets presets be listed directly in a dropdown by name
+impl AsRef<str> for BeautifyPreset {
+    fn as_ref(&self) -> &str {
+        &self.name
+    }
+}
+
+impl BeautifyPreset {
+    fn new(name: &str, padding: u32, background: BeautifyBackground, corner_radius: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            padding,
+            background,
+            corner_radius,
+            shadow: 24,
+        }
+    }
+}
+
+fn default_beautify_presets() -> Vec<BeautifyPreset> {
+    let rgb = |r: u8, g: u8, b: u8| ShapeColor {
+        r: r as f32 / 255.0,
+        g: g as f32 / 255.0,
+        b: b as f32 / 255.0,
+    };
+    vec![
+        BeautifyPreset::new(
+            "Ocean",
+            64,
+            BeautifyBackground::Gradient {
+                from: rgb(0x43, 0x8c, 0xf0),
+                to: rgb(0x7a, 0x3c, 0xe0),
+                angle: 45.0,
+            },
+            12,
+        ),
+        BeautifyPreset::new(
+            "Sunset",
+            64,
+            BeautifyBackground::Gradient {
+                from: rgb(0xff, 0x9a, 0x5c),
+                to: rgb(0xe0, 0x3c, 0x7a),
+                angle: 45.0,
+            },
+            12,
+        ),
+        BeautifyPreset::new(
+            "Light",
+            48,
+            BeautifyBackground::Solid(rgb(0xf2, 0xf2, 0xf5)),
+            10,
+        ),
+        BeautifyPreset::new(
+            "Dark",
+            48,
+            BeautifyBackground::Solid(rgb(0x1e, 0x1e, 0x24)),
+            10,
+        ),
+    ]
+}
+
+fn default_beautify_preset() -> String {
+    "Ocean".to_string()
+}
+
+/// Tool an annotation preset switches to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PresetTool {
+    Shape(ShapeTool),
+    Redact(RedactTool),
+}
+
+/// Named annotation style, bound to a number key (the first preset is 1)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnnotationPreset {
+    pub name: String,
+    pub tool: PresetTool,
+    pub color: ShapeColor,
+    /// Stroke width in logical pixels
+    pub width: f32,
+    pub shadow: bool,
+    /// Whether the shadow color is picked to contrast with the image below
+    #[serde(default)]
+    pub auto_contrast: bool,
+    /// Whether circles and rectangles get a translucent fill
+    pub fill: bool,
+}
+
+/// Number of presets reachable from the number keys 1-9
+pub const MAX_KEYED_PRESETS: usize = 9;
+
+fn default_annotation_presets() -> Vec<AnnotationPreset> {
+    let preset = |name: String, tool, color, width, shadow, fill| AnnotationPreset {
+        name,
+        tool,
+        color,
+        width,
+        shadow,
+        auto_contrast: false,
+        fill,
+    };
+    let red = ShapeColor::default();
+    let yellow = ShapeColor {
+        r: 1.0,
+        g: 0.85,
+        b: 0.0,
+    };
+    vec![
+        preset(
+            fl!("preset-red-arrow"),
+            PresetTool::Shape(ShapeTool::Arrow),
+            red,
+            3.0,
+            true,
+            false,
+        ),
+        preset(
+            fl!("preset-red-thick-arrow"),
+            PresetTool::Shape(ShapeTool::Arrow),
+            red,
+            6.0,
+            true,
+            false,
+        ),
+        preset(
+            fl!("preset-yellow-highlighter"),
+            PresetTool::Shape(ShapeTool::Rectangle),
+            yellow,
+            1.0,
+            false,
+            true,
+        ),
+        preset(
+            fl!("preset-blurred-box"),
+            PresetTool::Redact(RedactTool::Blur),
+            red,
+            3.0,
+            true,
+            false,
+        ),
+    ]
+}
+
+fn default_shape_width() -> f32 {
+    3.0
 }
 
 /// Save location choice for UI selection
@@ -77,6 +264,7 @@ pub enum ShapeTool {
     Arrow,
     Circle,
     Rectangle,
+    Spotlight,
 }
 
 impl ShapeTool {
@@ -85,7 +273,8 @@ impl ShapeTool {
         match self {
             ShapeTool::Arrow => ShapeTool::Circle,
             ShapeTool::Circle => ShapeTool::Rectangle,
-            ShapeTool::Rectangle => ShapeTool::Arrow,
+            ShapeTool::Rectangle => ShapeTool::Spotlight,
+            ShapeTool::Spotlight => ShapeTool::Arrow,
         }
     }
 
@@ -95,6 +284,7 @@ impl ShapeTool {
             ShapeTool::Arrow => fl!("draw-arrow"),
             ShapeTool::Circle => fl!("draw-circle"),
             ShapeTool::Rectangle => fl!("draw-rectangle"),
+            ShapeTool::Spotlight => fl!("draw-spotlight"),
         }
     }
 }
@@ -115,6 +305,7 @@ pub enum RedactTool {
     #[default]
     Redact,
     Pixelate,
+    Blur,
 }
 
 impl RedactTool {
@@ -122,7 +313,8 @@ impl RedactTool {
     pub fn next(self) -> Self {
         match self {
             RedactTool::Redact => RedactTool::Pixelate,
-            RedactTool::Pixelate => RedactTool::Redact,
+            RedactTool::Pixelate => RedactTool::Blur,
+            RedactTool::Blur => RedactTool::Redact,
         }
     }
 
@@ -131,6 +323,7 @@ impl RedactTool {
         match self {
             RedactTool::Redact => fl!("redact-tool"),
             RedactTool::Pixelate => fl!("pixelate-tool"),
+            RedactTool::Blur => fl!("blur-tool"),
         }
     }
 
@@ -139,6 +332,83 @@ impl RedactTool {
         match self {
             RedactTool::Redact => 0,
             RedactTool::Pixelate => 1,
+            RedactTool::Blur => 2,
+        }
+    }
+}
+
+/// Text format the color picker copies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorFormat {
+    #[default]
+    Hex,
+    Rgb,
+    Hsl,
+}
+
+impl ColorFormat {
+    /// Get the next format in the cycle
+    pub fn next(self) -> Self {
+        match self {
+            ColorFormat::Hex => ColorFormat::Rgb,
+            ColorFormat::Rgb => ColorFormat::Hsl,
+            ColorFormat::Hsl => ColorFormat::Hex,
+        }
+    }
+
+    /// Short label shown in the color picker tooltip
+    pub fn label(self) -> &'static str {
+        match self {
+            ColorFormat::Hex => "HEX",
+            ColorFormat::Rgb => "RGB",
+            ColorFormat::Hsl => "HSL",
+        }
+    }
+
+    /// Get the index of this format (for indicator dots)
+    pub fn index(self) -> usize {
+        match self {
+            ColorFormat::Hex => 0,
+            ColorFormat::Rgb => 1,
+            ColorFormat::Hsl => 2,
+        }
+    }
+}
+
+/// Engine used for text recognition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OcrEngine {
+    /// The system tesseract binary, with its installed language packs
+    #[default]
+    Tesseract,
+    /// The bundled ocrs engine; models are downloaded on first use
+    Builtin,
+}
+
+/// What the measure tool measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MeasureKind {
+    /// Straight distance and angle between two points
+    #[default]
+    Line,
+    /// Width and height of a box
+    Box,
+}
+
+impl MeasureKind {
+    /// Get the next kind in the cycle
+    pub fn next(self) -> Self {
+        match self {
+            MeasureKind::Line => MeasureKind::Box,
+            MeasureKind::Box => MeasureKind::Line,
+        }
+    }
+
+    /// Get the index of this kind (for indicator dots)
+    pub fn index(self) -> usize {
+        match self {
+            MeasureKind::Line => 0,
+            MeasureKind::Box => 1,
         }
     }
 }
@@ -197,13 +467,79 @@ pub struct SnapPeaConfig {
     pub shape_color: ShapeColor,
     /// Whether to add shadow/border to shapes
     pub shape_shadow: bool,
+    /// Stroke width of shapes in logical pixels (range 1-12)
+    #[serde(default = "default_shape_width")]
+    pub shape_width: f32,
+    /// Whether circles and rectangles get a translucent fill
+    #[serde(default)]
+    pub shape_fill: bool,
+    /// Whether shape shadows pick a color that contrasts with the image below
+    #[serde(default)]
+    pub shape_auto_contrast: bool,
+    /// Named annotation styles, applied with the number keys
+    #[serde(default = "default_annotation_presets")]
+    pub annotation_presets: Vec<AnnotationPreset>,
     /// Primary redact tool shown in the button
     pub primary_redact_tool: RedactTool,
     /// Pixelation block size (larger = more pixelated, range 4-64)
     pub pixelation_block_size: u32,
+    /// Mix noise into pixelation and quantise block colours so text can't be recovered
+    #[serde(default)]
+    pub secure_pixelation: bool,
+    /// Extra regexes treated as sensitive data when auto-redacting OCR text
+    #[serde(default)]
+    pub sensitive_patterns: Vec<String>,
+    /// Gaussian blur radius in logical pixels (range 2-50)
+    #[serde(default = "default_blur_radius")]
+    pub blur_radius: u32,
+    /// Spotlight dim strength outside the highlighted regions (range 0.1-0.9)
+    #[serde(default = "default_spotlight_dim")]
+    pub spotlight_dim: f32,
+    /// Whether spotlights highlight ellipses instead of rectangles
+    #[serde(default)]
+    pub spotlight_ellipse: bool,
+    /// Whether spotlights also blur the area outside the highlight
+    #[serde(default)]
+    pub spotlight_blur: bool,
     /// Magnifier zoom level (range 1.5-10.0)
     #[serde(default = "default_magnifier_magnification")]
     pub magnifier_magnification: f32,
+    /// Whether the magnifier tool draws rectangular callouts instead of loupes
+    #[serde(default)]
+    pub magnifier_callout: bool,
+    /// Callout frame and connector width in logical pixels (range 1-10)
+    #[serde(default = "default_magnifier_callout_border")]
+    pub magnifier_callout_border: f32,
+    /// Format the color picker copies to the clipboard
+    #[serde(default)]
+    pub color_picker_format: ColorFormat,
+    /// Whether picked colors also become the shape annotation color
+    #[serde(default)]
+    pub color_picker_sets_shape_color: bool,
+    /// Whether the measure tool measures lines or boxes
+    #[serde(default)]
+    pub measure_kind: MeasureKind,
+    /// Whether measurements are kept as dimension annotations in saved images
+    #[serde(default)]
+    pub measure_in_export: bool,
+    /// Name of the stamp the stamp tool places
+    #[serde(default = "default_stamp_name")]
+    pub stamp_name: String,
+    /// Stamp size (longer side) in logical pixels for click-placed stamps
+    #[serde(default = "default_stamp_size")]
+    pub stamp_size: f32,
+    /// Engine used for OCR
+    #[serde(default)]
+    pub ocr_engine: OcrEngine,
+    /// Whether OCR straightens skewed text first
+    #[serde(default)]
+    pub ocr_deskew: bool,
+    /// Whether the built-in OCR engine may download its models when missing
+    #[serde(default)]
+    pub ocr_model_download: bool,
+    /// Tesseract languages used for OCR, combined as `eng+deu+jpn`
+    #[serde(default = "default_ocr_languages")]
+    pub ocr_languages: Vec<String>,
     /// Delay in seconds for the "delayed screenshot" toolbar button
     #[serde(default = "default_capture_delay_secs")]
     pub capture_delay_secs: u32,
@@ -220,6 +556,12 @@ pub struct SnapPeaConfig {
     pub video_framerate: u32,
     /// Whether to show cursor in recordings
     pub video_show_cursor: bool,
+    /// Recently used annotation colors, most recent first
+    #[serde(default)]
+    pub recent_colors: Vec<ShapeColor>,
+    /// Named color palettes offered by the shape and pencil popups
+    #[serde(default)]
+    pub color_palettes: Vec<ColorPalette>,
     /// Pencil color for recording annotations (RGB, 0.0-1.0)
     #[serde(default = "default_pencil_color")]
     pub pencil_color: ShapeColor,
@@ -232,12 +574,45 @@ pub struct SnapPeaConfig {
     /// Whether to hide toolbar to system tray when recording
     #[serde(default)]
     pub hide_toolbar_to_tray: bool,
+    /// Whether saved/copied screenshots get the beautify frame
+    #[serde(default)]
+    pub beautify_enabled: bool,
+    /// Name of the beautify preset in use
+    #[serde(default = "default_beautify_preset")]
+    pub beautify_preset: String,
+    /// Available beautify presets (user-editable)
+    #[serde(default = "default_beautify_presets")]
+    pub beautify_presets: Vec<BeautifyPreset>,
+}
+
+fn default_blur_radius() -> u32 {
+    12
+}
+
+fn default_spotlight_dim() -> f32 {
+    0.6
 }
 
 fn default_magnifier_magnification() -> f32 {
     2.5
 }
 
+fn default_magnifier_callout_border() -> f32 {
+    3.0
+}
+
+fn default_stamp_name() -> String {
+    "OK".to_string()
+}
+
+fn default_stamp_size() -> f32 {
+    64.0
+}
+
+fn default_ocr_languages() -> Vec<String> {
+    vec![crate::capture::ocr::DEFAULT_LANGUAGE.to_string()]
+}
+
 fn default_capture_delay_secs() -> u32 {
     3
 }
@@ -331,12 +706,46 @@ impl Default for SnapPeaConfig {
             shape_color: ShapeColor::default(),
             // Shadow enabled by default (matches current arrow behavior)
             shape_shadow: true,
+            // Outlines as thick as before widths were configurable
+            shape_width: default_shape_width(),
+            shape_fill: false,
+            shape_auto_contrast: false,
+            annotation_presets: default_annotation_presets(),
             // Default to Redact as primary redact tool
             primary_redact_tool: RedactTool::Redact,
             // Default pixelation block size (16 is a good balance)
             pixelation_block_size: 16,
+            // Plain block averaging unless secure mode is requested
+            secure_pixelation: false,
+            // Only the built-in sensitive data patterns
+            sensitive_patterns: Vec::new(),
+            // Default blur radius (strong enough to hide small text)
+            blur_radius: default_blur_radius(),
+            // Default spotlight: rectangular, fairly dark, no blur
+            spotlight_dim: default_spotlight_dim(),
+            spotlight_ellipse: false,
+            spotlight_blur: false,
             // Default magnifier zoom level
             magnifier_magnification: default_magnifier_magnification(),
+            // Circular loupe by default; callouts get a frame as thick as shapes
+            magnifier_callout: false,
+            magnifier_callout_border: default_magnifier_callout_border(),
+            // Color picker copies hex and leaves the shape color alone
+            color_picker_format: ColorFormat::Hex,
+            color_picker_sets_shape_color: false,
+            // Measurements are an on-screen aid unless asked for in the export
+            measure_kind: MeasureKind::Line,
+            measure_in_export: false,
+            // Built-in pass mark at a size that reads well next to UI text
+            stamp_name: default_stamp_name(),
+            stamp_size: default_stamp_size(),
+            // System tesseract, English until other language packs are picked
+            ocr_engine: OcrEngine::Tesseract,
+            // Screenshots are rarely skewed, and deskewing costs time
+            ocr_deskew: false,
+            // Nothing is fetched from the network until the user allows it
+            ocr_model_download: false,
+            ocr_languages: default_ocr_languages(),
             // Default delayed-screenshot delay
             capture_delay_secs: default_capture_delay_secs(),
             // Default toolbar position at the bottom
@@ -348,10 +757,17 @@ impl Default for SnapPeaConfig {
             video_container: Container::Mp4,
             video_framerate: 60,
             video_show_cursor: true, // Show cursor by default
+            // No custom colors until the user picks or saves some
+            recent_colors: Vec::new(),
+            color_palettes: Vec::new(),
             pencil_color: default_pencil_color(),
             pencil_fade_duration: default_pencil_fade_duration(),
             pencil_thickness: default_pencil_thickness(),
             hide_toolbar_to_tray: false,
+            // Plain screenshots unless the beautify frame is switched on
+            beautify_enabled: false,
+            beautify_preset: default_beautify_preset(),
+            beautify_presets: default_beautify_presets(),
         }
     }
 }
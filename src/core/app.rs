@@ -272,7 +272,11 @@ impl cosmic::Application for App {
         } else if let Some(indicator) = &self.recording_indicator {
             if indicator.window_id == id {
                 // Render the blinking recording indicator
-                render_recording_indicator(indicator, self.toolbar_visible)
+                render_recording_indicator(
+                    indicator,
+                    self.toolbar_visible,
+                    self.screenshot_args.as_ref().map(|args| &args.ui),
+                )
             } else {
                 cosmic::iced::widget::space()
                     .width(cosmic::iced::core::Length::Fixed(1.0))
@@ -919,7 +923,7 @@ impl cosmic::Application for App {
 
         let mut subscriptions = vec![
             screenshot_sub,
-            listen_with(|e, _, _| match e {
+            listen_with(|e, status, _| match e {
                 cosmic::iced::core::Event::PlatformSpecific(
                     cosmic::iced::core::event::PlatformSpecific::Wayland(
                         cosmic::iced::core::event::wayland::Event::Output(o_event, wl_output),
@@ -934,7 +938,10 @@ impl cosmic::Application for App {
                         ),
                     ),
                 ) => Some(Msg::LayerClosed(id)),
-                cosmic::iced::core::Event::Keyboard(keyboard_event) => {
+                // Keys consumed by a widget (e.g. typing into a text field) aren't shortcuts
+                cosmic::iced::core::Event::Keyboard(keyboard_event)
+                    if status == cosmic::iced::core::event::Status::Ignored =>
+                {
                     Some(Msg::Keyboard(keyboard_event))
                 }
                 _ => None,
@@ -1266,11 +1273,38 @@ pub(crate) fn direct_screenshot_subscription(
                         shape_popup_open: false,
                         shape_color: config.shape_color,
                         shape_shadow: config.shape_shadow,
+                        shape_width: config.shape_width,
+                        shape_fill: config.shape_fill,
+                        shape_auto_contrast: config.shape_auto_contrast,
+                        annotation_presets: config.annotation_presets.clone(),
                         primary_redact_tool: config.primary_redact_tool,
                         redact_popup_open: false,
                         pixelation_block_size: config.pixelation_block_size,
+                        secure_pixelation: config.secure_pixelation,
+                        blur_radius: config.blur_radius,
+                        ocr_search_query: String::new(),
+                        ocr_search_regex: false,
+                        sensitive_patterns: config.sensitive_patterns.clone(),
+                        spotlight_dim: config.spotlight_dim,
+                        spotlight_ellipse: config.spotlight_ellipse,
+                        spotlight_blur: config.spotlight_blur,
                         magnifier_popup_open: false,
                         magnifier_magnification: config.magnifier_magnification,
+                        magnifier_callout: config.magnifier_callout,
+                        magnifier_callout_border: config.magnifier_callout_border,
+                        color_picker_format: config.color_picker_format,
+                        color_picker_sets_shape_color: config.color_picker_sets_shape_color,
+                        recent_colors: config.recent_colors.clone(),
+                        color_palettes: config.color_palettes.clone(),
+                        unsaved_names: false,
+                        color_hex_input: String::new(),
+                        color_hsv: None,
+                        measure_kind: config.measure_kind,
+                        measure_in_export: config.measure_in_export,
+                        stamp_popup_open: false,
+                        stamp_name: config.stamp_name.clone(),
+                        stamp_size: config.stamp_size,
+                        stamp_library: Vec::new(),
                         capture_delay_secs: config.capture_delay_secs,
                         magnifier_enabled: config.magnifier_enabled,
                         save_location_setting: config.save_location,
@@ -1278,6 +1312,9 @@ pub(crate) fn direct_screenshot_subscription(
                         video_save_location_setting: config.video_save_location,
                         video_custom_save_path: config.video_custom_save_path.clone(),
                         copy_to_clipboard_on_save: config.copy_to_clipboard_on_save,
+                        beautify_enabled: config.beautify_enabled,
+                        beautify_preset: config.beautify_preset.clone(),
+                        beautify_presets: config.beautify_presets.clone(),
                         toolbar_unhovered_opacity: config.toolbar_unhovered_opacity,
                         toolbar_is_hovered: false,
                         toolbar_hover_animation: cosmic::iced::Animation::new(false)
@@ -1285,6 +1322,13 @@ pub(crate) fn direct_screenshot_subscription(
                             .easing(animation::Easing::EaseInOut),
                         toolbar_opacity_save_id: 0,
                         tesseract_available: crate::capture::ocr::is_tesseract_available(),
+                        ocr_engine: config.ocr_engine,
+                        ocr_deskew: config.ocr_deskew,
+                        ocr_model_download: config.ocr_model_download,
+                        ocr_languages: config.ocr_languages.clone(),
+                        ocr_installed_languages: Vec::new(),
+                        ocr_capture_languages: None,
+                        ocr_popup_open: false,
                         available_encoders: Vec::new(),
                         encoder_displays: Vec::new(),
                         selected_encoder: config.video_encoder.clone(),
@@ -1304,6 +1348,7 @@ pub(crate) fn direct_screenshot_subscription(
                         toolbar_bounds: None,
                         hide_toolbar_to_tray: config.hide_toolbar_to_tray,
                         move_offset: None,
+                        crop_mode: false,
                         is_default_portal: crate::screenshot::is_snappea_default_portal(),
                     },
                 };
@@ -1370,6 +1415,7 @@ fn tray_subscription(rx: CbReceiver<TrayAction>) -> Subscription<Msg> {
 fn render_recording_indicator(
     indicator: &RecordingIndicator,
     toolbar_visible: bool,
+    ui: Option<&crate::session::state::UiState>,
 ) -> cosmic::Element<'static, Msg> {
     use cosmic::iced::core::Length;
     use cosmic::iced::widget::canvas::{self, Geometry, Path, Stroke};
@@ -1748,16 +1794,39 @@ fn render_recording_indicator(
 
     // Build pencil popup if open
     let pencil_popup = if pencil_popup_open {
+        // Recent colors and palettes are shared with the screenshot session
+        let (recent_colors, palettes, hex_input, hsv) = match ui {
+            Some(ui) => (
+                &ui.recent_colors[..],
+                &ui.color_palettes[..],
+                ui.color_hex_input.as_str(),
+                ui.color_hsv,
+            ),
+            None => (&[][..], &[][..], "", None),
+        };
         Some(crate::widget::tool_button::build_pencil_popup(
-            pencil_color,
+            crate::widget::tool_button::build_color_section(
+                pencil_color,
+                recent_colors,
+                palettes,
+                hex_input,
+                hsv,
+                &|c| {
+                    Msg::Screenshot(crate::session::messages::Msg::Tool(
+                        crate::session::messages::ToolMsg::SetPencilColor(c),
+                    ))
+                },
+                |edit| {
+                    Msg::Screenshot(crate::session::messages::Msg::edit_color(
+                        crate::session::messages::ColorTarget::Pencil,
+                        edit,
+                    ))
+                },
+                8, // space_xs
+            ),
             pencil_fade_duration,
             pencil_thickness,
             true, // has annotations (always enable clear during recording)
-            &|c| {
-                Msg::Screenshot(crate::session::messages::Msg::Tool(
-                    crate::session::messages::ToolMsg::SetPencilColor(c),
-                ))
-            },
             |d| {
                 Msg::Screenshot(crate::session::messages::Msg::Tool(
                     crate::session::messages::ToolMsg::SetPencilFadeDuration(d),
@@ -1834,7 +1903,10 @@ fn render_recording_indicator(
         if let Some(popup) = pencil_popup {
             // Popup layer - positioned relative to toolbar, above or below based on space
             let popup_gap = 16.0_f32; // Gap between popup and toolbar
-            let popup_height = 380.0_f32; // Approximate popup height (must match screenshot/mod.rs)
+            // Approximate popup height, sized in screenshot/mod.rs when the popup opened
+            let popup_height = indicator
+                .pencil_popup_bounds
+                .map_or(380.0_f32, |bounds| bounds.height);
             let toolbar_height = 72.0_f32; // Toolbar height including padding
             let output_size = indicator.output_size;
 
@@ -2086,11 +2158,38 @@ async fn trigger_screenshot(
             shape_popup_open: false,
             shape_color: config.shape_color,
             shape_shadow: config.shape_shadow,
+            shape_width: config.shape_width,
+            shape_fill: config.shape_fill,
+            shape_auto_contrast: config.shape_auto_contrast,
+            annotation_presets: config.annotation_presets.clone(),
             primary_redact_tool: config.primary_redact_tool,
             redact_popup_open: false,
             pixelation_block_size: config.pixelation_block_size,
+            secure_pixelation: config.secure_pixelation,
+            blur_radius: config.blur_radius,
+            ocr_search_query: String::new(),
+            ocr_search_regex: false,
+            sensitive_patterns: config.sensitive_patterns.clone(),
+            spotlight_dim: config.spotlight_dim,
+            spotlight_ellipse: config.spotlight_ellipse,
+            spotlight_blur: config.spotlight_blur,
             magnifier_popup_open: false,
             magnifier_magnification: config.magnifier_magnification,
+            magnifier_callout: config.magnifier_callout,
+            magnifier_callout_border: config.magnifier_callout_border,
+            color_picker_format: config.color_picker_format,
+            color_picker_sets_shape_color: config.color_picker_sets_shape_color,
+            recent_colors: config.recent_colors.clone(),
+            color_palettes: config.color_palettes.clone(),
+            unsaved_names: false,
+            color_hex_input: String::new(),
+            color_hsv: None,
+            measure_kind: config.measure_kind,
+            measure_in_export: config.measure_in_export,
+            stamp_popup_open: false,
+            stamp_name: config.stamp_name.clone(),
+            stamp_size: config.stamp_size,
+            stamp_library: Vec::new(),
             capture_delay_secs: config.capture_delay_secs,
             magnifier_enabled: config.magnifier_enabled,
             save_location_setting: config.save_location,
@@ -2098,6 +2197,9 @@ async fn trigger_screenshot(
             video_save_location_setting: config.video_save_location,
             video_custom_save_path: config.video_custom_save_path.clone(),
             copy_to_clipboard_on_save: config.copy_to_clipboard_on_save,
+            beautify_enabled: config.beautify_enabled,
+            beautify_preset: config.beautify_preset.clone(),
+            beautify_presets: config.beautify_presets.clone(),
             toolbar_unhovered_opacity: config.toolbar_unhovered_opacity,
             toolbar_is_hovered: false,
             toolbar_hover_animation: cosmic::iced::Animation::new(false)
@@ -2105,6 +2207,13 @@ async fn trigger_screenshot(
                 .easing(animation::Easing::EaseInOut),
             toolbar_opacity_save_id: 0,
             tesseract_available: crate::capture::ocr::is_tesseract_available(),
+            ocr_engine: config.ocr_engine,
+            ocr_deskew: config.ocr_deskew,
+            ocr_model_download: config.ocr_model_download,
+            ocr_languages: config.ocr_languages.clone(),
+            ocr_installed_languages: Vec::new(),
+            ocr_capture_languages: None,
+            ocr_popup_open: false,
             available_encoders: Vec::new(),
             encoder_displays: Vec::new(),
             selected_encoder: config.video_encoder.clone(),
@@ -2124,6 +2233,7 @@ async fn trigger_screenshot(
             toolbar_bounds: None,
             hide_toolbar_to_tray: config.hide_toolbar_to_tray,
             move_offset: None,
+            crop_mode: false,
             is_default_portal: crate::screenshot::is_snappea_default_portal(),
         },
     };
@@ -2,7 +2,11 @@
 //!
 //! All annotation types store coordinates in global logical coordinates.
 
-use crate::config::ShapeColor;
+use std::sync::Arc;
+
+use image::RgbaImage;
+
+use crate::config::{MeasureKind, ShapeColor};
 
 /// Arrow annotation for drawing on screenshots
 #[derive(Clone, Debug, PartialEq)]
@@ -13,10 +17,52 @@ pub struct ArrowAnnotation {
     /// End point in global logical coordinates
     pub end_x: f32,
     pub end_y: f32,
+    /// Control point of a curved (quadratic Bezier) arrow in global logical
+    /// coordinates; `None` for a straight arrow
+    pub control: Option<(f32, f32)>,
+    /// Stroke width setting in logical pixels (see `render::geometry::arrow::thickness`)
+    pub width: f32,
     /// Color of this arrow
     pub color: ShapeColor,
     /// Whether to draw shadow/border
     pub shadow: bool,
+    /// Whether the shadow/border color is picked to contrast with the image below
+    pub auto_contrast: bool,
+}
+
+/// Dragging an arrow's bend handle this close to the middle of the straight
+/// line makes the arrow straight again (logical px)
+pub const ARROW_STRAIGHTEN_DISTANCE: f32 = 4.0;
+
+impl ArrowAnnotation {
+    /// Where the bend handle sits: the middle of the curve (or of the line)
+    pub fn bend_point(&self) -> (f32, f32) {
+        let mid = self.chord_mid();
+        match self.control {
+            // A quadratic Bezier at t = 0.5 is halfway between the chord middle
+            // and the control point
+            Some((cx, cy)) => ((mid.0 + cx) / 2.0, (mid.1 + cy) / 2.0),
+            None => mid,
+        }
+    }
+
+    /// Bend the arrow so its curve passes through (x, y), or straighten it when
+    /// (x, y) is back on the middle of the straight line
+    pub fn bend_through(&mut self, x: f32, y: f32) {
+        let mid = self.chord_mid();
+        self.control = if (x - mid.0).hypot(y - mid.1) <= ARROW_STRAIGHTEN_DISTANCE {
+            None
+        } else {
+            Some((2.0 * x - mid.0, 2.0 * y - mid.1))
+        };
+    }
+
+    fn chord_mid(&self) -> (f32, f32) {
+        (
+            (self.start_x + self.end_x) / 2.0,
+            (self.start_y + self.end_y) / 2.0,
+        )
+    }
 }
 
 /// Redaction annotation (black rectangle) for hiding sensitive content
@@ -41,9 +87,24 @@ pub struct PixelateAnnotation {
     pub y2: f32,
     /// Block size for this pixelation
     pub block_size: u32,
+    /// Noise seed when secure pixelation is on (`None` = plain block averaging)
+    pub noise_seed: Option<u64>,
+}
+
+/// Blur annotation for obscuring sensitive content with a Gaussian blur
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlurAnnotation {
+    /// Top-left point in global logical coordinates
+    pub x: f32,
+    pub y: f32,
+    /// Bottom-right point in global logical coordinates
+    pub x2: f32,
+    pub y2: f32,
+    /// Blur radius in logical pixels
+    pub radius: u32,
 }
 
-/// Outline rectangle annotation (no fill)
+/// Outline rectangle annotation, optionally with a translucent fill
 #[derive(Clone, Debug, PartialEq)]
 pub struct RectOutlineAnnotation {
     /// Start point in global logical coordinates
@@ -52,13 +113,19 @@ pub struct RectOutlineAnnotation {
     /// End point in global logical coordinates
     pub end_x: f32,
     pub end_y: f32,
+    /// Stroke width in logical pixels
+    pub width: f32,
+    /// Whether the inside is tinted with the color
+    pub fill: bool,
     /// Color of this rectangle
     pub color: ShapeColor,
     /// Whether to draw shadow/border
     pub shadow: bool,
+    /// Whether the shadow/border color is picked to contrast with the image below
+    pub auto_contrast: bool,
 }
 
-/// Outline circle/ellipse annotation (no fill)
+/// Outline circle/ellipse annotation, optionally with a translucent fill
 #[derive(Clone, Debug, PartialEq)]
 pub struct CircleOutlineAnnotation {
     /// Start point in global logical coordinates
@@ -67,10 +134,157 @@ pub struct CircleOutlineAnnotation {
     /// End point in global logical coordinates
     pub end_x: f32,
     pub end_y: f32,
+    /// Stroke width in logical pixels
+    pub width: f32,
+    /// Whether the inside is tinted with the color
+    pub fill: bool,
     /// Color of this circle
     pub color: ShapeColor,
     /// Whether to draw shadow/border
     pub shadow: bool,
+    /// Whether the shadow/border color is picked to contrast with the image below
+    pub auto_contrast: bool,
+}
+
+/// Spotlight annotation: keeps a region bright and dims (or blurs) the rest
+///
+/// All spotlights in a capture share one mask, so several of them highlight
+/// several regions at once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpotlightAnnotation {
+    /// Top-left point in global logical coordinates
+    pub x: f32,
+    pub y: f32,
+    /// Bottom-right point in global logical coordinates
+    pub x2: f32,
+    pub y2: f32,
+    /// Highlight an ellipse inscribed in the rectangle instead of the rectangle
+    pub ellipse: bool,
+    /// How strongly the outside is darkened (0.0 = not at all, 1.0 = black)
+    pub dim: f32,
+    /// Whether the outside is also blurred
+    pub blur: bool,
+}
+
+/// Measurement annotation: a dimension line or box labelled with its size
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeasureAnnotation {
+    /// Start point in global logical coordinates
+    pub start_x: f32,
+    pub start_y: f32,
+    /// End point in global logical coordinates
+    pub end_x: f32,
+    pub end_y: f32,
+    /// Whether the distance between the points or the box they span is measured
+    pub kind: MeasureKind,
+    /// Color of the dimension line or box
+    pub color: ShapeColor,
+}
+
+impl MeasureAnnotation {
+    /// Label text, with physical sizes at `scale` physical pixels per logical pixel
+    pub fn label(&self, scale: f32) -> String {
+        measure_label(
+            self.kind,
+            (self.start_x, self.start_y),
+            (self.end_x, self.end_y),
+            scale,
+        )
+    }
+}
+
+/// Dimension label for a measurement from `start` to `end`
+///
+/// Sizes are given in logical pixels ("pt") and, on scaled outputs, also in
+/// physical pixels ("px"). Lines get their angle from the horizontal, counted
+/// counter-clockwise; a line has no direction, so it stays within 0-180°.
+pub fn measure_label(kind: MeasureKind, start: (f32, f32), end: (f32, f32), scale: f32) -> String {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let unscaled = (scale - 1.0).abs() < 0.01;
+    match kind {
+        MeasureKind::Box => {
+            let (w, h) = (dx.abs(), dy.abs());
+            if unscaled {
+                format!("{:.0} × {:.0} px", w, h)
+            } else {
+                format!(
+                    "{:.0} × {:.0} pt · {:.0} × {:.0} px",
+                    w,
+                    h,
+                    w * scale,
+                    h * scale
+                )
+            }
+        }
+        MeasureKind::Line => {
+            let length = dx.hypot(dy);
+            // Screen y grows downwards. Fold after rounding to the shown
+            // precision so 179.96° reads 0.0°; abs() turns -0.0 into 0.0.
+            let angle = ((-dy).atan2(dx).to_degrees() * 10.0)
+                .round()
+                .rem_euclid(1800.0)
+                .abs()
+                / 10.0;
+            if unscaled {
+                format!("{:.1} px · {:.1}°", length, angle)
+            } else {
+                format!(
+                    "{:.1} pt · {:.1} px · {:.1}°",
+                    length,
+                    length * scale,
+                    angle
+                )
+            }
+        }
+    }
+}
+
+/// Smallest stamp size (longer side) in logical pixels
+pub const STAMP_MIN_SIZE: f32 = 16.0;
+/// Largest stamp size (longer side) in logical pixels (matches the config slider)
+pub const STAMP_MAX_SIZE: f32 = 512.0;
+
+/// Stamp annotation: an image (logo, emoji, pass/fail mark) placed on the screenshot
+#[derive(Clone, Debug, PartialEq)]
+pub struct StampAnnotation {
+    /// Center in global logical coordinates
+    pub x: f32,
+    pub y: f32,
+    /// Length of the stamp's longer side in logical pixels
+    pub size: f32,
+    /// Stamp name (file stem in the stamp directory, or a built-in)
+    pub name: String,
+    /// Straight-alpha stamp image, shared with the stamp library
+    pub image: Arc<RgbaImage>,
+}
+
+impl StampAnnotation {
+    /// Bounds `(x1, y1, x2, y2)` in global logical coordinates
+    pub fn rect(&self) -> (f32, f32, f32, f32) {
+        let (w, h) = stamp_extent(self.image.dimensions(), self.size);
+        (
+            self.x - w / 2.0,
+            self.y - h / 2.0,
+            self.x + w / 2.0,
+            self.y + h / 2.0,
+        )
+    }
+}
+
+/// Width and height of an image with `dimensions` scaled so its longer side is `size`
+pub fn stamp_extent(dimensions: (u32, u32), size: f32) -> (f32, f32) {
+    let (w, h) = dimensions;
+    let longer = w.max(h).max(1) as f32;
+    (size * w as f32 / longer, size * h as f32 / longer)
+}
+
+/// Size of a stamp placed by pressing at its center and releasing at `end`
+///
+/// Dragging out from the center sizes the stamp; a plain click uses `default`.
+pub fn stamp_size_from_drag(center: (f32, f32), end: (f32, f32), default: f32) -> f32 {
+    let reach = (end.0 - center.0).abs().max((end.1 - center.1).abs());
+    let size = if reach < 4.0 { default } else { 2.0 * reach };
+    size.clamp(STAMP_MIN_SIZE, STAMP_MAX_SIZE)
 }
 
 /// Minimum magnifier zoom factor (matches the config slider)
@@ -79,11 +293,16 @@ pub const MAGNIFIER_MIN_ZOOM: f32 = 1.5;
 pub const MAGNIFIER_MAX_ZOOM: f32 = 10.0;
 /// Minimum magnifier radius in logical units
 pub const MAGNIFIER_MIN_RADIUS: f32 = 12.0;
+/// Gap in logical units between a callout's source rectangle and its enlarged copy
+pub const CALLOUT_GAP: f32 = 24.0;
 
 /// Magnifier annotation: a circular loupe that zooms into the content beneath it.
 ///
 /// Defined by a bounding box (like a circle); the interior shows the underlying
 /// image content scaled up by `magnification`.
+///
+/// As a callout, the bounding box is instead a rectangular source region whose
+/// enlarged copy is drawn elsewhere (at `callout`), joined to it by connector lines.
 #[derive(Clone, Debug, PartialEq)]
 pub struct MagnifierAnnotation {
     /// Start point in global logical coordinates
@@ -98,6 +317,11 @@ pub struct MagnifierAnnotation {
     pub color: ShapeColor,
     /// Whether to draw shadow/border on the ring
     pub shadow: bool,
+    /// Callout only: top-left of the enlarged copy in global logical coordinates
+    /// (`None` = circular loupe drawn in place)
+    pub callout: Option<(f32, f32)>,
+    /// Callout only: frame and connector line width in logical units
+    pub callout_border: f32,
 }
 
 impl MagnifierAnnotation {
@@ -119,6 +343,94 @@ impl MagnifierAnnotation {
         self.end_x = cx + r;
         self.end_y = cy + r;
     }
+
+    /// Whether this is a rectangular callout rather than an in-place loupe
+    pub fn is_callout(&self) -> bool {
+        self.callout.is_some()
+    }
+
+    /// Normalized source rectangle `(min_x, min_y, max_x, max_y)` in global logical coordinates
+    pub fn source_rect(&self) -> (f32, f32, f32, f32) {
+        (
+            self.start_x.min(self.end_x),
+            self.start_y.min(self.end_y),
+            self.start_x.max(self.end_x),
+            self.start_y.max(self.end_y),
+        )
+    }
+
+    /// Rectangle of the enlarged copy `(min_x, min_y, max_x, max_y)`, for callouts
+    pub fn callout_rect(&self) -> Option<(f32, f32, f32, f32)> {
+        let (x, y) = self.callout?;
+        let (x1, y1, x2, y2) = self.source_rect();
+        let zoom = self.magnification.max(1.0);
+        Some((x, y, x + (x2 - x1) * zoom, y + (y2 - y1) * zoom))
+    }
+
+    /// Point that follows the cursor when dragging: the loupe center, or the
+    /// center of a callout's enlarged copy.
+    pub fn anchor(&self) -> (f32, f32) {
+        match self.callout_rect() {
+            Some((x1, y1, x2, y2)) => ((x1 + x2) * 0.5, (y1 + y2) * 0.5),
+            None => self.center(),
+        }
+    }
+
+    /// Whether (x, y) hits the loupe or the callout's enlarged copy
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        match self.callout_rect() {
+            Some((x1, y1, x2, y2)) => x >= x1 && x <= x2 && y >= y1 && y <= y2,
+            None => {
+                let (cx, cy) = self.center();
+                (x - cx).hypot(y - cy) <= self.radius()
+            }
+        }
+    }
+
+    /// Move the loupe, or a callout's enlarged copy, so its anchor is at (x, y).
+    pub fn move_to(&mut self, x: f32, y: f32) {
+        match self.callout_rect() {
+            Some((x1, y1, x2, y2)) => {
+                self.callout = Some((x - (x2 - x1) * 0.5, y - (y2 - y1) * 0.5));
+            }
+            None => self.set_geometry(x, y, self.radius()),
+        }
+    }
+
+    /// Put the enlarged copy next to the source rectangle.
+    ///
+    /// Tries the right, left, top and bottom sides in that order, sliding along
+    /// the side to stay within `bounds` (global logical `(min_x, min_y, max_x,
+    /// max_y)`); if no side fits it is clamped into `bounds`.
+    pub fn place_callout(&mut self, bounds: Option<(f32, f32, f32, f32)>) {
+        let (x1, y1, x2, y2) = self.source_rect();
+        let zoom = self.magnification.max(1.0);
+        let (w, h) = ((x2 - x1) * zoom, (y2 - y1) * zoom);
+        let (cx, cy) = ((x1 + x2) * 0.5, (y1 + y2) * 0.5);
+        let (bx1, by1, bx2, by2) = bounds.unwrap_or((
+            f32::NEG_INFINITY,
+            f32::NEG_INFINITY,
+            f32::INFINITY,
+            f32::INFINITY,
+        ));
+        let clamp_x = |x: f32| x.min(bx2 - w).max(bx1);
+        let clamp_y = |y: f32| y.min(by2 - h).max(by1);
+
+        let candidates = [
+            (x2 + CALLOUT_GAP, clamp_y(cy - h * 0.5)),
+            (x1 - CALLOUT_GAP - w, clamp_y(cy - h * 0.5)),
+            (clamp_x(cx - w * 0.5), y1 - CALLOUT_GAP - h),
+            (clamp_x(cx - w * 0.5), y2 + CALLOUT_GAP),
+        ];
+        let fits = |&(x, y): &(f32, f32)| x >= bx1 && y >= by1 && x + w <= bx2 && y + h <= by2;
+        let (x, y) = candidates[0];
+        self.callout = Some(
+            candidates
+                .into_iter()
+                .find(fits)
+                .unwrap_or((clamp_x(x), clamp_y(y))),
+        );
+    }
 }
 
 /// Unified annotation type for ordered drawing and undo/redo
@@ -128,12 +440,17 @@ pub enum Annotation {
     Circle(CircleOutlineAnnotation),
     Rectangle(RectOutlineAnnotation),
     Magnifier(MagnifierAnnotation),
+    Spotlight(SpotlightAnnotation),
+    Measure(MeasureAnnotation),
+    Stamp(StampAnnotation),
     Redact(RedactAnnotation),
     Pixelate(PixelateAnnotation),
+    Blur(BlurAnnotation),
 }
 
 impl Annotation {
-    /// Check if this is a shape annotation (arrow, circle, rectangle, magnifier)
+    /// Check if this is a shape annotation (arrow, circle, rectangle, magnifier,
+    /// spotlight, measurement, stamp)
     pub fn is_shape(&self) -> bool {
         matches!(
             self,
@@ -141,11 +458,100 @@ impl Annotation {
                 | Annotation::Circle(_)
                 | Annotation::Rectangle(_)
                 | Annotation::Magnifier(_)
+                | Annotation::Spotlight(_)
+                | Annotation::Measure(_)
+                | Annotation::Stamp(_)
         )
     }
 
-    /// Check if this is a redaction annotation (redact, pixelate)
+    /// Check if this is a measurement (only part of the export when asked for)
+    pub fn is_measurement(&self) -> bool {
+        matches!(self, Annotation::Measure(_))
+    }
+
+    /// Normalized bounds `(min_x, min_y, max_x, max_y)` of a redaction annotation
+    /// in global logical coordinates, or `None` for shapes.
+    pub fn redacted_bounds(&self) -> Option<(f32, f32, f32, f32)> {
+        let (x1, y1, x2, y2) = match self {
+            Annotation::Redact(r) => (r.x, r.y, r.x2, r.y2),
+            Annotation::Pixelate(p) => (p.x, p.y, p.x2, p.y2),
+            Annotation::Blur(b) => (b.x, b.y, b.x2, b.y2),
+            _ => return None,
+        };
+        Some((x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2)))
+    }
+
+    /// Check if this is a redaction annotation (redact, pixelate, blur)
     pub fn is_redaction(&self) -> bool {
-        matches!(self, Annotation::Redact(_) | Annotation::Pixelate(_))
+        matches!(
+            self,
+            Annotation::Redact(_) | Annotation::Pixelate(_) | Annotation::Blur(_)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrows_bend_through_their_handle() {
+        let mut arrow = ArrowAnnotation {
+            start_x: 0.0,
+            start_y: 0.0,
+            end_x: 100.0,
+            end_y: 0.0,
+            control: None,
+            width: 3.0,
+            color: ShapeColor::default(),
+            shadow: false,
+            auto_contrast: false,
+        };
+        assert_eq!(arrow.bend_point(), (50.0, 0.0));
+
+        arrow.bend_through(50.0, 40.0);
+        assert_eq!(arrow.control, Some((50.0, 80.0)));
+        assert_eq!(arrow.bend_point(), (50.0, 40.0));
+
+        // Dropping the handle back on the line straightens the arrow
+        arrow.bend_through(51.0, 2.0);
+        assert_eq!(arrow.control, None);
+    }
+
+    #[test]
+    fn measure_labels_show_logical_and_physical_sizes() {
+        assert_eq!(
+            measure_label(MeasureKind::Box, (10.0, 20.0), (130.0, 100.0), 1.0),
+            "120 × 80 px"
+        );
+        assert_eq!(
+            measure_label(MeasureKind::Box, (130.0, 100.0), (10.0, 20.0), 2.0),
+            "120 × 80 pt · 240 × 160 px"
+        );
+        assert_eq!(
+            measure_label(MeasureKind::Line, (0.0, 0.0), (30.0, -40.0), 1.5),
+            "50.0 pt · 75.0 px · 53.1°"
+        );
+        // Direction doesn't matter, and a flat line is 0° either way
+        assert_eq!(
+            measure_label(MeasureKind::Line, (30.0, -40.0), (0.0, 0.0), 1.0),
+            "50.0 px · 53.1°"
+        );
+        assert_eq!(
+            measure_label(MeasureKind::Line, (10.0, 5.0), (0.0, 5.0), 1.0),
+            "10.0 px · 0.0°"
+        );
+    }
+    #[test]
+    fn stamps_keep_their_aspect_ratio() {
+        assert_eq!(stamp_extent((200, 100), 64.0), (64.0, 32.0));
+        assert_eq!(stamp_extent((50, 100), 64.0), (32.0, 64.0));
+        // A click uses the default size, a drag from the center sets it
+        assert_eq!(stamp_size_from_drag((10.0, 10.0), (11.0, 12.0), 64.0), 64.0);
+        assert_eq!(stamp_size_from_drag((10.0, 10.0), (40.0, -5.0), 64.0), 60.0);
+        assert_eq!(
+            stamp_size_from_drag((0.0, 0.0), (5.0, 0.0), 64.0),
+            STAMP_MIN_SIZE
+        );
     }
 }
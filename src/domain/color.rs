@@ -0,0 +1,221 @@
+//! Color conversions, text formats for picked colors and custom color input,
+//! and contrast for auto-contrast annotation outlines
+
+use crate::config::{ColorFormat, ShapeColor};
+
+/// Number of recently used annotation colors that are remembered
+pub const MAX_RECENT_COLORS: usize = 8;
+
+/// `#rrggbb` (lowercase) for an 8-bit RGB color
+pub fn to_hex(rgb: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2])
+}
+
+/// Convert 8-bit RGB to HSL: hue in degrees (0-360), saturation and lightness in percent
+pub fn rgb_to_hsl(rgb: [u8; 3]) -> (f32, f32, f32) {
+    let [r, g, b] = rgb.map(|c| c as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return (0.0, 0.0, lightness * 100.0);
+    }
+
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    (hue, saturation * 100.0, lightness * 100.0)
+}
+
+/// Parse `#rgb` or `#rrggbb` (the `#` is optional, case-insensitive)
+pub fn parse_hex(text: &str) -> Option<[u8; 3]> {
+    let hex = text.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if !hex.is_ascii() {
+        return None;
+    }
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+    match hex.len() {
+        3 => {
+            let mut rgb = [0; 3];
+            for (i, c) in rgb.iter_mut().enumerate() {
+                *c = channel(&hex[i..i + 1])? * 0x11;
+            }
+            Some(rgb)
+        }
+        6 => Some([
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        ]),
+        _ => None,
+    }
+}
+
+/// Convert RGB (0.0-1.0) to HSV: hue in degrees (0-360), saturation and value 0.0-1.0
+pub fn rgb_to_hsv(color: ShapeColor) -> [f32; 3] {
+    let (r, g, b) = (color.r, color.g, color.b);
+    let max = r.max(g).max(b);
+    let delta = max - r.min(g).min(b);
+    if delta <= 0.0 {
+        return [0.0, 0.0, max];
+    }
+
+    let hue = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    [hue, delta / max, max]
+}
+
+/// Convert HSV (hue in degrees, saturation and value 0.0-1.0) to RGB
+pub fn hsv_to_rgb(hsv: [f32; 3]) -> ShapeColor {
+    let [hue, saturation, value] = hsv;
+    let chroma = value * saturation;
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    ShapeColor {
+        r: r + m,
+        g: g + m,
+        b: b + m,
+    }
+}
+
+/// Move `color` to the front of the recent colors, dropping duplicates and the oldest entries
+pub fn remember_color(recent: &mut Vec<ShapeColor>, color: ShapeColor) {
+    recent.retain(|c| c.to_rgba_u8() != color.to_rgba_u8());
+    recent.insert(0, color);
+    recent.truncate(MAX_RECENT_COLORS);
+}
+
+/// WCAG relative luminance of an 8-bit sRGB color (0.0 black - 1.0 white)
+pub fn relative_luminance(rgb: [u8; 3]) -> f32 {
+    let linear = |c: u8| {
+        let c = c as f32 / 255.0;
+        if c <= 0.040_45 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(rgb[0]) + 0.7152 * linear(rgb[1]) + 0.0722 * linear(rgb[2])
+}
+
+/// WCAG contrast ratio between two relative luminances (1.0 - 21.0)
+pub fn contrast_ratio(a: f32, b: f32) -> f32 {
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Outline color that keeps a stroke visible over a background: black or
+/// white, whichever stands out more from the stroke and the background alike
+pub fn contrasting_outline(stroke: [u8; 3], background: [u8; 3]) -> [u8; 3] {
+    let stroke = relative_luminance(stroke);
+    let background = relative_luminance(background);
+    let separation =
+        |outline: f32| contrast_ratio(outline, stroke).min(contrast_ratio(outline, background));
+    if separation(1.0) > separation(0.0) {
+        [255, 255, 255]
+    } else {
+        [0, 0, 0]
+    }
+}
+
+/// Text for a picked color in the given format, ready for the clipboard
+pub fn format_color(rgb: [u8; 3], format: ColorFormat) -> String {
+    match format {
+        ColorFormat::Hex => to_hex(rgb),
+        ColorFormat::Rgb => format!("rgb({}, {}, {})", rgb[0], rgb[1], rgb[2]),
+        ColorFormat::Hsl => {
+            let (h, s, l) = rgb_to_hsl(rgb);
+            format!("hsl({:.0}, {:.0}%, {:.0}%)", h, s, l)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_picked_colors() {
+        let orange = [255, 136, 0];
+        assert_eq!(format_color(orange, ColorFormat::Hex), "#ff8800");
+        assert_eq!(format_color(orange, ColorFormat::Rgb), "rgb(255, 136, 0)");
+        assert_eq!(format_color(orange, ColorFormat::Hsl), "hsl(32, 100%, 50%)");
+        assert_eq!(
+            format_color([128, 128, 128], ColorFormat::Hsl),
+            "hsl(0, 0%, 50%)"
+        );
+        assert_eq!(
+            format_color([0, 0, 255], ColorFormat::Hsl),
+            "hsl(240, 100%, 50%)"
+        );
+    }
+
+    #[test]
+    fn parses_hex_and_round_trips_hsv() {
+        assert_eq!(parse_hex("#FF8800"), Some([255, 136, 0]));
+        assert_eq!(parse_hex(" 0a7 "), Some([0x00, 0xaa, 0x77]));
+        assert_eq!(parse_hex("#12345"), None);
+        assert_eq!(parse_hex("#ggg"), None);
+        assert_eq!(parse_hex("#ééé"), None);
+
+        let orange = ShapeColor::from_rgb_u8([255, 136, 0]);
+        let [h, s, v] = rgb_to_hsv(orange);
+        assert!((h - 32.0).abs() < 0.1 && (s - 1.0).abs() < 1e-6 && (v - 1.0).abs() < 1e-6);
+        assert_eq!(hsv_to_rgb([h, s, v]).to_rgba_u8(), orange.to_rgba_u8());
+        assert_eq!(hsv_to_rgb([360.0, 1.0, 1.0]).to_rgba_u8(), [255, 0, 0, 255]);
+        assert_eq!(
+            rgb_to_hsv(ShapeColor::from_rgb_u8([0, 0, 0])),
+            [0.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn outlines_contrast_with_stroke_and_background() {
+        let red = [255, 0, 0];
+        let yellow = [255, 217, 0];
+        assert_eq!(contrasting_outline(red, red), [0, 0, 0]);
+        assert_eq!(contrasting_outline(yellow, [255, 255, 255]), [0, 0, 0]);
+        assert_eq!(contrasting_outline(red, [30, 30, 30]), [255, 255, 255]);
+        assert!((contrast_ratio(1.0, 0.0) - 21.0).abs() < 1e-4);
+        assert!((relative_luminance([255, 255, 255]) - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn recent_colors_are_unique_and_bounded() {
+        let mut recent = Vec::new();
+        for i in 0..10u8 {
+            remember_color(&mut recent, ShapeColor::from_rgb_u8([i, 0, 0]));
+        }
+        remember_color(&mut recent, ShapeColor::from_rgb_u8([5, 0, 0]));
+        assert_eq!(recent.len(), MAX_RECENT_COLORS);
+        assert_eq!(recent[0].to_rgba_u8(), [5, 0, 0, 255]);
+        assert_eq!(recent[1].to_rgba_u8(), [9, 0, 0, 255]);
+        assert_eq!(
+            recent
+                .iter()
+                .filter(|c| c.to_rgba_u8() == [5, 0, 0, 255])
+                .count(),
+            1
+        );
+    }
+}
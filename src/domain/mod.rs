@@ -5,6 +5,7 @@
 //! to avoid circular dependencies.
 
 pub mod annotation;
+pub mod color;
 pub mod geometry;
 pub mod selection;
 
@@ -72,6 +72,33 @@ pub enum Choice {
     Rectangle(Rect, DragState),
 }
 
+/// What a new choice does while re-cropping
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recrop {
+    /// Keep the current region, as the new rectangle has no area (an edge
+    /// was dragged onto the opposite one)
+    Ignore,
+    /// Move or resize the region; `changed` if it differs from the current one
+    Update { changed: bool },
+    /// Leave crop mode: a screen or the picker was chosen
+    Leave,
+}
+
+impl Choice {
+    /// How `new` replaces this choice in crop mode, where annotations must
+    /// survive every change of the region
+    pub fn recrop(&self, new: &Choice) -> Recrop {
+        match (self, new) {
+            (_, Choice::Rectangle(r, _)) if r.dimensions().is_none() => Recrop::Ignore,
+            (Choice::Rectangle(old, _), Choice::Rectangle(r, _)) => {
+                Recrop::Update { changed: old != r }
+            }
+            (_, Choice::Rectangle(..)) => Recrop::Update { changed: true },
+            (_, Choice::Output(_)) => Recrop::Leave,
+        }
+    }
+}
+
 /// Action to perform after screenshot capture
 #[derive(Debug, Clone, Default)]
 pub enum Action {
@@ -93,3 +120,24 @@ pub enum ImageSaveLocation {
     /// Save to Documents folder
     Documents,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recrop_ignores_rectangles_without_area() {
+        let current = Choice::Rectangle(Rect::new(0, 0, 100, 80), DragState::None);
+
+        // Dragging the east edge onto the west one
+        let collapsed = Choice::Rectangle(Rect::new(0, 0, 0, 80), DragState::E);
+        assert_eq!(current.recrop(&collapsed), Recrop::Ignore);
+        let released = Choice::Rectangle(Rect::new(0, 0, 0, 80), DragState::None);
+        assert_eq!(current.recrop(&released), Recrop::Ignore);
+
+        let resized = Choice::Rectangle(Rect::new(0, 0, 1, 80), DragState::E);
+        assert_eq!(current.recrop(&resized), Recrop::Update { changed: true });
+        assert_eq!(current.recrop(&current), Recrop::Update { changed: false });
+        assert_eq!(current.recrop(&Choice::Output(None)), Recrop::Leave);
+    }
+}
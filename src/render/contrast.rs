@@ -0,0 +1,169 @@
+//! Auto-contrast outlines
+//!
+//! Samples the captured image under an annotation's stroke and picks a black
+//! or white outline that separates the stroke from that background. Shared by
+//! the live canvas and image export so both pick the same outline.
+
+use image::RgbaImage;
+
+use super::geometry::{self, arrow};
+use crate::config::ShapeColor;
+use crate::domain::color::contrasting_outline;
+use crate::domain::{ArrowAnnotation, CircleOutlineAnnotation, RectOutlineAnnotation};
+
+/// Number of points sampled along a stroke
+pub const SAMPLES: usize = 32;
+
+/// Outline color of annotations without auto-contrast
+pub const DEFAULT_OUTLINE: [u8; 3] = [0, 0, 0];
+
+/// Captured pixels under annotations, for sampling their background
+pub struct Backdrop<'a> {
+    pub image: &'a RgbaImage,
+    /// Image pixels per logical pixel
+    pub scale: f32,
+    /// Global logical position of the image's top-left corner
+    pub origin: (f32, f32),
+}
+
+impl Backdrop<'_> {
+    /// Average color of the image at the given global logical points
+    pub fn average(&self, points: &[(f32, f32)]) -> Option<[u8; 3]> {
+        let (mut sum, mut count) = ([0u32; 3], 0u32);
+        for &(x, y) in points {
+            let px = ((x - self.origin.0) * self.scale).floor();
+            let py = ((y - self.origin.1) * self.scale).floor();
+            if px < 0.0
+                || py < 0.0
+                || px >= self.image.width() as f32
+                || py >= self.image.height() as f32
+            {
+                continue;
+            }
+            let pixel = self.image.get_pixel(px as u32, py as u32);
+            for (total, &channel) in sum.iter_mut().zip(&pixel.0[..3]) {
+                *total += channel as u32;
+            }
+            count += 1;
+        }
+        (count > 0).then(|| sum.map(|total| (total / count) as u8))
+    }
+
+    /// Outline color for a stroke along `points`: contrasting with the
+    /// background when `auto_contrast` is set, the default outline otherwise
+    pub fn outline(
+        &self,
+        points: &[(f32, f32)],
+        stroke: ShapeColor,
+        auto_contrast: bool,
+    ) -> [u8; 3] {
+        if !auto_contrast {
+            return DEFAULT_OUTLINE;
+        }
+        let [r, g, b, _] = stroke.to_rgba_u8();
+        self.average(points).map_or(DEFAULT_OUTLINE, |background| {
+            contrasting_outline([r, g, b], background)
+        })
+    }
+
+    pub fn arrow_outline(&self, a: &ArrowAnnotation) -> [u8; 3] {
+        self.outline(&arrow_samples(a), a.color, a.auto_contrast)
+    }
+
+    pub fn rect_outline(&self, r: &RectOutlineAnnotation) -> [u8; 3] {
+        let (x1, y1, x2, y2) = geometry::normalize_rect(r.start_x, r.start_y, r.end_x, r.end_y);
+        let corners = [(x1, y1), (x2, y1), (x2, y2), (x1, y2), (x1, y1)];
+        self.outline(&along(&corners), r.color, r.auto_contrast)
+    }
+
+    pub fn circle_outline(&self, c: &CircleOutlineAnnotation) -> [u8; 3] {
+        let (x1, y1, x2, y2) = geometry::normalize_rect(c.start_x, c.start_y, c.end_x, c.end_y);
+        let (cx, cy, rx, ry) = geometry::ellipse_from_bounds(x1, y1, x2, y2);
+        let points: Vec<_> = (0..SAMPLES)
+            .map(|i| {
+                let t = i as f32 * std::f32::consts::TAU / SAMPLES as f32;
+                (cx + rx * t.cos(), cy + ry * t.sin())
+            })
+            .collect();
+        self.outline(&points, c.color, c.auto_contrast)
+    }
+}
+
+/// Points along an arrow's shaft
+fn arrow_samples(a: &ArrowAnnotation) -> Vec<(f32, f32)> {
+    along(&arrow::shaft_points(
+        (a.start_x, a.start_y),
+        a.control,
+        (a.end_x, a.end_y),
+    ))
+}
+
+/// `SAMPLES` points evenly spread along a polyline
+fn along(polyline: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let lengths: Vec<f32> = polyline
+        .windows(2)
+        .map(|w| (w[1].0 - w[0].0).hypot(w[1].1 - w[0].1))
+        .collect();
+    let total: f32 = lengths.iter().sum();
+    if total <= 0.0 {
+        return polyline.first().copied().into_iter().collect();
+    }
+    let mut points = Vec::with_capacity(SAMPLES);
+    let (mut segment, mut start) = (0, 0.0);
+    for i in 0..SAMPLES {
+        let target = total * (i as f32 + 0.5) / SAMPLES as f32;
+        while segment + 1 < lengths.len() && start + lengths[segment] < target {
+            start += lengths[segment];
+            segment += 1;
+        }
+        let t = ((target - start) / lengths[segment].max(f32::EPSILON)).min(1.0);
+        let (a, b) = (polyline[segment], polyline[segment + 1]);
+        points.push((a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t));
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arrow(auto_contrast: bool) -> ArrowAnnotation {
+        ArrowAnnotation {
+            start_x: 110.0,
+            start_y: 20.0,
+            end_x: 150.0,
+            end_y: 20.0,
+            control: None,
+            width: 3.0,
+            color: ShapeColor::from_rgb_u8([255, 0, 0]),
+            shadow: true,
+            auto_contrast,
+        }
+    }
+
+    #[test]
+    fn outlines_follow_the_background_under_the_stroke() {
+        // Dark left half, red right half; the image starts at global (100, 0)
+        let image = RgbaImage::from_fn(200, 80, |x, _| {
+            if x < 100 {
+                image::Rgba([30, 30, 30, 255])
+            } else {
+                image::Rgba([255, 0, 0, 255])
+            }
+        });
+        let backdrop = Backdrop {
+            image: &image,
+            scale: 2.0,
+            origin: (100.0, 0.0),
+        };
+        // Over the dark half (image x 20..100) a red arrow gets a white halo
+        assert_eq!(backdrop.arrow_outline(&arrow(true)), [255, 255, 255]);
+        // Moved over the red half it gets a black one
+        let mut red_arrow = arrow(true);
+        red_arrow.start_x += 60.0;
+        red_arrow.end_x += 40.0;
+        assert_eq!(backdrop.arrow_outline(&red_arrow), [0, 0, 0]);
+        assert_eq!(backdrop.arrow_outline(&arrow(false)), DEFAULT_OUTLINE);
+        assert_eq!(along(&[(0.0, 0.0), (32.0, 0.0)])[0], (0.5, 0.0));
+    }
+}
@@ -16,6 +16,19 @@ pub mod arrow {
     /// Minimum arrow length to be drawn
     pub const MIN_LENGTH: f32 = 5.0;
 
+    /// Shaft thickness for an arrow with the given width setting
+    ///
+    /// Widths are shared with circles and rectangles; arrows are drawn a bit
+    /// heavier so that the default width gives the default `THICKNESS`.
+    pub fn thickness(width: f32) -> f32 {
+        width * THICKNESS / super::shape::THICKNESS
+    }
+
+    /// Arrowhead size for an arrow with the given width setting
+    pub fn head_size(width: f32) -> f32 {
+        width * HEAD_SIZE / super::shape::THICKNESS
+    }
+
     /// Calculate arrow head points given start, end, and head size
     /// Returns (head1_x, head1_y, head2_x, head2_y) for the two head lines
     pub fn head_points(
@@ -53,6 +66,46 @@ pub mod arrow {
 
         Some((head1_x, head1_y, head2_x, head2_y))
     }
+
+    /// Number of straight segments a curved arrow's shaft is flattened into
+    pub const CURVE_SEGMENTS: usize = 32;
+
+    /// Shaft of an arrow as a polyline: the straight line from `start` to `end`,
+    /// or the quadratic Bezier bent by `control`, flattened
+    pub fn shaft_points(
+        start: (f32, f32),
+        control: Option<(f32, f32)>,
+        end: (f32, f32),
+    ) -> Vec<(f32, f32)> {
+        let Some((cx, cy)) = control else {
+            return vec![start, end];
+        };
+        (0..=CURVE_SEGMENTS)
+            .map(|i| {
+                let t = i as f32 / CURVE_SEGMENTS as f32;
+                let (a, b, c) = ((1.0 - t) * (1.0 - t), 2.0 * (1.0 - t) * t, t * t);
+                (
+                    a * start.0 + b * cx + c * end.0,
+                    a * start.1 + b * cy + c * end.1,
+                )
+            })
+            .collect()
+    }
+
+    /// Point the arrowhead is aimed from, so the head follows the shaft
+    ///
+    /// The tangent of a quadratic Bezier at its end points from the control
+    /// point to the end; a control point on top of the end falls back to `start`.
+    pub fn head_base(
+        start: (f32, f32),
+        control: Option<(f32, f32)>,
+        end: (f32, f32),
+    ) -> (f32, f32) {
+        match control {
+            Some(c) if (end.0 - c.0).hypot(end.1 - c.1) >= MIN_LENGTH => c,
+            _ => start,
+        }
+    }
 }
 
 /// Shape (rectangle/circle) geometry constants
@@ -61,11 +114,88 @@ pub mod shape {
     pub const THICKNESS: f32 = 3.0;
     /// Border/shadow thickness in logical pixels
     pub const BORDER_THICKNESS: f32 = 5.0;
+    /// Opacity of the tint inside filled circles and rectangles
+    pub const FILL_ALPHA: f32 = 0.3;
+    /// Range of the stroke width slider in logical pixels
+    pub const MIN_WIDTH: f32 = 1.0;
+    pub const MAX_WIDTH: f32 = 12.0;
+
+    /// Shadow stroke width for a shape with the given stroke width
+    pub fn border_thickness(width: f32) -> f32 {
+        width + BORDER_THICKNESS - THICKNESS
+    }
 
     /// Ellipse bezier approximation constant: 4/3 * (sqrt(2) - 1)
     pub const BEZIER_K: f32 = 0.552_284_8;
 }
 
+/// Measurement (dimension line/box) geometry
+pub mod measure {
+    use crate::config::MeasureKind;
+
+    /// Stroke thickness in logical pixels
+    pub const THICKNESS: f32 = 2.0;
+    /// Half length of the ticks across a dimension line's ends, in logical pixels
+    pub const TICK: f32 = 6.0;
+    /// Gap between a box and its label, in logical pixels
+    pub const LABEL_GAP: f32 = 6.0;
+    /// Label font size in logical pixels
+    pub const LABEL_SIZE: f32 = 13.0;
+
+    /// Line segments to stroke: the line with a tick across each end, or the
+    /// four edges of the box. `scale` converts the logical constants to the
+    /// coordinate space of `start`/`end`.
+    pub fn segments(
+        kind: MeasureKind,
+        start: (f32, f32),
+        end: (f32, f32),
+        scale: f32,
+    ) -> Vec<((f32, f32), (f32, f32))> {
+        match kind {
+            MeasureKind::Line => {
+                let mut lines = vec![(start, end)];
+                let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+                let length = dx.hypot(dy);
+                if length > 0.0 {
+                    let tick = TICK * scale;
+                    let (nx, ny) = (-dy / length * tick, dx / length * tick);
+                    for (x, y) in [start, end] {
+                        lines.push(((x - nx, y - ny), (x + nx, y + ny)));
+                    }
+                }
+                lines
+            }
+            MeasureKind::Box => {
+                let (x1, y1, x2, y2) = super::normalize_rect(start.0, start.1, end.0, end.1);
+                vec![
+                    ((x1, y1), (x2, y1)),
+                    ((x2, y1), (x2, y2)),
+                    ((x2, y2), (x1, y2)),
+                    ((x1, y2), (x1, y1)),
+                ]
+            }
+        }
+    }
+
+    /// Center of the label: on the middle of a line, or centered below a box
+    pub fn label_center(
+        kind: MeasureKind,
+        start: (f32, f32),
+        end: (f32, f32),
+        label_height: f32,
+        scale: f32,
+    ) -> (f32, f32) {
+        let cx = (start.0 + end.0) * 0.5;
+        match kind {
+            MeasureKind::Line => (cx, (start.1 + end.1) * 0.5),
+            MeasureKind::Box => (
+                cx,
+                start.1.max(end.1) + LABEL_GAP * scale + label_height * 0.5,
+            ),
+        }
+    }
+}
+
 /// Mesh rendering constants (for anti-aliased screen preview)
 pub mod mesh {
     /// Anti-aliasing feather width in pixels
@@ -103,3 +233,27 @@ pub fn circle_from_points(x1: f32, y1: f32, x2: f32, y2: f32) -> (f32, f32, f32)
     let radius = (((x2 - x1).abs() + (y2 - y1).abs()) * 0.25).max(1.0);
     (cx, cy, radius)
 }
+
+/// Connector lines between a callout's source rectangle and its enlarged copy.
+///
+/// Both rectangles are `(min_x, min_y, max_x, max_y)`. The two lines join the
+/// facing corners on the side where the copy sits; overlapping rectangles get
+/// no connectors.
+pub fn callout_connectors(
+    src: (f32, f32, f32, f32),
+    dst: (f32, f32, f32, f32),
+) -> Vec<((f32, f32), (f32, f32))> {
+    let (sx1, sy1, sx2, sy2) = src;
+    let (dx1, dy1, dx2, dy2) = dst;
+    if dx1 >= sx2 {
+        vec![((sx2, sy1), (dx1, dy1)), ((sx2, sy2), (dx1, dy2))]
+    } else if dx2 <= sx1 {
+        vec![((sx1, sy1), (dx2, dy1)), ((sx1, sy2), (dx2, dy2))]
+    } else if dy1 >= sy2 {
+        vec![((sx1, sy2), (dx1, dy1)), ((sx2, sy2), (dx2, dy1))]
+    } else if dy2 <= sy1 {
+        vec![((sx1, sy1), (dx1, dy2)), ((sx2, sy1), (dx2, dy2))]
+    } else {
+        Vec::new()
+    }
+}
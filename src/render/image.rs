@@ -5,12 +5,26 @@
 use image::RgbaImage;
 use tiny_skia::{Color, LineCap, LineJoin, Paint, PathBuilder, Pixmap, Stroke, Transform};
 
-use super::geometry::{self, arrow, shape};
+use super::contrast::Backdrop;
+use super::geometry::{self, arrow, measure, shape};
+use super::text;
+use crate::config::{BeautifyBackground, BeautifyPreset, ShapeColor};
 use crate::domain::{
-    Annotation, ArrowAnnotation, CircleOutlineAnnotation, MagnifierAnnotation, PixelateAnnotation,
-    Rect, RectOutlineAnnotation, RedactAnnotation,
+    Annotation, ArrowAnnotation, BlurAnnotation, CircleOutlineAnnotation, MagnifierAnnotation,
+    MeasureAnnotation, PixelateAnnotation, Rect, RectOutlineAnnotation, RedactAnnotation,
+    SpotlightAnnotation, StampAnnotation,
 };
 
+/// The capture under the annotations, before any of them were drawn, as
+/// their backdrop; the live canvas samples the untouched screenshot too
+fn backdrop<'a>(img: &'a RgbaImage, selection_rect: &Rect, scale: f32) -> Backdrop<'a> {
+    Backdrop {
+        image: img,
+        scale,
+        origin: (selection_rect.left as f32, selection_rect.top as f32),
+    }
+}
+
 /// Convert RgbaImage to Pixmap, apply drawing function, and copy back
 fn with_pixmap(img: &mut RgbaImage, f: impl FnOnce(&mut Pixmap)) {
     let (w, h) = (img.width(), img.height());
@@ -28,21 +42,29 @@ fn with_pixmap(img: &mut RgbaImage, f: impl FnOnce(&mut Pixmap)) {
 }
 
 /// Build an arrow path as stroked lines (shaft + two angled head lines)
+///
+/// A curved arrow's shaft is the quadratic Bezier through `control`, and its
+/// head follows the curve's tangent at the tip.
 fn build_arrow_path(
     start_x: f32,
     start_y: f32,
     end_x: f32,
     end_y: f32,
+    control: Option<(f32, f32)>,
     head_size: f32,
 ) -> Option<tiny_skia::Path> {
+    let (base_x, base_y) = arrow::head_base((start_x, start_y), control, (end_x, end_y));
     let (head1_x, head1_y, head2_x, head2_y) =
-        arrow::head_points(start_x, start_y, end_x, end_y, head_size)?;
+        arrow::head_points(base_x, base_y, end_x, end_y, head_size)?;
 
     let mut pb = PathBuilder::new();
 
-    // Shaft line from start to end
+    // Shaft from start to end
     pb.move_to(start_x, start_y);
-    pb.line_to(end_x, end_y);
+    match control {
+        Some((cx, cy)) => pb.quad_to(cx, cy, end_x, end_y),
+        None => pb.line_to(end_x, end_y),
+    }
 
     // First head line
     pb.move_to(end_x, end_y);
@@ -82,8 +104,12 @@ fn build_ellipse_path(cx: f32, cy: f32, rx: f32, ry: f32) -> Option<tiny_skia::P
 }
 
 /// Draw arrows onto an image using tiny-skia with stroked lines and rounded caps
+///
+/// Auto-contrast outlines are picked from `capture`, the image before any
+/// annotation was drawn.
 pub fn draw_arrows_on_image(
     img: &mut RgbaImage,
+    capture: &RgbaImage,
     arrows: &[ArrowAnnotation],
     selection_rect: &Rect,
     scale: f32,
@@ -92,8 +118,11 @@ pub fn draw_arrows_on_image(
         return;
     }
 
+    let backdrop = backdrop(capture, selection_rect, scale);
+    let outlines: Vec<[u8; 3]> = arrows.iter().map(|a| backdrop.arrow_outline(a)).collect();
+
     with_pixmap(img, |pixmap| {
-        for arrow_ann in arrows {
+        for (arrow_ann, [or, og, ob]) in arrows.iter().zip(outlines) {
             let [r, g, b, a] = arrow_ann.color.to_rgba_u8();
 
             // Convert from global logical to image pixel coordinates
@@ -101,18 +130,24 @@ pub fn draw_arrows_on_image(
             let start_y = (arrow_ann.start_y - selection_rect.top as f32) * scale;
             let end_x = (arrow_ann.end_x - selection_rect.left as f32) * scale;
             let end_y = (arrow_ann.end_y - selection_rect.top as f32) * scale;
-
-            let thickness = arrow::THICKNESS * scale;
-            let head_size = arrow::HEAD_SIZE * scale;
+            let control = arrow_ann.control.map(|(x, y)| {
+                (
+                    (x - selection_rect.left as f32) * scale,
+                    (y - selection_rect.top as f32) * scale,
+                )
+            });
+
+            let thickness = arrow::thickness(arrow_ann.width) * scale;
+            let head_size = arrow::head_size(arrow_ann.width) * scale;
             let outline = arrow::OUTLINE * scale;
 
             // Draw shadow/border first (thicker stroke)
             if arrow_ann.shadow
                 && let Some(path) =
-                    build_arrow_path(start_x, start_y, end_x, end_y, head_size + outline)
+                    build_arrow_path(start_x, start_y, end_x, end_y, control, head_size + outline)
             {
                 let mut paint = Paint::default();
-                paint.set_color_rgba8(0, 0, 0, 220);
+                paint.set_color_rgba8(or, og, ob, 220);
                 paint.anti_alias = true;
 
                 let stroke = Stroke {
@@ -125,7 +160,8 @@ pub fn draw_arrows_on_image(
             }
 
             // Draw main arrow with rounded caps
-            if let Some(path) = build_arrow_path(start_x, start_y, end_x, end_y, head_size) {
+            if let Some(path) = build_arrow_path(start_x, start_y, end_x, end_y, control, head_size)
+            {
                 let mut paint = Paint::default();
                 paint.set_color_rgba8(r, g, b, a);
                 paint.anti_alias = true;
@@ -174,7 +210,92 @@ pub fn draw_redactions_on_image(
     });
 }
 
+/// Quantisation step for secure pixelation samples and block colours
+const SECURE_QUANT_STEP: i32 = 32;
+/// Maximum per-channel noise (plus or minus) mixed into secure pixelation
+const SECURE_NOISE: i32 = 40;
+
+/// A fresh random seed for secure pixelation noise
+///
+/// Uses the randomly keyed std hasher so no extra RNG dependency is needed.
+/// The seed is never persisted, so the noise can't be replayed from a saved image.
+pub fn secure_noise_seed() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+    std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish()
+}
+
+/// SplitMix64 step, used as a small deterministic noise generator
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Signed noise in `-SECURE_NOISE..=SECURE_NOISE` from 16 bits of `bits`
+fn secure_noise(bits: u64) -> i32 {
+    ((bits & 0xFFFF) % (2 * SECURE_NOISE as u64 + 1)) as i32 - SECURE_NOISE
+}
+
+/// Snap a channel value to the centre of its quantisation bucket
+fn secure_quantize(v: i32) -> u8 {
+    let q = (v.clamp(0, 255) / SECURE_QUANT_STEP) * SECURE_QUANT_STEP + SECURE_QUANT_STEP / 2;
+    q.min(255) as u8
+}
+
+/// Colour of one secure pixelation block covering `[x1, x2) x [y1, y2)`
+///
+/// Every sample gets random noise and is quantised before averaging, and the
+/// average is perturbed and quantised again. Unlike a plain mean, the result is
+/// not a linear function of the glyphs underneath, which defeats the
+/// render-and-compare attacks that recover pixelated text. `block` (column, row)
+/// keeps the noise stable for a given seed so the preview matches the export.
+pub fn secure_block_color(
+    img: &RgbaImage,
+    x1: u32,
+    y1: u32,
+    x2: u32,
+    y2: u32,
+    seed: u64,
+    block: (u32, u32),
+) -> Option<[u8; 4]> {
+    let x2 = x2.min(img.width());
+    let y2 = y2.min(img.height());
+    if x2 <= x1 || y2 <= y1 {
+        return None;
+    }
+
+    let mut state = splitmix64(seed ^ ((block.0 as u64) << 32 | block.1 as u64));
+    let mut total = [0i64; 4];
+    for py in y1..y2 {
+        for px in x1..x2 {
+            let pixel = img.get_pixel(px, py);
+            state = splitmix64(state);
+            for c in 0..3 {
+                let noisy = pixel[c] as i32 + secure_noise(state >> (c * 16));
+                total[c] += secure_quantize(noisy) as i64;
+            }
+            total[3] += pixel[3] as i64;
+        }
+    }
+
+    let count = ((x2 - x1) * (y2 - y1)) as i64;
+    state = splitmix64(state);
+    let mut color = [0u8; 4];
+    for c in 0..3 {
+        let avg = (total[c] / count) as i32;
+        color[c] = secure_quantize(avg + secure_noise(state >> (c * 16)));
+    }
+    color[3] = (total[3] / count) as u8;
+    Some(color)
+}
+
 /// Draw pixelation rectangles onto an image
+///
+/// Pixelations with a `noise_seed` use [`secure_block_color`] instead of a plain
+/// block average.
 pub fn draw_pixelations_on_image(
     img: &mut RgbaImage,
     pixelations: &[PixelateAnnotation],
@@ -205,6 +326,30 @@ pub fn draw_pixelations_on_image(
             while block_x <= max_x {
                 let block_end_x = (block_x + block_size - 1).min(max_x);
 
+                if let Some(seed) = pixelate.noise_seed {
+                    let block = (
+                        (block_x - min_x) / block_size,
+                        (block_y - min_y) / block_size,
+                    );
+                    if let Some(color) = secure_block_color(
+                        img,
+                        block_x,
+                        block_y,
+                        block_end_x + 1,
+                        block_end_y + 1,
+                        seed,
+                        block,
+                    ) {
+                        for py in block_y..=block_end_y {
+                            for px in block_x..=block_end_x {
+                                img.put_pixel(px, py, image::Rgba(color));
+                            }
+                        }
+                    }
+                    block_x += block_size;
+                    continue;
+                }
+
                 // Calculate average color for this block
                 let mut total_r: u64 = 0;
                 let mut total_g: u64 = 0;
@@ -245,9 +390,276 @@ pub fn draw_pixelations_on_image(
     }
 }
 
+/// Radii of three successive box blurs that together approximate a Gaussian
+/// blur with standard deviation `sigma`.
+fn gaussian_box_radii(sigma: f32) -> [usize; 3] {
+    const PASSES: f32 = 3.0;
+    let ideal_width = (12.0 * sigma * sigma / PASSES + 1.0).sqrt();
+    let mut lower = (ideal_width.floor() as i32).max(1);
+    if lower % 2 == 0 {
+        lower -= 1;
+    }
+    let upper = lower + 2;
+    let lower_f = lower as f32;
+    let lower_passes = ((12.0 * sigma * sigma
+        - PASSES * lower_f * lower_f
+        - 4.0 * PASSES * lower_f
+        - 3.0 * PASSES)
+        / (-4.0 * lower_f - 4.0))
+        .round() as i32;
+
+    let mut radii = [0; 3];
+    for (i, r) in radii.iter_mut().enumerate() {
+        let width = if (i as i32) < lower_passes {
+            lower
+        } else {
+            upper
+        };
+        *r = ((width - 1) / 2).max(0) as usize;
+    }
+    radii
+}
+
+/// Box-blur one line of pixels (edges clamped), emitting each result via `put`
+fn box_blur_line(src: &[[u8; 4]], radius: usize, mut put: impl FnMut(usize, [u8; 4])) {
+    let len = src.len();
+    if len == 0 {
+        return;
+    }
+    let last = len - 1;
+    let window = (2 * radius + 1) as u32;
+
+    let mut acc = [0u32; 4];
+    for k in 0..window as usize {
+        let p = src[(k as isize - radius as isize).clamp(0, last as isize) as usize];
+        for (a, v) in acc.iter_mut().zip(p) {
+            *a += v as u32;
+        }
+    }
+
+    for i in 0..len {
+        put(i, acc.map(|a| ((a + window / 2) / window) as u8));
+        let leaving = src[(i as isize - radius as isize).clamp(0, last as isize) as usize];
+        let entering = src[(i + radius + 1).min(last)];
+        for ((a, e), l) in acc.iter_mut().zip(entering).zip(leaving) {
+            *a = *a + e as u32 - l as u32;
+        }
+    }
+}
+
+/// Apply a separable box blur (horizontal then vertical) to the whole image
+fn box_blur(img: &mut RgbaImage, radius: usize) {
+    if radius == 0 {
+        return;
+    }
+    let (w, h) = img.dimensions();
+    let mut line: Vec<[u8; 4]> = Vec::with_capacity(w.max(h) as usize);
+
+    for y in 0..h {
+        line.clear();
+        line.extend((0..w).map(|x| img.get_pixel(x, y).0));
+        box_blur_line(&line, radius, |x, p| {
+            img.put_pixel(x as u32, y, image::Rgba(p))
+        });
+    }
+
+    for x in 0..w {
+        line.clear();
+        line.extend((0..h).map(|y| img.get_pixel(x, y).0));
+        box_blur_line(&line, radius, |y, p| {
+            img.put_pixel(x, y as u32, image::Rgba(p))
+        });
+    }
+}
+
+/// Draw Gaussian blur rectangles onto an image
+///
+/// The blur `radius` is treated as roughly two standard deviations and is
+/// approximated with three box-blur passes. Pixels just outside the region are
+/// sampled so its edges blend naturally, but only the region itself is written.
+pub fn draw_blurs_on_image(
+    img: &mut RgbaImage,
+    blurs: &[BlurAnnotation],
+    selection_rect: &Rect,
+    scale: f32,
+) {
+    let (img_w, img_h) = (img.width() as i32, img.height() as i32);
+
+    for blur in blurs {
+        let x1 = ((blur.x - selection_rect.left as f32) * scale).round() as i32;
+        let y1 = ((blur.y - selection_rect.top as f32) * scale).round() as i32;
+        let x2 = ((blur.x2 - selection_rect.left as f32) * scale).round() as i32;
+        let y2 = ((blur.y2 - selection_rect.top as f32) * scale).round() as i32;
+
+        let min_x = x1.min(x2).clamp(0, img_w);
+        let max_x = x1.max(x2).clamp(0, img_w);
+        let min_y = y1.min(y2).clamp(0, img_h);
+        let max_y = y1.max(y2).clamp(0, img_h);
+        if max_x <= min_x || max_y <= min_y {
+            continue;
+        }
+
+        let sigma = (blur.radius as f32 * scale / 2.0).max(0.5);
+        let radii = gaussian_box_radii(sigma);
+        let pad = radii.iter().sum::<usize>() as i32;
+
+        // Blur a padded copy of the region, then write back only the region
+        let pad_x0 = (min_x - pad).max(0);
+        let pad_y0 = (min_y - pad).max(0);
+        let pad_x1 = (max_x + pad).min(img_w);
+        let pad_y1 = (max_y + pad).min(img_h);
+        let mut region = image::imageops::crop_imm(
+            &*img,
+            pad_x0 as u32,
+            pad_y0 as u32,
+            (pad_x1 - pad_x0) as u32,
+            (pad_y1 - pad_y0) as u32,
+        )
+        .to_image();
+
+        for radius in radii {
+            box_blur(&mut region, radius);
+        }
+
+        for py in min_y..max_y {
+            for px in min_x..max_x {
+                let pixel = *region.get_pixel((px - pad_x0) as u32, (py - pad_y0) as u32);
+                img.put_pixel(px as u32, py as u32, pixel);
+            }
+        }
+    }
+}
+
+/// Downsampled Gaussian blur used for live previews
+///
+/// Averages `img` over `cell`-sized blocks inside the pixel bounds
+/// `[x1, x2) x [y1, y2)` and blurs the resulting grid with `radius` (in image
+/// pixels). Each grid pixel corresponds to one block, so the preview can be drawn
+/// as a few quads instead of re-blurring every pixel on each frame.
+pub fn blur_preview_grid(
+    img: &RgbaImage,
+    x1: u32,
+    y1: u32,
+    x2: u32,
+    y2: u32,
+    cell: u32,
+    radius: f32,
+) -> RgbaImage {
+    let x2 = x2.min(img.width());
+    let y2 = y2.min(img.height());
+    if x2 <= x1 || y2 <= y1 {
+        return RgbaImage::new(0, 0);
+    }
+
+    let cell = cell.max(1);
+    let cols = (x2 - x1).div_ceil(cell);
+    let rows = (y2 - y1).div_ceil(cell);
+
+    let mut grid = RgbaImage::from_fn(cols, rows, |c, r| {
+        let bx = x1 + c * cell;
+        let by = y1 + r * cell;
+        let bx2 = (bx + cell).min(x2);
+        let by2 = (by + cell).min(y2);
+
+        let mut total = [0u64; 4];
+        for py in by..by2 {
+            for px in bx..bx2 {
+                for (t, v) in total.iter_mut().zip(img.get_pixel(px, py).0) {
+                    *t += v as u64;
+                }
+            }
+        }
+        let count = ((bx2 - bx) * (by2 - by)) as u64;
+        image::Rgba(total.map(|t| (t / count) as u8))
+    });
+
+    let sigma = radius / 2.0 / cell as f32;
+    if sigma >= 0.5 {
+        for r in gaussian_box_radii(sigma) {
+            box_blur(&mut grid, r);
+        }
+    }
+    grid
+}
+
+/// Blur radius (logical pixels) applied outside spotlights that blur
+pub const SPOTLIGHT_BLUR_RADIUS: f32 = 8.0;
+
+/// Dim (and optionally blur) everything outside the spotlights
+///
+/// The spotlights are combined into one mask: a pixel stays untouched if it
+/// lies in any of their rectangles or ellipses. Each spotlight keeps its own dim
+/// strength and blur; the shared outside gets the strongest dim of them, and is
+/// blurred if any of them blurs.
+pub fn draw_spotlights_on_image(
+    img: &mut RgbaImage,
+    spotlights: &[SpotlightAnnotation],
+    selection_rect: &Rect,
+    scale: f32,
+) {
+    if spotlights.is_empty() {
+        return;
+    }
+
+    // Holes in image pixel coordinates: (min_x, min_y, max_x, max_y, ellipse)
+    let holes: Vec<(f32, f32, f32, f32, bool)> = spotlights
+        .iter()
+        .map(|s| {
+            let x1 = (s.x - selection_rect.left as f32) * scale;
+            let y1 = (s.y - selection_rect.top as f32) * scale;
+            let x2 = (s.x2 - selection_rect.left as f32) * scale;
+            let y2 = (s.y2 - selection_rect.top as f32) * scale;
+            (x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2), s.ellipse)
+        })
+        .collect();
+    let in_hole = |x: f32, y: f32| {
+        holes.iter().any(|&(x1, y1, x2, y2, ellipse)| {
+            if x < x1 || x >= x2 || y < y1 || y >= y2 {
+                return false;
+            }
+            if !ellipse {
+                return true;
+            }
+            let (rx, ry) = ((x2 - x1) / 2.0, (y2 - y1) / 2.0);
+            let dx = (x - (x1 + rx)) / rx;
+            let dy = (y - (y1 + ry)) / ry;
+            dx * dx + dy * dy <= 1.0
+        })
+    };
+
+    let blur = spotlights.iter().any(|s| s.blur);
+    let dim = spotlights.iter().map(|s| s.dim).fold(0.0, f32::max);
+
+    let blurred = blur.then(|| {
+        let mut copy = img.clone();
+        let sigma = (SPOTLIGHT_BLUR_RADIUS * scale / 2.0).max(0.5);
+        for radius in gaussian_box_radii(sigma) {
+            box_blur(&mut copy, radius);
+        }
+        copy
+    });
+    let keep = 1.0 - dim.clamp(0.0, 1.0);
+
+    for (x, y, pixel) in img.enumerate_pixels_mut() {
+        if in_hole(x as f32 + 0.5, y as f32 + 0.5) {
+            continue;
+        }
+        let src = blurred.as_ref().map_or(pixel.0, |b| b.get_pixel(x, y).0);
+        pixel.0 = [
+            (src[0] as f32 * keep).round() as u8,
+            (src[1] as f32 * keep).round() as u8,
+            (src[2] as f32 * keep).round() as u8,
+            src[3],
+        ];
+    }
+}
+
 /// Draw rectangle outlines onto an image using tiny-skia strokes
+///
+/// Auto-contrast outlines are picked from `capture`, as for arrows.
 pub fn draw_rect_outlines_on_image(
     img: &mut RgbaImage,
+    capture: &RgbaImage,
     rects: &[RectOutlineAnnotation],
     selection_rect: &Rect,
     scale: f32,
@@ -256,12 +668,14 @@ pub fn draw_rect_outlines_on_image(
         return;
     }
 
-    with_pixmap(img, |pixmap| {
-        let thickness = (shape::THICKNESS * scale).max(1.0);
-        let border_thickness = (shape::BORDER_THICKNESS * scale).max(2.0);
+    let backdrop = backdrop(capture, selection_rect, scale);
+    let outlines: Vec<[u8; 3]> = rects.iter().map(|r| backdrop.rect_outline(r)).collect();
 
-        for rect in rects {
+    with_pixmap(img, |pixmap| {
+        for (rect, [or, og, ob]) in rects.iter().zip(outlines) {
             let [r, g, b, a] = rect.color.to_rgba_u8();
+            let thickness = (rect.width * scale).max(1.0);
+            let border_thickness = (shape::border_thickness(rect.width) * scale).max(2.0);
 
             let x1 = (rect.start_x - selection_rect.left as f32) * scale;
             let y1 = (rect.start_y - selection_rect.top as f32) * scale;
@@ -281,10 +695,15 @@ pub fn draw_rect_outlines_on_image(
                 continue;
             };
 
+            // Tint the inside before any strokes
+            if rect.fill {
+                fill_shape(pixmap, &path, rect.color);
+            }
+
             // Draw shadow first
             if rect.shadow {
                 let mut paint = Paint::default();
-                paint.set_color_rgba8(0, 0, 0, 220);
+                paint.set_color_rgba8(or, og, ob, 220);
                 paint.anti_alias = true;
 
                 let stroke = Stroke {
@@ -312,9 +731,27 @@ pub fn draw_rect_outlines_on_image(
     });
 }
 
+/// Fill a circle or rectangle path with its color at `shape::FILL_ALPHA`
+fn fill_shape(pixmap: &mut Pixmap, path: &tiny_skia::Path, color: ShapeColor) {
+    let [r, g, b, _] = color.to_rgba_u8();
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(r, g, b, (shape::FILL_ALPHA * 255.0).round() as u8);
+    paint.anti_alias = true;
+    pixmap.fill_path(
+        path,
+        &paint,
+        tiny_skia::FillRule::Winding,
+        Transform::identity(),
+        None,
+    );
+}
+
 /// Draw circle/ellipse outlines onto an image using tiny-skia
+///
+/// Auto-contrast outlines are picked from `capture`, as for arrows.
 pub fn draw_circle_outlines_on_image(
     img: &mut RgbaImage,
+    capture: &RgbaImage,
     circles: &[CircleOutlineAnnotation],
     selection_rect: &Rect,
     scale: f32,
@@ -323,12 +760,14 @@ pub fn draw_circle_outlines_on_image(
         return;
     }
 
-    with_pixmap(img, |pixmap| {
-        let thickness = (shape::THICKNESS * scale).max(1.0);
-        let border_thickness = (shape::BORDER_THICKNESS * scale).max(2.0);
+    let backdrop = backdrop(capture, selection_rect, scale);
+    let outlines: Vec<[u8; 3]> = circles.iter().map(|c| backdrop.circle_outline(c)).collect();
 
-        for c in circles {
+    with_pixmap(img, |pixmap| {
+        for (c, [or, og, ob]) in circles.iter().zip(outlines) {
             let [r, g, b, a] = c.color.to_rgba_u8();
+            let thickness = (c.width * scale).max(1.0);
+            let border_thickness = (shape::border_thickness(c.width) * scale).max(2.0);
 
             let x1 = (c.start_x - selection_rect.left as f32) * scale;
             let y1 = (c.start_y - selection_rect.top as f32) * scale;
@@ -343,10 +782,15 @@ pub fn draw_circle_outlines_on_image(
                 continue;
             };
 
+            // Tint the inside before any strokes
+            if c.fill {
+                fill_shape(pixmap, &path, c.color);
+            }
+
             // Draw shadow first
             if c.shadow {
                 let mut paint = Paint::default();
-                paint.set_color_rgba8(0, 0, 0, 220);
+                paint.set_color_rgba8(or, og, ob, 220);
                 paint.anti_alias = true;
 
                 let stroke = Stroke {
@@ -374,6 +818,120 @@ pub fn draw_circle_outlines_on_image(
     });
 }
 
+/// Draw measurements (dimension lines or boxes with size labels) onto an image
+///
+/// Labels give physical sizes at the export `scale`, so they match the saved pixels.
+pub fn draw_measurements_on_image(
+    img: &mut RgbaImage,
+    measurements: &[MeasureAnnotation],
+    selection_rect: &Rect,
+    scale: f32,
+) {
+    if measurements.is_empty() {
+        return;
+    }
+
+    let to_image = |x: f32, y: f32| {
+        (
+            (x - selection_rect.left as f32) * scale,
+            (y - selection_rect.top as f32) * scale,
+        )
+    };
+
+    with_pixmap(img, |pixmap| {
+        let thickness = (measure::THICKNESS * scale).max(1.0);
+        for m in measurements {
+            let start = to_image(m.start_x, m.start_y);
+            let end = to_image(m.end_x, m.end_y);
+            let mut pb = PathBuilder::new();
+            for (a, b) in measure::segments(m.kind, start, end, scale) {
+                pb.move_to(a.0, a.1);
+                pb.line_to(b.0, b.1);
+            }
+            let Some(path) = pb.finish() else {
+                continue;
+            };
+
+            // Dark outline keeps the thin lines visible on any background
+            let [r, g, b, a] = m.color.to_rgba_u8();
+            for (color, width) in [
+                ([0, 0, 0, 160], thickness + 2.0 * scale),
+                ([r, g, b, a], thickness),
+            ] {
+                let mut paint = Paint::default();
+                paint.set_color_rgba8(color[0], color[1], color[2], color[3]);
+                paint.anti_alias = true;
+                let stroke = Stroke {
+                    width,
+                    line_cap: LineCap::Round,
+                    line_join: LineJoin::Round,
+                    ..Default::default()
+                };
+                pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+            }
+        }
+    });
+
+    // White label on a dark plate, kept inside the image
+    let dot = (measure::LABEL_SIZE / text::GLYPH_HEIGHT as f32 * scale)
+        .round()
+        .max(1.0) as u32;
+    let pad = 2 * dot;
+    for m in measurements {
+        let label = m.label(scale);
+        let (text_w, text_h) = text::text_size(&label, dot);
+        let (plate_w, plate_h) = (text_w + 2 * pad, text_h + 2 * pad);
+        let (cx, cy) = measure::label_center(
+            m.kind,
+            to_image(m.start_x, m.start_y),
+            to_image(m.end_x, m.end_y),
+            plate_h as f32,
+            scale,
+        );
+        let max_x = img.width().saturating_sub(plate_w) as f32;
+        let max_y = img.height().saturating_sub(plate_h) as f32;
+        let x = (cx - plate_w as f32 * 0.5).clamp(0.0, max_x) as u32;
+        let y = (cy - plate_h as f32 * 0.5).clamp(0.0, max_y) as u32;
+
+        for py in y..(y + plate_h).min(img.height()) {
+            for px in x..(x + plate_w).min(img.width()) {
+                blend_pixel(img.get_pixel_mut(px, py), [0, 0, 0], 0.75);
+            }
+        }
+        text::draw_text(
+            img,
+            &label,
+            (x + pad) as i32,
+            (y + pad) as i32,
+            dot,
+            image::Rgba([255, 255, 255, 255]),
+        );
+    }
+}
+
+/// Draw stamps onto an image, scaled to the export `scale`
+pub fn draw_stamps_on_image(
+    img: &mut RgbaImage,
+    stamps: &[StampAnnotation],
+    selection_rect: &Rect,
+    scale: f32,
+) {
+    for stamp in stamps {
+        let (x1, y1, x2, y2) = stamp.rect();
+        let w = ((x2 - x1) * scale).round().max(1.0) as u32;
+        let h = ((y2 - y1) * scale).round().max(1.0) as u32;
+        let resized = image::imageops::resize(
+            stamp.image.as_ref(),
+            w,
+            h,
+            image::imageops::FilterType::Triangle,
+        );
+        let x = ((x1 - selection_rect.left as f32) * scale).round() as i64;
+        let y = ((y1 - selection_rect.top as f32) * scale).round() as i64;
+        image::imageops::overlay(img, &resized, x, y);
+    }
+}
+
 /// Bilinearly sample an image at floating-point coordinates.
 ///
 /// Coordinates are clamped to the image bounds. Produces the smooth,
@@ -429,6 +987,11 @@ pub fn draw_magnifiers_on_image(
     let img_h = img.height();
 
     for m in magnifiers {
+        if m.is_callout() {
+            draw_callout_on_image(img, m, selection_rect, scale);
+            continue;
+        }
+
         let x1 = (m.start_x - selection_rect.left as f32) * scale;
         let y1 = (m.start_y - selection_rect.top as f32) * scale;
         let x2 = (m.end_x - selection_rect.left as f32) * scale;
@@ -518,6 +1081,224 @@ pub fn draw_magnifiers_on_image(
     }
 }
 
+/// Draw a rectangular zoom callout: the source rectangle enlarged into its
+/// callout rectangle, both framed and joined by connector lines.
+fn draw_callout_on_image(
+    img: &mut RgbaImage,
+    m: &MagnifierAnnotation,
+    selection_rect: &Rect,
+    scale: f32,
+) {
+    let Some((cx1, cy1, cx2, cy2)) = m.callout_rect() else {
+        return;
+    };
+    let to_img = |x1: f32, y1: f32, x2: f32, y2: f32| {
+        (
+            (x1 - selection_rect.left as f32) * scale,
+            (y1 - selection_rect.top as f32) * scale,
+            (x2 - selection_rect.left as f32) * scale,
+            (y2 - selection_rect.top as f32) * scale,
+        )
+    };
+    let (sx1, sy1, sx2, sy2) = m.source_rect();
+    let src_rect = to_img(sx1, sy1, sx2, sy2);
+    let dst_rect = to_img(cx1, cy1, cx2, cy2);
+    if src_rect.2 - src_rect.0 < 1.0 || src_rect.3 - src_rect.1 < 1.0 {
+        return;
+    }
+
+    let img_w = img.width();
+    let img_h = img.height();
+    let magnification = m.magnification.max(1.0);
+
+    // Snapshot the source region (the copy may overlap it)
+    let sub_x0 = (src_rect.0 - 1.0).floor().clamp(0.0, img_w as f32) as u32;
+    let sub_y0 = (src_rect.1 - 1.0).floor().clamp(0.0, img_h as f32) as u32;
+    let sub_x1 = (src_rect.2 + 1.0).ceil().clamp(0.0, img_w as f32) as u32;
+    let sub_y1 = (src_rect.3 + 1.0).ceil().clamp(0.0, img_h as f32) as u32;
+    if sub_x1 <= sub_x0 || sub_y1 <= sub_y0 {
+        return;
+    }
+    let src = image::imageops::crop_imm(&*img, sub_x0, sub_y0, sub_x1 - sub_x0, sub_y1 - sub_y0)
+        .to_image();
+
+    let px_start = dst_rect.0.round().clamp(0.0, img_w as f32) as u32;
+    let py_start = dst_rect.1.round().clamp(0.0, img_h as f32) as u32;
+    let px_end = dst_rect.2.round().clamp(0.0, img_w as f32) as u32;
+    let py_end = dst_rect.3.round().clamp(0.0, img_h as f32) as u32;
+
+    for py in py_start..py_end {
+        for px in px_start..px_end {
+            // Map the destination pixel center back into the source rectangle
+            let sx =
+                src_rect.0 + (px as f32 + 0.5 - dst_rect.0) / magnification - 0.5 - sub_x0 as f32;
+            let sy =
+                src_rect.1 + (py as f32 + 0.5 - dst_rect.1) / magnification - 0.5 - sub_y0 as f32;
+            img.put_pixel(px, py, bilinear_sample(&src, sx, sy));
+        }
+    }
+
+    // Frames around both rectangles, plus the connectors between them
+    with_pixmap(img, |pixmap| {
+        let width = (m.callout_border * scale).max(1.0);
+        let connector_width = (width * 0.5).max(1.0);
+        let [r, g, b, a] = m.color.to_rgba_u8();
+
+        let mut frames = PathBuilder::new();
+        for (x1, y1, x2, y2) in [src_rect, dst_rect] {
+            frames.move_to(x1, y1);
+            frames.line_to(x2, y1);
+            frames.line_to(x2, y2);
+            frames.line_to(x1, y2);
+            frames.close();
+        }
+        let mut connectors = PathBuilder::new();
+        for ((x0, y0), (x1, y1)) in geometry::callout_connectors(src_rect, dst_rect) {
+            connectors.move_to(x0, y0);
+            connectors.line_to(x1, y1);
+        }
+
+        for (path, width) in [
+            (frames.finish(), width),
+            (connectors.finish(), connector_width),
+        ] {
+            let Some(path) = path else {
+                continue;
+            };
+            if m.shadow {
+                let mut paint = Paint::default();
+                paint.set_color_rgba8(0, 0, 0, 220);
+                paint.anti_alias = true;
+                let stroke = Stroke {
+                    width: width + 2.0 * scale,
+                    line_cap: LineCap::Round,
+                    line_join: LineJoin::Round,
+                    ..Default::default()
+                };
+                pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+            }
+
+            let mut paint = Paint::default();
+            paint.set_color_rgba8(r, g, b, a);
+            paint.anti_alias = true;
+            let stroke = Stroke {
+                width,
+                line_cap: LineCap::Round,
+                line_join: LineJoin::Round,
+                ..Default::default()
+            };
+            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+        }
+    });
+}
+
+/// Opacity of the beautify frame's drop shadow at its darkest
+const BEAUTIFY_SHADOW_OPACITY: f32 = 0.45;
+
+/// Blend `color` over `dst` with the given opacity (0.0-1.0)
+fn blend_pixel(dst: &mut image::Rgba<u8>, color: [u8; 3], opacity: f32) {
+    for (d, c) in dst.0.iter_mut().zip(color) {
+        *d = (*d as f32 * (1.0 - opacity) + c as f32 * opacity).round() as u8;
+    }
+}
+
+/// Anti-aliased coverage of pixel (x, y) by a `w` x `h` rectangle with
+/// corners rounded to `radius`
+fn rounded_rect_coverage(x: u32, y: u32, w: u32, h: u32, radius: f32) -> f32 {
+    if radius < 0.5 {
+        return 1.0;
+    }
+    let px = x as f32 + 0.5;
+    let py = y as f32 + 0.5;
+    let cx = px.clamp(radius, w as f32 - radius);
+    let cy = py.clamp(radius, h as f32 - radius);
+    (radius - (px - cx).hypot(py - cy) + 0.5).clamp(0.0, 1.0)
+}
+
+/// Background colour of the beautify frame at pixel (x, y) of a `w` x `h` canvas
+fn beautify_background_pixel(
+    background: &BeautifyBackground,
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+) -> image::Rgba<u8> {
+    match *background {
+        BeautifyBackground::Solid(color) => image::Rgba(color.to_rgba_u8()),
+        BeautifyBackground::Gradient { from, to, angle } => {
+            let (sin, cos) = angle.to_radians().sin_cos();
+            let half_extent = (w as f32 * cos.abs() + h as f32 * sin.abs()) * 0.5;
+            let along =
+                (x as f32 + 0.5 - w as f32 * 0.5) * cos + (y as f32 + 0.5 - h as f32 * 0.5) * sin;
+            let t = (along / half_extent.max(1.0) * 0.5 + 0.5).clamp(0.0, 1.0);
+            let [r0, g0, b0, _] = from.to_rgba_u8();
+            let [r1, g1, b1, _] = to.to_rgba_u8();
+            let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+            image::Rgba([mix(r0, r1), mix(g0, g1), mix(b0, b1), 255])
+        }
+    }
+}
+
+/// Wrap an exported screenshot in the beautify frame: padding on a solid or
+/// gradient background, rounded corners and a soft drop shadow.
+///
+/// Preset sizes are logical pixels and are multiplied by `scale`.
+pub fn beautify_image(img: &RgbaImage, preset: &BeautifyPreset, scale: f32) -> RgbaImage {
+    let (w, h) = img.dimensions();
+    let pad = (preset.padding as f32 * scale).round() as u32;
+    let radius = (preset.corner_radius as f32 * scale).min(w.min(h) as f32 * 0.5);
+    let (out_w, out_h) = (w + 2 * pad, h + 2 * pad);
+
+    let mut out = RgbaImage::from_fn(out_w, out_h, |x, y| {
+        beautify_background_pixel(&preset.background, x, y, out_w, out_h)
+    });
+
+    // Drop shadow: the screenshot's rounded shape, nudged down and blurred
+    if preset.shadow > 0 {
+        let sigma = preset.shadow as f32 * scale * 0.5;
+        let drop = (sigma * 0.5).round() as u32;
+        let mut mask = RgbaImage::new(out_w, out_h);
+        for y in 0..h {
+            let my = pad + y + drop;
+            if my >= out_h {
+                break;
+            }
+            for x in 0..w {
+                let coverage = rounded_rect_coverage(x, y, w, h, radius);
+                mask.put_pixel(
+                    pad + x,
+                    my,
+                    image::Rgba([0, 0, 0, (coverage * 255.0) as u8]),
+                );
+            }
+        }
+        for r in gaussian_box_radii(sigma) {
+            box_blur(&mut mask, r);
+        }
+        for (dst, m) in out.pixels_mut().zip(mask.pixels()) {
+            blend_pixel(
+                dst,
+                [0, 0, 0],
+                m[3] as f32 / 255.0 * BEAUTIFY_SHADOW_OPACITY,
+            );
+        }
+    }
+
+    // The screenshot itself, clipped to its rounded corners
+    for (x, y, p) in img.enumerate_pixels() {
+        let coverage = rounded_rect_coverage(x, y, w, h, radius) * p[3] as f32 / 255.0;
+        if coverage > 0.0 {
+            blend_pixel(
+                out.get_pixel_mut(pad + x, pad + y),
+                [p[0], p[1], p[2]],
+                coverage,
+            );
+        }
+    }
+
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -550,6 +1331,8 @@ mod tests {
                 b: 0.0,
             },
             shadow: false,
+            callout: None,
+            callout_border: 3.0,
         };
 
         draw_magnifiers_on_image(&mut img, std::slice::from_ref(&m), &selection, 1.0);
@@ -582,6 +1365,8 @@ mod tests {
                 b: 0.0,
             },
             shadow: false,
+            callout: None,
+            callout_border: 3.0,
         };
 
         draw_magnifiers_on_image(&mut img, std::slice::from_ref(&m), &selection, 1.0);
@@ -590,20 +1375,290 @@ mod tests {
         // so it keeps its original encoded coordinate (red = 0).
         assert_eq!(img.get_pixel(0, 0)[0], 0);
     }
+
+    #[test]
+    fn callout_copies_enlarged_source_beside_it() {
+        let mut img = coord_image(40, 20);
+        let selection = Rect {
+            left: 0,
+            top: 0,
+            right: 40,
+            bottom: 20,
+        };
+        // Source 0..4 x 0..4 enlarged 2x into 10..18 x 0..8
+        let m = MagnifierAnnotation {
+            start_x: 0.0,
+            start_y: 0.0,
+            end_x: 4.0,
+            end_y: 4.0,
+            magnification: 2.0,
+            color: ShapeColor {
+                r: 0.0,
+                g: 1.0,
+                b: 0.0,
+            },
+            shadow: false,
+            callout: Some((10.0, 0.0)),
+            callout_border: 1.0,
+        };
+
+        draw_magnifiers_on_image(&mut img, std::slice::from_ref(&m), &selection, 1.0);
+
+        // Destination pixel 13 maps back to source x = (13.5 - 10) / 2 - 0.5 = 1.25
+        assert_eq!(img.get_pixel(13, 4)[0], 1);
+        assert_eq!(img.get_pixel(15, 4)[0], 2);
+        // Pixels beyond the callout keep their encoded coordinate
+        assert_eq!(img.get_pixel(30, 12)[0], 30);
+    }
+
+    #[test]
+    fn blur_smooths_region_and_leaves_outside_untouched() {
+        // Vertical stripes alternating black/white every pixel
+        let mut img = RgbaImage::from_fn(40, 20, |x, _y| {
+            let v = if x % 2 == 0 { 0 } else { 255 };
+            image::Rgba([v, v, v, 255])
+        });
+        let selection = Rect {
+            left: 0,
+            top: 0,
+            right: 40,
+            bottom: 20,
+        };
+        let blur = BlurAnnotation {
+            x: 10.0,
+            y: 0.0,
+            x2: 30.0,
+            y2: 20.0,
+            radius: 8,
+        };
+
+        draw_blurs_on_image(&mut img, std::slice::from_ref(&blur), &selection, 1.0);
+
+        // Inside the region the stripes average out to mid-grey
+        let inside = img.get_pixel(20, 10)[0];
+        assert!((100..=155).contains(&inside), "got {inside}");
+        // Outside the region pixels keep their original values
+        assert_eq!(img.get_pixel(2, 10)[0], 0);
+        assert_eq!(img.get_pixel(35, 10)[0], 255);
+    }
+
+    #[test]
+    fn blur_preview_grid_has_one_pixel_per_cell() {
+        let img = RgbaImage::from_pixel(30, 20, image::Rgba([40, 80, 120, 255]));
+        let grid = blur_preview_grid(&img, 5, 0, 30, 20, 4, 12.0);
+        assert_eq!(grid.dimensions(), (7, 5));
+        // A uniform source stays uniform after blurring
+        assert_eq!(grid.get_pixel(3, 2).0, [40, 80, 120, 255]);
+    }
+
+    #[test]
+    fn secure_pixelation_is_quantised_and_seed_stable() {
+        let source = RgbaImage::from_fn(32, 32, |x, y| {
+            image::Rgba([(x * 8) as u8, (y * 8) as u8, 128, 255])
+        });
+        let selection = Rect {
+            left: 0,
+            top: 0,
+            right: 32,
+            bottom: 32,
+        };
+        let pixelate = PixelateAnnotation {
+            x: 0.0,
+            y: 0.0,
+            x2: 31.0,
+            y2: 31.0,
+            block_size: 8,
+            noise_seed: Some(42),
+        };
+
+        let mut a = source.clone();
+        let mut b = source.clone();
+        draw_pixelations_on_image(&mut a, std::slice::from_ref(&pixelate), &selection, 1.0);
+        draw_pixelations_on_image(&mut b, std::slice::from_ref(&pixelate), &selection, 1.0);
+        assert_eq!(a, b, "same seed must give the same output");
+
+        // Every channel lands on a quantisation bucket centre
+        for p in a.pixels() {
+            for c in 0..3 {
+                assert_eq!(p[c] as i32 % SECURE_QUANT_STEP, SECURE_QUANT_STEP / 2);
+            }
+        }
+
+        // Blocks are uniform
+        assert_eq!(a.get_pixel(8, 8), a.get_pixel(15, 15));
+
+        let mut other = source.clone();
+        let reseeded = PixelateAnnotation {
+            noise_seed: Some(7),
+            ..pixelate
+        };
+        draw_pixelations_on_image(&mut other, std::slice::from_ref(&reseeded), &selection, 1.0);
+        assert_ne!(a, other, "different seeds should perturb block colours");
+    }
+
+    #[test]
+    fn gaussian_box_radii_grow_with_sigma() {
+        let small = gaussian_box_radii(1.0).iter().sum::<usize>();
+        let large = gaussian_box_radii(10.0).iter().sum::<usize>();
+        assert!(large > small);
+    }
+
+    #[test]
+    fn spotlight_dims_only_outside_the_union_of_holes() {
+        let selection = Rect {
+            left: 0,
+            top: 0,
+            right: 40,
+            bottom: 20,
+        };
+        let spot = |x: f32, ellipse: bool| SpotlightAnnotation {
+            x,
+            y: 0.0,
+            x2: x + 20.0,
+            y2: 20.0,
+            ellipse,
+            dim: 0.5,
+            blur: false,
+        };
+        let mut img = RgbaImage::from_pixel(40, 20, image::Rgba([200, 100, 50, 255]));
+        draw_spotlights_on_image(
+            &mut img,
+            &[spot(0.0, false), spot(20.0, true)],
+            &selection,
+            1.0,
+        );
+
+        // Inside the rectangle and at the ellipse centre: untouched
+        assert_eq!(img.get_pixel(1, 1).0, [200, 100, 50, 255]);
+        assert_eq!(img.get_pixel(30, 10).0, [200, 100, 50, 255]);
+        // Ellipse corner lies outside the hole: dimmed, alpha kept
+        assert_eq!(img.get_pixel(21, 1).0, [100, 50, 25, 255]);
+    }
+
+    #[test]
+    fn spotlights_keep_their_own_settings() {
+        let selection = Rect {
+            left: 0,
+            top: 0,
+            right: 60,
+            bottom: 20,
+        };
+        let spot = |x: f32, dim: f32, blur: bool| SpotlightAnnotation {
+            x,
+            y: 0.0,
+            x2: x + 20.0,
+            y2: 20.0,
+            ellipse: false,
+            dim,
+            blur,
+        };
+        let mut img = RgbaImage::from_pixel(60, 20, image::Rgba([200, 200, 200, 255]));
+        img.put_pixel(50, 10, image::Rgba([0, 0, 0, 255]));
+        // The later, lighter spotlight doesn't undo the earlier one's dim or blur
+        draw_spotlights_on_image(
+            &mut img,
+            &[spot(0.0, 0.5, true), spot(20.0, 0.25, false)],
+            &selection,
+            1.0,
+        );
+
+        assert_eq!(img.get_pixel(30, 10).0, [200, 200, 200, 255]);
+        assert!(img.get_pixel(50, 10)[0] > 0);
+        assert!(img.get_pixel(42, 2)[0] <= 100);
+    }
+
+    #[test]
+    fn outlines_contrast_with_the_capture_not_redactions() {
+        let selection = Rect {
+            left: 0,
+            top: 0,
+            right: 40,
+            bottom: 40,
+        };
+        let redact = Annotation::Redact(RedactAnnotation {
+            x: 0.0,
+            y: 0.0,
+            x2: 40.0,
+            y2: 40.0,
+        });
+        let rect = Annotation::Rectangle(RectOutlineAnnotation {
+            start_x: 10.0,
+            start_y: 10.0,
+            end_x: 30.0,
+            end_y: 30.0,
+            width: 4.0,
+            fill: false,
+            color: ShapeColor {
+                r: 1.0,
+                g: 0.0,
+                b: 0.0,
+            },
+            shadow: true,
+            auto_contrast: true,
+        });
+        let mut img = RgbaImage::from_pixel(40, 40, image::Rgba([255, 255, 255, 255]));
+        draw_annotations_in_order(&mut img, &[redact, rect], &selection, 1.0);
+
+        // Red on the white capture gets a black outline, as on the live canvas,
+        // even though the rectangle was drawn over a black redaction
+        assert!(img.get_pixel(7, 20)[0] < 50);
+    }
+
+    #[test]
+    fn beautify_pads_rounds_corners_and_keeps_content() {
+        let img = RgbaImage::from_pixel(40, 40, image::Rgba([255, 255, 255, 255]));
+        let preset = BeautifyPreset {
+            name: "Test".to_string(),
+            padding: 5,
+            background: BeautifyBackground::Solid(ShapeColor {
+                r: 0.0,
+                g: 0.0,
+                b: 0.0,
+            }),
+            corner_radius: 6,
+            shadow: 0,
+        };
+
+        let out = beautify_image(&img, &preset, 2.0);
+
+        // Padding and radius scale with the output: 10px on each side
+        assert_eq!(out.dimensions(), (60, 60));
+        assert_eq!(out.get_pixel(0, 0)[0], 0);
+        // Centre is screenshot content, its corner is cut away by the radius
+        assert_eq!(out.get_pixel(30, 30)[0], 255);
+        assert_eq!(out.get_pixel(10, 10)[0], 0);
+        // Edge midpoints are not affected by the rounding
+        assert_eq!(out.get_pixel(10, 30)[0], 255);
+    }
 }
 
 /// Draw all annotations in order (for proper layering and undo/redo support)
 ///
-/// Redactions and pixelations are ALWAYS drawn first (in their relative order),
-/// then annotations (arrows, circles, rectangles) are drawn on top (in their relative order).
-/// This ensures annotations are never obscured by redactions.
+/// Redactions, pixelations and blurs are ALWAYS drawn first (in their relative order),
+/// then spotlights dim the rest of the image, and finally annotations (arrows,
+/// circles, rectangles, measurements, stamps) are drawn on top (in their relative order).
+/// This ensures annotations are never obscured by redactions or dimmed.
 pub fn draw_annotations_in_order(
     img: &mut RgbaImage,
     annotations: &[Annotation],
     selection_rect: &Rect,
     scale: f32,
 ) {
-    // First pass: draw all redactions and pixelations (in order)
+    // Outlines contrast with the capture itself, not with the redactions,
+    // spotlights and shapes drawn on it, so they match the live canvas
+    let outlined = annotations.iter().any(|annotation| {
+        matches!(
+            annotation,
+            Annotation::Arrow(_) | Annotation::Circle(_) | Annotation::Rectangle(_)
+        )
+    });
+    let capture = if outlined {
+        img.clone()
+    } else {
+        RgbaImage::new(0, 0)
+    };
+
+    // First pass: draw all redactions, pixelations and blurs (in order)
     for annotation in annotations {
         match annotation {
             Annotation::Redact(redact) => {
@@ -617,26 +1672,52 @@ pub fn draw_annotations_in_order(
                     scale,
                 );
             }
+            Annotation::Blur(blur) => {
+                draw_blurs_on_image(img, std::slice::from_ref(blur), selection_rect, scale);
+            }
             _ => {}
         }
     }
 
+    // Spotlights share one mask, so they are applied together
+    let spotlights: Vec<SpotlightAnnotation> = annotations
+        .iter()
+        .filter_map(|annotation| match annotation {
+            Annotation::Spotlight(spotlight) => Some(spotlight.clone()),
+            _ => None,
+        })
+        .collect();
+    draw_spotlights_on_image(img, &spotlights, selection_rect, scale);
+
     // Second pass: draw all shape annotations on top (in order)
     for annotation in annotations {
         match annotation {
             Annotation::Arrow(arrow) => {
-                draw_arrows_on_image(img, std::slice::from_ref(arrow), selection_rect, scale);
+                draw_arrows_on_image(
+                    img,
+                    &capture,
+                    std::slice::from_ref(arrow),
+                    selection_rect,
+                    scale,
+                );
             }
             Annotation::Circle(circle) => {
                 draw_circle_outlines_on_image(
                     img,
+                    &capture,
                     std::slice::from_ref(circle),
                     selection_rect,
                     scale,
                 );
             }
             Annotation::Rectangle(rect) => {
-                draw_rect_outlines_on_image(img, std::slice::from_ref(rect), selection_rect, scale);
+                draw_rect_outlines_on_image(
+                    img,
+                    &capture,
+                    std::slice::from_ref(rect),
+                    selection_rect,
+                    scale,
+                );
             }
             Annotation::Magnifier(magnifier) => {
                 draw_magnifiers_on_image(
@@ -646,6 +1727,17 @@ pub fn draw_annotations_in_order(
                     scale,
                 );
             }
+            Annotation::Measure(measurement) => {
+                draw_measurements_on_image(
+                    img,
+                    std::slice::from_ref(measurement),
+                    selection_rect,
+                    scale,
+                );
+            }
+            Annotation::Stamp(stamp) => {
+                draw_stamps_on_image(img, std::slice::from_ref(stamp), selection_rect, scale);
+            }
             _ => {}
         }
     }
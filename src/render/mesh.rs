@@ -10,47 +10,46 @@ use cosmic::iced::advanced::graphics::{
 };
 use cosmic::iced::core::Rectangle;
 
+use super::contrast::Backdrop;
 use super::geometry::{arrow, mesh as mesh_const};
 
 use crate::domain::ArrowAnnotation;
 
 /// Default arrow rendering parameters
 pub mod arrow_params {
-    pub const THICKNESS: f32 = 4.0;
-    pub const HEAD_SIZE: f32 = 16.0;
     pub const OUTLINE_PX: f32 = 1.0;
-    pub const BORDER_COLOR: cosmic::iced::Color = cosmic::iced::Color {
-        r: 0.0,
-        g: 0.0,
-        b: 0.0,
-        a: 0.9,
-    };
+    /// Opacity of the border; its color comes from `contrast::Backdrop`
+    pub const BORDER_ALPHA: f32 = 0.9;
 }
 
 /// Arrow mesh result: vertices and indices for rendering
 pub type ArrowMesh = (Vec<SolidVertex2D>, Vec<u32>);
 
-/// Build an arrow mesh using lines with rounded caps (shaft line + 2 angled head lines)
+/// Build an arrow mesh using lines with rounded caps (shaft + 2 angled head lines)
 ///
+/// A curved arrow's shaft follows the quadratic Bezier through `control`, and
+/// its head is aimed along the curve's tangent at the tip.
 /// Returns None if arrow is too short to render.
+#[allow(clippy::too_many_arguments)]
 pub fn build_arrow_mesh(
     start_x: f32,
     start_y: f32,
     end_x: f32,
     end_y: f32,
+    control: Option<(f32, f32)>,
     color: Color,
     thickness: f32,
     head_size: f32,
 ) -> Option<ArrowMesh> {
-    let dx = end_x - start_x;
-    let dy = end_y - start_y;
-    let length = (dx * dx + dy * dy).sqrt();
+    let shaft = arrow::shaft_points((start_x, start_y), control, (end_x, end_y));
+    let length: f32 = shaft
+        .windows(2)
+        .map(|w| (w[1].0 - w[0].0).hypot(w[1].1 - w[0].1))
+        .sum();
     if length < arrow::MIN_LENGTH {
         return None;
     }
 
-    // Direction vector is calculated by arrow::head_points
-
     let mut inner = color;
     inner.a = inner.a.clamp(0.0, 1.0);
     let packed_inner = pack(inner);
@@ -65,45 +64,40 @@ pub fn build_arrow_mesh(
     let mut vertices = Vec::new();
     let mut indices = Vec::new();
 
-    // Draw the shaft line from start to end
-    add_line_segment(
-        &mut vertices,
-        &mut indices,
-        start_x,
-        start_y,
-        end_x,
-        end_y,
-        radius,
-        feather,
-        packed_inner,
-        packed_outer,
-    );
+    // Draw the shaft from start to end, segment by segment for curves
+    for w in shaft.windows(2) {
+        add_line_segment(
+            &mut vertices,
+            &mut indices,
+            w[0].0,
+            w[0].1,
+            w[1].0,
+            w[1].1,
+            radius,
+            feather,
+            packed_inner,
+            packed_outer,
+        );
+    }
 
-    // Add rounded caps at start and end
-    add_circle(
-        &mut vertices,
-        &mut indices,
-        start_x,
-        start_y,
-        radius,
-        feather,
-        packed_inner,
-        packed_outer,
-    );
-    add_circle(
-        &mut vertices,
-        &mut indices,
-        end_x,
-        end_y,
-        radius,
-        feather,
-        packed_inner,
-        packed_outer,
-    );
+    // Add rounded caps at start and end, and round joins along a curve
+    for &(x, y) in &shaft {
+        add_circle(
+            &mut vertices,
+            &mut indices,
+            x,
+            y,
+            radius,
+            feather,
+            packed_inner,
+            packed_outer,
+        );
+    }
 
-    // Arrowhead: two angled lines at the tip
+    // Arrowhead: two angled lines at the tip, along the shaft's direction there
+    let (base_x, base_y) = arrow::head_base((start_x, start_y), control, (end_x, end_y));
     let (head1_x, head1_y, head2_x, head2_y) =
-        arrow::head_points(start_x, start_y, end_x, end_y, head_size)?;
+        arrow::head_points(base_x, base_y, end_x, end_y, head_size)?;
 
     // First head line
     add_line_segment(
@@ -156,6 +150,50 @@ pub fn build_arrow_mesh(
     Some((vertices, indices))
 }
 
+/// Build a straight line mesh with rounded caps (used for callout connectors)
+pub fn build_line_mesh(
+    start: (f32, f32),
+    end: (f32, f32),
+    color: Color,
+    thickness: f32,
+) -> ArrowMesh {
+    let packed_inner = pack(color);
+    let mut outer = color;
+    outer.a = 0.0;
+    let packed_outer = pack(outer);
+
+    let radius = thickness / 2.0;
+    let feather = mesh_const::FEATHER;
+
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    add_line_segment(
+        &mut vertices,
+        &mut indices,
+        start.0,
+        start.1,
+        end.0,
+        end.1,
+        radius,
+        feather,
+        packed_inner,
+        packed_outer,
+    );
+    for (x, y) in [start, end] {
+        add_circle(
+            &mut vertices,
+            &mut indices,
+            x,
+            y,
+            radius,
+            feather,
+            packed_inner,
+            packed_outer,
+        );
+    }
+    (vertices, indices)
+}
+
 /// Build a line segment with anti-aliased feathering
 #[allow(clippy::too_many_arguments)]
 fn add_line_segment(
@@ -310,11 +348,13 @@ fn add_circle(
 /// * `viewport` - The clipping viewport
 /// * `arrows` - Slice of arrow annotations to draw
 /// * `output_offset` - (left, top) offset to convert from global to widget-local coords
+/// * `backdrop` - The screenshot under the arrows, for auto-contrast borders
 pub fn draw_arrows(
     renderer: &mut cosmic::Renderer,
     viewport: &Rectangle,
     arrows: &[ArrowAnnotation],
     output_offset: (f32, f32),
+    backdrop: &Backdrop,
 ) {
     use cosmic::iced::core::Renderer as CoreRenderer;
 
@@ -328,17 +368,23 @@ pub fn draw_arrows(
         let start_y = arrow.start_y - offset_y;
         let end_x = arrow.end_x - offset_x;
         let end_y = arrow.end_y - offset_y;
+        let control = arrow.control.map(|(x, y)| (x - offset_x, y - offset_y));
+        let thickness = arrow::thickness(arrow.width);
+        let head_size = arrow::head_size(arrow.width);
 
         // Border/shadow first, then main arrow
+        let [r, g, b] = backdrop.arrow_outline(arrow);
+        let border_color = Color::from_rgba8(r, g, b, arrow_params::BORDER_ALPHA);
         if arrow.shadow
             && let Some((vertices, indices)) = build_arrow_mesh(
                 start_x,
                 start_y,
                 end_x,
                 end_y,
-                arrow_params::BORDER_COLOR,
-                arrow_params::THICKNESS + 2.0 * arrow_params::OUTLINE_PX,
-                arrow_params::HEAD_SIZE + arrow_params::OUTLINE_PX,
+                control,
+                border_color,
+                thickness + 2.0 * arrow_params::OUTLINE_PX,
+                head_size + arrow_params::OUTLINE_PX,
             )
         {
             renderer.with_layer(*viewport, |renderer| {
@@ -355,9 +401,10 @@ pub fn draw_arrows(
             start_y,
             end_x,
             end_y,
+            control,
             arrow_color,
-            arrow_params::THICKNESS,
-            arrow_params::HEAD_SIZE,
+            thickness,
+            head_size,
         ) {
             renderer.with_layer(*viewport, |renderer| {
                 renderer.draw_mesh(Mesh::Solid {
@@ -378,6 +425,7 @@ pub fn draw_arrows(
 /// * `start` - Start position (local coordinates)
 /// * `end` - End position (local coordinates)
 /// * `color` - Arrow color
+/// * `width` - Width setting (see `geometry::arrow::thickness`)
 /// * `with_shadow` - Whether to draw shadow/border
 pub fn draw_arrow_preview(
     renderer: &mut cosmic::Renderer,
@@ -385,12 +433,15 @@ pub fn draw_arrow_preview(
     start: (f32, f32),
     end: (f32, f32),
     color: Color,
+    width: f32,
     with_shadow: bool,
 ) {
     use cosmic::iced::core::Renderer as CoreRenderer;
 
     let (start_x, start_y) = start;
     let (end_x, end_y) = end;
+    let thickness = arrow::thickness(width);
+    let head_size = arrow::head_size(width);
 
     let mut preview_color = color;
     preview_color.a = 0.7;
@@ -402,9 +453,10 @@ pub fn draw_arrow_preview(
             start_y,
             end_x,
             end_y,
+            None,
             preview_border_color,
-            arrow_params::THICKNESS + 2.0 * arrow_params::OUTLINE_PX,
-            arrow_params::HEAD_SIZE + arrow_params::OUTLINE_PX,
+            thickness + 2.0 * arrow_params::OUTLINE_PX,
+            head_size + arrow_params::OUTLINE_PX,
         )
     {
         renderer.with_layer(*viewport, |renderer| {
@@ -421,9 +473,10 @@ pub fn draw_arrow_preview(
         start_y,
         end_x,
         end_y,
+        None,
         preview_color,
-        arrow_params::THICKNESS,
-        arrow_params::HEAD_SIZE,
+        thickness,
+        head_size,
     ) {
         renderer.with_layer(*viewport, |renderer| {
             renderer.draw_mesh(Mesh::Solid {
@@ -2,9 +2,15 @@
 //!
 //! This module contains:
 //! - Geometry calculations shared between screen and image rendering
+//! - Background sampling for auto-contrast outlines
 //! - Image rendering using tiny-skia (for saving to file)
 //! - Mesh building using iced (for screen preview)
+//! - Bitmap label text for exports
+//! - The stamp library (built-in marks and user stamp files)
 
+pub mod contrast;
 pub mod geometry;
 pub mod image;
 pub mod mesh;
+pub mod stamp;
+pub mod text;
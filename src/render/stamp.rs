@@ -0,0 +1,224 @@
+//! Stamp library for the stamp tool
+//!
+//! Stamps are PNG, GIF or SVG files in the user stamp directory
+//! (`~/.local/share/snappea/stamps`), listed after the built-in OK/NG marks.
+//! Everything is decoded once into straight-alpha RGBA; SVGs and the built-ins
+//! are rasterised at `RASTER_SIZE` so they stay sharp at usual stamp sizes.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use image::{Rgba, RgbaImage, imageops};
+use tiny_skia::{
+    Color, FillRule, LineCap, LineJoin, Paint, PathBuilder, Pixmap, Stroke, Transform,
+};
+
+/// Longer side in pixels of rasterised SVG and built-in stamps
+const RASTER_SIZE: u32 = 256;
+/// Bitmaps with a longer side above this are scaled down when loaded
+const MAX_BITMAP_SIZE: u32 = 1024;
+
+/// A stamp ready to be placed: the image plus its display handle
+#[derive(Clone, Debug)]
+pub struct Stamp {
+    pub name: String,
+    pub image: Arc<RgbaImage>,
+    pub handle: cosmic::widget::image::Handle,
+}
+
+impl Stamp {
+    fn new(name: String, image: RgbaImage) -> Self {
+        let handle = cosmic::widget::image::Handle::from_rgba(
+            image.width(),
+            image.height(),
+            image.as_raw().clone(),
+        );
+        Self {
+            name,
+            image: Arc::new(image),
+            handle,
+        }
+    }
+}
+
+/// Directory users put their own stamps in
+pub fn stamp_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("snappea").join("stamps"))
+}
+
+/// Load the built-in stamps followed by the stamps in the user stamp directory
+pub fn load_stamps() -> Vec<Stamp> {
+    let mut stamps = builtin_stamps();
+    if let Some(dir) = stamp_dir() {
+        stamps.extend(load_dir(&dir));
+    }
+    stamps
+        .into_iter()
+        .map(|(name, image)| Stamp::new(name, image))
+        .collect()
+}
+
+/// Decode every stamp file in `dir`, sorted by name; unreadable files are skipped
+fn load_dir(dir: &Path) -> Vec<(String, RgbaImage)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_string_lossy().into_owned();
+            let extension = path.extension()?.to_string_lossy().to_lowercase();
+            let image = match extension.as_str() {
+                "svg" => std::fs::read(&path)
+                    .ok()
+                    .and_then(|data| rasterize_svg(&data)),
+                "png" | "gif" => image::open(&path)
+                    .ok()
+                    .map(|img| limit_size(img.into_rgba8())),
+                _ => return None,
+            };
+            if image.is_none() {
+                log::warn!("Could not load stamp {}", path.display());
+            }
+            Some((name, image?))
+        })
+        .collect()
+}
+
+fn limit_size(img: RgbaImage) -> RgbaImage {
+    let longer = img.width().max(img.height());
+    if longer <= MAX_BITMAP_SIZE {
+        return img;
+    }
+    let scale = MAX_BITMAP_SIZE as f32 / longer as f32;
+    let w = ((img.width() as f32 * scale).round() as u32).max(1);
+    let h = ((img.height() as f32 * scale).round() as u32).max(1);
+    imageops::resize(&img, w, h, imageops::FilterType::Triangle)
+}
+
+/// Rasterise an SVG document so its longer side is `RASTER_SIZE`
+fn rasterize_svg(data: &[u8]) -> Option<RgbaImage> {
+    let tree = resvg::usvg::Tree::from_data(data, &resvg::usvg::Options::default()).ok()?;
+    let size = tree.size();
+    let scale = RASTER_SIZE as f32 / size.width().max(size.height());
+    let w = ((size.width() * scale).round() as u32).max(1);
+    let h = ((size.height() * scale).round() as u32).max(1);
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(w, h)?;
+    resvg::render(
+        &tree,
+        resvg::tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    Some(demultiply(pixmap.data(), w, h))
+}
+
+/// Convert premultiplied RGBA pixels (as tiny-skia draws them) to straight alpha
+fn demultiply(data: &[u8], width: u32, height: u32) -> RgbaImage {
+    let mut img = RgbaImage::new(width, height);
+    for (dst, src) in img.pixels_mut().zip(data.chunks_exact(4)) {
+        let a = src[3] as u32;
+        let channel = |c: u8| {
+            (c as u32 * 255 + a / 2)
+                .checked_div(a)
+                .map_or(0, |v| v.min(255) as u8)
+        };
+        *dst = Rgba([channel(src[0]), channel(src[1]), channel(src[2]), src[3]]);
+    }
+    img
+}
+
+/// Built-in pass/fail marks: a white check or cross on a green or red disc
+fn builtin_stamps() -> Vec<(String, RgbaImage)> {
+    let check: &[(f32, f32)] = &[(0.28, 0.53), (0.44, 0.69), (0.73, 0.35)];
+    let cross: &[&[(f32, f32)]] = &[&[(0.33, 0.33), (0.67, 0.67)], &[(0.67, 0.33), (0.33, 0.67)]];
+    [
+        ("OK", Color::from_rgba8(0x2e, 0xa0, 0x43, 0xff), vec![check]),
+        (
+            "NG",
+            Color::from_rgba8(0xd9, 0x30, 0x25, 0xff),
+            cross.to_vec(),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(name, disc, strokes)| Some((name.to_string(), draw_mark(disc, &strokes)?)))
+    .collect()
+}
+
+fn draw_mark(disc: Color, strokes: &[&[(f32, f32)]]) -> Option<RgbaImage> {
+    let size = RASTER_SIZE as f32;
+    let mut pixmap = Pixmap::new(RASTER_SIZE, RASTER_SIZE)?;
+
+    let mut paint = Paint::default();
+    paint.set_color(disc);
+    paint.anti_alias = true;
+    let circle = PathBuilder::from_circle(size / 2.0, size / 2.0, size * 0.46)?;
+    pixmap.fill_path(
+        &circle,
+        &paint,
+        FillRule::Winding,
+        Transform::identity(),
+        None,
+    );
+
+    let mut pb = PathBuilder::new();
+    for points in strokes {
+        let mut points = points.iter().map(|&(x, y)| (x * size, y * size));
+        let (x, y) = points.next()?;
+        pb.move_to(x, y);
+        for (x, y) in points {
+            pb.line_to(x, y);
+        }
+    }
+    let path = pb.finish()?;
+    paint.set_color(Color::WHITE);
+    let stroke = Stroke {
+        width: size * 0.09,
+        line_cap: LineCap::Round,
+        line_join: LineJoin::Round,
+        ..Default::default()
+    };
+    pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+
+    Some(demultiply(pixmap.data(), RASTER_SIZE, RASTER_SIZE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_marks_are_transparent_outside_the_disc() {
+        let stamps = builtin_stamps();
+        let names: Vec<&str> = stamps.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["OK", "NG"]);
+        for (_, img) in &stamps {
+            assert_eq!(img.dimensions(), (RASTER_SIZE, RASTER_SIZE));
+            assert_eq!(img.get_pixel(0, 0)[3], 0);
+            // Opaque, coloured disc near its top edge, clear of the mark
+            let disc = img.get_pixel(RASTER_SIZE / 2, 20);
+            assert_eq!(disc[3], 255);
+            assert_ne!(*disc, Rgba([255; 4]));
+        }
+    }
+
+    #[test]
+    fn loads_image_files_from_the_stamp_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        RgbaImage::from_pixel(4, 2, Rgba([0, 0, 255, 128]))
+            .save(dir.path().join("logo.png"))
+            .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a stamp").unwrap();
+        std::fs::write(dir.path().join("broken.png"), "not a png").unwrap();
+
+        let stamps = load_dir(dir.path());
+        assert_eq!(stamps.len(), 1);
+        assert_eq!(stamps[0].0, "logo");
+        assert_eq!(*stamps[0].1.get_pixel(3, 1), Rgba([0, 0, 255, 128]));
+    }
+}
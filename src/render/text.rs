@@ -0,0 +1,102 @@
+//! Minimal bitmap text for labels burned into exported images
+//!
+//! The export path has no font stack, and measurement labels only need
+//! digits, units and a few symbols, so they are drawn from a built-in 5×7
+//! dot font. Each dot is `dot` pixels square; characters without a glyph are
+//! left blank.
+
+use image::{Rgba, RgbaImage};
+
+/// Glyph width in dots
+const GLYPH_WIDTH: u32 = 5;
+/// Glyph height in dots
+pub const GLYPH_HEIGHT: u32 = 7;
+/// Horizontal advance per character in dots (glyph plus one dot of spacing)
+const ADVANCE: u32 = GLYPH_WIDTH + 1;
+
+/// Rows of a glyph, top to bottom, with the leftmost dot in bit 4
+fn glyph(c: char) -> Option<[u8; 7]> {
+    Some(match c {
+        '0' => [0x0e, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0e],
+        '1' => [0x04, 0x0c, 0x04, 0x04, 0x04, 0x04, 0x0e],
+        '2' => [0x0e, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1f],
+        '3' => [0x1f, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0e],
+        '4' => [0x02, 0x06, 0x0a, 0x12, 0x1f, 0x02, 0x02],
+        '5' => [0x1f, 0x10, 0x1e, 0x01, 0x01, 0x11, 0x0e],
+        '6' => [0x06, 0x08, 0x10, 0x1e, 0x11, 0x11, 0x0e],
+        '7' => [0x1f, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0e, 0x11, 0x11, 0x0e, 0x11, 0x11, 0x0e],
+        '9' => [0x0e, 0x11, 0x11, 0x0f, 0x01, 0x02, 0x0c],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c],
+        '-' => [0x00, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00],
+        '×' => [0x00, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x00],
+        '·' => [0x00, 0x00, 0x00, 0x0c, 0x0c, 0x00, 0x00],
+        '°' => [0x0c, 0x12, 0x12, 0x0c, 0x00, 0x00, 0x00],
+        'p' => [0x00, 0x00, 0x1e, 0x11, 0x1e, 0x10, 0x10],
+        't' => [0x08, 0x08, 0x1c, 0x08, 0x08, 0x09, 0x06],
+        'x' => [0x00, 0x00, 0x11, 0x0a, 0x04, 0x0a, 0x11],
+        _ => return None,
+    })
+}
+
+/// Size in pixels of `text` drawn with `dot`-pixel dots
+pub fn text_size(text: &str, dot: u32) -> (u32, u32) {
+    let chars = text.chars().count() as u32;
+    let width = (chars * ADVANCE).saturating_sub(1) * dot;
+    (width, GLYPH_HEIGHT * dot)
+}
+
+/// Draw `text` with its top-left corner at (x, y), clipped to the image
+pub fn draw_text(img: &mut RgbaImage, text: &str, x: i32, y: i32, dot: u32, color: Rgba<u8>) {
+    let (width, height) = (img.width() as i32, img.height() as i32);
+    let dot = dot as i32;
+    for (i, c) in text.chars().enumerate() {
+        let Some(rows) = glyph(c) else {
+            continue;
+        };
+        let glyph_x = x + i as i32 * ADVANCE as i32 * dot;
+        for (row, bits) in rows.iter().enumerate() {
+            for col in 0..GLYPH_WIDTH as i32 {
+                if bits & (0x10 >> col) == 0 {
+                    continue;
+                }
+                let px = glyph_x + col * dot;
+                let py = y + row as i32 * dot;
+                for yy in py.max(0)..(py + dot).min(height) {
+                    for xx in px.max(0)..(px + dot).min(width) {
+                        img.put_pixel(xx as u32, yy as u32, color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_scaled_glyphs_inside_their_box() {
+        let white = Rgba([255, 255, 255, 255]);
+        let mut img = RgbaImage::from_pixel(40, 20, Rgba([0, 0, 0, 255]));
+        assert_eq!(text_size("1.", 2), (22, 14));
+
+        draw_text(&mut img, "1.", 3, 2, 2, white);
+        let lit: Vec<(u32, u32)> = img
+            .enumerate_pixels()
+            .filter(|(_, _, p)| **p == white)
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        assert!(!lit.is_empty());
+        assert!(
+            lit.iter()
+                .all(|&(x, y)| (3..3 + 22).contains(&x) && (2..2 + 14).contains(&y))
+        );
+        // Top of the "1" stem: dot column 2 of row 0
+        assert_eq!(*img.get_pixel(3 + 4, 2), white);
+
+        // Clipped at the image edge without panicking
+        draw_text(&mut img, "888", 30, 15, 2, white);
+    }
+}
@@ -21,24 +21,37 @@ use tokio::sync::mpsc::Sender;
 use wayland_client::protocol::wl_output::WlOutput;
 use zbus::zvariant;
 
+use crate::capture::barcode::detect_barcodes;
 use crate::capture::image::ScreenshotImage;
 use crate::capture::ocr::{
-    OcrMapping, OcrStatus, is_tesseract_available, models_need_download,
-    run_ocr_on_image_with_status,
+    OcrLayout, OcrMapping, OcrStatus, backend, is_tesseract_available, language_arg,
+    layout_overlays, models_need_download, overlays_text, run_ocr_on_image_with_status,
+    scrub_redacted_overlays, toggle_language,
 };
-use crate::capture::qr::{DetectedQrCode, detect_qr_codes_at_resolution, is_duplicate_qr};
-use crate::config::{SaveLocationChoice, SnapPeaConfig};
+use crate::capture::qr::{
+    DetectedQrCode, QrFormat, detect_qr_codes_at_resolution, format_codes, is_duplicate_qr,
+};
+use crate::capture::search::search_overlays;
+use crate::capture::sensitive::find_sensitive_matches;
+use crate::capture::trim::{TRIM_TOLERANCE, content_bounds};
+use crate::config::{RedactTool, SaveLocationChoice, SnapPeaConfig};
 use crate::core::app::{App, OutputState, RecordingIndicator};
 use crate::core::portal::PortalResponse;
+use crate::domain::color::{MAX_RECENT_COLORS, format_color, hsv_to_rgb};
 pub use crate::domain::{Action, Choice, DragState, ImageSaveLocation, Rect, RectDimension};
-use crate::render::image::draw_annotations_in_order;
+use crate::domain::{
+    Annotation, BlurAnnotation, PixelateAnnotation, Recrop, RectOutlineAnnotation, RedactAnnotation,
+};
+use crate::render::image::{beautify_image, draw_annotations_in_order, secure_noise_seed};
 use crate::session::messages::{
-    CaptureMsg, DetectMsg, Direction, DrawMsg, Msg, OcrMsg, QrMsg, SelectMsg, SettingsMsg, ToolMsg,
+    CaptureMsg, ColorEdit, ColorTarget, DetectMsg, Direction, DrawMsg, Msg, OcrMsg,
+    OcrSearchAction, QrMsg, SelectMsg, SettingsMsg, ToolMsg,
 };
 use crate::session::state::{
     AnnotationState, CaptureData, DetectionState, PortalContext, SessionState, UiState,
 };
 use crate::wayland::{CaptureSource, WaylandHelper};
+use crate::widget::tool_button::color_section_extra_height;
 use crate::{fl, with_args};
 
 // Submodules for reorganized code
@@ -186,6 +199,10 @@ fn combined_image(bounds: Rect, frames: Vec<(RgbaImage, Rect)>) -> RgbaImage {
     image
 }
 
+/// Encode an image as PNG.
+///
+/// Only the image itself is written (IHDR/IDAT/IEND): no text, EXIF or time
+/// chunks, so OCR results and other capture metadata never end up in the file.
 fn write_png<W: io::Write>(w: W, image: &RgbaImage) -> Result<(), png::EncodingError> {
     let mut encoder = png::Encoder::new(w, image.width(), image.height());
     encoder.set_color(png::ColorType::Rgba);
@@ -240,6 +257,226 @@ impl Args {
     pub fn close_all_popups(&mut self) {
         self.ui.close_all_popups();
     }
+
+    /// Drop OCR results that fall inside redacted regions
+    ///
+    /// Redactions can be drawn after OCR ran, so the recognized text is checked
+    /// again before it's shown or copied.
+    pub fn scrub_redacted_ocr(&mut self, outputs: &[OutputState]) {
+        let redacted: Vec<_> = self.annotations.annotations[..self.annotations.annotation_index]
+            .iter()
+            .filter_map(|a| a.redacted_bounds())
+            .collect();
+        let origin = |name: &str| output_origin(outputs, name);
+        if !scrub_redacted_overlays(&mut self.detection.ocr_overlays, &redacted, origin) {
+            return;
+        }
+
+        let text = overlays_text(&self.detection.ocr_overlays);
+        self.detection.ocr_text = (!text.is_empty()).then(|| text.clone());
+        if let OcrStatus::Done(done_text, done_overlays) = &mut self.detection.ocr_status {
+            *done_text = if text.is_empty() {
+                fl!("no-text-detected")
+            } else {
+                text
+            };
+            *done_overlays = self.detection.ocr_overlays.clone();
+        }
+    }
+
+    /// Cover OCR blocks containing sensitive data with the current redact tool
+    ///
+    /// The redactions are regular annotations, so they show up on the canvas
+    /// for review and can be undone before saving. Returns how many were added.
+    pub fn redact_sensitive_ocr(&mut self, outputs: &[OutputState]) -> usize {
+        let matches =
+            find_sensitive_matches(&self.detection.ocr_overlays, &self.ui.sensitive_patterns);
+        let mut blocks: Vec<usize> = matches.iter().map(|m| m.overlay).collect();
+        blocks.sort_unstable();
+        blocks.dedup();
+
+        for &index in &blocks {
+            let overlay = &self.detection.ocr_overlays[index];
+            let (ox, oy) = output_origin(outputs, &overlay.output_name);
+            let bounds = padded_bounds(
+                ox + overlay.left,
+                oy + overlay.top,
+                overlay.width,
+                overlay.height,
+            );
+            let annotation = self.redaction_annotation(self.ui.primary_redact_tool, bounds);
+            self.annotations.add(annotation);
+        }
+        self.annotations.rebuild_arrays();
+        log::info!(
+            "Redacted {} sensitive matches in {} OCR blocks",
+            matches.len(),
+            blocks.len()
+        );
+
+        self.scrub_redacted_ocr(outputs);
+        blocks.len()
+    }
+
+    /// Recompute find-and-redact matches for the current query
+    pub fn refresh_ocr_search(&mut self) {
+        match search_overlays(
+            &self.detection.ocr_overlays,
+            &self.ui.ocr_search_query,
+            self.ui.ocr_search_regex,
+        ) {
+            Ok(matches) => {
+                self.detection.search_matches = matches;
+                self.detection.search_invalid = false;
+            }
+            Err(_) => {
+                self.detection.search_matches.clear();
+                self.detection.search_invalid = true;
+            }
+        }
+    }
+
+    /// Redact, pixelate or highlight every find-and-redact match at once
+    pub fn apply_ocr_search(&mut self, action: OcrSearchAction, outputs: &[OutputState]) {
+        let matches = std::mem::take(&mut self.detection.search_matches);
+        for m in &matches {
+            let (ox, oy) = output_origin(outputs, &m.output_name);
+            let bounds = padded_bounds(ox + m.left, oy + m.top, m.width, m.height);
+            let annotation = match action {
+                OcrSearchAction::Redact => self.redaction_annotation(RedactTool::Redact, bounds),
+                OcrSearchAction::Pixelate => {
+                    self.redaction_annotation(RedactTool::Pixelate, bounds)
+                }
+                OcrSearchAction::Highlight => {
+                    let (start_x, start_y, end_x, end_y) = bounds;
+                    Annotation::Rectangle(RectOutlineAnnotation {
+                        start_x,
+                        start_y,
+                        end_x,
+                        end_y,
+                        width: self.ui.shape_width,
+                        fill: false,
+                        color: self.ui.shape_color,
+                        shadow: self.ui.shape_shadow,
+                        auto_contrast: self.ui.shape_auto_contrast,
+                    })
+                }
+            };
+            self.annotations.add(annotation);
+        }
+        self.annotations.rebuild_arrays();
+        log::info!("Applied {:?} to {} search matches", action, matches.len());
+
+        self.scrub_redacted_ocr(outputs);
+        self.refresh_ocr_search();
+    }
+
+    /// Shrink the selection to its content by stripping uniform-colour margins
+    ///
+    /// A confirmed screen becomes a region so it can be adjusted further.
+    /// Annotations are stored in global coordinates and keep their place.
+    /// Returns false if there was nothing to trim.
+    pub fn auto_trim(&mut self, outputs: &[OutputState]) -> bool {
+        let selection = match &self.session.choice {
+            Choice::Rectangle(r, _) if r.dimensions().is_some() => *r,
+            Choice::Output(Some(name)) => match outputs.iter().find(|o| &o.name == name) {
+                Some(output) => output_bounds(output),
+                None => return false,
+            },
+            _ => return false,
+        };
+
+        // Trim against the output holding most of the selection
+        let Some((output, region)) = outputs
+            .iter()
+            .filter_map(|o| Some((o, selection.intersect(output_bounds(o))?)))
+            .max_by_key(|(_, r)| r.width() as i64 * r.height() as i64)
+        else {
+            return false;
+        };
+        let Some(img) = self.capture.output_images.get(&output.name) else {
+            return false;
+        };
+
+        let scale = img.rgba.width() as f32 / output.logical_size.0 as f32;
+        let x = ((region.left - output.logical_pos.0) as f32 * scale) as u32;
+        let y = ((region.top - output.logical_pos.1) as f32 * scale) as u32;
+        let width = (region.width() as f32 * scale) as u32;
+        let height = (region.height() as f32 * scale) as u32;
+        let pixels = image::imageops::crop_imm(&img.rgba, x, y, width, height).to_image();
+        let Some((left, top, right, bottom)) = content_bounds(&pixels, TRIM_TOLERANCE) else {
+            return false;
+        };
+
+        // Round outwards so no content pixel is lost to the logical grid
+        let trimmed = Rect {
+            left: region.left + (left as f32 / scale).floor() as i32,
+            top: region.top + (top as f32 / scale).floor() as i32,
+            right: region.left + (right as f32 / scale).ceil() as i32,
+            bottom: region.top + (bottom as f32 / scale).ceil() as i32,
+        };
+        if trimmed == selection {
+            return false;
+        }
+
+        log::info!("Auto-trimmed selection {:?} to {:?}", selection, trimmed);
+        self.session.choice = Choice::Rectangle(trimmed, DragState::None);
+        self.clear_ocr_qr();
+        true
+    }
+
+    /// Build a redaction of the given tool kind with the current tool settings
+    fn redaction_annotation(&self, tool: RedactTool, bounds: (f32, f32, f32, f32)) -> Annotation {
+        let (x, y, x2, y2) = bounds;
+        match tool {
+            RedactTool::Redact => Annotation::Redact(RedactAnnotation { x, y, x2, y2 }),
+            RedactTool::Pixelate => Annotation::Pixelate(PixelateAnnotation {
+                x,
+                y,
+                x2,
+                y2,
+                block_size: self.ui.pixelation_block_size,
+                noise_seed: self.ui.secure_pixelation.then(secure_noise_seed),
+            }),
+            RedactTool::Blur => Annotation::Blur(BlurAnnotation {
+                x,
+                y,
+                x2,
+                y2,
+                radius: self.ui.blur_radius,
+            }),
+        }
+    }
+}
+
+/// Grow a text box slightly so glyph edges aren't left peeking out of a redaction
+fn padded_bounds(left: f32, top: f32, width: f32, height: f32) -> (f32, f32, f32, f32) {
+    const PADDING: f32 = 2.0;
+    (
+        left - PADDING,
+        top - PADDING,
+        left + width + PADDING,
+        top + height + PADDING,
+    )
+}
+
+/// Global logical bounds of an output
+fn output_bounds(output: &OutputState) -> Rect {
+    Rect::new(
+        output.logical_pos.0,
+        output.logical_pos.1,
+        output.logical_pos.0 + output.logical_size.0 as i32,
+        output.logical_pos.1 + output.logical_size.1 as i32,
+    )
+}
+
+/// Global logical position of the named output (origin if unknown)
+fn output_origin(outputs: &[OutputState], name: &str) -> (f32, f32) {
+    outputs
+        .iter()
+        .find(|o| o.name == name)
+        .map(|o| (o.logical_pos.0 as f32, o.logical_pos.1 as f32))
+        .unwrap_or((0.0, 0.0))
 }
 struct Output {
     output: WlOutput,
@@ -384,11 +621,38 @@ impl Screenshot {
                         shape_popup_open: false,
                         shape_color: config.shape_color,
                         shape_shadow: config.shape_shadow,
+                        shape_width: config.shape_width,
+                        shape_fill: config.shape_fill,
+                        shape_auto_contrast: config.shape_auto_contrast,
+                        annotation_presets: config.annotation_presets.clone(),
                         primary_redact_tool: config.primary_redact_tool,
                         redact_popup_open: false,
                         pixelation_block_size: config.pixelation_block_size,
+                        secure_pixelation: config.secure_pixelation,
+                        blur_radius: config.blur_radius,
+                        ocr_search_query: String::new(),
+                        ocr_search_regex: false,
+                        sensitive_patterns: config.sensitive_patterns.clone(),
+                        spotlight_dim: config.spotlight_dim,
+                        spotlight_ellipse: config.spotlight_ellipse,
+                        spotlight_blur: config.spotlight_blur,
                         magnifier_popup_open: false,
                         magnifier_magnification: config.magnifier_magnification,
+                        magnifier_callout: config.magnifier_callout,
+                        magnifier_callout_border: config.magnifier_callout_border,
+                        color_picker_format: config.color_picker_format,
+                        color_picker_sets_shape_color: config.color_picker_sets_shape_color,
+                        recent_colors: config.recent_colors.clone(),
+                        color_palettes: config.color_palettes.clone(),
+                        unsaved_names: false,
+                        color_hex_input: String::new(),
+                        color_hsv: None,
+                        measure_kind: config.measure_kind,
+                        measure_in_export: config.measure_in_export,
+                        stamp_popup_open: false,
+                        stamp_name: config.stamp_name.clone(),
+                        stamp_size: config.stamp_size,
+                        stamp_library: Vec::new(),
                         capture_delay_secs: config.capture_delay_secs,
                         magnifier_enabled: config.magnifier_enabled,
                         save_location_setting: config.save_location,
@@ -396,6 +660,9 @@ impl Screenshot {
                         video_save_location_setting: config.video_save_location,
                         video_custom_save_path: config.video_custom_save_path.clone(),
                         copy_to_clipboard_on_save: config.copy_to_clipboard_on_save,
+                        beautify_enabled: config.beautify_enabled,
+                        beautify_preset: config.beautify_preset.clone(),
+                        beautify_presets: config.beautify_presets.clone(),
                         toolbar_unhovered_opacity: config.toolbar_unhovered_opacity,
                         toolbar_is_hovered: false,
                         toolbar_hover_animation: cosmic::iced::Animation::new(false)
@@ -403,6 +670,13 @@ impl Screenshot {
                             .easing(animation::Easing::EaseInOut),
                         toolbar_opacity_save_id: 0,
                         tesseract_available: is_tesseract_available(),
+                        ocr_engine: config.ocr_engine,
+                        ocr_deskew: config.ocr_deskew,
+                        ocr_model_download: config.ocr_model_download,
+                        ocr_languages: config.ocr_languages.clone(),
+                        ocr_installed_languages: Vec::new(),
+                        ocr_capture_languages: None,
+                        ocr_popup_open: false,
                         available_encoders: Vec::new(),
                         encoder_displays: Vec::new(),
                         selected_encoder: config.video_encoder.clone(),
@@ -422,6 +696,7 @@ impl Screenshot {
                         toolbar_bounds: None,
                         hide_toolbar_to_tray: config.hide_toolbar_to_tray,
                         move_offset: None,
+                        crop_mode: false,
                         is_default_portal: is_snappea_default_portal(),
                     }
                 },
@@ -469,9 +744,13 @@ pub(crate) fn view(app: &App, id: window::Id) -> cosmic::Element<'_, Msg> {
     let has_any_annotations = !args.annotations.arrows.is_empty()
         || !args.annotations.circles.is_empty()
         || !args.annotations.rect_outlines.is_empty()
-        || !args.annotations.magnifiers.is_empty();
-    let has_any_redactions =
-        !args.annotations.redactions.is_empty() || !args.annotations.pixelations.is_empty();
+        || !args.annotations.magnifiers.is_empty()
+        || !args.annotations.spotlights.is_empty()
+        || !args.annotations.measurements.is_empty()
+        || !args.annotations.stamps.is_empty();
+    let has_any_redactions = !args.annotations.redactions.is_empty()
+        || !args.annotations.pixelations.is_empty()
+        || !args.annotations.blurs.is_empty();
     let has_ocr_text = args.detection.ocr_text.is_some();
 
     let is_active_output = {
@@ -516,7 +795,11 @@ pub(crate) fn view(app: &App, id: window::Id) -> cosmic::Element<'_, Msg> {
 }
 
 pub fn update_msg(app: &mut App, msg: Msg) -> cosmic::Task<crate::core::app::Msg> {
-    match msg {
+    // A capture ends the session, so edited names can't wait for their popup
+    if matches!(msg, Msg::Capture(_)) {
+        save_unsaved_names(app, true);
+    }
+    let task = match msg {
         // === Draw messages - annotation drawing ===
         Msg::Draw(draw_msg) => handle_draw_msg(app, draw_msg),
 
@@ -534,6 +817,19 @@ pub fn update_msg(app: &mut App, msg: Msg) -> cosmic::Task<crate::core::app::Msg
 
         // === Capture messages - capture workflow ===
         Msg::Capture(capture_msg) => handle_capture_msg(app, capture_msg),
+    };
+    save_unsaved_names(app, false);
+    task
+}
+
+/// Save renamed palettes and presets once the popups showing them are closed
+fn save_unsaved_names(app: &mut App, force: bool) {
+    if let Some(args) = app.screenshot_args.as_mut()
+        && args.ui.unsaved_names
+        && (force || !(args.ui.shape_popup_open || args.ui.pencil_popup_open))
+    {
+        args.ui.unsaved_names = false;
+        crate::widget::tool_handlers::save_tool_config(args);
     }
 }
 
@@ -591,7 +887,12 @@ fn handle_tool_msg(app: &mut App, msg: ToolMsg) -> cosmic::Task<crate::core::app
             // Popup follows toolbar position and appears above or below based on available space
             if indicator.pencil_popup_open {
                 let popup_width = 262.0_f32; // 230 content + 16*2 padding
-                let popup_height = 380.0_f32; // Approximate height of popup content (increased to prevent overlap)
+                // Custom colors grow the popup; leave room for a full recent
+                // colors list and another row of palette colors
+                let color_height = app.screenshot_args.as_ref().map_or(0.0, |args| {
+                    color_section_extra_height(MAX_RECENT_COLORS, &args.ui.color_palettes) + 36.0
+                });
+                let popup_height = 380.0_f32 + color_height; // Approximate height of popup content (increased to prevent overlap)
                 let popup_gap = 16.0_f32; // Gap between popup and toolbar
                 let toolbar_height = 72.0_f32; // Toolbar height including padding
 
@@ -645,6 +946,11 @@ fn handle_tool_msg(app: &mut App, msg: ToolMsg) -> cosmic::Task<crate::core::app
                 indicator.pencil_color = *color;
             }
         }
+        ToolMsg::EditColor(ColorTarget::Pencil, ColorEdit::Hsv(hsv)) => {
+            if let Some(indicator) = &mut app.recording_indicator {
+                indicator.pencil_color = hsv_to_rgb(*hsv);
+            }
+        }
         ToolMsg::SetPencilFadeDuration(duration) => {
             if let Some(indicator) = &mut app.recording_indicator {
                 indicator.pencil_fade_duration = *duration;
@@ -658,13 +964,28 @@ fn handle_tool_msg(app: &mut App, msg: ToolMsg) -> cosmic::Task<crate::core::app
         _ => {}
     }
 
+    // Picked colors are copied in the chosen format; the tool handler only
+    // updates the shape color
+    let picked = match (&msg, app.screenshot_args.as_ref()) {
+        (ToolMsg::ColorPicked(rgb), Some(args)) => {
+            Some(format_color(*rgb, args.ui.color_picker_format))
+        }
+        _ => None,
+    };
+
     if let Some(args) = app.screenshot_args.as_mut() {
         let needs_save = crate::widget::tool_handlers::handle_tool_msg(args, msg);
         if needs_save {
             crate::widget::tool_handlers::save_tool_config(args);
         }
     }
-    cosmic::Task::none()
+    match picked {
+        Some(text) => {
+            log::info!("Copied picked color {}", text);
+            clipboard::write(text)
+        }
+        None => cosmic::Task::none(),
+    }
 }
 
 /// Handle Select messages (mode and navigation)
@@ -722,6 +1043,8 @@ fn handle_settings_msg(app: &mut App, msg: SettingsMsg) -> cosmic::Task<crate::c
                 // Close other popups
                 args.ui.shape_popup_open = false;
                 args.ui.redact_popup_open = false;
+                args.ui.ocr_popup_open = false;
+                args.ui.ensure_ocr_languages_loaded();
                 args.disable_all_modes();
             }
         }
@@ -859,6 +1182,26 @@ fn handle_settings_msg(app: &mut App, msg: SettingsMsg) -> cosmic::Task<crate::c
                 cosmic::Task::none()
             }
             SettingsMsg::ToggleCopyOnSave => settings_handlers::handle_toggle_copy_on_save(args),
+            SettingsMsg::ToggleBeautify => settings_handlers::handle_toggle_beautify(args),
+            SettingsMsg::SetBeautifyPreset(name) => {
+                settings_handlers::handle_set_beautify_preset(args, name)
+            }
+            SettingsMsg::ToggleColorPickerSetsShapeColor => {
+                settings_handlers::handle_toggle_color_picker_sets_shape_color(args)
+            }
+            SettingsMsg::ToggleMeasureInExport => {
+                settings_handlers::handle_toggle_measure_in_export(args)
+            }
+            SettingsMsg::SetOcrEngine(engine) => {
+                settings_handlers::handle_set_ocr_engine(args, engine)
+            }
+            SettingsMsg::ToggleOcrDeskew => settings_handlers::handle_toggle_ocr_deskew(args),
+            SettingsMsg::ToggleOcrModelDownload => {
+                settings_handlers::handle_toggle_ocr_model_download(args)
+            }
+            SettingsMsg::ToggleOcrLanguage(lang) => {
+                settings_handlers::handle_toggle_ocr_language(args, lang)
+            }
             SettingsMsg::SetCaptureDelay(secs) => {
                 args.ui.capture_delay_secs = secs;
                 let mut config = crate::config::SnapPeaConfig::load();
@@ -949,7 +1292,10 @@ fn handle_detect_msg(app: &mut App, msg: DetectMsg) -> cosmic::Task<crate::core:
             DetectMsg::Qr(QrMsg::Requested)
                 | DetectMsg::Ocr(OcrMsg::Requested)
                 | DetectMsg::Qr(QrMsg::CopyAndClose)
+                | DetectMsg::Qr(QrMsg::CopyOneAndClose(_))
+                | DetectMsg::Qr(QrMsg::CopyAllAndClose(_))
                 | DetectMsg::Ocr(OcrMsg::CopyAndClose)
+                | DetectMsg::Ocr(OcrMsg::RedactSensitive)
         ) {
             args.disable_all_modes();
             args.close_all_popups();
@@ -1003,6 +1349,15 @@ fn handle_capture_msg(app: &mut App, msg: CaptureMsg) -> cosmic::Task<crate::cor
             }
             cosmic::Task::none()
         }
+        CaptureMsg::AutoTrim => {
+            if let Some(args) = app.screenshot_args.as_mut()
+                && !args.auto_trim(&app.outputs)
+            {
+                log::info!("Auto-trim found no uniform margins to strip");
+            }
+            cosmic::Task::none()
+        }
+        CaptureMsg::ToggleCropMode => handle_toggle_crop_mode_inner(app),
         CaptureMsg::DelayedCapture => {
             let Some(args) = app.screenshot_args.as_ref() else {
                 return cosmic::Task::none();
@@ -1527,7 +1882,15 @@ fn handle_qr_msg(app: &mut App, msg: QrMsg) -> cosmic::Task<crate::core::app::Ms
     match msg {
         QrMsg::Requested => handle_qr_requested_inner(app),
         QrMsg::Detected(codes) => handle_qr_detected_inner(app, codes),
-        QrMsg::CopyAndClose => handle_qr_copy_and_close_inner(app),
+        QrMsg::CopyAndClose => handle_qr_copy_and_close_inner(app, None, QrFormat::Lines),
+        QrMsg::CopyOneAndClose(index) => {
+            handle_qr_copy_and_close_inner(app, Some(index), QrFormat::Lines)
+        }
+        QrMsg::CopyAllAndClose(format) => handle_qr_copy_and_close_inner(app, None, format),
+        QrMsg::CopyText(text) => {
+            log::info!("Copied {} characters of QR content", text.chars().count());
+            clipboard::write(text)
+        }
     }
 }
 
@@ -1537,7 +1900,51 @@ fn handle_ocr_msg(app: &mut App, msg: OcrMsg) -> cosmic::Task<crate::core::app::
         OcrMsg::Requested => handle_ocr_requested_inner(app),
         OcrMsg::Status(status) => handle_ocr_status_inner(app, status),
         OcrMsg::StatusClear => handle_ocr_status_clear_inner(app),
-        OcrMsg::CopyAndClose => handle_ocr_copy_and_close_inner(app),
+        OcrMsg::CopyAndClose => handle_ocr_copy_and_close_inner(app, None),
+        OcrMsg::CopyAsAndClose(layout) => handle_ocr_copy_and_close_inner(app, Some(layout)),
+        OcrMsg::Select(selection) => {
+            if let Some(args) = app.screenshot_args.as_mut() {
+                args.detection.ocr_selection = selection;
+            }
+            cosmic::Task::none()
+        }
+        OcrMsg::CopySelection => handle_ocr_copy_selection_inner(app),
+        OcrMsg::RedactSensitive => handle_ocr_redact_sensitive_inner(app),
+        OcrMsg::SearchQuery(query) => {
+            if let Some(args) = app.screenshot_args.as_mut() {
+                args.ui.ocr_search_query = query;
+                args.refresh_ocr_search();
+            }
+            cosmic::Task::none()
+        }
+        OcrMsg::SearchRegexToggle => {
+            if let Some(args) = app.screenshot_args.as_mut() {
+                args.ui.ocr_search_regex = !args.ui.ocr_search_regex;
+                args.refresh_ocr_search();
+            }
+            cosmic::Task::none()
+        }
+        OcrMsg::SearchSubmit => handle_ocr_search_submit_inner(app),
+        OcrMsg::SearchApply(action) => {
+            if let Some(args) = app.screenshot_args.as_mut() {
+                args.apply_ocr_search(action, &app.outputs);
+            }
+            cosmic::Task::none()
+        }
+        OcrMsg::ToggleCaptureLanguage(lang) => {
+            if let Some(args) = app.screenshot_args.as_mut() {
+                let mut languages = args.ui.effective_ocr_languages().to_vec();
+                toggle_language(&mut languages, lang);
+                args.ui.ocr_capture_languages = Some(languages);
+            }
+            cosmic::Task::none()
+        }
+        OcrMsg::ResetCaptureLanguages => {
+            if let Some(args) = app.screenshot_args.as_mut() {
+                args.ui.ocr_capture_languages = None;
+            }
+            cosmic::Task::none()
+        }
     }
 }
 
@@ -1569,12 +1976,19 @@ fn handle_capture_inner(app: &mut App) -> cosmic::Task<crate::core::app::Msg> {
     let choice = session.choice;
     let mut images = capture.output_images;
     let location = session.location;
-    let annotations = args_annotations.annotations;
+    let mut annotations = args_annotations.annotations;
     let annotation_index = args_annotations.annotation_index;
     let also_copy_to_clipboard = session.also_copy_to_clipboard;
 
     // Only use annotations up to annotation_index (respects undo)
-    let annotations = &annotations[..annotation_index];
+    annotations.truncate(annotation_index);
+    // Measurements are an on-screen aid unless they should be kept in the image
+    if !ui.measure_in_export {
+        annotations.retain(|a| !a.is_measurement());
+    }
+    let annotations = &annotations[..];
+    // Beautify frame is applied last, after annotations and before encoding
+    let beautify = ui.active_beautify_preset().cloned();
 
     // Determine custom save path based on save location setting
     let custom_dir = match ui.save_location_setting {
@@ -1610,6 +2024,15 @@ fn handle_capture_inner(app: &mut App) -> cosmic::Task<crate::core::app::Msg> {
                     }
                 }
 
+                if let Some(ref preset) = beautify {
+                    let scale = outputs
+                        .iter()
+                        .find(|o| o.name == output_name)
+                        .map(|o| final_img.width() as f32 / o.logical_size.0 as f32)
+                        .unwrap_or(1.0);
+                    final_img = beautify_image(&final_img, preset, scale);
+                }
+
                 if let Some(ref image_path) = image_path {
                     if let Err(_err) = Screenshot::save_rgba(&final_img, image_path) {
                         log::error!("Failed to capture screenshot: {:?}", _err);
@@ -1708,6 +2131,9 @@ fn handle_capture_inner(app: &mut App) -> cosmic::Task<crate::core::app::Msg> {
                 if !annotations.is_empty() {
                     draw_annotations_in_order(&mut img, annotations, &r, target_scale);
                 }
+                if let Some(ref preset) = beautify {
+                    img = beautify_image(&img, preset, target_scale);
+                }
 
                 if let Some(ref image_path) = image_path {
                     if let Err(_err) = Screenshot::save_rgba(&img, image_path) {
@@ -1792,7 +2218,10 @@ fn handle_capture_inner(app: &mut App) -> cosmic::Task<crate::core::app::Msg> {
                         })
                         .collect::<Vec<_>>();
 
-                    let img = combined_image(physical_bounds, frames);
+                    let mut img = combined_image(physical_bounds, frames);
+                    if let Some(ref preset) = beautify {
+                        img = beautify_image(&img, preset, target_scale);
+                    }
 
                     if let Some(ref image_path) = image_path {
                         if let Err(err) = Screenshot::save_rgba(&img, image_path) {
@@ -1880,8 +2309,51 @@ fn handle_cancel_inner(app: &mut App) -> cosmic::Task<crate::core::app::Msg> {
     cosmic::Task::batch(cmds)
 }
 
+fn handle_toggle_crop_mode_inner(app: &mut App) -> cosmic::Task<crate::core::app::Msg> {
+    let Some(args) = app.screenshot_args.as_mut() else {
+        return cosmic::Task::none();
+    };
+    if args.ui.crop_mode {
+        args.ui.crop_mode = false;
+        return cosmic::Task::none();
+    }
+
+    // A confirmed screen becomes a region covering it, so its edges get handles
+    let selection = match &args.session.choice {
+        Choice::Rectangle(r, _) if r.dimensions().is_some() => *r,
+        Choice::Output(Some(name)) => match app.outputs.iter().find(|o| &o.name == name) {
+            Some(output) => output_bounds(output),
+            None => return cosmic::Task::none(),
+        },
+        _ => return cosmic::Task::none(),
+    };
+    args.session.choice = Choice::Rectangle(selection, DragState::None);
+    args.disable_all_modes();
+    args.close_all_popups();
+    args.ui.crop_mode = true;
+    cosmic::Task::none()
+}
+
 fn handle_choice_inner(app: &mut App, c: Choice) -> cosmic::Task<crate::core::app::Msg> {
     if let Some(args) = app.screenshot_args.as_mut() {
+        // Re-crop: annotations are in global coordinates, so moving or resizing the
+        // selection keeps them aligned with the image. Only detection results,
+        // which describe the old region, are dropped.
+        if args.ui.crop_mode {
+            match args.session.choice.recrop(&c) {
+                Recrop::Ignore => return cosmic::Task::none(),
+                Recrop::Update { changed } => {
+                    if changed {
+                        args.clear_ocr_qr();
+                    }
+                    args.session.choice = c;
+                    return cosmic::Task::none();
+                }
+                // Picking a screen leaves crop mode
+                Recrop::Leave => args.ui.crop_mode = false,
+            }
+        }
+
         // Clear OCR/QR/arrows when rectangle changes (new selection started)
         if let Choice::Rectangle(new_r, new_s) = &c {
             if let Choice::Rectangle(old_r, _) = &args.session.choice {
@@ -1963,9 +2435,13 @@ fn handle_qr_requested_inner(app: &mut App) -> cosmic::Task<crate::core::app::Ms
 
     // Get the selection and run QR detection on that area
     if let Some(args) = app.screenshot_args.as_ref() {
-        // Only use annotations up to annotation_index (respects undo)
-        let annotations =
-            args.annotations.annotations[..args.annotations.annotation_index].to_vec();
+        // Only use annotations up to annotation_index (respects undo);
+        // measurement labels would only get in the way of detection
+        let annotations: Vec<_> = args.annotations.annotations[..args.annotations.annotation_index]
+            .iter()
+            .filter(|a| !a.is_measurement())
+            .cloned()
+            .collect();
         let outputs_clone = app.outputs.clone();
 
         // Get image data and parameters based on choice type
@@ -2044,7 +2520,7 @@ fn handle_qr_requested_inner(app: &mut App) -> cosmic::Task<crate::core::app::Ms
             if !annotations.is_empty() {
                 draw_annotations_in_order(&mut cropped, &annotations, &selection_rect, scale);
             }
-            // Spawn progressive QR detection tasks (3 passes with increasing resolution)
+            // Spawn progressive code detection tasks (3 passes with increasing resolution)
             let resolutions = [500u32, 1500, 0]; // 0 = full resolution
             let mut qr_detection_tasks = Vec::new();
 
@@ -2054,17 +2530,29 @@ fn handle_qr_requested_inner(app: &mut App) -> cosmic::Task<crate::core::app::Ms
                 let task = cosmic::Task::perform(
                     async move {
                         tokio::task::spawn_blocking(move || {
-                            let detected = detect_qr_codes_at_resolution(
+                            let mut detected = detect_qr_codes_at_resolution(
                                 &cropped_clone,
                                 &output_name_clone,
                                 scale,
                                 max_dim,
                             );
+                            // Other barcodes on the full-resolution pass only
+                            if max_dim == 0 {
+                                detected.extend(detect_barcodes(
+                                    &cropped_clone,
+                                    &output_name_clone,
+                                    scale,
+                                ));
+                            }
                             detected
                                 .into_iter()
                                 .map(|mut qr| {
                                     qr.center_x += origin_x;
                                     qr.center_y += origin_y;
+                                    for corner in &mut qr.corners {
+                                        corner.0 += origin_x;
+                                        corner.1 += origin_y;
+                                    }
                                     qr
                                 })
                                 .collect::<Vec<_>>()
@@ -2102,9 +2590,9 @@ fn handle_qr_detected_inner(
 }
 
 fn handle_ocr_requested_inner(app: &mut App) -> cosmic::Task<crate::core::app::Msg> {
-    // Check if models need downloading and set appropriate status
-    let needs_download = models_need_download();
     if let Some(args) = app.screenshot_args.as_mut() {
+        // Check if models need downloading and set appropriate status
+        let needs_download = models_need_download(args.ui.ocr_engine, args.ui.ocr_model_download);
         args.detection.ocr_status = if needs_download {
             OcrStatus::DownloadingModels
         } else {
@@ -2118,13 +2606,28 @@ fn handle_ocr_requested_inner(app: &mut App) -> cosmic::Task<crate::core::app::M
         args.disable_all_modes();
         args.close_all_popups();
     }
+    spawn_ocr_task(app)
+}
 
-    // Get the selection and run OCR on that area
+/// Run OCR on the current selection in the background
+///
+/// The result arrives as an `OcrMsg::Status` message.
+fn spawn_ocr_task(app: &App) -> cosmic::Task<crate::core::app::Msg> {
     if let Some(args) = app.screenshot_args.as_ref() {
-        // Only use annotations up to annotation_index (respects undo)
-        let annotations =
-            args.annotations.annotations[..args.annotations.annotation_index].to_vec();
+        // Only use annotations up to annotation_index (respects undo);
+        // measurement labels would only get in the way of detection
+        let annotations: Vec<_> = args.annotations.annotations[..args.annotations.annotation_index]
+            .iter()
+            .filter(|a| !a.is_measurement())
+            .cloned()
+            .collect();
         let outputs_clone = app.outputs.clone();
+        let (engine, deskew, download) = (
+            args.ui.ocr_engine,
+            args.ui.ocr_deskew,
+            args.ui.ocr_model_download,
+        );
+        let lang = language_arg(args.ui.effective_ocr_languages());
 
         // Returns: (image, mapping, selection_rect_for_redactions, scale_for_redactions)
         let region_data: Option<(RgbaImage, OcrMapping, Rect, f32)> = match &args.session.choice {
@@ -2213,7 +2716,11 @@ fn handle_ocr_requested_inner(app: &mut App) -> cosmic::Task<crate::core::app::M
             return cosmic::Task::perform(
                 async move {
                     tokio::task::spawn_blocking(move || {
-                        run_ocr_on_image_with_status(&cropped_img, mapping)
+                        run_ocr_on_image_with_status(
+                            &cropped_img,
+                            mapping,
+                            backend(engine, lang, deskew, download).as_ref(),
+                        )
                     })
                     .await
                     .unwrap_or_else(|_| OcrStatus::Error("OCR task panicked".to_string()))
@@ -2235,10 +2742,16 @@ fn handle_ocr_status_inner(
             if let Some(args) = app.screenshot_args.as_mut() {
                 args.detection.ocr_status = status.clone();
                 args.detection.ocr_overlays = overlays.clone();
+                args.detection.ocr_selection = None;
                 // Store text for later copying when user clicks the button
                 if !text.is_empty() && *text != fl!("no-text-detected") {
                     args.detection.ocr_text = Some(text.clone());
                 }
+                args.scrub_redacted_ocr(&app.outputs);
+                if std::mem::take(&mut args.detection.redact_sensitive_pending) {
+                    args.redact_sensitive_ocr(&app.outputs);
+                }
+                args.refresh_ocr_search();
                 log::info!(
                     "Stored {} overlays in args",
                     args.detection.ocr_overlays.len()
@@ -2252,6 +2765,7 @@ fn handle_ocr_status_inner(
                 args.detection.ocr_status = status;
                 args.detection.ocr_overlays.clear();
                 args.detection.ocr_text = None;
+                args.detection.redact_sensitive_pending = false;
             }
         }
         _ => {
@@ -2263,6 +2777,62 @@ fn handle_ocr_status_inner(
     cosmic::Task::none()
 }
 
+fn handle_ocr_search_submit_inner(app: &mut App) -> cosmic::Task<crate::core::app::Msg> {
+    let Some(args) = app.screenshot_args.as_mut() else {
+        return cosmic::Task::none();
+    };
+    match args.detection.ocr_status {
+        OcrStatus::Done(..) => {
+            args.refresh_ocr_search();
+            cosmic::Task::none()
+        }
+        OcrStatus::Running | OcrStatus::DownloadingModels => cosmic::Task::none(),
+        _ if args.ui.ocr_search_query.is_empty() => cosmic::Task::none(),
+        _ => {
+            // Unlike the OCR button, keep shapes and the popup holding the search field
+            args.detection.ocr_status =
+                if models_need_download(args.ui.ocr_engine, args.ui.ocr_model_download) {
+                    OcrStatus::DownloadingModels
+                } else {
+                    OcrStatus::Running
+                };
+            args.detection.ocr_overlays.clear();
+            args.detection.ocr_text = None;
+            spawn_ocr_task(app)
+        }
+    }
+}
+
+fn handle_ocr_redact_sensitive_inner(app: &mut App) -> cosmic::Task<crate::core::app::Msg> {
+    let Some(args) = app.screenshot_args.as_mut() else {
+        return cosmic::Task::none();
+    };
+    // Reuse finished OCR results, otherwise run OCR and redact once it's done
+    match args.detection.ocr_status {
+        OcrStatus::Done(..) => {
+            args.redact_sensitive_ocr(&app.outputs);
+            cosmic::Task::none()
+        }
+        OcrStatus::Running | OcrStatus::DownloadingModels => {
+            args.detection.redact_sensitive_pending = true;
+            cosmic::Task::none()
+        }
+        _ => {
+            // Unlike the OCR button, keep shapes so the redactions join them
+            args.detection.redact_sensitive_pending = true;
+            args.detection.ocr_status =
+                if models_need_download(args.ui.ocr_engine, args.ui.ocr_model_download) {
+                    OcrStatus::DownloadingModels
+                } else {
+                    OcrStatus::Running
+                };
+            args.detection.ocr_overlays.clear();
+            args.detection.ocr_text = None;
+            spawn_ocr_task(app)
+        }
+    }
+}
+
 fn handle_ocr_status_clear_inner(app: &mut App) -> cosmic::Task<crate::core::app::Msg> {
     if let Some(args) = app.screenshot_args.as_mut() {
         args.detection.ocr_status = OcrStatus::Idle;
@@ -2270,7 +2840,10 @@ fn handle_ocr_status_clear_inner(app: &mut App) -> cosmic::Task<crate::core::app
     cosmic::Task::none()
 }
 
-fn handle_ocr_copy_and_close_inner(app: &mut App) -> cosmic::Task<crate::core::app::Msg> {
+fn handle_ocr_copy_and_close_inner(
+    app: &mut App,
+    layout: Option<OcrLayout>,
+) -> cosmic::Task<crate::core::app::Msg> {
     // Copy OCR text and close the app
     let mut cmds: Vec<cosmic::Task<crate::core::app::Msg>> = app
         .outputs
@@ -2278,9 +2851,18 @@ fn handle_ocr_copy_and_close_inner(app: &mut App) -> cosmic::Task<crate::core::a
         .map(|o| destroy_layer_surface(o.id))
         .collect();
 
-    if let Some(args) = app.screenshot_args.take() {
+    if let Some(mut args) = app.screenshot_args.take() {
+        args.scrub_redacted_ocr(&app.outputs);
         let tx = args.portal.tx;
-        let ocr_text = args.detection.ocr_text;
+        // Only the selected words, if any were picked out
+        let overlays = &args.detection.ocr_overlays;
+        let selection = args.detection.ocr_selection.as_ref();
+        let ocr_text = match layout {
+            Some(layout) => Some(layout_overlays(overlays, selection, layout)),
+            None => selection.map(|selection| selection.text(overlays)),
+        }
+        .filter(|text| !text.is_empty())
+        .or(args.detection.ocr_text);
 
         if let Some(text) = ocr_text {
             cmds.push(clipboard::write(text));
@@ -2295,8 +2877,30 @@ fn handle_ocr_copy_and_close_inner(app: &mut App) -> cosmic::Task<crate::core::a
     cosmic::Task::batch(cmds)
 }
 
-fn handle_qr_copy_and_close_inner(app: &mut App) -> cosmic::Task<crate::core::app::Msg> {
-    // Copy first QR code content and close the app
+fn handle_ocr_copy_selection_inner(app: &mut App) -> cosmic::Task<crate::core::app::Msg> {
+    let Some(args) = app.screenshot_args.as_mut() else {
+        return cosmic::Task::none();
+    };
+    args.scrub_redacted_ocr(&app.outputs);
+    let text = args
+        .detection
+        .ocr_selection
+        .as_ref()
+        .map(|selection| selection.text(&args.detection.ocr_overlays))
+        .unwrap_or_default();
+    if text.is_empty() {
+        return cosmic::Task::none();
+    }
+    log::info!("Copied {} selected OCR characters", text.chars().count());
+    clipboard::write(text)
+}
+
+/// Copy the code at `index`, or else all codes in `format`, and close the app
+fn handle_qr_copy_and_close_inner(
+    app: &mut App,
+    index: Option<usize>,
+    format: QrFormat,
+) -> cosmic::Task<crate::core::app::Msg> {
     let mut cmds: Vec<cosmic::Task<crate::core::app::Msg>> = app
         .outputs
         .iter()
@@ -2307,9 +2911,13 @@ fn handle_qr_copy_and_close_inner(app: &mut App) -> cosmic::Task<crate::core::ap
         let tx = args.portal.tx;
         let qr_codes = args.detection.qr_codes;
 
-        // Copy first QR code content
-        if let Some(qr) = qr_codes.first() {
-            cmds.push(clipboard::write(qr.content.clone()));
+        let text = match index {
+            Some(index) => qr_codes.get(index).map(|qr| qr.content.clone()),
+            None if qr_codes.is_empty() => None,
+            None => Some(format_codes(&qr_codes, format)),
+        };
+        if let Some(text) = text {
+            cmds.push(clipboard::write(text));
         }
 
         tokio::spawn(async move {
@@ -2516,3 +3124,32 @@ fn recreate_screenshot_surfaces(app: &mut App) -> cosmic::Task<crate::core::app:
 
     cosmic::Task::batch(cmds)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn png_has_no_metadata_chunks() {
+        let mut png = Vec::new();
+        write_png(&mut png, &RgbaImage::new(4, 3)).unwrap();
+
+        // Walk the chunks after the 8-byte signature: length, type, data, CRC
+        let mut chunks = Vec::new();
+        let mut rest = &png[8..];
+        while rest.len() >= 12 {
+            let length = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            chunks.push(String::from_utf8_lossy(&rest[4..8]).into_owned());
+            rest = &rest[12 + length..];
+        }
+        assert!(rest.is_empty());
+        assert_eq!(chunks.first().map(String::as_str), Some("IHDR"));
+        assert_eq!(chunks.last().map(String::as_str), Some("IEND"));
+        for chunk in &chunks {
+            assert!(
+                !["tEXt", "iTXt", "zTXt", "eXIf"].contains(&chunk.as_str()),
+                "{chunk} chunk written"
+            );
+        }
+    }
+}
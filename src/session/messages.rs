@@ -6,9 +6,9 @@
 
 use wayland_client::protocol::wl_output::WlOutput;
 
-use crate::capture::ocr::OcrStatus;
-use crate::capture::qr::DetectedQrCode;
-use crate::config::{Container, RedactTool, ShapeColor, ShapeTool, ToolbarPosition};
+use crate::capture::ocr::{OcrLayout, OcrSelection, OcrStatus};
+use crate::capture::qr::{DetectedQrCode, QrFormat};
+use crate::config::{Container, OcrEngine, RedactTool, ShapeColor, ShapeTool, ToolbarPosition};
 use crate::domain::Choice;
 use cosmic::iced::core::Rectangle;
 use cosmic::iced::time::Instant;
@@ -19,7 +19,7 @@ use cosmic::widget::segmented_button;
 // Drawing Action Types
 // ============================================================================
 
-/// Common draw action for annotation tools (arrow, circle, rectangle, redact, pixelate)
+/// Common draw action for annotation tools (arrow, circle, rectangle, redact, pixelate, blur)
 #[derive(Debug, Clone)]
 pub enum DrawAction {
     /// Toggle drawing mode on/off
@@ -35,6 +35,8 @@ pub enum DrawAction {
 pub enum DrawMsg {
     /// Arrow annotation actions
     Arrow(DrawAction),
+    /// Bend the given arrow so its curve passes through (global x, y)
+    ArrowBend(usize, f32, f32),
     /// Circle/ellipse annotation actions
     Circle(DrawAction),
     /// Rectangle outline annotation actions
@@ -49,10 +51,20 @@ pub enum DrawMsg {
     MagnifierResize(usize, f32),
     /// Set the magnification (zoom) of the given magnifier
     MagnifierSetZoom(usize, f32),
+    /// Spotlight (dim outside) actions
+    Spotlight(DrawAction),
+    /// Measurement (dimension line/box) actions
+    Measure(DrawAction),
+    /// Stamp (image) placement actions
+    Stamp(DrawAction),
     /// Redaction (black box) actions
     Redact(DrawAction),
     /// Pixelation actions
     Pixelate(DrawAction),
+    /// Gaussian blur actions
+    Blur(DrawAction),
+    /// Shapes constrained or not: Shift or Ctrl held, latched until the drawing ends
+    Constrain(bool),
     /// Clear all shape annotations (keeps redactions)
     ClearShapes,
     /// Clear all redactions (keeps shapes)
@@ -78,6 +90,47 @@ pub enum ToolPopupAction {
     Close,
 }
 
+/// Which tool color a color chooser edits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorTarget {
+    Shape,
+    Pencil,
+}
+
+/// Edits made in the custom color chooser of a tool popup
+#[derive(Debug, Clone)]
+pub enum ColorEdit {
+    /// Set the color from the HSV sliders (during drag, no save)
+    Hsv([f32; 3]),
+    /// Remember the current color and save (on slider release)
+    Commit,
+    /// Text typed into the hex field
+    HexInput(String),
+    /// Add the current color to a palette (None = start a new palette)
+    AddToPalette(Option<usize>),
+    /// Remove a color from a palette (palette index, color index)
+    RemoveFromPalette(usize, usize),
+    /// Rename a palette (while typing, no save)
+    RenamePalette(usize, String),
+    /// Save edited palette names (on submit)
+    CommitNames,
+}
+
+/// Edits to the annotation presets in the shape popup
+#[derive(Debug, Clone)]
+pub enum PresetEdit {
+    /// Switch to a preset's tool and style
+    Apply(usize),
+    /// Save the current tool and style as a new preset
+    AddCurrent,
+    /// Rename a preset (while typing, no save)
+    Rename(usize, String),
+    /// Save edited preset names (on submit)
+    CommitNames,
+    /// Remove a preset
+    Remove(usize),
+}
+
 /// Tool configuration messages (popups and settings for shape/redact tools)
 #[derive(Debug, Clone)]
 pub enum ToolMsg {
@@ -93,6 +146,24 @@ pub enum ToolMsg {
     SetShapeColor(ShapeColor),
     /// Toggle shadow on shapes
     ToggleShapeShadow,
+    /// Set shape stroke width (UI only, no save)
+    SetShapeWidth(f32),
+    /// Save current shape stroke width to config
+    SaveShapeWidth,
+    /// Toggle shadows that contrast with the image below
+    ToggleShapeAutoContrast,
+    /// Toggle the translucent fill of circles and rectangles
+    ToggleShapeFill,
+    /// Apply, add, rename or remove an annotation preset
+    EditPreset(PresetEdit),
+    /// Set spotlight dim strength (UI only, no save)
+    SetSpotlightDim(f32),
+    /// Save current spotlight dim strength to config
+    SaveSpotlightDim,
+    /// Toggle rectangle/ellipse spotlights
+    ToggleSpotlightEllipse,
+    /// Toggle blurring outside spotlights
+    ToggleSpotlightBlur,
 
     /// Set the primary redact tool
     SetRedactTool(RedactTool),
@@ -106,6 +177,12 @@ pub enum ToolMsg {
     SetPixelationBlockSize(u32),
     /// Save current pixelation block size to config
     SavePixelationBlockSize,
+    /// Toggle secure pixelation (noise + quantised block colours)
+    ToggleSecurePixelation,
+    /// Set blur radius (UI only, no save)
+    SetBlurRadius(u32),
+    /// Save current blur radius to config
+    SaveBlurRadius,
 
     /// Magnifier tool mode toggle
     MagnifierModeToggle,
@@ -115,6 +192,42 @@ pub enum ToolMsg {
     SetMagnification(f32),
     /// Save current magnifier magnification to config
     SaveMagnification,
+    /// Toggle between circular loupes and rectangular callouts
+    ToggleMagnifierCallout,
+    /// Set callout frame width (UI only, no save)
+    SetCalloutBorder(f32),
+    /// Save current callout frame width to config
+    SaveCalloutBorder,
+
+    /// Color picker (eyedropper) mode toggle
+    ColorPickerModeToggle,
+    /// Cycle the copied color format (hex -> rgb -> hsl)
+    CycleColorFormat,
+    /// A color was picked from the screenshot (8-bit RGB)
+    ColorPicked([u8; 3]),
+
+    /// Measure tool mode toggle
+    MeasureModeToggle,
+    /// Cycle what the measure tool measures (line -> box)
+    CycleMeasureKind,
+
+    /// Stamp tool mode toggle
+    StampModeToggle,
+    /// Stamp popup actions
+    StampPopup(ToolPopupAction),
+    /// Select the stamp to place (by name)
+    SelectStamp(String),
+    /// Set the size of clicked stamps (UI only, no save)
+    SetStampSize(f32),
+    /// Save current stamp size to config
+    SaveStampSize,
+    /// Open the stamp directory in the file manager (creating it if needed)
+    OpenStampFolder,
+    /// Reload the stamp library from disk
+    ReloadStamps,
+
+    /// OCR language popup actions
+    OcrPopup(ToolPopupAction),
 
     /// Pencil popup actions
     PencilPopup(ToolPopupAction),
@@ -130,6 +243,9 @@ pub enum ToolMsg {
     SavePencilThickness,
     /// Clear all pencil drawings
     ClearPencilDrawings,
+
+    /// Custom color chooser and palette edits for a tool color
+    EditColor(ColorTarget, ColorEdit),
 }
 
 // ============================================================================
@@ -177,6 +293,10 @@ pub enum CaptureMsg {
     DelayedCapture,
     /// Cycle the delayed-screenshot delay (3 -> 5 -> 10 -> 3 seconds)
     CycleCaptureDelay,
+    /// Shrink the selection to its content by stripping uniform margins
+    AutoTrim,
+    /// Toggle re-crop mode (adjust the selection while keeping annotations)
+    ToggleCropMode,
     /// Record selected region
     RecordRegion,
     /// Stop recording
@@ -211,8 +331,14 @@ pub enum QrMsg {
     Requested,
     /// QR codes detected
     Detected(Vec<DetectedQrCode>),
-    /// Copy QR content and close
+    /// Copy QR content and close; all codes, one per line, if there are several
     CopyAndClose,
+    /// Copy one of several codes, by index, and close
+    CopyOneAndClose(usize),
+    /// Copy all codes and close
+    CopyAllAndClose(QrFormat),
+    /// Copy one field of a QR code without closing
+    CopyText(String),
 }
 
 /// OCR detection messages
@@ -224,8 +350,36 @@ pub enum OcrMsg {
     Status(OcrStatus),
     /// Clear OCR status
     StatusClear,
-    /// Copy OCR text and close
+    /// Copy OCR text (or the selected part) and close
     CopyAndClose,
+    /// Copy OCR text laid out as a table, code or paragraphs, and close
+    CopyAsAndClose(OcrLayout),
+    /// Select recognized words, or clear the selection
+    Select(Option<OcrSelection>),
+    /// Copy the selected words without closing
+    CopySelection,
+    /// Redact sensitive data found by OCR (runs OCR first if needed)
+    RedactSensitive,
+    /// Find-and-redact search text changed
+    SearchQuery(String),
+    /// Toggle regex matching for find-and-redact
+    SearchRegexToggle,
+    /// Run the search (runs OCR first if needed)
+    SearchSubmit,
+    /// Apply an action to every search match
+    SearchApply(OcrSearchAction),
+    /// Toggle a language for this capture's OCR only
+    ToggleCaptureLanguage(String),
+    /// Go back to the OCR languages from the settings
+    ResetCaptureLanguages,
+}
+
+/// What to do with all find-and-redact matches at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OcrSearchAction {
+    Redact,
+    Pixelate,
+    Highlight,
 }
 
 /// Detection feature messages (OCR and QR)
@@ -271,6 +425,22 @@ pub enum SettingsMsg {
     BrowseVideoSaveLocationResult(Option<String>),
     /// Toggle copy to clipboard on save
     ToggleCopyOnSave,
+    /// Toggle the beautify frame on exported screenshots
+    ToggleBeautify,
+    /// Select the beautify preset by name
+    SetBeautifyPreset(String),
+    /// Toggle whether picked colors become the shape color
+    ToggleColorPickerSetsShapeColor,
+    /// Toggle keeping measurements in saved images
+    ToggleMeasureInExport,
+    /// Select the OCR engine
+    SetOcrEngine(OcrEngine),
+    /// Toggle straightening skewed text before OCR
+    ToggleOcrDeskew,
+    /// Toggle downloading missing OCR models
+    ToggleOcrModelDownload,
+    /// Toggle an OCR language in the settings
+    ToggleOcrLanguage(String),
     /// Set the delayed-screenshot delay (seconds)
     SetCaptureDelay(u32),
     /// Settings tab activated (by segmented button entity)
@@ -339,6 +509,9 @@ impl Msg {
     pub fn arrow_end(x: f32, y: f32) -> Self {
         Self::Draw(DrawMsg::Arrow(DrawAction::End(x, y)))
     }
+    pub fn arrow_bend(index: usize, x: f32, y: f32) -> Self {
+        Self::Draw(DrawMsg::ArrowBend(index, x, y))
+    }
     pub fn circle_mode_toggle() -> Self {
         Self::Draw(DrawMsg::Circle(DrawAction::ModeToggle))
     }
@@ -378,6 +551,27 @@ impl Msg {
     pub fn magnifier_set_zoom(index: usize, zoom: f32) -> Self {
         Self::Draw(DrawMsg::MagnifierSetZoom(index, zoom))
     }
+    pub fn spotlight_mode_toggle() -> Self {
+        Self::Draw(DrawMsg::Spotlight(DrawAction::ModeToggle))
+    }
+    pub fn spotlight_start(x: f32, y: f32) -> Self {
+        Self::Draw(DrawMsg::Spotlight(DrawAction::Start(x, y)))
+    }
+    pub fn spotlight_end(x: f32, y: f32) -> Self {
+        Self::Draw(DrawMsg::Spotlight(DrawAction::End(x, y)))
+    }
+    pub fn measure_start(x: f32, y: f32) -> Self {
+        Self::Draw(DrawMsg::Measure(DrawAction::Start(x, y)))
+    }
+    pub fn measure_end(x: f32, y: f32) -> Self {
+        Self::Draw(DrawMsg::Measure(DrawAction::End(x, y)))
+    }
+    pub fn stamp_start(x: f32, y: f32) -> Self {
+        Self::Draw(DrawMsg::Stamp(DrawAction::Start(x, y)))
+    }
+    pub fn stamp_end(x: f32, y: f32) -> Self {
+        Self::Draw(DrawMsg::Stamp(DrawAction::End(x, y)))
+    }
 
     pub fn redact_mode_toggle() -> Self {
         Self::Draw(DrawMsg::Redact(DrawAction::ModeToggle))
@@ -398,6 +592,19 @@ impl Msg {
     pub fn pixelate_end(x: f32, y: f32) -> Self {
         Self::Draw(DrawMsg::Pixelate(DrawAction::End(x, y)))
     }
+
+    pub fn blur_mode_toggle() -> Self {
+        Self::Draw(DrawMsg::Blur(DrawAction::ModeToggle))
+    }
+    pub fn blur_start(x: f32, y: f32) -> Self {
+        Self::Draw(DrawMsg::Blur(DrawAction::Start(x, y)))
+    }
+    pub fn blur_end(x: f32, y: f32) -> Self {
+        Self::Draw(DrawMsg::Blur(DrawAction::End(x, y)))
+    }
+    pub fn constrain(held: bool) -> Self {
+        Self::Draw(DrawMsg::Constrain(held))
+    }
     pub fn undo() -> Self {
         Self::Draw(DrawMsg::Undo)
     }
@@ -436,6 +643,36 @@ impl Msg {
     pub fn toggle_shape_shadow() -> Self {
         Self::Tool(ToolMsg::ToggleShapeShadow)
     }
+    pub fn set_shape_width(width: f32) -> Self {
+        Self::Tool(ToolMsg::SetShapeWidth(width))
+    }
+    pub fn save_shape_width() -> Self {
+        Self::Tool(ToolMsg::SaveShapeWidth)
+    }
+    pub fn toggle_shape_auto_contrast() -> Self {
+        Self::Tool(ToolMsg::ToggleShapeAutoContrast)
+    }
+    pub fn toggle_shape_fill() -> Self {
+        Self::Tool(ToolMsg::ToggleShapeFill)
+    }
+    pub fn edit_preset(edit: PresetEdit) -> Self {
+        Self::Tool(ToolMsg::EditPreset(edit))
+    }
+    pub fn apply_preset(index: usize) -> Self {
+        Self::Tool(ToolMsg::EditPreset(PresetEdit::Apply(index)))
+    }
+    pub fn set_spotlight_dim(dim: f32) -> Self {
+        Self::Tool(ToolMsg::SetSpotlightDim(dim))
+    }
+    pub fn save_spotlight_dim() -> Self {
+        Self::Tool(ToolMsg::SaveSpotlightDim)
+    }
+    pub fn toggle_spotlight_ellipse() -> Self {
+        Self::Tool(ToolMsg::ToggleSpotlightEllipse)
+    }
+    pub fn toggle_spotlight_blur() -> Self {
+        Self::Tool(ToolMsg::ToggleSpotlightBlur)
+    }
 
     pub fn set_redact_tool(tool: RedactTool) -> Self {
         Self::Tool(ToolMsg::SetRedactTool(tool))
@@ -461,6 +698,15 @@ impl Msg {
     pub fn save_pixelation_block_size() -> Self {
         Self::Tool(ToolMsg::SavePixelationBlockSize)
     }
+    pub fn toggle_secure_pixelation() -> Self {
+        Self::Tool(ToolMsg::ToggleSecurePixelation)
+    }
+    pub fn set_blur_radius(radius: u32) -> Self {
+        Self::Tool(ToolMsg::SetBlurRadius(radius))
+    }
+    pub fn save_blur_radius() -> Self {
+        Self::Tool(ToolMsg::SaveBlurRadius)
+    }
 
     // Magnifier tool shortcuts
     pub fn magnifier_tool_mode_toggle() -> Self {
@@ -481,6 +727,71 @@ impl Msg {
     pub fn save_magnification() -> Self {
         Self::Tool(ToolMsg::SaveMagnification)
     }
+    pub fn toggle_magnifier_callout() -> Self {
+        Self::Tool(ToolMsg::ToggleMagnifierCallout)
+    }
+    pub fn set_callout_border(width: f32) -> Self {
+        Self::Tool(ToolMsg::SetCalloutBorder(width))
+    }
+    pub fn save_callout_border() -> Self {
+        Self::Tool(ToolMsg::SaveCalloutBorder)
+    }
+
+    // Color picker shortcuts
+    pub fn color_picker_mode_toggle() -> Self {
+        Self::Tool(ToolMsg::ColorPickerModeToggle)
+    }
+    pub fn cycle_color_format() -> Self {
+        Self::Tool(ToolMsg::CycleColorFormat)
+    }
+    pub fn color_picked(rgb: [u8; 3]) -> Self {
+        Self::Tool(ToolMsg::ColorPicked(rgb))
+    }
+
+    // Measure tool shortcuts
+    pub fn measure_mode_toggle() -> Self {
+        Self::Tool(ToolMsg::MeasureModeToggle)
+    }
+    pub fn cycle_measure_kind() -> Self {
+        Self::Tool(ToolMsg::CycleMeasureKind)
+    }
+
+    // Stamp tool shortcuts
+    pub fn stamp_mode_toggle() -> Self {
+        Self::Tool(ToolMsg::StampModeToggle)
+    }
+    pub fn toggle_stamp_popup() -> Self {
+        Self::Tool(ToolMsg::StampPopup(ToolPopupAction::Toggle))
+    }
+    pub fn open_stamp_popup() -> Self {
+        Self::Tool(ToolMsg::StampPopup(ToolPopupAction::Open))
+    }
+    pub fn close_stamp_popup() -> Self {
+        Self::Tool(ToolMsg::StampPopup(ToolPopupAction::Close))
+    }
+    pub fn select_stamp(name: String) -> Self {
+        Self::Tool(ToolMsg::SelectStamp(name))
+    }
+    pub fn set_stamp_size(size: f32) -> Self {
+        Self::Tool(ToolMsg::SetStampSize(size))
+    }
+    pub fn save_stamp_size() -> Self {
+        Self::Tool(ToolMsg::SaveStampSize)
+    }
+    pub fn open_stamp_folder() -> Self {
+        Self::Tool(ToolMsg::OpenStampFolder)
+    }
+    pub fn reload_stamps() -> Self {
+        Self::Tool(ToolMsg::ReloadStamps)
+    }
+
+    // OCR language popup
+    pub fn toggle_ocr_popup() -> Self {
+        Self::Tool(ToolMsg::OcrPopup(ToolPopupAction::Toggle))
+    }
+    pub fn close_ocr_popup() -> Self {
+        Self::Tool(ToolMsg::OcrPopup(ToolPopupAction::Close))
+    }
 
     // Pencil tool shortcuts (for recording annotations)
     pub fn toggle_pencil_popup() -> Self {
@@ -508,6 +819,11 @@ impl Msg {
         Self::Tool(ToolMsg::ClearPencilDrawings)
     }
 
+    // Custom colors and palettes
+    pub fn edit_color(target: ColorTarget, edit: ColorEdit) -> Self {
+        Self::Tool(ToolMsg::EditColor(target, edit))
+    }
+
     // Selection shortcuts
     pub fn region_mode() -> Self {
         Self::Select(SelectMsg::RegionMode)
@@ -547,6 +863,12 @@ impl Msg {
     pub fn cycle_capture_delay() -> Self {
         Self::Capture(CaptureMsg::CycleCaptureDelay)
     }
+    pub fn auto_trim() -> Self {
+        Self::Capture(CaptureMsg::AutoTrim)
+    }
+    pub fn toggle_crop_mode() -> Self {
+        Self::Capture(CaptureMsg::ToggleCropMode)
+    }
     pub fn record_region() -> Self {
         Self::Capture(CaptureMsg::RecordRegion)
     }
@@ -576,6 +898,15 @@ impl Msg {
     pub fn qr_copy_and_close() -> Self {
         Self::Detect(DetectMsg::Qr(QrMsg::CopyAndClose))
     }
+    pub fn qr_copy_one_and_close(index: usize) -> Self {
+        Self::Detect(DetectMsg::Qr(QrMsg::CopyOneAndClose(index)))
+    }
+    pub fn qr_copy_all_and_close(format: QrFormat) -> Self {
+        Self::Detect(DetectMsg::Qr(QrMsg::CopyAllAndClose(format)))
+    }
+    pub fn qr_copy_text(text: String) -> Self {
+        Self::Detect(DetectMsg::Qr(QrMsg::CopyText(text)))
+    }
     pub fn ocr_requested() -> Self {
         Self::Detect(DetectMsg::Ocr(OcrMsg::Requested))
     }
@@ -585,6 +916,36 @@ impl Msg {
     pub fn ocr_copy_and_close() -> Self {
         Self::Detect(DetectMsg::Ocr(OcrMsg::CopyAndClose))
     }
+    pub fn ocr_copy_as_and_close(layout: OcrLayout) -> Self {
+        Self::Detect(DetectMsg::Ocr(OcrMsg::CopyAsAndClose(layout)))
+    }
+    pub fn ocr_select(selection: Option<OcrSelection>) -> Self {
+        Self::Detect(DetectMsg::Ocr(OcrMsg::Select(selection)))
+    }
+    pub fn ocr_copy_selection() -> Self {
+        Self::Detect(DetectMsg::Ocr(OcrMsg::CopySelection))
+    }
+    pub fn ocr_redact_sensitive() -> Self {
+        Self::Detect(DetectMsg::Ocr(OcrMsg::RedactSensitive))
+    }
+    pub fn ocr_search_query(query: String) -> Self {
+        Self::Detect(DetectMsg::Ocr(OcrMsg::SearchQuery(query)))
+    }
+    pub fn ocr_search_regex_toggle() -> Self {
+        Self::Detect(DetectMsg::Ocr(OcrMsg::SearchRegexToggle))
+    }
+    pub fn ocr_search_submit() -> Self {
+        Self::Detect(DetectMsg::Ocr(OcrMsg::SearchSubmit))
+    }
+    pub fn ocr_search_apply(action: OcrSearchAction) -> Self {
+        Self::Detect(DetectMsg::Ocr(OcrMsg::SearchApply(action)))
+    }
+    pub fn ocr_toggle_capture_language(lang: String) -> Self {
+        Self::Detect(DetectMsg::Ocr(OcrMsg::ToggleCaptureLanguage(lang)))
+    }
+    pub fn ocr_reset_capture_languages() -> Self {
+        Self::Detect(DetectMsg::Ocr(OcrMsg::ResetCaptureLanguages))
+    }
 
     // Settings shortcuts
     pub fn toolbar_position(pos: ToolbarPosition) -> Self {
@@ -636,6 +997,30 @@ impl Msg {
     pub fn toggle_copy_on_save() -> Self {
         Self::Settings(SettingsMsg::ToggleCopyOnSave)
     }
+    pub fn toggle_beautify() -> Self {
+        Self::Settings(SettingsMsg::ToggleBeautify)
+    }
+    pub fn set_beautify_preset(name: String) -> Self {
+        Self::Settings(SettingsMsg::SetBeautifyPreset(name))
+    }
+    pub fn toggle_color_picker_sets_shape_color() -> Self {
+        Self::Settings(SettingsMsg::ToggleColorPickerSetsShapeColor)
+    }
+    pub fn toggle_measure_in_export() -> Self {
+        Self::Settings(SettingsMsg::ToggleMeasureInExport)
+    }
+    pub fn set_ocr_engine(engine: OcrEngine) -> Self {
+        Self::Settings(SettingsMsg::SetOcrEngine(engine))
+    }
+    pub fn toggle_ocr_deskew() -> Self {
+        Self::Settings(SettingsMsg::ToggleOcrDeskew)
+    }
+    pub fn toggle_ocr_model_download() -> Self {
+        Self::Settings(SettingsMsg::ToggleOcrModelDownload)
+    }
+    pub fn toggle_ocr_language(lang: String) -> Self {
+        Self::Settings(SettingsMsg::ToggleOcrLanguage(lang))
+    }
     pub fn set_capture_delay(secs: u32) -> Self {
         Self::Settings(SettingsMsg::SetCaptureDelay(secs))
     }
@@ -1,4 +1,5 @@
-use crate::config::ToolbarPosition;
+use crate::capture::qr::QrFormat;
+use crate::config::{MAX_KEYED_PRESETS, ToolbarPosition};
 use crate::domain::Choice;
 use crate::screenshot::Args;
 use crate::session::messages::Msg;
@@ -26,6 +27,11 @@ pub fn handle_key_event(
     // Check if OCR/QR have results (pressing O/Q again should copy and close)
     let has_ocr_result = args.detection.ocr_text.is_some();
     let has_qr_result = !args.detection.qr_codes.is_empty();
+    let has_ocr_selection = args.detection.ocr_selection.is_some();
+    // With several codes shown, number keys pick one instead of a preset
+    let qr_count = args.detection.qr_codes.len();
+    let qr_pick =
+        |key: &str| number_key_index(key).filter(|&index| qr_count > 1 && index < qr_count);
 
     match key {
         // Ctrl+hjkl or Ctrl+arrows: move toolbar position
@@ -63,6 +69,10 @@ pub fn handle_key_event(
         {
             Some(Msg::redo())
         }
+        // Ctrl+C: copy the selected OCR words and keep going
+        Key::Character(c) if c.as_str() == "c" && modifiers.control() && has_ocr_selection => {
+            Some(Msg::ocr_copy_selection())
+        }
         // Save/copy shortcuts (always available - empty selection captures all screens)
         Key::Named(Named::Enter) if modifiers.control() => Some(Msg::save_to_pictures()),
         Key::Named(Named::Escape) => Some(Msg::cancel()),
@@ -97,8 +107,27 @@ pub fn handle_key_event(
         Key::Character(c) if c.as_str() == "o" && has_ocr_result => Some(Msg::ocr_copy_and_close()),
         Key::Character(c) if c.as_str() == "o" && has_selection => Some(Msg::ocr_requested()),
         // QR shortcut: if result exists, copy and close; otherwise start scan
+        // Shift+Q: copy all codes as JSON
+        Key::Character(c) if c.as_str() == "Q" && modifiers.shift() && has_qr_result => {
+            Some(Msg::qr_copy_all_and_close(QrFormat::Json))
+        }
         Key::Character(c) if c.as_str() == "q" && has_qr_result => Some(Msg::qr_copy_and_close()),
         Key::Character(c) if c.as_str() == "q" && has_selection => Some(Msg::qr_requested()),
+        // 1-9 with several codes detected: copy that code and close
+        Key::Character(c) if !modifiers.control() && qr_pick(c.as_str()).is_some() => {
+            qr_pick(c.as_str()).map(Msg::qr_copy_one_and_close)
+        }
+        // I: eyedropper
+        Key::Character(c) if c.as_str() == "i" && has_selection => {
+            Some(Msg::color_picker_mode_toggle())
+        }
+        // M: measure tool
+        Key::Character(c) if c.as_str() == "m" && has_selection => Some(Msg::measure_mode_toggle()),
+        // P: stamp tool
+        Key::Character(c) if c.as_str() == "p" && has_selection => Some(Msg::stamp_mode_toggle()),
+        // T: strip uniform margins, C: toggle re-crop mode
+        Key::Character(c) if c.as_str() == "t" && has_selection => Some(Msg::auto_trim()),
+        Key::Character(c) if c.as_str() == "c" && has_selection => Some(Msg::toggle_crop_mode()),
         // Shift+R: trigger recording (only when region is selected)
         Key::Character(c) if c.as_str() == "R" && modifiers.shift() && has_selection => {
             Some(Msg::record_region())
@@ -111,6 +140,36 @@ pub fn handle_key_event(
         Key::Character(c) if c.as_str() == "s" && !arrow_mode && !redact_mode => {
             Some(Msg::screen_mode(current_output_index))
         }
+        // 1-9: apply an annotation preset
+        Key::Character(c) if has_selection && !modifiers.control() => {
+            preset_index(c.as_str(), args.ui.annotation_presets.len()).map(Msg::apply_preset)
+        }
         _ => None,
     }
 }
+
+/// Zero-based index bound to a number key 1-9
+fn number_key_index(key: &str) -> Option<usize> {
+    let digit = key.parse::<usize>().ok()?;
+    digit.checked_sub(1).filter(|&index| index < 9)
+}
+
+/// Index of the preset bound to a number key, if that preset exists
+fn preset_index(key: &str, preset_count: usize) -> Option<usize> {
+    number_key_index(key).filter(|&index| index < MAX_KEYED_PRESETS.min(preset_count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_keys_pick_existing_presets() {
+        assert_eq!(preset_index("1", 3), Some(0));
+        assert_eq!(preset_index("3", 3), Some(2));
+        assert_eq!(preset_index("4", 3), None);
+        assert_eq!(preset_index("0", 12), None);
+        assert_eq!(preset_index("10", 12), None);
+        assert_eq!(preset_index("a", 3), None);
+    }
+}
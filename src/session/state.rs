@@ -1,15 +1,20 @@
+use crate::annotations::constrain::{Guides, selection_bounds};
 use crate::capture::image::ScreenshotImage;
-use crate::capture::ocr::{OcrStatus, OcrTextOverlay};
+use crate::capture::ocr::{OcrSelection, OcrStatus, OcrTextOverlay};
 use crate::capture::qr::DetectedQrCode;
+use crate::capture::search::OcrMatch;
 use crate::config::{
-    Container, RedactTool, SaveLocationChoice, ShapeColor, ShapeTool, ToolbarPosition,
+    AnnotationPreset, BeautifyPreset, ColorFormat, ColorPalette, Container, MeasureKind, OcrEngine,
+    RedactTool, SaveLocationChoice, ShapeColor, ShapeTool, ToolbarPosition,
     VideoSaveLocationChoice,
 };
 use crate::core::portal::PortalResponse;
 use crate::domain::{
-    Action, Annotation, ArrowAnnotation, Choice, CircleOutlineAnnotation, ImageSaveLocation,
-    MagnifierAnnotation, PixelateAnnotation, RectOutlineAnnotation, RedactAnnotation,
+    Action, Annotation, ArrowAnnotation, BlurAnnotation, Choice, CircleOutlineAnnotation,
+    ImageSaveLocation, MagnifierAnnotation, MeasureAnnotation, PixelateAnnotation,
+    RectOutlineAnnotation, RedactAnnotation, SpotlightAnnotation, StampAnnotation,
 };
+use crate::render::stamp::Stamp;
 use crate::screencast::encoder::EncoderInfo;
 use crate::screenshot::portal::{ScreenshotOptions, ScreenshotResult};
 use cosmic::iced::Animation;
@@ -40,12 +45,24 @@ pub struct DetectionState {
     pub ocr_status: OcrStatus,
     pub ocr_overlays: Vec<OcrTextOverlay>,
     pub ocr_text: Option<String>,
+    /// Words picked out of the OCR result by dragging across them
+    pub ocr_selection: Option<OcrSelection>,
+    /// Redact sensitive data as soon as the running OCR finishes
+    pub redact_sensitive_pending: bool,
+    /// Find-and-redact: boxes of the current search hits
+    pub search_matches: Vec<OcrMatch>,
+    /// Find-and-redact: the search text isn't a valid regex
+    pub search_invalid: bool,
 }
 
 impl DetectionState {
     pub fn clear(&mut self) {
         self.ocr_status = OcrStatus::Idle;
+        self.redact_sensitive_pending = false;
+        self.search_matches.clear();
+        self.search_invalid = false;
         self.ocr_text = None;
+        self.ocr_selection = None;
         self.ocr_overlays.clear();
         self.qr_codes.clear();
         self.qr_scanning = false;
@@ -65,6 +82,11 @@ pub struct AnnotationState {
     pub pixelations: Vec<PixelateAnnotation>,
     pub pixelate_mode: bool,
     pub pixelate_drawing: Option<(f32, f32)>,
+    /// Noise seed of the pixelation being drawn, shared by its preview and result
+    pub pixelate_noise_seed: Option<u64>,
+    pub blurs: Vec<BlurAnnotation>,
+    pub blur_mode: bool,
+    pub blur_drawing: Option<(f32, f32)>,
     pub circles: Vec<CircleOutlineAnnotation>,
     pub circle_mode: bool,
     pub circle_drawing: Option<(f32, f32)>,
@@ -76,6 +98,20 @@ pub struct AnnotationState {
     pub magnifier_drawing: Option<(f32, f32)>,
     /// Index (into `magnifiers`) of the currently selected magnifier, if any
     pub selected_magnifier: Option<usize>,
+    pub spotlights: Vec<SpotlightAnnotation>,
+    pub spotlight_mode: bool,
+    pub spotlight_drawing: Option<(f32, f32)>,
+    pub measurements: Vec<MeasureAnnotation>,
+    pub measure_mode: bool,
+    pub measure_drawing: Option<(f32, f32)>,
+    pub stamps: Vec<StampAnnotation>,
+    pub stamp_mode: bool,
+    pub stamp_drawing: Option<(f32, f32)>,
+    /// Eyedropper: clicking copies the color under the cursor
+    pub color_picker_mode: bool,
+    /// Shift or Ctrl is held, or was during the current drawing: snap arrow
+    /// angles and lock squares and circles
+    pub constrain: bool,
 }
 
 impl AnnotationState {
@@ -91,6 +127,9 @@ impl AnnotationState {
         self.pixelations.clear();
         self.pixelate_mode = false;
         self.pixelate_drawing = None;
+        self.blurs.clear();
+        self.blur_mode = false;
+        self.blur_drawing = None;
         self.circles.clear();
         self.circle_mode = false;
         self.circle_drawing = None;
@@ -101,6 +140,17 @@ impl AnnotationState {
         self.magnifier_mode = false;
         self.magnifier_drawing = None;
         self.selected_magnifier = None;
+        self.spotlights.clear();
+        self.spotlight_mode = false;
+        self.spotlight_drawing = None;
+        self.measurements.clear();
+        self.measure_mode = false;
+        self.measure_drawing = None;
+        self.stamps.clear();
+        self.stamp_mode = false;
+        self.stamp_drawing = None;
+        self.color_picker_mode = false;
+        self.constrain = false;
     }
 
     pub fn clear_shapes(&mut self) {
@@ -117,34 +167,37 @@ impl AnnotationState {
         self.magnifier_drawing = None;
         self.magnifier_mode = false;
         self.selected_magnifier = None;
+        self.spotlights.clear();
+        self.spotlight_drawing = None;
+        self.spotlight_mode = false;
+        self.measurements.clear();
+        self.measure_drawing = None;
+        self.measure_mode = false;
+        self.stamps.clear();
+        self.stamp_drawing = None;
+        self.stamp_mode = false;
         // Also filter unified annotations array
-        self.annotations
-            .retain(|a| matches!(a, Annotation::Redact(_) | Annotation::Pixelate(_)));
+        self.annotations.retain(|a| a.is_redaction());
         self.annotation_index = self.annotations.len();
     }
 
     pub fn clear_redactions(&mut self) {
         // Clear only redaction annotations from the unified array
-        self.annotations.retain(|a| {
-            matches!(
-                a,
-                Annotation::Arrow(_)
-                    | Annotation::Circle(_)
-                    | Annotation::Rectangle(_)
-                    | Annotation::Magnifier(_)
-            )
-        });
+        self.annotations.retain(|a| a.is_shape());
         self.annotation_index = self.annotations.len();
 
         // Clear redaction arrays
         self.redactions.clear();
         self.pixelations.clear();
+        self.blurs.clear();
 
         // Disable redact modes
         self.redact_mode = false;
         self.redact_drawing = None;
         self.pixelate_mode = false;
         self.pixelate_drawing = None;
+        self.blur_mode = false;
+        self.blur_drawing = None;
     }
 
     pub fn undo(&mut self) {
@@ -161,6 +214,15 @@ impl AnnotationState {
         }
     }
 
+    /// Alignment guides for new shapes: the selection in `choice` and the
+    /// visible annotations
+    pub fn guides(&self, choice: &Choice) -> Guides {
+        Guides::collect(
+            selection_bounds(choice),
+            &self.annotations[..self.annotation_index],
+        )
+    }
+
     pub fn add(&mut self, annotation: Annotation) {
         // Truncate any redo history
         self.annotations.truncate(self.annotation_index);
@@ -173,8 +235,12 @@ impl AnnotationState {
         self.circles.clear();
         self.rect_outlines.clear();
         self.magnifiers.clear();
+        self.spotlights.clear();
+        self.measurements.clear();
+        self.stamps.clear();
         self.redactions.clear();
         self.pixelations.clear();
+        self.blurs.clear();
 
         for annotation in self.annotations.iter().take(self.annotation_index) {
             match annotation {
@@ -182,8 +248,12 @@ impl AnnotationState {
                 Annotation::Circle(c) => self.circles.push(c.clone()),
                 Annotation::Rectangle(r) => self.rect_outlines.push(r.clone()),
                 Annotation::Magnifier(m) => self.magnifiers.push(m.clone()),
+                Annotation::Spotlight(s) => self.spotlights.push(s.clone()),
+                Annotation::Measure(m) => self.measurements.push(m.clone()),
+                Annotation::Stamp(s) => self.stamps.push(s.clone()),
                 Annotation::Redact(r) => self.redactions.push(r.clone()),
                 Annotation::Pixelate(p) => self.pixelations.push(p.clone()),
+                Annotation::Blur(b) => self.blurs.push(b.clone()),
             }
         }
 
@@ -199,16 +269,32 @@ impl AnnotationState {
     /// the unified `annotations` array (respecting the current undo index).
     fn selected_magnifier_unified_index(&self) -> Option<usize> {
         let target = self.selected_magnifier?;
-        let mut count = 0;
-        for (i, a) in self.annotations.iter().take(self.annotation_index).enumerate() {
-            if matches!(a, Annotation::Magnifier(_)) {
-                if count == target {
-                    return Some(i);
-                }
-                count += 1;
-            }
+        self.unified_index(target, |a| matches!(a, Annotation::Magnifier(_)))
+    }
+
+    /// Position in the unified `annotations` array (respecting the current undo
+    /// index) of the `target`-th annotation of the kind matched by `is_kind`.
+    fn unified_index(&self, target: usize, is_kind: fn(&Annotation) -> bool) -> Option<usize> {
+        self.annotations
+            .iter()
+            .take(self.annotation_index)
+            .enumerate()
+            .filter(|(_, a)| is_kind(a))
+            .nth(target)
+            .map(|(i, _)| i)
+    }
+
+    /// Apply an in-place edit to the arrow at `index` (into `arrows`), updating
+    /// both the unified annotation array and the flat `arrows`.
+    pub fn edit_arrow(&mut self, index: usize, f: impl Fn(&mut ArrowAnnotation)) {
+        let Some(unified_idx) = self.unified_index(index, |a| matches!(a, Annotation::Arrow(_)))
+        else {
+            return;
+        };
+        if let Some(Annotation::Arrow(a)) = self.annotations.get_mut(unified_idx) {
+            f(a);
         }
-        None
+        self.rebuild_arrays();
     }
 
     /// The magnification of the currently selected magnifier, if any.
@@ -237,12 +323,21 @@ impl AnnotationState {
         self.redact_drawing = None;
         self.pixelate_mode = false;
         self.pixelate_drawing = None;
+        self.blur_mode = false;
+        self.blur_drawing = None;
         self.circle_mode = false;
         self.circle_drawing = None;
         self.rect_outline_mode = false;
         self.rect_outline_drawing = None;
         self.magnifier_mode = false;
         self.magnifier_drawing = None;
+        self.spotlight_mode = false;
+        self.spotlight_drawing = None;
+        self.measure_mode = false;
+        self.measure_drawing = None;
+        self.stamp_mode = false;
+        self.stamp_drawing = None;
+        self.color_picker_mode = false;
         // Note: `selected_magnifier` is intentionally preserved here so the
         // right-click config popup (which disables modes) can still edit the
         // selected magnifier. It is cleared when switching to another tool.
@@ -277,13 +372,57 @@ pub struct UiState {
     pub shape_popup_open: bool,
     pub shape_color: ShapeColor,
     pub shape_shadow: bool,
+    /// Stroke width of new arrows, circles and rectangles in logical pixels
+    pub shape_width: f32,
+    /// Whether new circles and rectangles get a translucent fill
+    pub shape_fill: bool,
+    /// Whether new shapes get a shadow that contrasts with the image below
+    pub shape_auto_contrast: bool,
+    /// Named annotation styles (from config), applied with the number keys
+    pub annotation_presets: Vec<AnnotationPreset>,
     pub primary_redact_tool: RedactTool,
     pub redact_popup_open: bool,
     pub pixelation_block_size: u32,
+    /// Whether new pixelations use secure (noisy, quantised) block colours
+    pub secure_pixelation: bool,
+    /// Blur redaction tool: blur radius in logical pixels
+    pub blur_radius: u32,
+    /// Find-and-redact: current search text
+    pub ocr_search_query: String,
+    /// Find-and-redact: treat the search text as a regex
+    pub ocr_search_regex: bool,
+    /// Extra regexes (from config) treated as sensitive data when auto-redacting
+    pub sensitive_patterns: Vec<String>,
+    /// Spotlight tool: how strongly the outside is darkened (0.0-1.0)
+    pub spotlight_dim: f32,
+    /// Spotlight tool: highlight ellipses instead of rectangles
+    pub spotlight_ellipse: bool,
+    /// Spotlight tool: also blur the outside
+    pub spotlight_blur: bool,
     /// Magnifier annotation tool: whether its config popup is open
     pub magnifier_popup_open: bool,
     /// Magnifier annotation tool: zoom level (1.5-10.0)
     pub magnifier_magnification: f32,
+    /// Magnifier annotation tool: draw rectangular callouts instead of loupes
+    pub magnifier_callout: bool,
+    /// Magnifier annotation tool: callout frame width in logical pixels
+    pub magnifier_callout_border: f32,
+    /// Format the color picker copies
+    pub color_picker_format: ColorFormat,
+    /// Whether picked colors also become the shape color
+    pub color_picker_sets_shape_color: bool,
+    /// Measure tool: measure lines or boxes
+    pub measure_kind: MeasureKind,
+    /// Whether measurements are kept in saved images
+    pub measure_in_export: bool,
+    /// Stamp tool: whether its popup is open
+    pub stamp_popup_open: bool,
+    /// Stamp tool: name of the selected stamp
+    pub stamp_name: String,
+    /// Stamp tool: size of a clicked stamp (longer side, logical pixels)
+    pub stamp_size: f32,
+    /// Stamp library, loaded when the stamp tool is first used
+    pub stamp_library: Vec<Stamp>,
     /// Delay (seconds) for the delayed-screenshot toolbar button
     pub capture_delay_secs: u32,
     pub magnifier_enabled: bool,
@@ -292,6 +431,12 @@ pub struct UiState {
     pub video_save_location_setting: VideoSaveLocationChoice,
     pub video_custom_save_path: String,
     pub copy_to_clipboard_on_save: bool,
+    /// Whether exported screenshots get the beautify frame
+    pub beautify_enabled: bool,
+    /// Name of the selected beautify preset
+    pub beautify_preset: String,
+    /// Available beautify presets (from config)
+    pub beautify_presets: Vec<BeautifyPreset>,
     pub toolbar_unhovered_opacity: f32,
     /// Whether the toolbar is currently being hovered (for animated opacity)
     pub toolbar_is_hovered: bool,
@@ -299,6 +444,20 @@ pub struct UiState {
     /// ID for debouncing toolbar opacity saves (incremented on each change)
     pub toolbar_opacity_save_id: u64,
     pub tesseract_available: bool,
+    /// Engine used for OCR
+    pub ocr_engine: OcrEngine,
+    /// Whether OCR straightens skewed text first
+    pub ocr_deskew: bool,
+    /// Whether the built-in OCR engine may download missing models
+    pub ocr_model_download: bool,
+    /// OCR languages from the settings
+    pub ocr_languages: Vec<String>,
+    /// Installed tesseract language packs, listed when first needed
+    pub ocr_installed_languages: Vec<String>,
+    /// OCR languages chosen for this capture only, overriding the settings
+    pub ocr_capture_languages: Option<Vec<String>>,
+    /// Whether the OCR language popup is open
+    pub ocr_popup_open: bool,
     // Recording settings
     pub available_encoders: Vec<EncoderInfo>,
     pub encoder_displays: Vec<(String, String)>, // Cached (display_name, gst_element) for UI
@@ -321,12 +480,25 @@ pub struct UiState {
     pub pencil_fade_duration: f32,
     /// Pencil line thickness in pixels
     pub pencil_thickness: f32,
+    /// Recently used annotation colors, most recent first
+    pub recent_colors: Vec<ShapeColor>,
+    /// Named color palettes (from config)
+    pub color_palettes: Vec<ColorPalette>,
+    /// Names were edited but not saved yet; saved on submit or when the popup closes
+    pub unsaved_names: bool,
+    /// Text in the hex field of the color chooser while it is being edited
+    pub color_hex_input: String,
+    /// HSV the color sliders were last dragged to, so hue and saturation
+    /// survive passing through black or gray
+    pub color_hsv: Option<[f32; 3]>,
     /// Last known toolbar bounds (output-local)
     pub toolbar_bounds: Option<Rectangle>,
     /// Whether to hide toolbar to system tray when recording
     pub hide_toolbar_to_tray: bool,
     /// Move offset for dragging selection rectangle (cursor pos relative to rect top-left when move started)
     pub move_offset: Option<(i32, i32)>,
+    /// Re-crop mode: handles adjust the selection without discarding annotations
+    pub crop_mode: bool,
     /// Whether snappea is currently set as the default screenshot portal for the current user
     pub is_default_portal: bool,
 }
@@ -337,12 +509,63 @@ impl UiState {
             || self.capture_mode_animation.is_animating(self.now)
     }
 
+    /// The beautify preset to apply on export, if the frame is enabled
+    pub fn active_beautify_preset(&self) -> Option<&BeautifyPreset> {
+        if !self.beautify_enabled {
+            return None;
+        }
+        self.beautify_presets
+            .iter()
+            .find(|p| p.name == self.beautify_preset)
+            .or_else(|| self.beautify_presets.first())
+    }
+
     pub fn close_all_popups(&mut self) {
         self.shape_popup_open = false;
         self.redact_popup_open = false;
         self.magnifier_popup_open = false;
         self.settings_drawer_open = false;
         self.pencil_popup_open = false;
+        self.stamp_popup_open = false;
+        self.ocr_popup_open = false;
+    }
+
+    /// Load the stamp library if it has not been loaded yet
+    pub fn ensure_stamps_loaded(&mut self) {
+        if self.stamp_library.is_empty() {
+            self.stamp_library = crate::render::stamp::load_stamps();
+        }
+    }
+
+    /// List the installed OCR languages if they have not been listed yet
+    pub fn ensure_ocr_languages_loaded(&mut self) {
+        if self.ocr_installed_languages.is_empty() && self.tesseract_available {
+            self.ocr_installed_languages = crate::capture::ocr::installed_languages();
+        }
+    }
+
+    /// Whether the selected OCR engine can run; the built-in one reports
+    /// missing models when run
+    pub fn ocr_available(&self) -> bool {
+        match self.ocr_engine {
+            OcrEngine::Tesseract => self.tesseract_available,
+            OcrEngine::Builtin => true,
+        }
+    }
+
+    /// Languages the next OCR run uses
+    pub fn effective_ocr_languages(&self) -> &[String] {
+        self.ocr_capture_languages
+            .as_deref()
+            .unwrap_or(&self.ocr_languages)
+    }
+
+    /// The selected stamp, falling back to the first one if it no longer exists
+    pub fn selected_stamp(&self) -> Option<&Stamp> {
+        self.stamp_library
+            .iter()
+            .find(|s| s.name == self.stamp_name)
+            .or_else(|| self.stamp_library.first())
     }
 }
 
@@ -360,6 +583,8 @@ mod tests {
             magnification: zoom,
             color: ShapeColor::default(),
             shadow: true,
+            callout: None,
+            callout_border: 3.0,
         }
     }
 
@@ -369,8 +594,11 @@ mod tests {
             start_y: 0.0,
             end_x: 10.0,
             end_y: 10.0,
+            control: None,
+            width: 3.0,
             color: ShapeColor::default(),
             shadow: true,
+            auto_contrast: false,
         }
     }
 
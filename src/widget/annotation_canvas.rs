@@ -1,7 +1,7 @@
 //! AnnotationCanvas widget for rendering and handling annotation drawing
 //!
 //! This widget handles:
-//! - Rendering completed annotations (arrows, circles, rectangles, redactions, pixelations)
+//! - Rendering completed annotations (arrows, circles, rectangles, redactions, pixelations, blurs)
 //! - Rendering in-progress annotation previews
 //! - Mouse events for starting/updating/completing annotations
 
@@ -24,10 +24,10 @@ use image::RgbaImage;
 use crate::{
     config::ShapeColor,
     domain::{
-        ArrowAnnotation, CircleOutlineAnnotation, PixelateAnnotation, Rect, RectOutlineAnnotation,
-        RedactAnnotation,
+        ArrowAnnotation, BlurAnnotation, CircleOutlineAnnotation, PixelateAnnotation, Rect,
+        RectOutlineAnnotation, RedactAnnotation,
     },
-    render::mesh::build_arrow_mesh,
+    render::{image::blur_preview_grid, mesh::build_arrow_mesh},
 };
 
 /// Current drawing mode for annotations
@@ -39,6 +39,7 @@ pub enum DrawingMode {
     Rectangle,
     Redact,
     Pixelate,
+    Blur,
 }
 
 /// Messages emitted by the AnnotationCanvas
@@ -64,6 +65,10 @@ pub enum AnnotationEvent {
     PixelateStart(f32, f32),
     /// Pixelate drawing ended at position
     PixelateEnd(f32, f32),
+    /// Blur drawing started at position
+    BlurStart(f32, f32),
+    /// Blur drawing ended at position
+    BlurEnd(f32, f32),
 }
 
 /// Configuration for the annotation canvas
@@ -82,6 +87,8 @@ pub struct AnnotationCanvasConfig<'a> {
     pub shape_shadow: bool,
     /// Pixelation block size
     pub pixelation_block_size: u32,
+    /// Blur radius in logical pixels
+    pub blur_radius: u32,
     /// Image scale factor
     pub image_scale: f32,
     /// Reference to screenshot image (for pixelation preview)
@@ -104,6 +111,8 @@ pub struct AnnotationCanvas<'a, Msg> {
     redactions: &'a [RedactAnnotation],
     /// Completed pixelation annotations
     pixelations: &'a [PixelateAnnotation],
+    /// Completed blur annotations
+    blurs: &'a [BlurAnnotation],
     /// Configuration
     config: AnnotationCanvasConfig<'a>,
     /// Event handler
@@ -119,6 +128,7 @@ impl<'a, Msg> AnnotationCanvas<'a, Msg> {
             rect_outlines: &[],
             redactions: &[],
             pixelations: &[],
+            blurs: &[],
             config,
             on_event: None,
         }
@@ -154,6 +164,12 @@ impl<'a, Msg> AnnotationCanvas<'a, Msg> {
         self
     }
 
+    /// Set blur annotations
+    pub fn blurs(mut self, blurs: &'a [BlurAnnotation]) -> Self {
+        self.blurs = blurs;
+        self
+    }
+
     /// Set event handler
     pub fn on_event(mut self, handler: impl Fn(AnnotationEvent) -> Msg + 'a) -> Self {
         self.on_event = Some(Box::new(handler));
@@ -231,6 +247,9 @@ impl<'a, Msg: Clone + 'static> Widget<Msg, cosmic::Theme, cosmic::Renderer>
         // Draw completed pixelations
         self.draw_pixelations(renderer, &bounds);
 
+        // Draw completed blurs
+        self.draw_blurs(renderer);
+
         // Draw completed shape annotations (circles, rectangles)
         self.draw_circles(renderer, &bounds);
         self.draw_rectangles(renderer, &bounds);
@@ -282,6 +301,9 @@ impl<'a, Msg: Clone + 'static> Widget<Msg, cosmic::Theme, cosmic::Renderer>
                         end_y,
                     );
                 }
+                DrawingMode::Blur => {
+                    self.draw_blur_preview(renderer, local_start_x, local_start_y, end_x, end_y);
+                }
                 DrawingMode::None => {}
             }
         }
@@ -352,6 +374,9 @@ impl<'a, Msg: Clone + 'static> Widget<Msg, cosmic::Theme, cosmic::Renderer>
                         DrawingMode::Pixelate => {
                             Some(on_event(AnnotationEvent::PixelateStart(global_x, global_y)))
                         }
+                        DrawingMode::Blur => {
+                            Some(on_event(AnnotationEvent::BlurStart(global_x, global_y)))
+                        }
                         DrawingMode::None => None,
                     };
 
@@ -387,6 +412,9 @@ impl<'a, Msg: Clone + 'static> Widget<Msg, cosmic::Theme, cosmic::Renderer>
                         DrawingMode::Pixelate => {
                             Some(on_event(AnnotationEvent::PixelateEnd(global_x, global_y)))
                         }
+                        DrawingMode::Blur => {
+                            Some(on_event(AnnotationEvent::BlurEnd(global_x, global_y)))
+                        }
                         DrawingMode::None => None,
                     };
 
@@ -441,6 +469,12 @@ impl<'a, Msg: Clone + 'static> AnnotationCanvas<'a, Msg> {
             let start_y = arrow.start_y - self.config.output_rect.top as f32;
             let end_x = arrow.end_x - self.config.output_rect.left as f32;
             let end_y = arrow.end_y - self.config.output_rect.top as f32;
+            let control = arrow.control.map(|(x, y)| {
+                (
+                    x - self.config.output_rect.left as f32,
+                    y - self.config.output_rect.top as f32,
+                )
+            });
 
             // Draw shadow first
             if arrow.shadow
@@ -449,6 +483,7 @@ impl<'a, Msg: Clone + 'static> AnnotationCanvas<'a, Msg> {
                     start_y,
                     end_x,
                     end_y,
+                    control,
                     border_color,
                     arrow_thickness + 2.0 * outline_px,
                     head_size + outline_px,
@@ -469,6 +504,7 @@ impl<'a, Msg: Clone + 'static> AnnotationCanvas<'a, Msg> {
                 start_y,
                 end_x,
                 end_y,
+                control,
                 arrow_color,
                 arrow_thickness,
                 head_size,
@@ -508,6 +544,7 @@ impl<'a, Msg: Clone + 'static> AnnotationCanvas<'a, Msg> {
                 start_y,
                 end_x,
                 end_y,
+                None,
                 preview_border_color,
                 arrow_thickness + 2.0 * outline_px,
                 head_size + outline_px,
@@ -528,6 +565,7 @@ impl<'a, Msg: Clone + 'static> AnnotationCanvas<'a, Msg> {
             start_y,
             end_x,
             end_y,
+            None,
             preview_color,
             arrow_thickness,
             head_size,
@@ -1043,6 +1081,133 @@ impl<'a, Msg: Clone + 'static> AnnotationCanvas<'a, Msg> {
         }
     }
 
+    fn draw_blurs(&self, renderer: &mut cosmic::Renderer) {
+        for blur in self.blurs {
+            let x1 = blur.x - self.config.output_rect.left as f32;
+            let y1 = blur.y - self.config.output_rect.top as f32;
+            let x2 = blur.x2 - self.config.output_rect.left as f32;
+            let y2 = blur.y2 - self.config.output_rect.top as f32;
+            let (min_x, max_x) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
+            let (min_y, max_y) = if y1 < y2 { (y1, y2) } else { (y2, y1) };
+
+            self.draw_blur_region(renderer, min_x, min_y, max_x, max_y, blur.radius);
+        }
+    }
+
+    fn draw_blur_preview(
+        &self,
+        renderer: &mut cosmic::Renderer,
+        start_x: f32,
+        start_y: f32,
+        end_x: f32,
+        end_y: f32,
+    ) {
+        let (min_x, max_x) = if start_x < end_x {
+            (start_x, end_x)
+        } else {
+            (end_x, start_x)
+        };
+        let (min_y, max_y) = if start_y < end_y {
+            (start_y, end_y)
+        } else {
+            (end_y, start_y)
+        };
+
+        self.draw_blur_region(
+            renderer,
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+            self.config.blur_radius,
+        );
+
+        // Draw border
+        renderer.fill_quad(
+            cosmic::iced::core::renderer::Quad {
+                bounds: Rectangle {
+                    x: min_x,
+                    y: min_y,
+                    width: max_x - min_x,
+                    height: max_y - min_y,
+                },
+                border: Border {
+                    color: Color::WHITE,
+                    width: 1.0,
+                    radius: cosmic::iced::core::border::Radius::from(0.0),
+                },
+                shadow: cosmic::iced::core::Shadow::default(),
+                snap: false,
+            },
+            Background::Color(Color::TRANSPARENT),
+        );
+    }
+
+    /// Draw a blurred region as a grid of cells from a downsampled blur
+    fn draw_blur_region(
+        &self,
+        renderer: &mut cosmic::Renderer,
+        min_x: f32,
+        min_y: f32,
+        max_x: f32,
+        max_y: f32,
+        radius: u32,
+    ) {
+        // Source image and the display position of its origin
+        let (img, origin_x, origin_y, img_scale) =
+            if let (Some(win_img), Some((win_x, win_y, _, _, display_to_img_scale))) =
+                (self.config.window_image, self.config.window_display_info)
+            {
+                (win_img, win_x, win_y, display_to_img_scale)
+            } else {
+                (
+                    self.config.screenshot_image,
+                    0.0,
+                    0.0,
+                    self.config.image_scale,
+                )
+            };
+
+        let min_x = min_x.max(origin_x);
+        let min_y = min_y.max(origin_y);
+        if max_x <= min_x || max_y <= min_y {
+            return;
+        }
+
+        let radius = radius as f32;
+        let cell_px = ((radius / 3.0).max(2.0) * img_scale).round().max(1.0) as u32;
+        let cell = cell_px as f32 / img_scale;
+        let grid = blur_preview_grid(
+            img,
+            ((min_x - origin_x) * img_scale).round() as u32,
+            ((min_y - origin_y) * img_scale).round() as u32,
+            ((max_x - origin_x) * img_scale).round() as u32,
+            ((max_y - origin_y) * img_scale).round() as u32,
+            cell_px,
+            radius * img_scale,
+        );
+
+        for (col, row, pixel) in grid.enumerate_pixels() {
+            let x = min_x + col as f32 * cell;
+            let y = min_y + row as f32 * cell;
+            let [r, g, b, _] = pixel.0;
+            renderer.fill_quad(
+                cosmic::iced::core::renderer::Quad {
+                    bounds: Rectangle {
+                        x,
+                        y,
+                        width: cell.min(max_x - x),
+                        height: cell.min(max_y - y),
+                    },
+                    border: Border::default(),
+                    shadow: cosmic::iced::core::Shadow::default(),
+                    snap: false,
+                },
+                Background::Color(Color::from_rgb8(r, g, b)),
+            );
+        }
+    }
+
     fn average_color(img: &RgbaImage, x1: u32, y1: u32, x2: u32, y2: u32) -> Option<Color> {
         let x1 = x1.min(img.width().saturating_sub(1));
         let y1 = y1.min(img.height().saturating_sub(1));
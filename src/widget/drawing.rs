@@ -101,6 +101,127 @@ pub fn draw_dark_overlay_around_selection(
     }
 }
 
+/// Height (logical px) of the horizontal bands used to approximate ellipses
+const ELLIPSE_BAND_HEIGHT: f32 = 2.0;
+
+/// Draw a dark overlay around several regions (rectangles or ellipses)
+///
+/// A single rectangular hole is handed to [`draw_dark_overlay_around_selection`].
+/// Otherwise `outer_bounds` is cut into horizontal bands at every hole edge (and
+/// every couple of pixels across ellipses), and the uncovered parts of each band
+/// are filled with quads.
+///
+/// # Arguments
+/// * `renderer` - The renderer to draw with
+/// * `outer_bounds` - The full bounds to cover with the overlay
+/// * `holes` - Regions to leave clear, each with a flag for "ellipse inscribed in this rectangle"
+/// * `opacity` - Opacity of the dark overlay (0.0 = transparent, 1.0 = opaque)
+pub fn draw_dark_overlay_around_regions(
+    renderer: &mut cosmic::Renderer,
+    outer_bounds: Rectangle,
+    holes: &[(Rectangle, bool)],
+    opacity: f32,
+) {
+    if let [(hole, false)] = holes {
+        draw_dark_overlay_around_selection(renderer, outer_bounds, *hole, opacity);
+        return;
+    }
+
+    let outer = outer_bounds;
+    let outer_bottom = outer.y + outer.height;
+    let outer_right = outer.x + outer.width;
+
+    let mut cuts = vec![outer.y, outer_bottom];
+    for (hole, ellipse) in holes {
+        cuts.push(hole.y);
+        cuts.push(hole.y + hole.height);
+        if *ellipse {
+            let mut y = hole.y + ELLIPSE_BAND_HEIGHT;
+            while y < hole.y + hole.height {
+                cuts.push(y);
+                y += ELLIPSE_BAND_HEIGHT;
+            }
+        }
+    }
+    cuts.retain(|y| *y >= outer.y && *y <= outer_bottom);
+    cuts.sort_by(f32::total_cmp);
+    cuts.dedup();
+
+    let overlay = Color::from_rgba(0.0, 0.0, 0.0, opacity);
+    let mut spans: Vec<(f32, f32)> = Vec::new();
+    for band in cuts.windows(2) {
+        let (top, bottom) = (band[0], band[1]);
+        let mid = (top + bottom) / 2.0;
+
+        // Horizontal extent of each hole at this band
+        spans.clear();
+        for (hole, ellipse) in holes {
+            if mid < hole.y || mid >= hole.y + hole.height {
+                continue;
+            }
+            if *ellipse {
+                let (rx, ry) = (hole.width / 2.0, hole.height / 2.0);
+                let dy = (mid - (hole.y + ry)) / ry;
+                let half = rx * (1.0 - dy * dy).max(0.0).sqrt();
+                spans.push((hole.x + rx - half, hole.x + rx + half));
+            } else {
+                spans.push((hole.x, hole.x + hole.width));
+            }
+        }
+        spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        // Fill the gaps between the (merged) spans
+        let mut x = outer.x;
+        for &(left, right) in &spans {
+            if left > x {
+                fill_band(renderer, x, left.min(outer_right), top, bottom, overlay);
+            }
+            x = x.max(right);
+        }
+        if x < outer_right {
+            fill_band(renderer, x, outer_right, top, bottom, overlay);
+        }
+    }
+}
+
+/// Whether a point lies in any of the regions (rectangles or inscribed ellipses)
+pub fn regions_contain(regions: &[(Rectangle, bool)], x: f32, y: f32) -> bool {
+    regions.iter().any(|(r, ellipse)| {
+        if x < r.x || x >= r.x + r.width || y < r.y || y >= r.y + r.height {
+            return false;
+        }
+        if !ellipse {
+            return true;
+        }
+        let (rx, ry) = (r.width / 2.0, r.height / 2.0);
+        let dx = (x - (r.x + rx)) / rx;
+        let dy = (y - (r.y + ry)) / ry;
+        dx * dx + dy * dy <= 1.0
+    })
+}
+
+fn fill_band(
+    renderer: &mut cosmic::Renderer,
+    left: f32,
+    right: f32,
+    top: f32,
+    bottom: f32,
+    color: Color,
+) {
+    if right <= left {
+        return;
+    }
+    renderer.fill_quad(
+        Quad {
+            bounds: Rectangle::new(Point::new(left, top), Size::new(right - left, bottom - top)),
+            border: Border::default(),
+            shadow: Shadow::default(),
+            snap: false,
+        },
+        color,
+    );
+}
+
 /// Draw a full-screen dark overlay with a centered hint box
 ///
 /// Used to indicate non-active outputs in multi-monitor setups.
@@ -10,8 +10,14 @@ pub enum AppIcon {
     Square,
     Redact,
     Pixelate,
+    Blur,
     Magnifier,
+    Spotlight,
+    ColorPicker,
+    Ruler,
+    Stamp,
     Timer,
+    Crop,
     Ocr,
     Qr,
     Drag,
@@ -60,8 +66,14 @@ fn codepoint(icon: AppIcon) -> &'static str {
         AppIcon::Square => "square",
         AppIcon::Redact => "redact",
         AppIcon::Pixelate => "pixelate",
+        AppIcon::Blur => "blur",
         AppIcon::Magnifier => "magnifier",
+        AppIcon::Spotlight => "spotlight",
+        AppIcon::ColorPicker => "color-picker",
+        AppIcon::Ruler => "ruler",
+        AppIcon::Stamp => "stamp",
         AppIcon::Timer => "timer",
+        AppIcon::Crop => "crop",
         AppIcon::Ocr => "ocr",
         AppIcon::Qr => "qr",
         AppIcon::Drag => "drag",
@@ -1,9 +1,15 @@
 //! Magnifier widget for zoomed preview during selection
 
 use cosmic::iced::Color;
-use cosmic::iced::core::{Border, Point, Rectangle, Shadow, Size, renderer::Quad};
+use cosmic::iced::core::{
+    Border, Point, Rectangle, Shadow, Size, alignment,
+    renderer::Quad,
+    text::{Renderer as TextRenderer, Text},
+};
 use image::RgbaImage;
 
+use crate::domain::color::to_hex;
+
 /// Magnifier radius in pixels
 pub const MAGNIFIER_RADIUS: f32 = 60.0;
 /// Magnifier zoom factor
@@ -165,3 +171,179 @@ pub fn draw_magnifier(
         );
     });
 }
+
+/// Pixels across the color picker grid (odd, so one pixel sits in the middle)
+pub const PICKER_GRID: i32 = 11;
+/// On-screen size of one color picker grid cell
+pub const PICKER_CELL: f32 = 10.0;
+
+/// Color of the image pixel under a point in output-local logical coordinates
+pub fn pixel_at(screenshot_image: &RgbaImage, image_scale: f32, x: f32, y: f32) -> Option<[u8; 3]> {
+    let img_x = (x * image_scale) as i64;
+    let img_y = (y * image_scale) as i64;
+    if img_x < 0
+        || img_y < 0
+        || img_x >= screenshot_image.width() as i64
+        || img_y >= screenshot_image.height() as i64
+    {
+        return None;
+    }
+    let p = screenshot_image.get_pixel(img_x as u32, img_y as u32);
+    Some([p[0], p[1], p[2]])
+}
+
+/// Draw the color picker loupe: a grid of physical pixels around the cursor
+/// with the centre pixel outlined and its hex value underneath
+///
+/// `cursor` is in output-local logical coordinates. The loupe sits below-right
+/// of the cursor and flips to stay inside the viewport.
+pub fn draw_color_picker(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    screenshot_image: &RgbaImage,
+    image_scale: f32,
+    cursor: Point,
+    accent: Color,
+) {
+    use cosmic::iced::core::Renderer as _;
+
+    let Some(center_rgb) = pixel_at(screenshot_image, image_scale, cursor.x, cursor.y) else {
+        return;
+    };
+
+    let grid_size = PICKER_GRID as f32 * PICKER_CELL;
+    let label_height = 26.0;
+    let offset = 24.0;
+    let total = Size::new(grid_size, grid_size + label_height);
+    let x = if cursor.x + offset + total.width > viewport.width {
+        cursor.x - offset - total.width
+    } else {
+        cursor.x + offset
+    };
+    let y = if cursor.y + offset + total.height > viewport.height {
+        cursor.y - offset - total.height
+    } else {
+        cursor.y + offset
+    };
+
+    let img_x = (cursor.x * image_scale) as i32;
+    let img_y = (cursor.y * image_scale) as i32;
+    let half = PICKER_GRID / 2;
+
+    renderer.with_layer(*viewport, |renderer| {
+        // Backing panel (also shows through for pixels outside the image)
+        renderer.fill_quad(
+            Quad {
+                bounds: Rectangle::new(Point::new(x - 2.0, y - 2.0), total + Size::new(4.0, 4.0)),
+                border: Border {
+                    radius: 4.0.into(),
+                    width: 2.0,
+                    color: accent,
+                },
+                shadow: Shadow::default(),
+                snap: false,
+            },
+            Color::from_rgba(0.0, 0.0, 0.0, 0.9),
+        );
+
+        for dy in -half..=half {
+            for dx in -half..=half {
+                let (src_x, src_y) = (img_x + dx, img_y + dy);
+                if src_x < 0
+                    || src_y < 0
+                    || src_x >= screenshot_image.width() as i32
+                    || src_y >= screenshot_image.height() as i32
+                {
+                    continue;
+                }
+                let p = screenshot_image.get_pixel(src_x as u32, src_y as u32);
+                let cell = Rectangle::new(
+                    Point::new(
+                        x + (dx + half) as f32 * PICKER_CELL,
+                        y + (dy + half) as f32 * PICKER_CELL,
+                    ),
+                    Size::new(PICKER_CELL, PICKER_CELL),
+                );
+                renderer.fill_quad(
+                    Quad {
+                        bounds: cell,
+                        // Faint grid lines between pixels
+                        border: Border {
+                            radius: 0.0.into(),
+                            width: 0.5,
+                            color: Color::from_rgba(0.0, 0.0, 0.0, 0.25),
+                        },
+                        shadow: Shadow::default(),
+                        snap: false,
+                    },
+                    Color::from_rgb8(p[0], p[1], p[2]),
+                );
+            }
+        }
+
+        // Outline the sampled pixel in black and white so it shows on any color
+        let center = Rectangle::new(
+            Point::new(x + half as f32 * PICKER_CELL, y + half as f32 * PICKER_CELL),
+            Size::new(PICKER_CELL, PICKER_CELL),
+        );
+        for (grow, color) in [(1.0, Color::BLACK), (0.0, Color::WHITE)] {
+            renderer.fill_quad(
+                Quad {
+                    bounds: Rectangle::new(
+                        Point::new(center.x - grow, center.y - grow),
+                        Size::new(center.width + grow * 2.0, center.height + grow * 2.0),
+                    ),
+                    border: Border {
+                        radius: 0.0.into(),
+                        width: 1.0,
+                        color,
+                    },
+                    shadow: Shadow::default(),
+                    snap: false,
+                },
+                Color::TRANSPARENT,
+            );
+        }
+
+        // Swatch and hex readout
+        let label_y = y + grid_size;
+        let swatch = label_height - 10.0;
+        renderer.fill_quad(
+            Quad {
+                bounds: Rectangle::new(
+                    Point::new(x + 5.0, label_y + 5.0),
+                    Size::new(swatch, swatch),
+                ),
+                border: Border {
+                    radius: 2.0.into(),
+                    width: 1.0,
+                    color: Color::WHITE,
+                },
+                shadow: Shadow::default(),
+                snap: false,
+            },
+            Color::from_rgb8(center_rgb[0], center_rgb[1], center_rgb[2]),
+        );
+        let text_width = grid_size - swatch - 10.0;
+        renderer.fill_text(
+            Text {
+                content: to_hex(center_rgb),
+                bounds: Size::new(text_width, label_height),
+                size: cosmic::iced::Pixels(14.0),
+                line_height: cosmic::iced::core::text::LineHeight::default(),
+                font: cosmic::iced::Font::MONOSPACE,
+                align_x: alignment::Horizontal::Center.into(),
+                align_y: alignment::Vertical::Center,
+                shaping: cosmic::iced::core::text::Shaping::Basic,
+                wrapping: cosmic::iced::core::text::Wrapping::None,
+                ellipsize: cosmic::iced::core::text::Ellipsize::default(),
+            },
+            Point::new(
+                x + swatch + 10.0 + text_width / 2.0,
+                label_y + label_height / 2.0,
+            ),
+            Color::WHITE,
+            *viewport,
+        );
+    });
+}
@@ -0,0 +1,46 @@
+//! Arrow overlay drawing functions
+//!
+//! Draws the bend handles shown on arrows while the arrow tool is active.
+//! Dragging a handle curves the arrow through it.
+
+use cosmic::iced::Color;
+use cosmic::iced::core::{Background, Border, Rectangle, Renderer as _, Shadow, renderer::Quad};
+
+use crate::domain::ArrowAnnotation;
+
+/// Radius of an arrow's bend handle in logical pixels
+pub const BEND_HANDLE_RADIUS: f32 = 6.0;
+
+/// Draw a bend handle at the middle of every arrow
+pub fn draw_arrow_handles(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    arrows: &[ArrowAnnotation],
+    output_offset: (f32, f32),
+    accent: Color,
+) {
+    let r = BEND_HANDLE_RADIUS;
+    renderer.with_layer(*viewport, |renderer| {
+        for arrow in arrows {
+            let (x, y) = arrow.bend_point();
+            renderer.fill_quad(
+                Quad {
+                    bounds: Rectangle {
+                        x: x - output_offset.0 - r,
+                        y: y - output_offset.1 - r,
+                        width: r * 2.0,
+                        height: r * 2.0,
+                    },
+                    border: Border {
+                        radius: r.into(),
+                        width: 1.5,
+                        color: Color::WHITE,
+                    },
+                    shadow: Shadow::default(),
+                    snap: false,
+                },
+                Background::Color(accent),
+            );
+        }
+    });
+}
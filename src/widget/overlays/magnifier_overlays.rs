@@ -1,7 +1,8 @@
 //! Magnifier annotation overlay drawing functions
 //!
 //! Draws magnifier loupes onto the screenshot preview: a circular region that
-//! shows the underlying image content zoomed in, plus a ring outline.
+//! shows the underlying image content zoomed in, plus a ring outline. Callouts
+//! show a source rectangle enlarged elsewhere, joined by connector lines.
 //!
 //! The zoomed content is drawn as a single scaled image primitive (clipped to a
 //! circle via `border_radius`), which is both fast and seamless — far better
@@ -9,12 +10,17 @@
 
 use cosmic::iced::Radians;
 use cosmic::iced::Color;
+use cosmic::iced::advanced::graphics::{
+    Mesh,
+    mesh::{Indexed, Renderer as MeshRenderer},
+};
 use cosmic::iced::advanced::image::{FilterMethod, Handle, Image, Renderer as ImageRenderer};
 use cosmic::iced::core::{Background, Border, Rectangle, Renderer as _, Shadow, renderer::Quad};
 
 use crate::config::ShapeColor;
-use crate::domain::Annotation;
+use crate::domain::{Annotation, MagnifierAnnotation};
 use crate::render::geometry::{self, shape};
+use crate::render::mesh::build_line_mesh;
 
 /// Draw all magnifier annotations from the unified annotations array
 #[allow(clippy::too_many_arguments)]
@@ -28,6 +34,10 @@ pub fn draw_magnifiers(
 ) {
     for annotation in annotations {
         if let Annotation::Magnifier(m) = annotation {
+            if m.is_callout() {
+                draw_magnifier_callout(renderer, viewport, m, output_offset, handle, base_rect);
+                continue;
+            }
             let (offset_x, offset_y) = output_offset;
             draw_magnifier_circle(
                 renderer,
@@ -76,6 +86,144 @@ pub fn draw_magnifier_preview(
     );
 }
 
+/// Draw a rectangular callout: the source rectangle's content enlarged into the
+/// callout rectangle, both framed, with connector lines between them.
+pub fn draw_magnifier_callout(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    m: &MagnifierAnnotation,
+    output_offset: (f32, f32),
+    handle: &Handle,
+    base_rect: Rectangle,
+) {
+    let Some((cx1, cy1, cx2, cy2)) = m.callout_rect() else {
+        return;
+    };
+    let (offset_x, offset_y) = output_offset;
+    let (sx1, sy1, sx2, sy2) = m.source_rect();
+    let src = (
+        sx1 - offset_x,
+        sy1 - offset_y,
+        sx2 - offset_x,
+        sy2 - offset_y,
+    );
+    let dst = (
+        cx1 - offset_x,
+        cy1 - offset_y,
+        cx2 - offset_x,
+        cy2 - offset_y,
+    );
+    if src.2 - src.0 < 1.0 || src.3 - src.1 < 1.0 {
+        return;
+    }
+    let mag = m.magnification.max(1.0);
+
+    // Scale the background so the source's top-left lands on the callout's
+    // top-left, then clip to the callout rectangle.
+    let scaled_bounds = Rectangle {
+        x: dst.0 - (src.0 - base_rect.x) * mag,
+        y: dst.1 - (src.1 - base_rect.y) * mag,
+        width: base_rect.width * mag,
+        height: base_rect.height * mag,
+    };
+    let to_rect = |(x1, y1, x2, y2): (f32, f32, f32, f32)| Rectangle {
+        x: x1,
+        y: y1,
+        width: x2 - x1,
+        height: y2 - y1,
+    };
+    let clip_bounds = to_rect(dst);
+
+    renderer.with_layer(*viewport, |renderer| {
+        let image = Image {
+            handle: handle.clone(),
+            filter_method: FilterMethod::Linear,
+            rotation: Radians(0.0),
+            border_radius: 0.0.into(),
+            opacity: 1.0,
+            snap: false,
+        };
+        renderer.draw_image(image, scaled_bounds, clip_bounds);
+    });
+
+    // Frames and connectors in a later layer so they sit above the image
+    let width = m.callout_border.max(1.0);
+    let connector_width = (width * 0.5).max(1.0);
+    let connectors = geometry::callout_connectors(src, dst);
+    let shadow_color = Color::from_rgba(0.0, 0.0, 0.0, 0.9);
+    let mut strokes = Vec::new();
+    if m.shadow {
+        strokes.push((shadow_color, width + 2.0, connector_width + 2.0));
+    }
+    strokes.push((m.color.into(), width, connector_width));
+
+    renderer.with_layer(*viewport, |renderer| {
+        for (color, frame_width, line_width) in strokes {
+            for &(start, end) in &connectors {
+                let (vertices, indices) = build_line_mesh(start, end, color, line_width);
+                renderer.draw_mesh(Mesh::Solid {
+                    buffers: Indexed { vertices, indices },
+                    transformation: cosmic::iced::core::Transformation::IDENTITY,
+                    clip_bounds: *viewport,
+                });
+            }
+            // Quad borders are drawn inside the bounds; grow them so the frame
+            // is centered on the rectangle edge like the exported stroke.
+            for rect in [src, dst] {
+                let half = frame_width * 0.5;
+                renderer.fill_quad(
+                    Quad {
+                        bounds: to_rect((
+                            rect.0 - half,
+                            rect.1 - half,
+                            rect.2 + half,
+                            rect.3 + half,
+                        )),
+                        border: Border {
+                            radius: 0.0.into(),
+                            width: frame_width,
+                            color,
+                        },
+                        shadow: Shadow::default(),
+                        snap: false,
+                    },
+                    Background::Color(Color::TRANSPARENT),
+                );
+            }
+        }
+    });
+}
+
+/// Draw an accent outline around a selected callout's enlarged copy, given
+/// its bounds in output-local logical coordinates.
+pub fn draw_callout_handles(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    bounds: Rectangle,
+    accent: Color,
+) {
+    renderer.with_layer(*viewport, |renderer| {
+        renderer.fill_quad(
+            Quad {
+                bounds: Rectangle {
+                    x: bounds.x - 4.0,
+                    y: bounds.y - 4.0,
+                    width: bounds.width + 8.0,
+                    height: bounds.height + 8.0,
+                },
+                border: Border {
+                    radius: 2.0.into(),
+                    width: 2.0,
+                    color: accent,
+                },
+                shadow: Shadow::default(),
+                snap: false,
+            },
+            Background::Color(Color::TRANSPARENT),
+        );
+    });
+}
+
 /// Draw selection handles (an accent ring + 4 cardinal grab handles) around a
 /// magnifier, given its center/radius in output-local logical coordinates.
 pub fn draw_magnifier_handles(
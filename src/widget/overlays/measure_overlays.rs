@@ -0,0 +1,143 @@
+//! Measurement overlay drawing functions
+//!
+//! Draws dimension lines and boxes with their size labels onto the screenshot
+//! preview. Labels use the scale of the output they are drawn on, so the
+//! physical sizes match that screen's pixels.
+
+use cosmic::iced::advanced::graphics::{
+    Mesh,
+    mesh::{Indexed, Renderer as MeshRenderer},
+};
+use cosmic::iced::core::{
+    Background, Border, Rectangle, Renderer as _, Shadow, alignment,
+    renderer::Quad,
+    text::{Renderer as TextRenderer, Text},
+};
+use cosmic::iced::{Color, Point, Size};
+
+use crate::config::{MeasureKind, ShapeColor};
+use crate::domain::{MeasureAnnotation, measure_label};
+use crate::render::geometry::measure;
+use crate::render::mesh::build_line_mesh;
+
+/// Draw all completed measurements
+pub fn draw_measurements(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    measurements: &[MeasureAnnotation],
+    output_offset: (f32, f32),
+    scale: f32,
+) {
+    let (offset_x, offset_y) = output_offset;
+    for m in measurements {
+        draw_measurement(
+            renderer,
+            viewport,
+            m.kind,
+            (m.start_x - offset_x, m.start_y - offset_y),
+            (m.end_x - offset_x, m.end_y - offset_y),
+            m.color.into(),
+            &m.label(scale),
+        );
+    }
+}
+
+/// Draw the measurement being dragged (`start` is global, `end` output-local)
+#[allow(clippy::too_many_arguments)]
+pub fn draw_measure_preview(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    kind: MeasureKind,
+    start: (f32, f32),
+    end: (f32, f32),
+    output_offset: (f32, f32),
+    color: ShapeColor,
+    scale: f32,
+) {
+    let start = (start.0 - output_offset.0, start.1 - output_offset.1);
+    draw_measurement(
+        renderer,
+        viewport,
+        kind,
+        start,
+        end,
+        color.into(),
+        &measure_label(kind, start, end, scale),
+    );
+}
+
+fn draw_measurement(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    kind: MeasureKind,
+    start: (f32, f32),
+    end: (f32, f32),
+    color: Color,
+    label: &str,
+) {
+    let segments = measure::segments(kind, start, end, 1.0);
+    renderer.with_layer(*viewport, |renderer| {
+        // Dark outline keeps the thin lines visible on any background
+        let outline = Color::from_rgba(0.0, 0.0, 0.0, 0.6);
+        for (stroke_color, width) in [
+            (outline, measure::THICKNESS + 2.0),
+            (color, measure::THICKNESS),
+        ] {
+            for &(a, b) in &segments {
+                let (vertices, indices) = build_line_mesh(a, b, stroke_color, width);
+                renderer.draw_mesh(Mesh::Solid {
+                    buffers: Indexed { vertices, indices },
+                    transformation: cosmic::iced::core::Transformation::IDENTITY,
+                    clip_bounds: *viewport,
+                });
+            }
+        }
+    });
+
+    // Separate layer so the label sits on top of the line it is centered on
+    let font_size = measure::LABEL_SIZE;
+    let padding = 4.0;
+    let plate_width = label.chars().count() as f32 * font_size * 0.6 + padding * 2.0;
+    let plate_height = font_size * 1.3 + padding * 2.0;
+    let (cx, cy) = measure::label_center(kind, start, end, plate_height, 1.0);
+    let x = (cx - plate_width / 2.0)
+        .min(viewport.x + viewport.width - plate_width)
+        .max(viewport.x);
+    let y = (cy - plate_height / 2.0)
+        .min(viewport.y + viewport.height - plate_height)
+        .max(viewport.y);
+    let plate = Rectangle::new(Point::new(x, y), Size::new(plate_width, plate_height));
+
+    renderer.with_layer(*viewport, |renderer| {
+        renderer.fill_quad(
+            Quad {
+                bounds: plate,
+                border: Border {
+                    radius: 4.0.into(),
+                    width: 0.0,
+                    color: Color::TRANSPARENT,
+                },
+                shadow: Shadow::default(),
+                snap: false,
+            },
+            Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.75)),
+        );
+        renderer.fill_text(
+            Text {
+                content: label.to_string(),
+                bounds: plate.size(),
+                size: cosmic::iced::Pixels(font_size),
+                line_height: cosmic::iced::core::text::LineHeight::default(),
+                font: cosmic::iced::Font::MONOSPACE,
+                align_x: alignment::Horizontal::Center.into(),
+                align_y: alignment::Vertical::Center,
+                shaping: cosmic::iced::core::text::Shaping::Advanced,
+                wrapping: cosmic::iced::core::text::Wrapping::None,
+                ellipsize: cosmic::iced::core::text::Ellipsize::default(),
+            },
+            plate.center(),
+            Color::WHITE,
+            *viewport,
+        );
+    });
+}
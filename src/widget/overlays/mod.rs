@@ -3,9 +3,12 @@
 //! This module contains canvas-based overlay widgets used for
 //! rendering annotations on top of the screenshot.
 
+pub mod arrow_overlays;
 pub mod magnifier_overlays;
+pub mod measure_overlays;
 pub mod redact_overlays;
 mod shapes_overlay;
+pub mod stamp_overlays;
 pub mod status_overlays;
 
 pub use shapes_overlay::ShapesOverlay;
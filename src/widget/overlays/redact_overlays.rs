@@ -1,13 +1,15 @@
 //! Redaction and pixelation overlay drawing functions
 //!
-//! This module contains helper functions for drawing redactions and pixelations
-//! on the screenshot preview.
+//! This module contains helper functions for drawing redactions, pixelations
+//! and blurs on the screenshot preview.
 
 use cosmic::iced::Color;
 use cosmic::iced::core::{Background, Border, Rectangle};
 use image::RgbaImage;
 
-use crate::domain::{Annotation, PixelateAnnotation, RedactAnnotation};
+use crate::domain::{Annotation, BlurAnnotation, PixelateAnnotation, RedactAnnotation};
+use crate::render::image::{SPOTLIGHT_BLUR_RADIUS, blur_preview_grid, secure_block_color};
+use crate::widget::drawing::regions_contain;
 
 /// Image source for pixelation sampling
 pub enum PixelationSource<'a> {
@@ -82,6 +84,41 @@ pub fn draw_pixelation(
                 max_y,
                 *scale,
                 block_size_logical,
+                pixelate.noise_seed,
+            );
+        }
+    }
+}
+
+/// Draw a single blur region
+pub fn draw_blur(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    blur: &BlurAnnotation,
+    output_offset: (f32, f32),
+    source: &PixelationSource,
+) {
+    let (offset_x, offset_y) = output_offset;
+    let x1 = blur.x - offset_x;
+    let y1 = blur.y - offset_y;
+    let x2 = blur.x2 - offset_x;
+    let y2 = blur.y2 - offset_y;
+    let (min_x, max_x) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
+    let (min_y, max_y) = if y1 < y2 { (y1, y2) } else { (y2, y1) };
+
+    match source {
+        PixelationSource::Screenshot { image, scale } => {
+            draw_blur_cells_screenshot(
+                renderer,
+                viewport,
+                image,
+                min_x,
+                min_y,
+                max_x,
+                max_y,
+                *scale,
+                blur.radius as f32,
+                &[],
             );
         }
     }
@@ -103,6 +140,9 @@ pub fn draw_redactions_and_pixelations(
             Annotation::Pixelate(pixelate) => {
                 draw_pixelation(renderer, viewport, pixelate, output_offset, source);
             }
+            Annotation::Blur(blur) => {
+                draw_blur(renderer, viewport, blur, output_offset, source);
+            }
             _ => {}
         }
     }
@@ -117,6 +157,7 @@ pub fn draw_pixelation_preview(
     end: (f32, f32),   // Local end position (cursor)
     output_offset: (f32, f32),
     block_size: u32,
+    noise_seed: Option<u64>,
     source: &PixelationSource,
 ) {
     use cosmic::iced::core::Renderer;
@@ -150,6 +191,76 @@ pub fn draw_pixelation_preview(
                 max_y,
                 *scale,
                 block_size_logical,
+                noise_seed,
+            );
+        }
+    }
+
+    // Draw border
+    renderer.with_layer(*viewport, |renderer| {
+        renderer.fill_quad(
+            cosmic::iced::core::renderer::Quad {
+                bounds: Rectangle {
+                    x: min_x,
+                    y: min_y,
+                    width: max_x - min_x,
+                    height: max_y - min_y,
+                },
+                border: Border {
+                    color: Color::WHITE,
+                    width: 1.0,
+                    radius: 0.0.into(),
+                },
+                shadow: cosmic::iced::core::Shadow::default(),
+                snap: false,
+            },
+            Background::Color(Color::TRANSPARENT),
+        );
+    });
+}
+
+/// Draw blur preview (while dragging)
+#[allow(clippy::too_many_arguments)]
+pub fn draw_blur_preview(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    start: (f32, f32), // Global start position
+    end: (f32, f32),   // Local end position (cursor)
+    output_offset: (f32, f32),
+    radius: u32,
+    source: &PixelationSource,
+) {
+    use cosmic::iced::core::Renderer;
+
+    let (offset_x, offset_y) = output_offset;
+    let local_start_x = start.0 - offset_x;
+    let local_start_y = start.1 - offset_y;
+    let (end_x, end_y) = end;
+
+    let (min_x, max_x) = if local_start_x < end_x {
+        (local_start_x, end_x)
+    } else {
+        (end_x, local_start_x)
+    };
+    let (min_y, max_y) = if local_start_y < end_y {
+        (local_start_y, end_y)
+    } else {
+        (end_y, local_start_y)
+    };
+
+    match source {
+        PixelationSource::Screenshot { image, scale } => {
+            draw_blur_cells_screenshot(
+                renderer,
+                viewport,
+                image,
+                min_x,
+                min_y,
+                max_x,
+                max_y,
+                *scale,
+                radius as f32,
+                &[],
             );
         }
     }
@@ -177,6 +288,34 @@ pub fn draw_pixelation_preview(
     });
 }
 
+/// Draw the blurred outside of spotlights, leaving their regions clear
+///
+/// `outer` and the regions are in local logical coordinates.
+pub fn draw_spotlight_blur(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    outer: Rectangle,
+    regions: &[(Rectangle, bool)],
+    source: &PixelationSource,
+) {
+    match source {
+        PixelationSource::Screenshot { image, scale } => {
+            draw_blur_cells_screenshot(
+                renderer,
+                viewport,
+                image,
+                outer.x,
+                outer.y,
+                outer.x + outer.width,
+                outer.y + outer.height,
+                *scale,
+                SPOTLIGHT_BLUR_RADIUS,
+                regions,
+            );
+        }
+    }
+}
+
 /// Draw redaction preview (while dragging)
 pub fn draw_redaction_preview(
     renderer: &mut cosmic::Renderer,
@@ -228,6 +367,8 @@ pub fn draw_redaction_preview(
 // ============ Internal helper functions ============
 
 /// Draw pixelation blocks sampling from screenshot image
+///
+/// With a `noise_seed` the blocks use the same secure colouring as the export.
 #[allow(clippy::too_many_arguments)]
 fn draw_pixelation_blocks_screenshot(
     renderer: &mut cosmic::Renderer,
@@ -239,13 +380,16 @@ fn draw_pixelation_blocks_screenshot(
     max_y: f32,
     image_scale: f32,
     block_size: f32,
+    noise_seed: Option<u64>,
 ) {
     use cosmic::iced::core::Renderer;
 
     renderer.with_layer(*viewport, |renderer| {
         let mut y = min_y;
+        let mut row = 0;
         while y < max_y {
             let mut x = min_x;
+            let mut col = 0;
             let block_h = block_size.min(max_y - y);
             while x < max_x {
                 let block_w = block_size.min(max_x - x);
@@ -255,7 +399,14 @@ fn draw_pixelation_blocks_screenshot(
                 let img_x2 = ((x + block_w) * image_scale).round() as u32;
                 let img_y2 = ((y + block_h) * image_scale).round() as u32;
 
-                if let Some(color) = sample_average_color(image, img_x, img_y, img_x2, img_y2) {
+                let color = match noise_seed {
+                    Some(seed) => {
+                        secure_block_color(image, img_x, img_y, img_x2, img_y2, seed, (col, row))
+                            .map(|[r, g, b, _]| Color::from_rgb8(r, g, b))
+                    }
+                    None => sample_average_color(image, img_x, img_y, img_x2, img_y2),
+                };
+                if let Some(color) = color {
                     renderer.fill_quad(
                         cosmic::iced::core::renderer::Quad {
                             bounds: Rectangle {
@@ -272,8 +423,74 @@ fn draw_pixelation_blocks_screenshot(
                     );
                 }
                 x += block_w;
+                col += 1;
             }
             y += block_h;
+            row += 1;
+        }
+    });
+}
+
+/// Draw a blurred region sampling from screenshot image
+///
+/// The region is split into small cells whose colors come from a downsampled
+/// blur of the screenshot, which keeps the preview cheap enough to redraw on
+/// every frame. The saved image uses the full-resolution blur instead.
+#[allow(clippy::too_many_arguments)]
+fn draw_blur_cells_screenshot(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    image: &RgbaImage,
+    min_x: f32,
+    min_y: f32,
+    max_x: f32,
+    max_y: f32,
+    image_scale: f32,
+    radius: f32,
+    skip: &[(Rectangle, bool)],
+) {
+    use cosmic::iced::core::Renderer;
+
+    let min_x = min_x.max(0.0);
+    let min_y = min_y.max(0.0);
+    if max_x <= min_x || max_y <= min_y {
+        return;
+    }
+
+    let cell_px = ((radius / 3.0).max(2.0) * image_scale).round().max(1.0) as u32;
+    let cell = cell_px as f32 / image_scale;
+    let grid = blur_preview_grid(
+        image,
+        (min_x * image_scale).round() as u32,
+        (min_y * image_scale).round() as u32,
+        (max_x * image_scale).round() as u32,
+        (max_y * image_scale).round() as u32,
+        cell_px,
+        radius * image_scale,
+    );
+
+    renderer.with_layer(*viewport, |renderer| {
+        for (col, row, pixel) in grid.enumerate_pixels() {
+            let x = min_x + col as f32 * cell;
+            let y = min_y + row as f32 * cell;
+            if regions_contain(skip, x + cell / 2.0, y + cell / 2.0) {
+                continue;
+            }
+            let [r, g, b, _] = pixel.0;
+            renderer.fill_quad(
+                cosmic::iced::core::renderer::Quad {
+                    bounds: Rectangle {
+                        x,
+                        y,
+                        width: cell.min(max_x - x),
+                        height: cell.min(max_y - y),
+                    },
+                    border: Border::default(),
+                    shadow: cosmic::iced::core::Shadow::default(),
+                    snap: false,
+                },
+                Background::Color(Color::from_rgb8(r, g, b)),
+            );
         }
     });
 }
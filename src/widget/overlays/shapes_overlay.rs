@@ -4,10 +4,15 @@
 //! - Drawing existing circle and rectangle annotations
 //! - Live preview during shape drawing
 //! - Mouse input for shape creation
+//! - Tracking Shift/Ctrl for constrained drawing
+//! - Alignment guides while an arrow, rectangle or circle is being drawn
 
 use cosmic::iced::widget::canvas;
 
+use crate::annotations::constrain::{Guides, Snap, arrow_end, box_end};
 use crate::domain::{CircleOutlineAnnotation, Rect, RectOutlineAnnotation};
+use crate::render::contrast::Backdrop;
+use crate::render::geometry::shape;
 
 /// Canvas overlay for circle/rectangle outline rendering and input
 pub struct ShapesOverlay<'a, Message: Clone + 'static> {
@@ -27,6 +32,15 @@ pub struct ShapesOverlay<'a, Message: Clone + 'static> {
     pub circle_drawing: Option<(f32, f32)>,
     /// Current rectangle outline drawing start in global coordinates (if any)
     pub rect_outline_drawing: Option<(f32, f32)>,
+    /// Current arrow drawing start in global coordinates (if any), for guides
+    pub arrow_drawing: Option<(f32, f32)>,
+    /// Whether shapes are constrained (snap angles, lock squares and circles),
+    /// as last published from [`ShapesState::constrain`]
+    pub constrain: bool,
+    /// Alignment guides in global coordinates
+    pub guides: Guides,
+    /// Callback when the (latched) constraint turns on or off
+    pub on_constrain: Option<Box<dyn Fn(bool) -> Message + 'a>>,
     /// Callback when circle drawing starts
     pub on_circle_start: Option<Box<dyn Fn(f32, f32) -> Message + 'a>>,
     /// Callback when circle drawing ends
@@ -37,37 +51,58 @@ pub struct ShapesOverlay<'a, Message: Clone + 'static> {
     pub on_rect_end: Option<Box<dyn Fn(f32, f32) -> Message + 'a>>,
     /// Shape color for preview
     pub shape_color: crate::config::ShapeColor,
+    /// Stroke width for preview
+    pub shape_width: f32,
+    /// Whether the preview is filled
+    pub shape_fill: bool,
     /// Whether to draw shadow on shapes
     pub shape_shadow: bool,
+    /// Screenshot under the shapes, for auto-contrast shadows
+    pub backdrop: Backdrop<'a>,
 }
 
 /// State for ShapesOverlay canvas program
 #[derive(Debug, Default)]
 pub struct ShapesState {
-    /// Whether Ctrl key is currently pressed
-    pub ctrl_down: bool,
-    /// Whether Ctrl was pressed when drawing started (latched)
-    pub ctrl_latched: bool,
+    /// Whether Shift or Ctrl is currently pressed
+    pub constrain_down: bool,
+    /// Whether Shift or Ctrl was pressed during the current drawing (latched)
+    pub constrain_latched: bool,
 }
 
 impl ShapesState {
-    /// Latch Ctrl state if a drawing is active
-    pub fn latch_ctrl_if_needed(&mut self, drawing_active: bool) {
-        if drawing_active && self.ctrl_down {
-            self.ctrl_latched = true;
+    /// Latch Shift/Ctrl while a drawing is active, and drop the latch once it ends
+    pub fn latch_constrain_if_needed(&mut self, drawing_active: bool) {
+        if !drawing_active {
+            self.constrain_latched = false;
+        } else if self.constrain_down {
+            self.constrain_latched = true;
         }
     }
+
+    /// Whether the shape being drawn is constrained
+    pub fn constrain(&self) -> bool {
+        self.constrain_down || self.constrain_latched
+    }
 }
 
 impl<'a, Message: Clone + 'static> ShapesOverlay<'a, Message> {
-    /// Constrain end point to form a square/circle (when Ctrl is held)
-    fn constrain_end(sx: f32, sy: f32, ex: f32, ey: f32) -> (f32, f32) {
-        let dx = ex - sx;
-        let dy = ey - sy;
-        let side = dx.abs().min(dy.abs());
-        let sign_x = if dx < 0.0 { -1.0 } else { 1.0 };
-        let sign_y = if dy < 0.0 { -1.0 } else { 1.0 };
-        (sx + side * sign_x, sy + side * sign_y)
+    /// Where the shape being drawn ends with the cursor at `pos` (output-local),
+    /// in output-local coordinates, with the guides it lines up with
+    fn preview_end(&self, pos: (f32, f32)) -> Option<Snap> {
+        let offset = (self.output_rect.left as f32, self.output_rect.top as f32);
+        let end = (pos.0 + offset.0, pos.1 + offset.1);
+        let snap = if let Some(start) = self.arrow_drawing {
+            arrow_end(start, end, self.constrain, &self.guides)
+        } else {
+            let start = self.rect_outline_drawing.or(self.circle_drawing)?;
+            box_end(start, end, self.constrain, &self.guides)
+        };
+        Some(Snap {
+            point: (snap.point.0 - offset.0, snap.point.1 - offset.1),
+            guide_x: snap.guide_x.map(|x| x - offset.0),
+            guide_y: snap.guide_y.map(|y| y - offset.1),
+        })
     }
 }
 
@@ -113,12 +148,26 @@ impl<'a, Message: Clone + 'static> canvas::Program<Message, cosmic::Theme, cosmi
             }
         };
 
+        // Releasing Shift/Ctrl a moment before the mouse button keeps the
+        // square, circle or angle snap until the drawing ends
+        if let canvas::Event::Keyboard(keyboard::Event::ModifiersChanged(mods)) = event {
+            state.constrain_down = mods.shift() || mods.control();
+        }
+        state.latch_constrain_if_needed(
+            self.arrow_drawing.is_some()
+                || self.circle_drawing.is_some()
+                || self.rect_outline_drawing.is_some(),
+        );
+        let on_constrain = self
+            .on_constrain
+            .as_ref()
+            .filter(|_| state.constrain() != self.constrain);
+
         match event {
-            canvas::Event::Keyboard(keyboard::Event::ModifiersChanged(mods)) => {
-                state.ctrl_down = mods.control();
-                state.latch_ctrl_if_needed(
-                    self.circle_drawing.is_some() || self.rect_outline_drawing.is_some(),
-                );
+            canvas::Event::Keyboard(keyboard::Event::ModifiersChanged(_)) => {
+                if let Some(cb) = on_constrain {
+                    return Some(canvas::Action::publish(cb(state.constrain())).and_capture());
+                }
                 return Some(canvas::Action::capture());
             }
             canvas::Event::Mouse(MouseEvent::ButtonPressed(Button::Left)) => {
@@ -141,17 +190,15 @@ impl<'a, Message: Clone + 'static> canvas::Program<Message, cosmic::Theme, cosmi
                 let gx = cx + self.output_rect.left as f32;
                 let gy = cy + self.output_rect.top as f32;
 
-                if self.circle_mode {
-                    state.ctrl_latched = state.ctrl_down;
-                    if let Some(ref cb) = self.on_circle_start {
-                        return Some(canvas::Action::publish(cb(gx, gy)).and_capture());
-                    }
+                if self.circle_mode
+                    && let Some(ref cb) = self.on_circle_start
+                {
+                    return Some(canvas::Action::publish(cb(gx, gy)).and_capture());
                 }
-                if self.rect_outline_mode {
-                    state.ctrl_latched = state.ctrl_down;
-                    if let Some(ref cb) = self.on_rect_start {
-                        return Some(canvas::Action::publish(cb(gx, gy)).and_capture());
-                    }
+                if self.rect_outline_mode
+                    && let Some(ref cb) = self.on_rect_start
+                {
+                    return Some(canvas::Action::publish(cb(gx, gy)).and_capture());
                 }
             }
             canvas::Event::Mouse(MouseEvent::ButtonReleased(Button::Left)) => {
@@ -163,33 +210,27 @@ impl<'a, Message: Clone + 'static> canvas::Program<Message, cosmic::Theme, cosmi
                 let gx = cx + self.output_rect.left as f32;
                 let gy = cy + self.output_rect.top as f32;
 
-                if self.circle_mode && self.circle_drawing.is_some() {
-                    let (sx, sy) = self.circle_drawing.unwrap_or((gx, gy));
-                    let (ex, ey) = if state.ctrl_latched || state.ctrl_down {
-                        Self::constrain_end(sx, sy, gx, gy)
-                    } else {
-                        (gx, gy)
-                    };
-                    state.ctrl_latched = false;
-                    if let Some(ref cb) = self.on_circle_end {
-                        return Some(canvas::Action::publish(cb(ex, ey)).and_capture());
-                    }
+                // Snapping to guides and constraining happen in the handlers
+                if self.circle_mode
+                    && self.circle_drawing.is_some()
+                    && let Some(ref cb) = self.on_circle_end
+                {
+                    return Some(canvas::Action::publish(cb(gx, gy)).and_capture());
                 }
 
-                if self.rect_outline_mode && self.rect_outline_drawing.is_some() {
-                    let (sx, sy) = self.rect_outline_drawing.unwrap_or((gx, gy));
-                    let (ex, ey) = if state.ctrl_latched || state.ctrl_down {
-                        Self::constrain_end(sx, sy, gx, gy)
-                    } else {
-                        (gx, gy)
-                    };
-                    state.ctrl_latched = false;
-                    if let Some(ref cb) = self.on_rect_end {
-                        return Some(canvas::Action::publish(cb(ex, ey)).and_capture());
-                    }
+                if self.rect_outline_mode
+                    && self.rect_outline_drawing.is_some()
+                    && let Some(ref cb) = self.on_rect_end
+                {
+                    return Some(canvas::Action::publish(cb(gx, gy)).and_capture());
+                }
+            }
+            // The latch is dropped after the drawing ends
+            _ => {
+                if let Some(cb) = on_constrain {
+                    return Some(canvas::Action::publish(cb(state.constrain())));
                 }
             }
-            _ => {}
         }
 
         None
@@ -197,9 +238,9 @@ impl<'a, Message: Clone + 'static> canvas::Program<Message, cosmic::Theme, cosmi
 
     fn draw(
         &self,
-        state: &Self::State,
+        _state: &Self::State,
         renderer: &cosmic::Renderer,
-        _theme: &cosmic::Theme,
+        theme: &cosmic::Theme,
         bounds: cosmic::iced::core::Rectangle,
         cursor: cosmic::iced::core::mouse::Cursor,
     ) -> Vec<canvas::Geometry> {
@@ -208,18 +249,22 @@ impl<'a, Message: Clone + 'static> canvas::Program<Message, cosmic::Theme, cosmi
 
         let mut frame = Frame::new(renderer, bounds.size());
 
-        let shadow_stroke = Stroke {
-            style: Color::from_rgba(0.0, 0.0, 0.0, 0.9).into(),
-            width: 5.0,
+        let shadow_stroke = |width: f32, [r, g, b]: [u8; 3]| Stroke {
+            style: Color::from_rgba8(r, g, b, 0.9).into(),
+            width: shape::border_thickness(width),
             ..Stroke::default()
         };
+        let fill_color = |color: Color| Color {
+            a: shape::FILL_ALPHA,
+            ..color
+        };
 
         // Draw rectangle outlines with per-annotation colors
         for r in &self.rect_outlines {
             let rect_color: Color = r.color.into();
             let stroke = Stroke {
                 style: rect_color.into(),
-                width: 3.0,
+                width: r.width,
                 ..Stroke::default()
             };
             let x1 = r.start_x - self.output_rect.left as f32;
@@ -232,8 +277,11 @@ impl<'a, Message: Clone + 'static> canvas::Program<Message, cosmic::Theme, cosmi
                 Point::new(min_x, min_y),
                 Size::new((max_x - min_x).max(1.0), (max_y - min_y).max(1.0)),
             );
+            if r.fill {
+                frame.fill(&path, fill_color(rect_color));
+            }
             if r.shadow {
-                frame.stroke(&path, shadow_stroke);
+                frame.stroke(&path, shadow_stroke(r.width, self.backdrop.rect_outline(r)));
             }
             frame.stroke(&path, stroke);
         }
@@ -243,7 +291,7 @@ impl<'a, Message: Clone + 'static> canvas::Program<Message, cosmic::Theme, cosmi
             let circle_color: Color = c.color.into();
             let stroke = Stroke {
                 style: circle_color.into(),
-                width: 3.0,
+                width: c.width,
                 ..Stroke::default()
             };
             let x1 = c.start_x - self.output_rect.left as f32;
@@ -267,14 +315,22 @@ impl<'a, Message: Clone + 'static> canvas::Program<Message, cosmic::Theme, cosmi
                     b.line_to(Point::new(cx + rx * t.cos(), cy + ry * t.sin()));
                 }
             });
+            if c.fill {
+                frame.fill(&path, fill_color(circle_color));
+            }
             if c.shadow {
-                frame.stroke(&path, shadow_stroke);
+                frame.stroke(
+                    &path,
+                    shadow_stroke(c.width, self.backdrop.circle_outline(c)),
+                );
             }
             frame.stroke(&path, stroke);
         }
 
         // Live previews (during drag)
-        let constrain = state.ctrl_down || state.ctrl_latched;
+        let preview_end = cursor
+            .position_in(bounds)
+            .and_then(|pos| self.preview_end((pos.x, pos.y)));
         let shape_color: Color = self.shape_color.into();
         let preview_color = Color {
             a: 0.7,
@@ -282,31 +338,30 @@ impl<'a, Message: Clone + 'static> canvas::Program<Message, cosmic::Theme, cosmi
         };
         let preview_stroke = Stroke {
             style: preview_color.into(),
-            width: 3.0,
+            width: self.shape_width,
             ..Stroke::default()
         };
         let preview_shadow_stroke = Stroke {
             style: Color::from_rgba(0.0, 0.0, 0.0, 0.6).into(),
-            width: 5.0,
+            width: shape::border_thickness(self.shape_width),
             ..Stroke::default()
         };
 
         if let Some((sx_g, sy_g)) = self.rect_outline_drawing
-            && let Some(pos) = cursor.position_in(bounds)
+            && let Some(snap) = preview_end
         {
+            let (ex, ey) = snap.point;
             let sx = sx_g - self.output_rect.left as f32;
             let sy = sy_g - self.output_rect.top as f32;
-            let mut ex = pos.x;
-            let mut ey = pos.y;
-            if constrain {
-                (ex, ey) = Self::constrain_end(sx, sy, ex, ey);
-            }
             let (min_x, max_x) = if sx < ex { (sx, ex) } else { (ex, sx) };
             let (min_y, max_y) = if sy < ey { (sy, ey) } else { (ey, sy) };
             let path = Path::rectangle(
                 Point::new(min_x, min_y),
                 Size::new((max_x - min_x).max(1.0), (max_y - min_y).max(1.0)),
             );
+            if self.shape_fill {
+                frame.fill(&path, fill_color(shape_color));
+            }
             if self.shape_shadow {
                 frame.stroke(&path, preview_shadow_stroke);
             }
@@ -314,15 +369,11 @@ impl<'a, Message: Clone + 'static> canvas::Program<Message, cosmic::Theme, cosmi
         }
 
         if let Some((sx_g, sy_g)) = self.circle_drawing
-            && let Some(pos) = cursor.position_in(bounds)
+            && let Some(snap) = preview_end
         {
+            let (ex, ey) = snap.point;
             let sx = sx_g - self.output_rect.left as f32;
             let sy = sy_g - self.output_rect.top as f32;
-            let mut ex = pos.x;
-            let mut ey = pos.y;
-            if constrain {
-                (ex, ey) = Self::constrain_end(sx, sy, ex, ey);
-            }
             let (min_x, max_x) = if sx < ex { (sx, ex) } else { (ex, sx) };
             let (min_y, max_y) = if sy < ey { (sy, ey) } else { (ey, sy) };
             let cx = (min_x + max_x) * 0.5;
@@ -339,12 +390,34 @@ impl<'a, Message: Clone + 'static> canvas::Program<Message, cosmic::Theme, cosmi
                     b.line_to(Point::new(cx + rx * t.cos(), cy + ry * t.sin()));
                 }
             });
+            if self.shape_fill {
+                frame.fill(&path, fill_color(shape_color));
+            }
             if self.shape_shadow {
                 frame.stroke(&path, preview_shadow_stroke);
             }
             frame.stroke(&path, preview_stroke);
         }
 
+        // Alignment guides across the selection for the end being dragged
+        if let Some(snap) = preview_end
+            && let Some((x, y, w, h)) = self.selection_rect
+        {
+            let guide_stroke = Stroke {
+                style: Color::from(theme.cosmic().accent_color()).into(),
+                width: 1.0,
+                ..Stroke::default()
+            };
+            if let Some(gx) = snap.guide_x {
+                let path = Path::line(Point::new(gx, y), Point::new(gx, y + h));
+                frame.stroke(&path, guide_stroke);
+            }
+            if let Some(gy) = snap.guide_y {
+                let path = Path::line(Point::new(x, gy), Point::new(x + w, gy));
+                frame.stroke(&path, guide_stroke);
+            }
+        }
+
         vec![frame.into_geometry()]
     }
 }
@@ -0,0 +1,86 @@
+//! Stamp overlay drawing functions
+//!
+//! Draws placed stamps and the stamp being placed onto the screenshot preview.
+
+use std::sync::Arc;
+
+use cosmic::iced::Radians;
+use cosmic::iced::advanced::image::{FilterMethod, Handle, Image, Renderer as ImageRenderer};
+use cosmic::iced::core::{Rectangle, Renderer as _};
+
+use crate::domain::{StampAnnotation, stamp_extent};
+use crate::render::stamp::Stamp;
+
+/// Draw all placed stamps
+///
+/// Stamps reuse the display handle of their library entry, so the image is only
+/// uploaded once; stamps whose entry was reloaded get a fresh handle.
+pub fn draw_stamps(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    stamps: &[StampAnnotation],
+    library: &[Stamp],
+    output_offset: (f32, f32),
+) {
+    let (offset_x, offset_y) = output_offset;
+    for stamp in stamps {
+        let handle = library
+            .iter()
+            .find(|s| Arc::ptr_eq(&s.image, &stamp.image))
+            .map(|s| s.handle.clone())
+            .unwrap_or_else(|| {
+                Handle::from_rgba(
+                    stamp.image.width(),
+                    stamp.image.height(),
+                    stamp.image.as_raw().clone(),
+                )
+            });
+        let (x1, y1, x2, y2) = stamp.rect();
+        let bounds = Rectangle {
+            x: x1 - offset_x,
+            y: y1 - offset_y,
+            width: x2 - x1,
+            height: y2 - y1,
+        };
+        draw_stamp_image(renderer, viewport, handle, bounds, 1.0);
+    }
+}
+
+/// Draw a translucent preview of the stamp about to be placed
+pub fn draw_stamp_preview(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    stamp: &Stamp,
+    center: (f32, f32),
+    size: f32,
+    output_offset: (f32, f32),
+) {
+    let (w, h) = stamp_extent(stamp.image.dimensions(), size);
+    let bounds = Rectangle {
+        x: center.0 - output_offset.0 - w / 2.0,
+        y: center.1 - output_offset.1 - h / 2.0,
+        width: w,
+        height: h,
+    };
+    draw_stamp_image(renderer, viewport, stamp.handle.clone(), bounds, 0.6);
+}
+
+fn draw_stamp_image(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    handle: Handle,
+    bounds: Rectangle,
+    opacity: f32,
+) {
+    renderer.with_layer(*viewport, |renderer| {
+        let image = Image {
+            handle,
+            filter_method: FilterMethod::Linear,
+            rotation: Radians(0.0),
+            border_radius: 0.0.into(),
+            opacity,
+            snap: false,
+        };
+        renderer.draw_image(image, bounds, *viewport);
+    });
+}
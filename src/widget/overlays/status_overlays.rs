@@ -2,10 +2,15 @@
 //!
 //! This module contains helper functions for drawing various status overlays:
 //! - QR scanning status indicator
-//! - Detected QR code overlays with labels
+//! - Detected QR code and barcode overlays with outlines and labels
 //! - OCR status indicator  
 //! - OCR text region overlays
+//! - Find-and-redact search matches
 
+use cosmic::iced::advanced::graphics::{
+    Mesh,
+    mesh::{Indexed, Renderer as MeshRenderer},
+};
 use cosmic::iced::core::{
     Background, Border, Rectangle, alignment,
     text::{Renderer as TextRenderer, Text},
@@ -13,12 +18,10 @@ use cosmic::iced::core::{
 use cosmic::iced::{Color, Point, Size};
 
 use crate::capture::ocr::OcrStatus;
+use crate::capture::payload::{ContactField, QrPayload, parse_payload};
+use crate::capture::qr::{DetectedQrCode, QrFormat};
 use crate::fl;
-
-/// Check if a string looks like a URL
-pub fn is_url(s: &str) -> bool {
-    s.starts_with("http://") || s.starts_with("https://") || s.starts_with("www.")
-}
+use crate::render::mesh::build_line_mesh;
 
 /// Draw a status indicator badge with text
 pub fn draw_status_badge(
@@ -154,145 +157,408 @@ pub fn draw_ocr_status_indicator(
     );
 }
 
-/// Draw detected QR code overlays
-#[allow(clippy::too_many_arguments)]
+const QR_FONT_SIZE: f32 = 14.0;
+const QR_PADDING: f32 = 8.0;
+const QR_BUTTON_HEIGHT: f32 = 28.0;
+const QR_BUTTON_FONT_SIZE: f32 = 13.0;
+const QR_BUTTON_GAP: f32 = 6.0;
+
+/// What a button on a detected code's label does
+#[derive(Clone, Debug, PartialEq)]
+pub enum QrAction {
+    /// Open a URI with its default handler
+    Open(String),
+    /// Copy text to the clipboard
+    Copy(String),
+    /// Copy every detected code and close
+    CopyAll(QrFormat),
+}
+
+/// Label of a detected code, laid out within the selection
+pub struct QrLabel {
+    pub background: Rectangle,
+    pub text: String,
+    pub text_bounds: Rectangle,
+    /// Shown with a warning border, for 2FA secrets
+    pub warning: bool,
+    /// Bounds, caption and action of each button
+    pub buttons: Vec<(Rectangle, String, QrAction)>,
+}
+
+fn contact_line(field: ContactField, value: &str) -> String {
+    let value = value.to_string();
+    match field {
+        ContactField::Name => fl!("qr-contact-name", value = value),
+        ContactField::Organization => fl!("qr-contact-organization", value = value),
+        ContactField::Phone => fl!("qr-phone", number = value),
+        ContactField::Email => fl!("qr-email", address = value),
+        ContactField::Address => fl!("qr-contact-address", value = value),
+        ContactField::Url => fl!("qr-contact-website", value = value),
+        ContactField::Note => fl!("qr-contact-note", value = value),
+    }
+}
+
+/// Text, warning flag and buttons for a code's content
+fn describe_payload(content: &str) -> (String, bool, Vec<(String, QrAction)>) {
+    match parse_payload(content) {
+        QrPayload::Url(url) => (
+            content.to_string(),
+            false,
+            vec![(fl!("qr-open"), QrAction::Open(url))],
+        ),
+        QrPayload::Wifi { ssid, password } => {
+            let mut lines = vec![fl!("qr-wifi", ssid = ssid.clone())];
+            let mut buttons = vec![(fl!("qr-copy-network"), QrAction::Copy(ssid))];
+            match password {
+                Some(password) => {
+                    lines.push(fl!("qr-wifi-password", password = password.clone()));
+                    buttons.push((fl!("qr-copy-password"), QrAction::Copy(password)));
+                }
+                None => lines.push(fl!("qr-wifi-no-password")),
+            }
+            (lines.join("\n"), false, buttons)
+        }
+        QrPayload::Otp { account, secret } => (
+            format!(
+                "{}\n{}",
+                fl!("qr-otp", account = account),
+                fl!("qr-otp-warning")
+            ),
+            true,
+            vec![(fl!("qr-copy-secret"), QrAction::Copy(secret))],
+        ),
+        QrPayload::Contact(fields) if !fields.is_empty() => (
+            fields
+                .iter()
+                .map(|(field, value)| contact_line(*field, value))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            false,
+            Vec::new(),
+        ),
+        QrPayload::Mail { uri, address } => (
+            fl!("qr-email", address = address),
+            false,
+            vec![(fl!("qr-email-open"), QrAction::Open(uri))],
+        ),
+        QrPayload::Phone { uri, number } => (
+            fl!("qr-phone", number = number),
+            false,
+            vec![(fl!("qr-phone-open"), QrAction::Open(uri))],
+        ),
+        QrPayload::Geo {
+            uri,
+            latitude,
+            longitude,
+        } => (
+            fl!("qr-location", latitude = latitude, longitude = longitude),
+            false,
+            vec![(fl!("qr-location-open"), QrAction::Open(uri))],
+        ),
+        QrPayload::Contact(_) | QrPayload::Text => (content.to_string(), false, Vec::new()),
+    }
+}
+
+/// Lay out the label of a code centered at `(x, y)`, with its text above a
+/// row of action buttons, kept inside the selection
+///
+/// Shared by drawing and hit testing so buttons are clicked where they are drawn.
+pub fn qr_label(
+    x: f32,
+    y: f32,
+    content: &str,
+    selection_rect: (f32, f32, f32, f32), // (x, y, w, h)
+) -> QrLabel {
+    let (text, warning, actions) = describe_payload(content);
+    lay_out_label(x, y, text, warning, actions, selection_rect)
+}
+
+/// Bar at the bottom of the selection offering to copy all `count` codes,
+/// shown when there are several
+pub fn qr_copy_all_bar(count: usize, selection_rect: (f32, f32, f32, f32)) -> QrLabel {
+    let (sel_x, sel_y, sel_w, sel_h) = selection_rect;
+    let actions = vec![
+        (fl!("qr-copy-all"), QrAction::CopyAll(QrFormat::Lines)),
+        (fl!("qr-copy-all-json"), QrAction::CopyAll(QrFormat::Json)),
+    ];
+    lay_out_label(
+        sel_x + sel_w / 2.0,
+        sel_y + sel_h,
+        fl!("qr-codes-found", count = count),
+        false,
+        actions,
+        selection_rect,
+    )
+}
+
+fn lay_out_label(
+    x: f32,
+    y: f32,
+    text: String,
+    warning: bool,
+    actions: Vec<(String, QrAction)>,
+    selection_rect: (f32, f32, f32, f32),
+) -> QrLabel {
+    let (sel_x, sel_y, sel_w, sel_h) = selection_rect;
+    let padding = QR_PADDING;
+
+    let max_label_width = (sel_w - padding * 4.0).clamp(80.0, 400.0);
+
+    // Estimate number of lines for wrapped text
+    let chars_per_line = (max_label_width / (QR_FONT_SIZE * 0.55)).max(10.0) as usize;
+    let num_lines = text
+        .lines()
+        .map(|line| line.chars().count().div_ceil(chars_per_line).max(1))
+        .sum::<usize>();
+    let num_lines = (num_lines + 1).min(8);
+    let text_height = (num_lines as f32 * QR_FONT_SIZE * 1.3).min(sel_h * 0.6);
+
+    // Buttons flow left to right, wrapping onto further rows, as
+    // (x, row, width) relative to the button area
+    let mut placed = Vec::new();
+    let (mut button_x, mut rows) = (0.0_f32, 0_usize);
+    for (caption, action) in actions {
+        let width = (caption.chars().count() as f32 * QR_BUTTON_FONT_SIZE * 0.6 + padding * 2.0)
+            .min(max_label_width);
+        if rows == 0 || button_x + width > max_label_width {
+            button_x = 0.0;
+            rows += 1;
+        }
+        placed.push((button_x, rows - 1, width, caption, action));
+        button_x += width + QR_BUTTON_GAP;
+    }
+    let buttons_height = rows as f32 * (QR_BUTTON_HEIGHT + QR_BUTTON_GAP);
+
+    let bg_width = max_label_width + padding * 2.0;
+    let bg_height = text_height + buttons_height + padding * 2.0;
+
+    // Position centered on code location, but clamp to selection bounds
+    let label_x = (x - bg_width / 2.0)
+        .max(sel_x + padding)
+        .min(sel_x + sel_w - bg_width - padding);
+    let label_y = (y - bg_height / 2.0)
+        .max(sel_y + padding)
+        .min(sel_y + sel_h - bg_height - padding);
+
+    let text_bounds = Rectangle {
+        x: label_x + padding,
+        y: label_y + padding,
+        width: max_label_width,
+        height: text_height,
+    };
+    let buttons_top = text_bounds.y + text_height + QR_BUTTON_GAP;
+    let buttons = placed
+        .into_iter()
+        .map(|(bx, row, width, caption, action)| {
+            let bounds = Rectangle {
+                x: text_bounds.x + bx,
+                y: buttons_top + row as f32 * (QR_BUTTON_HEIGHT + QR_BUTTON_GAP),
+                width,
+                height: QR_BUTTON_HEIGHT,
+            };
+            (bounds, caption, action)
+        })
+        .collect();
+
+    QrLabel {
+        background: Rectangle {
+            x: label_x,
+            y: label_y,
+            width: bg_width,
+            height: bg_height,
+        },
+        text,
+        text_bounds,
+        warning,
+        buttons,
+    }
+}
+
+/// Draw detected QR code overlays: each code's outline and its label,
+/// numbered when there are several, with a bar to copy them all
 pub fn draw_qr_code_overlays(
     renderer: &mut cosmic::Renderer,
     viewport: &Rectangle,
-    qr_codes: &[(f32, f32, String)],
+    qr_codes: &[(usize, DetectedQrCode)],
+    total_count: usize,
     selection_rect: (f32, f32, f32, f32), // (x, y, w, h)
     accent_color: Color,
     corner_radius: f32,
 ) {
-    let (sel_x, sel_y, sel_w, sel_h) = selection_rect;
-    let button_size = 28.0_f32;
-
     use cosmic::iced::core::Renderer as RendererTrait;
 
-    for (x, y, content) in qr_codes {
-        let font_size = 14.0_f32;
-        let padding = 8.0;
-        let content_is_url = is_url(content);
+    // Outlines first so the labels stay readable on top of them
+    renderer.with_layer(*viewport, |renderer| {
+        // Dark outline keeps the line visible on any background
+        let outline = Color::from_rgba(0.0, 0.0, 0.0, 0.6);
+        for (_, qr) in qr_codes {
+            for (stroke_color, width) in [(outline, 4.0), (accent_color, 2.0)] {
+                for i in 0..qr.corners.len() {
+                    let (a, b) = (qr.corners[i], qr.corners[(i + 1) % qr.corners.len()]);
+                    let (vertices, indices) = build_line_mesh(a, b, stroke_color, width);
+                    renderer.draw_mesh(Mesh::Solid {
+                        buffers: Indexed { vertices, indices },
+                        transformation: cosmic::iced::core::Transformation::IDENTITY,
+                        clip_bounds: *viewport,
+                    });
+                }
+            }
+        }
+    });
 
-        // Calculate max label width based on selection rectangle
-        let button_space = if content_is_url {
-            button_size + padding
+    for (index, qr) in qr_codes {
+        let label = qr_label(qr.center_x, qr.center_y, &qr.content, selection_rect);
+        let tag = if total_count > 1 {
+            format!("{} · {}", index + 1, qr.symbology.label())
         } else {
-            0.0
+            qr.symbology.label().to_string()
         };
-        let max_label_width = (sel_w - padding * 4.0 - button_space).clamp(80.0, 400.0);
+        draw_qr_label(
+            renderer,
+            viewport,
+            &label,
+            Some(&tag),
+            accent_color,
+            corner_radius,
+        );
+    }
 
-        // Estimate number of lines for wrapped text
-        let chars_per_line = (max_label_width / (font_size * 0.55)).max(10.0) as usize;
-        let num_lines = ((content.len() / chars_per_line).max(1) + 1).min(6);
-        let text_height = (num_lines as f32 * font_size * 1.3).min(sel_h * 0.6);
+    if total_count > 1 && !qr_codes.is_empty() {
+        let bar = qr_copy_all_bar(total_count, selection_rect);
+        draw_qr_label(renderer, viewport, &bar, None, accent_color, corner_radius);
+    }
+}
 
-        let bg_width = max_label_width + padding * 2.0 + button_space;
-        let bg_height = text_height.max(button_size) + padding * 2.0;
+fn draw_qr_label(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    label: &QrLabel,
+    tag: Option<&str>,
+    accent_color: Color,
+    corner_radius: f32,
+) {
+    use cosmic::iced::core::Renderer as RendererTrait;
 
-        // Position centered on QR location, but clamp to selection bounds
-        let mut label_x = *x - bg_width / 2.0;
-        let mut label_y = *y - bg_height / 2.0;
+    let padding = QR_PADDING;
+    let bg_rect = label.background;
+    let border_color = if label.warning {
+        Color::from_rgb(0.95, 0.6, 0.1)
+    } else {
+        accent_color
+    };
 
-        label_x = label_x
-            .max(sel_x + padding)
-            .min(sel_x + sel_w - bg_width - padding);
-        label_y = label_y
-            .max(sel_y + padding)
-            .min(sel_y + sel_h - bg_height - padding);
+    renderer.with_layer(*viewport, |renderer| {
+        // Draw background
+        renderer.fill_quad(
+            cosmic::iced::core::renderer::Quad {
+                bounds: bg_rect,
+                border: Border {
+                    radius: corner_radius.into(),
+                    width: 2.0,
+                    color: border_color,
+                },
+                shadow: cosmic::iced::core::Shadow::default(),
+                snap: false,
+            },
+            Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.80)),
+        );
 
-        let bg_rect = Rectangle {
-            x: label_x,
-            y: label_y,
-            width: bg_width,
-            height: bg_height,
+        // Draw text with word wrapping
+        let text = Text {
+            content: label.text.clone(),
+            bounds: label.text_bounds.size(),
+            size: cosmic::iced::Pixels(QR_FONT_SIZE),
+            line_height: cosmic::iced::core::text::LineHeight::Relative(1.3),
+            font: cosmic::iced::Font::default(),
+            align_x: alignment::Horizontal::Left.into(),
+            align_y: alignment::Vertical::Top,
+            shaping: cosmic::iced::core::text::Shaping::Advanced,
+            wrapping: cosmic::iced::core::text::Wrapping::Word,
+            ellipsize: cosmic::iced::core::text::Ellipsize::default(),
         };
 
-        renderer.with_layer(*viewport, |renderer| {
-            // Draw background
+        renderer.fill_text(text, label.text_bounds.position(), Color::WHITE, *viewport);
+
+        // Number and symbology tag straddling the top border
+        if let Some(tag) = tag {
+            let tag_font_size = 11.0_f32;
+            let tag_height = 18.0_f32;
+            let tag_width = tag.chars().count() as f32 * tag_font_size * 0.6 + padding * 1.5;
+            let tag_rect = Rectangle {
+                x: bg_rect.x + padding,
+                y: bg_rect.y - tag_height / 2.0,
+                width: tag_width,
+                height: tag_height,
+            };
             renderer.fill_quad(
                 cosmic::iced::core::renderer::Quad {
-                    bounds: bg_rect,
+                    bounds: tag_rect,
                     border: Border {
-                        radius: corner_radius.into(),
-                        width: 2.0,
+                        radius: (tag_height / 2.0).into(),
+                        ..Default::default()
+                    },
+                    shadow: cosmic::iced::core::Shadow::default(),
+                    snap: false,
+                },
+                Background::Color(border_color),
+            );
+            let tag_text = Text {
+                content: tag.to_string(),
+                bounds: Size::new(tag_width, tag_height),
+                size: cosmic::iced::Pixels(tag_font_size),
+                line_height: cosmic::iced::core::text::LineHeight::default(),
+                font: cosmic::iced::Font::default(),
+                align_x: alignment::Horizontal::Center.into(),
+                align_y: alignment::Vertical::Center,
+                shaping: cosmic::iced::core::text::Shaping::Basic,
+                wrapping: cosmic::iced::core::text::Wrapping::None,
+                ellipsize: cosmic::iced::core::text::Ellipsize::default(),
+            };
+            renderer.fill_text(
+                tag_text,
+                Point::new(tag_rect.center_x(), tag_rect.center_y()),
+                Color::WHITE,
+                *viewport,
+            );
+        }
+
+        // Action buttons below the text
+        for (button_rect, caption, _) in &label.buttons {
+            renderer.fill_quad(
+                cosmic::iced::core::renderer::Quad {
+                    bounds: *button_rect,
+                    border: Border {
+                        radius: (QR_BUTTON_HEIGHT / 4.0).into(),
+                        width: 1.0,
                         color: accent_color,
                     },
                     shadow: cosmic::iced::core::Shadow::default(),
                     snap: false,
                 },
-                Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.80)),
+                Background::Color(accent_color),
             );
 
-            // Draw text with word wrapping
-            let text = Text {
-                content: content.clone(),
-                bounds: Size::new(max_label_width, text_height),
-                size: cosmic::iced::Pixels(font_size),
-                line_height: cosmic::iced::core::text::LineHeight::Relative(1.3),
+            let caption_text = Text {
+                content: caption.clone(),
+                bounds: button_rect.size(),
+                size: cosmic::iced::Pixels(QR_BUTTON_FONT_SIZE),
+                line_height: cosmic::iced::core::text::LineHeight::default(),
                 font: cosmic::iced::Font::default(),
-                align_x: alignment::Horizontal::Left.into(),
-                align_y: alignment::Vertical::Top,
+                align_x: alignment::Horizontal::Center.into(),
+                align_y: alignment::Vertical::Center,
                 shaping: cosmic::iced::core::text::Shaping::Advanced,
-                wrapping: cosmic::iced::core::text::Wrapping::Word,
+                wrapping: cosmic::iced::core::text::Wrapping::None,
                 ellipsize: cosmic::iced::core::text::Ellipsize::default(),
             };
 
             renderer.fill_text(
-                text,
-                Point::new(bg_rect.x + padding, bg_rect.y + padding),
+                caption_text,
+                Point::new(button_rect.center_x(), button_rect.center_y()),
                 Color::WHITE,
                 *viewport,
             );
-
-            // Draw "open URL" button if content is a URL
-            if content_is_url {
-                let button_x = bg_rect.x + bg_width - padding - button_size;
-                let button_y = bg_rect.y + (bg_height - button_size) / 2.0;
-
-                let button_rect = Rectangle {
-                    x: button_x,
-                    y: button_y,
-                    width: button_size,
-                    height: button_size,
-                };
-
-                renderer.fill_quad(
-                    cosmic::iced::core::renderer::Quad {
-                        bounds: button_rect,
-                        border: Border {
-                            radius: (button_size / 4.0).into(),
-                            width: 1.0,
-                            color: accent_color,
-                        },
-                        shadow: cosmic::iced::core::Shadow::default(),
-                        snap: false,
-                    },
-                    Background::Color(accent_color),
-                );
-
-                let icon_text = Text {
-                    content: "🔗".to_string(),
-                    bounds: Size::new(button_size, button_size),
-                    size: cosmic::iced::Pixels(16.0),
-                    line_height: cosmic::iced::core::text::LineHeight::default(),
-                    font: cosmic::iced::Font::default(),
-                    align_x: alignment::Horizontal::Center.into(),
-                    align_y: alignment::Vertical::Center,
-                    shaping: cosmic::iced::core::text::Shaping::Advanced,
-                    wrapping: cosmic::iced::core::text::Wrapping::None,
-                    ellipsize: cosmic::iced::core::text::Ellipsize::default(),
-                };
-
-                renderer.fill_text(
-                    icon_text,
-                    Point::new(button_x + button_size / 2.0, button_y + button_size / 2.0),
-                    Color::WHITE,
-                    *viewport,
-                );
-            }
-        });
-    }
+        }
+    });
 }
 
 /// OCR block color palette
@@ -343,3 +609,71 @@ pub fn draw_ocr_overlays(
         });
     }
 }
+
+/// Draw the selected OCR words, one accent-colored box per line
+pub fn draw_ocr_selection(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    lines: &[(f32, f32, f32, f32)], // (left, top, width, height)
+    accent_color: Color,
+) {
+    use cosmic::iced::core::Renderer as RendererTrait;
+
+    renderer.with_layer(*viewport, |renderer| {
+        for (left, top, width, height) in lines {
+            renderer.fill_quad(
+                cosmic::iced::core::renderer::Quad {
+                    bounds: Rectangle {
+                        x: *left,
+                        y: *top,
+                        width: *width,
+                        height: *height,
+                    },
+                    border: Border {
+                        radius: 2.0.into(),
+                        ..Default::default()
+                    },
+                    shadow: cosmic::iced::core::Shadow::default(),
+                    snap: false,
+                },
+                Background::Color(Color {
+                    a: 0.35,
+                    ..accent_color
+                }),
+            );
+        }
+    });
+}
+
+/// Draw find-and-redact search matches as translucent highlights
+pub fn draw_ocr_search_matches(
+    renderer: &mut cosmic::Renderer,
+    viewport: &Rectangle,
+    matches: &[(f32, f32, f32, f32)], // (left, top, width, height)
+) {
+    use cosmic::iced::core::Renderer as RendererTrait;
+
+    let highlight = Color::from_rgb(1.0, 0.85, 0.0);
+    renderer.with_layer(*viewport, |renderer| {
+        for (left, top, width, height) in matches {
+            renderer.fill_quad(
+                cosmic::iced::core::renderer::Quad {
+                    bounds: Rectangle {
+                        x: *left,
+                        y: *top,
+                        width: *width,
+                        height: *height,
+                    },
+                    border: Border {
+                        radius: 2.0.into(),
+                        width: 2.0,
+                        color: highlight,
+                    },
+                    shadow: cosmic::iced::core::Shadow::default(),
+                    snap: false,
+                },
+                Background::Color(Color::from_rgba(1.0, 0.85, 0.0, 0.3)),
+            );
+        }
+    });
+}
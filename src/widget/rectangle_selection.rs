@@ -69,12 +69,22 @@ pub struct RectangleSelection<'a, Msg> {
     redact_mode: bool,
     /// Whether pixelate drawing mode is active (skip rectangle capturing)
     pixelate_mode: bool,
+    /// Whether blur drawing mode is active (skip rectangle capturing)
+    blur_mode: bool,
     /// Whether circle drawing mode is active (skip rectangle capturing)
     circle_mode: bool,
     /// Whether rectangle-outline drawing mode is active (skip rectangle capturing)
     rect_outline_mode: bool,
     /// Whether magnifier drawing mode is active (skip rectangle capturing)
     magnifier_mode: bool,
+    /// Whether spotlight drawing mode is active (skip rectangle capturing)
+    spotlight_mode: bool,
+    /// Whether the color picker is active (skip rectangle capturing)
+    color_picker_mode: bool,
+    /// Whether measure mode is active (skip rectangle capturing)
+    measure_mode: bool,
+    /// Whether stamp mode is active (skip rectangle capturing)
+    stamp_mode: bool,
     /// Whether any popup or drawer is open (skip rectangle capturing)
     popup_open: bool,
     /// Whether magnifier is enabled
@@ -83,6 +93,8 @@ pub struct RectangleSelection<'a, Msg> {
     is_recording: bool,
     /// Move offset for dragging: (cursor_x - rect_left, cursor_y - rect_top) when move started
     move_offset: Option<(i32, i32)>,
+    /// Re-crop mode: only adjust the existing selection, never start a new one
+    crop_mode: bool,
     _phantom: std::marker::PhantomData<Msg>,
 }
 
@@ -101,9 +113,14 @@ impl<'a, Msg: Clone> RectangleSelection<'a, Msg> {
         arrow_mode: bool,
         redact_mode: bool,
         pixelate_mode: bool,
+        blur_mode: bool,
         circle_mode: bool,
         rect_outline_mode: bool,
         magnifier_mode: bool,
+        spotlight_mode: bool,
+        color_picker_mode: bool,
+        measure_mode: bool,
+        stamp_mode: bool,
         popup_open: bool,
         magnifier_enabled: bool,
         is_recording: bool,
@@ -123,17 +140,30 @@ impl<'a, Msg: Clone> RectangleSelection<'a, Msg> {
             arrow_mode,
             redact_mode,
             pixelate_mode,
+            blur_mode,
             circle_mode,
             rect_outline_mode,
             magnifier_mode,
+            spotlight_mode,
+            color_picker_mode,
+            measure_mode,
+            stamp_mode,
             popup_open,
             magnifier_enabled,
             is_recording,
             move_offset,
+            crop_mode: false,
             _phantom: std::marker::PhantomData,
         }
     }
 
+    /// Keep the current selection: clicks outside it don't start a new one
+    /// and a tiny resize doesn't clear it
+    pub fn crop_mode(mut self, crop_mode: bool) -> Self {
+        self.crop_mode = crop_mode;
+        self
+    }
+
     pub fn translated_inner_rect(&self) -> Rectangle {
         let inner_rect = self.rectangle_selection;
         let inner_rect = Rectangle::new(
@@ -436,13 +466,18 @@ impl<'a, Msg: 'static + Clone> Widget<Msg, cosmic::Theme, cosmic::Renderer>
                     return;
                 }
 
-                // Skip rectangle drawing when arrow, redact, pixelate, shape mode is active, or popup is open
+                // Skip rectangle drawing when arrow, redact, pixelate, blur, shape mode is active, or popup is open
                 if self.arrow_mode
                     || self.redact_mode
                     || self.pixelate_mode
+                    || self.blur_mode
                     || self.circle_mode
                     || self.rect_outline_mode
                     || self.magnifier_mode
+                    || self.spotlight_mode
+                    || self.color_picker_mode
+                    || self.measure_mode
+                    || self.stamp_mode
                     || self.popup_open
                 {
                     return;
@@ -465,6 +500,8 @@ impl<'a, Msg: 'static + Clone> Widget<Msg, cosmic::Theme, cosmic::Renderer>
                                 DragState::Move,
                                 self.rectangle_selection,
                             ));
+                        } else if s == DragState::None && self.crop_mode {
+                            return;
                         } else if s == DragState::None {
                             // New selection: start drawing from current position
                             let mut pos = cursor.position().unwrap_or_default();
@@ -510,7 +547,7 @@ impl<'a, Msg: 'static + Clone> Widget<Msg, cosmic::Theme, cosmic::Renderer>
                             let rect = self.rectangle_selection;
                             let width = (rect.right - rect.left).abs();
                             let height = (rect.bottom - rect.top).abs();
-                            if width > 5 && height > 5 {
+                            if (width > 5 && height > 5) || self.crop_mode {
                                 shell.publish((self.on_rectangle)(DragState::None, rect));
                             } else {
                                 // Clear the selection - just a click, not a drag
@@ -4,8 +4,11 @@
 //! application's message system. The parent component translates these
 //! events to its own message types.
 
-use crate::config::{Container, RedactTool, ShapeColor, ShapeTool, ToolbarPosition};
+use crate::capture::ocr::{OcrLayout, OcrSelection};
+use crate::capture::qr::QrFormat;
+use crate::config::{Container, OcrEngine, RedactTool, ShapeColor, ShapeTool, ToolbarPosition};
 use crate::domain::Choice;
+use crate::session::messages::{ColorEdit, ColorTarget, OcrSearchAction, PresetEdit};
 use cosmic::iced::core::Rectangle;
 use cosmic::widget::segmented_button;
 use wayland_client::protocol::wl_output::WlOutput;
@@ -30,8 +33,12 @@ pub enum AnnotationType {
     Circle,
     Rectangle,
     Magnifier,
+    Spotlight,
+    Measure,
+    Stamp,
     Redact,
     Pixelate,
+    Blur,
 }
 
 /// Annotation drawing events
@@ -43,6 +50,8 @@ pub enum AnnotationEvent {
     Ended(AnnotationType, Point),
     /// Mode toggled for an annotation type
     ModeToggle(AnnotationType),
+    /// Bend the arrow at `index` so its curve passes through the given global point
+    ArrowBend(usize, Point),
     /// Select a magnifier for editing (index into magnifiers, or None to deselect)
     MagnifierSelect(Option<usize>),
     /// Move the magnifier at `index` so its center is at the given global point
@@ -51,9 +60,11 @@ pub enum AnnotationEvent {
     MagnifierResize(usize, f32),
     /// Set the zoom of the magnifier at `index`
     MagnifierSetZoom(usize, f32),
+    /// Shift or Ctrl held, latched until the drawing ends: constrain angles and aspect ratios
+    Constrain(bool),
     /// Clear all shape annotations (arrows, circles, rectangles)
     ClearShapes,
-    /// Clear all redaction annotations (redact, pixelate)
+    /// Clear all redaction annotations (redact, pixelate, blur)
     ClearRedactions,
 }
 
@@ -79,10 +90,34 @@ pub enum DetectionEvent {
     OcrRequested,
     /// OCR copy and close
     OcrCopyAndClose,
+    /// OCR copy with a layout, and close
+    OcrCopyAs(OcrLayout),
+    /// OCR words selected by dragging, or the selection cleared
+    OcrSelect(Option<OcrSelection>),
+    /// Redact sensitive data found by OCR
+    RedactSensitive,
+    /// Find-and-redact search text changed
+    SearchQuery(String),
+    /// Toggle regex matching for find-and-redact
+    SearchRegexToggle,
+    /// Run find-and-redact search
+    SearchSubmit,
+    /// Apply an action to all find-and-redact matches
+    SearchApply(OcrSearchAction),
+    /// OCR language toggled for this capture
+    OcrCaptureLanguageToggle(String),
+    /// OCR languages for this capture reset to the settings
+    OcrCaptureLanguagesReset,
     /// QR detection requested
     QrRequested,
     /// QR copy and close
     QrCopyAndClose,
+    /// Copy one of several QR codes and close
+    QrCopyOneAndClose(usize),
+    /// Copy all QR codes and close
+    QrCopyAllAndClose(QrFormat),
+    /// Copy one field of a QR code, such as a Wi-Fi password
+    QrCopyText(String),
     /// Open URL from QR code
     OpenUrl(String),
 }
@@ -102,6 +137,24 @@ pub enum ToolPopupEvent {
     ShapeColorSet(ShapeColor),
     /// Shape shadow toggled
     ShapeShadowToggle,
+    /// Shape stroke width changed (during drag)
+    ShapeWidthSet(f32),
+    /// Shape stroke width saved (on release)
+    ShapeWidthSave,
+    /// Shape auto-contrast shadow toggled
+    ShapeAutoContrastToggle,
+    /// Shape fill toggled
+    ShapeFillToggle,
+    /// Annotation preset applied or edited
+    PresetEdit(PresetEdit),
+    /// Spotlight dim strength changed
+    SpotlightDimSet(f32),
+    /// Spotlight dim strength saved
+    SpotlightDimSave,
+    /// Spotlight rectangle/ellipse toggled
+    SpotlightEllipseToggle,
+    /// Spotlight blur toggled
+    SpotlightBlurToggle,
     /// Shape mode toggled (for primary shape)
     ShapeModeToggle,
     /// Redact mode toggled (for primary redact/pixelate)
@@ -118,6 +171,12 @@ pub enum ToolPopupEvent {
     PixelationSizeSet(u32),
     /// Pixelation size saved
     PixelationSizeSave,
+    /// Secure pixelation toggled
+    SecurePixelationToggle,
+    /// Blur radius changed
+    BlurRadiusSet(u32),
+    /// Blur radius saved
+    BlurRadiusSave,
     /// Magnifier mode toggled
     MagnifierModeToggle,
     /// Magnifier popup toggled
@@ -130,6 +189,44 @@ pub enum ToolPopupEvent {
     MagnificationSet(f32),
     /// Magnification level saved (on release)
     MagnificationSave,
+    /// Magnifier loupe/callout toggled
+    MagnifierCalloutToggle,
+    /// Callout frame width changed (during drag)
+    CalloutBorderSet(f32),
+    /// Callout frame width saved (on release)
+    CalloutBorderSave,
+    /// Color picker mode toggled
+    ColorPickerModeToggle,
+    /// Copied color format cycled
+    ColorFormatCycle,
+    /// Color picked from the screenshot
+    ColorPicked([u8; 3]),
+    /// Measure mode toggled
+    MeasureModeToggle,
+    /// Measured kind (line/box) cycled
+    MeasureKindCycle,
+    /// Stamp mode toggled
+    StampModeToggle,
+    /// Stamp popup toggled
+    StampPopupToggle,
+    /// Stamp popup opened
+    StampPopupOpen,
+    /// Stamp popup closed
+    StampPopupClose,
+    /// OCR language popup toggled
+    OcrPopupToggle,
+    /// OCR language popup closed
+    OcrPopupClose,
+    /// Stamp selected (by name)
+    StampSelect(String),
+    /// Stamp size changed (during drag)
+    StampSizeSet(f32),
+    /// Stamp size saved (on release)
+    StampSizeSave,
+    /// Stamp directory opened in the file manager
+    StampFolderOpen,
+    /// Stamp library reloaded
+    StampsReload,
     /// Pencil popup toggled
     PencilPopupToggle,
     /// Pencil popup closed
@@ -146,6 +243,8 @@ pub enum ToolPopupEvent {
     PencilThicknessSave,
     /// Clear pencil drawings
     PencilClear,
+    /// Custom color chooser or palette edit for a tool color
+    ColorEdit(ColorTarget, ColorEdit),
 }
 
 /// Settings drawer events
@@ -173,6 +272,22 @@ pub enum SettingsEvent {
     BrowseVideoSaveLocation,
     /// Copy on save toggled
     CopyOnSaveToggle,
+    /// Beautify frame toggled
+    BeautifyToggle,
+    /// Beautify preset selected (by name)
+    BeautifyPresetSelect(String),
+    /// Picked colors become the shape color toggled
+    ColorPickerSetsShapeColorToggle,
+    /// Keeping measurements in saved images toggled
+    MeasureInExportToggle,
+    /// OCR engine selected
+    OcrEngineSelect(OcrEngine),
+    /// Straightening skewed text before OCR toggled
+    OcrDeskewToggle,
+    /// Downloading missing OCR models toggled
+    OcrModelDownloadToggle,
+    /// OCR language toggled in the settings
+    OcrLanguageToggle(String),
     /// Delayed-screenshot delay selected (seconds)
     CaptureDelaySelect(u32),
     /// Settings tab activated (by entity from segmented button)
@@ -208,6 +323,10 @@ pub enum CaptureEvent {
     DelayedCapture,
     /// Cycle the delayed-screenshot delay
     CycleCaptureDelay,
+    /// Strip uniform margins from the selection
+    AutoTrim,
+    /// Toggle re-crop mode
+    ToggleCropMode,
     /// Record selected region
     RecordRegion,
     /// Stop recording
@@ -259,6 +378,10 @@ impl ScreenshotEvent {
         ))
     }
 
+    pub fn arrow_bend(index: usize, x: f32, y: f32) -> Self {
+        Self::Annotation(AnnotationEvent::ArrowBend(index, Point::new(x, y)))
+    }
+
     pub fn circle_start(x: f32, y: f32) -> Self {
         Self::Annotation(AnnotationEvent::Started(
             AnnotationType::Circle,
@@ -305,6 +428,48 @@ impl ScreenshotEvent {
         Self::Annotation(AnnotationEvent::MagnifierSelect(index))
     }
 
+    pub fn spotlight_start(x: f32, y: f32) -> Self {
+        Self::Annotation(AnnotationEvent::Started(
+            AnnotationType::Spotlight,
+            Point::new(x, y),
+        ))
+    }
+
+    pub fn spotlight_end(x: f32, y: f32) -> Self {
+        Self::Annotation(AnnotationEvent::Ended(
+            AnnotationType::Spotlight,
+            Point::new(x, y),
+        ))
+    }
+
+    pub fn measure_start(x: f32, y: f32) -> Self {
+        Self::Annotation(AnnotationEvent::Started(
+            AnnotationType::Measure,
+            Point::new(x, y),
+        ))
+    }
+
+    pub fn measure_end(x: f32, y: f32) -> Self {
+        Self::Annotation(AnnotationEvent::Ended(
+            AnnotationType::Measure,
+            Point::new(x, y),
+        ))
+    }
+
+    pub fn stamp_start(x: f32, y: f32) -> Self {
+        Self::Annotation(AnnotationEvent::Started(
+            AnnotationType::Stamp,
+            Point::new(x, y),
+        ))
+    }
+
+    pub fn stamp_end(x: f32, y: f32) -> Self {
+        Self::Annotation(AnnotationEvent::Ended(
+            AnnotationType::Stamp,
+            Point::new(x, y),
+        ))
+    }
+
     pub fn magnifier_move(index: usize, x: f32, y: f32) -> Self {
         Self::Annotation(AnnotationEvent::MagnifierMove(index, Point::new(x, y)))
     }
@@ -345,6 +510,20 @@ impl ScreenshotEvent {
         ))
     }
 
+    pub fn blur_start(x: f32, y: f32) -> Self {
+        Self::Annotation(AnnotationEvent::Started(
+            AnnotationType::Blur,
+            Point::new(x, y),
+        ))
+    }
+
+    pub fn blur_end(x: f32, y: f32) -> Self {
+        Self::Annotation(AnnotationEvent::Ended(
+            AnnotationType::Blur,
+            Point::new(x, y),
+        ))
+    }
+
     pub fn arrow_mode_toggle() -> Self {
         Self::Annotation(AnnotationEvent::ModeToggle(AnnotationType::Arrow))
     }
@@ -365,6 +544,14 @@ impl ScreenshotEvent {
         Self::Annotation(AnnotationEvent::ModeToggle(AnnotationType::Pixelate))
     }
 
+    pub fn blur_mode_toggle() -> Self {
+        Self::Annotation(AnnotationEvent::ModeToggle(AnnotationType::Blur))
+    }
+
+    pub fn constrain(held: bool) -> Self {
+        Self::Annotation(AnnotationEvent::Constrain(held))
+    }
+
     pub fn clear_shapes() -> Self {
         Self::Annotation(AnnotationEvent::ClearShapes)
     }
@@ -403,6 +590,42 @@ impl ScreenshotEvent {
         Self::Detection(DetectionEvent::OcrCopyAndClose)
     }
 
+    pub fn ocr_copy_as(layout: OcrLayout) -> Self {
+        Self::Detection(DetectionEvent::OcrCopyAs(layout))
+    }
+
+    pub fn ocr_select(selection: Option<OcrSelection>) -> Self {
+        Self::Detection(DetectionEvent::OcrSelect(selection))
+    }
+
+    pub fn redact_sensitive() -> Self {
+        Self::Detection(DetectionEvent::RedactSensitive)
+    }
+
+    pub fn ocr_search_query(query: String) -> Self {
+        Self::Detection(DetectionEvent::SearchQuery(query))
+    }
+
+    pub fn ocr_search_regex_toggle() -> Self {
+        Self::Detection(DetectionEvent::SearchRegexToggle)
+    }
+
+    pub fn ocr_search_submit() -> Self {
+        Self::Detection(DetectionEvent::SearchSubmit)
+    }
+
+    pub fn ocr_search_apply(action: OcrSearchAction) -> Self {
+        Self::Detection(DetectionEvent::SearchApply(action))
+    }
+
+    pub fn ocr_capture_language_toggle(lang: String) -> Self {
+        Self::Detection(DetectionEvent::OcrCaptureLanguageToggle(lang))
+    }
+
+    pub fn ocr_capture_languages_reset() -> Self {
+        Self::Detection(DetectionEvent::OcrCaptureLanguagesReset)
+    }
+
     pub fn qr_requested() -> Self {
         Self::Detection(DetectionEvent::QrRequested)
     }
@@ -411,6 +634,18 @@ impl ScreenshotEvent {
         Self::Detection(DetectionEvent::QrCopyAndClose)
     }
 
+    pub fn qr_copy_one_and_close(index: usize) -> Self {
+        Self::Detection(DetectionEvent::QrCopyOneAndClose(index))
+    }
+
+    pub fn qr_copy_all_and_close(format: QrFormat) -> Self {
+        Self::Detection(DetectionEvent::QrCopyAllAndClose(format))
+    }
+
+    pub fn qr_copy_text(text: String) -> Self {
+        Self::Detection(DetectionEvent::QrCopyText(text))
+    }
+
     pub fn open_url(url: String) -> Self {
         Self::Detection(DetectionEvent::OpenUrl(url))
     }
@@ -440,6 +675,42 @@ impl ScreenshotEvent {
         Self::ToolPopup(ToolPopupEvent::ShapeShadowToggle)
     }
 
+    pub fn shape_width_set(width: f32) -> Self {
+        Self::ToolPopup(ToolPopupEvent::ShapeWidthSet(width))
+    }
+
+    pub fn shape_width_save() -> Self {
+        Self::ToolPopup(ToolPopupEvent::ShapeWidthSave)
+    }
+
+    pub fn shape_auto_contrast_toggle() -> Self {
+        Self::ToolPopup(ToolPopupEvent::ShapeAutoContrastToggle)
+    }
+
+    pub fn shape_fill_toggle() -> Self {
+        Self::ToolPopup(ToolPopupEvent::ShapeFillToggle)
+    }
+
+    pub fn preset_edit(edit: PresetEdit) -> Self {
+        Self::ToolPopup(ToolPopupEvent::PresetEdit(edit))
+    }
+
+    pub fn spotlight_dim_set(dim: f32) -> Self {
+        Self::ToolPopup(ToolPopupEvent::SpotlightDimSet(dim))
+    }
+
+    pub fn spotlight_dim_save() -> Self {
+        Self::ToolPopup(ToolPopupEvent::SpotlightDimSave)
+    }
+
+    pub fn spotlight_ellipse_toggle() -> Self {
+        Self::ToolPopup(ToolPopupEvent::SpotlightEllipseToggle)
+    }
+
+    pub fn spotlight_blur_toggle() -> Self {
+        Self::ToolPopup(ToolPopupEvent::SpotlightBlurToggle)
+    }
+
     pub fn shape_mode_toggle() -> Self {
         Self::ToolPopup(ToolPopupEvent::ShapeModeToggle)
     }
@@ -472,6 +743,18 @@ impl ScreenshotEvent {
         Self::ToolPopup(ToolPopupEvent::PixelationSizeSave)
     }
 
+    pub fn secure_pixelation_toggle() -> Self {
+        Self::ToolPopup(ToolPopupEvent::SecurePixelationToggle)
+    }
+
+    pub fn blur_radius_set(radius: u32) -> Self {
+        Self::ToolPopup(ToolPopupEvent::BlurRadiusSet(radius))
+    }
+
+    pub fn blur_radius_save() -> Self {
+        Self::ToolPopup(ToolPopupEvent::BlurRadiusSave)
+    }
+
     pub fn magnifier_tool_mode_toggle() -> Self {
         Self::ToolPopup(ToolPopupEvent::MagnifierModeToggle)
     }
@@ -496,6 +779,82 @@ impl ScreenshotEvent {
         Self::ToolPopup(ToolPopupEvent::MagnificationSave)
     }
 
+    pub fn magnifier_callout_toggle() -> Self {
+        Self::ToolPopup(ToolPopupEvent::MagnifierCalloutToggle)
+    }
+
+    pub fn callout_border_set(width: f32) -> Self {
+        Self::ToolPopup(ToolPopupEvent::CalloutBorderSet(width))
+    }
+
+    pub fn callout_border_save() -> Self {
+        Self::ToolPopup(ToolPopupEvent::CalloutBorderSave)
+    }
+
+    pub fn color_picker_mode_toggle() -> Self {
+        Self::ToolPopup(ToolPopupEvent::ColorPickerModeToggle)
+    }
+
+    pub fn color_format_cycle() -> Self {
+        Self::ToolPopup(ToolPopupEvent::ColorFormatCycle)
+    }
+
+    pub fn color_picked(rgb: [u8; 3]) -> Self {
+        Self::ToolPopup(ToolPopupEvent::ColorPicked(rgb))
+    }
+
+    pub fn measure_mode_toggle() -> Self {
+        Self::ToolPopup(ToolPopupEvent::MeasureModeToggle)
+    }
+
+    pub fn measure_kind_cycle() -> Self {
+        Self::ToolPopup(ToolPopupEvent::MeasureKindCycle)
+    }
+
+    pub fn stamp_mode_toggle() -> Self {
+        Self::ToolPopup(ToolPopupEvent::StampModeToggle)
+    }
+
+    pub fn stamp_popup_toggle() -> Self {
+        Self::ToolPopup(ToolPopupEvent::StampPopupToggle)
+    }
+
+    pub fn stamp_popup_open() -> Self {
+        Self::ToolPopup(ToolPopupEvent::StampPopupOpen)
+    }
+
+    pub fn stamp_popup_close() -> Self {
+        Self::ToolPopup(ToolPopupEvent::StampPopupClose)
+    }
+
+    pub fn ocr_popup_toggle() -> Self {
+        Self::ToolPopup(ToolPopupEvent::OcrPopupToggle)
+    }
+
+    pub fn ocr_popup_close() -> Self {
+        Self::ToolPopup(ToolPopupEvent::OcrPopupClose)
+    }
+
+    pub fn stamp_select(name: String) -> Self {
+        Self::ToolPopup(ToolPopupEvent::StampSelect(name))
+    }
+
+    pub fn stamp_size_set(size: f32) -> Self {
+        Self::ToolPopup(ToolPopupEvent::StampSizeSet(size))
+    }
+
+    pub fn stamp_size_save() -> Self {
+        Self::ToolPopup(ToolPopupEvent::StampSizeSave)
+    }
+
+    pub fn stamp_folder_open() -> Self {
+        Self::ToolPopup(ToolPopupEvent::StampFolderOpen)
+    }
+
+    pub fn stamps_reload() -> Self {
+        Self::ToolPopup(ToolPopupEvent::StampsReload)
+    }
+
     // Settings events
     pub fn settings_drawer_toggle() -> Self {
         Self::Settings(SettingsEvent::DrawerToggle)
@@ -541,6 +900,37 @@ impl ScreenshotEvent {
         Self::Settings(SettingsEvent::CopyOnSaveToggle)
     }
 
+    pub fn beautify_toggle() -> Self {
+        Self::Settings(SettingsEvent::BeautifyToggle)
+    }
+
+    pub fn beautify_preset_select(name: String) -> Self {
+        Self::Settings(SettingsEvent::BeautifyPresetSelect(name))
+    }
+
+    pub fn color_picker_sets_shape_color_toggle() -> Self {
+        Self::Settings(SettingsEvent::ColorPickerSetsShapeColorToggle)
+    }
+
+    pub fn measure_in_export_toggle() -> Self {
+        Self::Settings(SettingsEvent::MeasureInExportToggle)
+    }
+
+    pub fn ocr_engine_select(engine: OcrEngine) -> Self {
+        Self::Settings(SettingsEvent::OcrEngineSelect(engine))
+    }
+
+    pub fn ocr_deskew_toggle() -> Self {
+        Self::Settings(SettingsEvent::OcrDeskewToggle)
+    }
+    pub fn ocr_model_download_toggle() -> Self {
+        Self::Settings(SettingsEvent::OcrModelDownloadToggle)
+    }
+
+    pub fn ocr_language_toggle(lang: String) -> Self {
+        Self::Settings(SettingsEvent::OcrLanguageToggle(lang))
+    }
+
     pub fn capture_delay_select(secs: u32) -> Self {
         Self::Settings(SettingsEvent::CaptureDelaySelect(secs))
     }
@@ -602,6 +992,14 @@ impl ScreenshotEvent {
         Self::Capture(CaptureEvent::CycleCaptureDelay)
     }
 
+    pub fn auto_trim() -> Self {
+        Self::Capture(CaptureEvent::AutoTrim)
+    }
+
+    pub fn toggle_crop_mode() -> Self {
+        Self::Capture(CaptureEvent::ToggleCropMode)
+    }
+
     pub fn record_region() -> Self {
         Self::Capture(CaptureEvent::RecordRegion)
     }
@@ -650,6 +1048,10 @@ impl ScreenshotEvent {
         Self::ToolPopup(ToolPopupEvent::PencilClear)
     }
 
+    pub fn color_edit(target: ColorTarget, edit: ColorEdit) -> Self {
+        Self::ToolPopup(ToolPopupEvent::ColorEdit(target, edit))
+    }
+
     pub fn cancel() -> Self {
         Self::Capture(CaptureEvent::Cancel)
     }
@@ -694,6 +1096,24 @@ impl ScreenshotEvent {
             Self::Annotation(AnnotationEvent::Ended(AnnotationType::Magnifier, p)) => {
                 Msg::magnifier_end(p.x, p.y)
             }
+            Self::Annotation(AnnotationEvent::Started(AnnotationType::Spotlight, p)) => {
+                Msg::spotlight_start(p.x, p.y)
+            }
+            Self::Annotation(AnnotationEvent::Ended(AnnotationType::Spotlight, p)) => {
+                Msg::spotlight_end(p.x, p.y)
+            }
+            Self::Annotation(AnnotationEvent::Started(AnnotationType::Measure, p)) => {
+                Msg::measure_start(p.x, p.y)
+            }
+            Self::Annotation(AnnotationEvent::Ended(AnnotationType::Measure, p)) => {
+                Msg::measure_end(p.x, p.y)
+            }
+            Self::Annotation(AnnotationEvent::Started(AnnotationType::Stamp, p)) => {
+                Msg::stamp_start(p.x, p.y)
+            }
+            Self::Annotation(AnnotationEvent::Ended(AnnotationType::Stamp, p)) => {
+                Msg::stamp_end(p.x, p.y)
+            }
             Self::Annotation(AnnotationEvent::Started(AnnotationType::Redact, p)) => {
                 Msg::redact_start(p.x, p.y)
             }
@@ -706,6 +1126,12 @@ impl ScreenshotEvent {
             Self::Annotation(AnnotationEvent::Ended(AnnotationType::Pixelate, p)) => {
                 Msg::pixelate_end(p.x, p.y)
             }
+            Self::Annotation(AnnotationEvent::Started(AnnotationType::Blur, p)) => {
+                Msg::blur_start(p.x, p.y)
+            }
+            Self::Annotation(AnnotationEvent::Ended(AnnotationType::Blur, p)) => {
+                Msg::blur_end(p.x, p.y)
+            }
             Self::Annotation(AnnotationEvent::ModeToggle(AnnotationType::Arrow)) => {
                 Msg::arrow_mode_toggle()
             }
@@ -718,9 +1144,21 @@ impl ScreenshotEvent {
             Self::Annotation(AnnotationEvent::ModeToggle(AnnotationType::Magnifier)) => {
                 Msg::magnifier_mode_toggle()
             }
+            Self::Annotation(AnnotationEvent::ModeToggle(AnnotationType::Spotlight)) => {
+                Msg::spotlight_mode_toggle()
+            }
+            Self::Annotation(AnnotationEvent::ModeToggle(AnnotationType::Measure)) => {
+                Msg::measure_mode_toggle()
+            }
+            Self::Annotation(AnnotationEvent::ModeToggle(AnnotationType::Stamp)) => {
+                Msg::stamp_mode_toggle()
+            }
             Self::Annotation(AnnotationEvent::MagnifierSelect(index)) => {
                 Msg::magnifier_select(index)
             }
+            Self::Annotation(AnnotationEvent::ArrowBend(index, p)) => {
+                Msg::arrow_bend(index, p.x, p.y)
+            }
             Self::Annotation(AnnotationEvent::MagnifierMove(index, p)) => {
                 Msg::magnifier_move(index, p.x, p.y)
             }
@@ -736,6 +1174,10 @@ impl ScreenshotEvent {
             Self::Annotation(AnnotationEvent::ModeToggle(AnnotationType::Pixelate)) => {
                 Msg::pixelate_mode_toggle()
             }
+            Self::Annotation(AnnotationEvent::ModeToggle(AnnotationType::Blur)) => {
+                Msg::blur_mode_toggle()
+            }
+            Self::Annotation(AnnotationEvent::Constrain(held)) => Msg::constrain(held),
             Self::Annotation(AnnotationEvent::ClearShapes) => Msg::clear_shapes(),
             Self::Annotation(AnnotationEvent::ClearRedactions) => Msg::clear_redactions(),
 
@@ -749,8 +1191,30 @@ impl ScreenshotEvent {
             // Detection events
             Self::Detection(DetectionEvent::OcrRequested) => Msg::ocr_requested(),
             Self::Detection(DetectionEvent::OcrCopyAndClose) => Msg::ocr_copy_and_close(),
+            Self::Detection(DetectionEvent::OcrCopyAs(layout)) => {
+                Msg::ocr_copy_as_and_close(layout)
+            }
+            Self::Detection(DetectionEvent::OcrSelect(selection)) => Msg::ocr_select(selection),
+            Self::Detection(DetectionEvent::RedactSensitive) => Msg::ocr_redact_sensitive(),
+            Self::Detection(DetectionEvent::SearchQuery(query)) => Msg::ocr_search_query(query),
+            Self::Detection(DetectionEvent::SearchRegexToggle) => Msg::ocr_search_regex_toggle(),
+            Self::Detection(DetectionEvent::SearchSubmit) => Msg::ocr_search_submit(),
+            Self::Detection(DetectionEvent::SearchApply(action)) => Msg::ocr_search_apply(action),
+            Self::Detection(DetectionEvent::OcrCaptureLanguageToggle(lang)) => {
+                Msg::ocr_toggle_capture_language(lang)
+            }
+            Self::Detection(DetectionEvent::OcrCaptureLanguagesReset) => {
+                Msg::ocr_reset_capture_languages()
+            }
             Self::Detection(DetectionEvent::QrRequested) => Msg::qr_requested(),
             Self::Detection(DetectionEvent::QrCopyAndClose) => Msg::qr_copy_and_close(),
+            Self::Detection(DetectionEvent::QrCopyOneAndClose(index)) => {
+                Msg::qr_copy_one_and_close(index)
+            }
+            Self::Detection(DetectionEvent::QrCopyAllAndClose(format)) => {
+                Msg::qr_copy_all_and_close(format)
+            }
+            Self::Detection(DetectionEvent::QrCopyText(text)) => Msg::qr_copy_text(text),
             Self::Detection(DetectionEvent::OpenUrl(url)) => Msg::open_url(url),
 
             // Tool popup events
@@ -760,6 +1224,19 @@ impl ScreenshotEvent {
             Self::ToolPopup(ToolPopupEvent::ShapeToolSet(tool)) => Msg::set_shape_tool(tool),
             Self::ToolPopup(ToolPopupEvent::ShapeColorSet(color)) => Msg::set_shape_color(color),
             Self::ToolPopup(ToolPopupEvent::ShapeShadowToggle) => Msg::toggle_shape_shadow(),
+            Self::ToolPopup(ToolPopupEvent::ShapeWidthSet(width)) => Msg::set_shape_width(width),
+            Self::ToolPopup(ToolPopupEvent::ShapeWidthSave) => Msg::save_shape_width(),
+            Self::ToolPopup(ToolPopupEvent::ShapeAutoContrastToggle) => {
+                Msg::toggle_shape_auto_contrast()
+            }
+            Self::ToolPopup(ToolPopupEvent::ShapeFillToggle) => Msg::toggle_shape_fill(),
+            Self::ToolPopup(ToolPopupEvent::PresetEdit(edit)) => Msg::edit_preset(edit),
+            Self::ToolPopup(ToolPopupEvent::SpotlightDimSet(dim)) => Msg::set_spotlight_dim(dim),
+            Self::ToolPopup(ToolPopupEvent::SpotlightDimSave) => Msg::save_spotlight_dim(),
+            Self::ToolPopup(ToolPopupEvent::SpotlightEllipseToggle) => {
+                Msg::toggle_spotlight_ellipse()
+            }
+            Self::ToolPopup(ToolPopupEvent::SpotlightBlurToggle) => Msg::toggle_spotlight_blur(),
             Self::ToolPopup(ToolPopupEvent::ShapeModeToggle) => Msg::shape_mode_toggle(),
             Self::ToolPopup(ToolPopupEvent::RedactModeToggle) => Msg::redact_tool_mode_toggle(),
             Self::ToolPopup(ToolPopupEvent::RedactPopupToggle) => Msg::toggle_redact_popup(),
@@ -772,6 +1249,11 @@ impl ScreenshotEvent {
             Self::ToolPopup(ToolPopupEvent::PixelationSizeSave) => {
                 Msg::save_pixelation_block_size()
             }
+            Self::ToolPopup(ToolPopupEvent::SecurePixelationToggle) => {
+                Msg::toggle_secure_pixelation()
+            }
+            Self::ToolPopup(ToolPopupEvent::BlurRadiusSet(radius)) => Msg::set_blur_radius(radius),
+            Self::ToolPopup(ToolPopupEvent::BlurRadiusSave) => Msg::save_blur_radius(),
             Self::ToolPopup(ToolPopupEvent::MagnifierModeToggle) => {
                 Msg::magnifier_tool_mode_toggle()
             }
@@ -780,6 +1262,31 @@ impl ScreenshotEvent {
             Self::ToolPopup(ToolPopupEvent::MagnifierPopupClose) => Msg::close_magnifier_popup(),
             Self::ToolPopup(ToolPopupEvent::MagnificationSet(value)) => Msg::set_magnification(value),
             Self::ToolPopup(ToolPopupEvent::MagnificationSave) => Msg::save_magnification(),
+            Self::ToolPopup(ToolPopupEvent::MagnifierCalloutToggle) => {
+                Msg::toggle_magnifier_callout()
+            }
+            Self::ToolPopup(ToolPopupEvent::CalloutBorderSet(width)) => {
+                Msg::set_callout_border(width)
+            }
+            Self::ToolPopup(ToolPopupEvent::CalloutBorderSave) => Msg::save_callout_border(),
+            Self::ToolPopup(ToolPopupEvent::ColorPickerModeToggle) => {
+                Msg::color_picker_mode_toggle()
+            }
+            Self::ToolPopup(ToolPopupEvent::ColorFormatCycle) => Msg::cycle_color_format(),
+            Self::ToolPopup(ToolPopupEvent::ColorPicked(rgb)) => Msg::color_picked(rgb),
+            Self::ToolPopup(ToolPopupEvent::MeasureModeToggle) => Msg::measure_mode_toggle(),
+            Self::ToolPopup(ToolPopupEvent::MeasureKindCycle) => Msg::cycle_measure_kind(),
+            Self::ToolPopup(ToolPopupEvent::StampModeToggle) => Msg::stamp_mode_toggle(),
+            Self::ToolPopup(ToolPopupEvent::StampPopupToggle) => Msg::toggle_stamp_popup(),
+            Self::ToolPopup(ToolPopupEvent::StampPopupOpen) => Msg::open_stamp_popup(),
+            Self::ToolPopup(ToolPopupEvent::StampPopupClose) => Msg::close_stamp_popup(),
+            Self::ToolPopup(ToolPopupEvent::OcrPopupToggle) => Msg::toggle_ocr_popup(),
+            Self::ToolPopup(ToolPopupEvent::OcrPopupClose) => Msg::close_ocr_popup(),
+            Self::ToolPopup(ToolPopupEvent::StampSelect(name)) => Msg::select_stamp(name),
+            Self::ToolPopup(ToolPopupEvent::StampSizeSet(size)) => Msg::set_stamp_size(size),
+            Self::ToolPopup(ToolPopupEvent::StampSizeSave) => Msg::save_stamp_size(),
+            Self::ToolPopup(ToolPopupEvent::StampFolderOpen) => Msg::open_stamp_folder(),
+            Self::ToolPopup(ToolPopupEvent::StampsReload) => Msg::reload_stamps(),
             Self::ToolPopup(ToolPopupEvent::PencilPopupToggle) => Msg::toggle_pencil_popup(),
             Self::ToolPopup(ToolPopupEvent::PencilPopupClose) => Msg::close_pencil_popup(),
             Self::ToolPopup(ToolPopupEvent::PencilColorSet(color)) => Msg::set_pencil_color(color),
@@ -794,6 +1301,9 @@ impl ScreenshotEvent {
             }
             Self::ToolPopup(ToolPopupEvent::PencilThicknessSave) => Msg::save_pencil_thickness(),
             Self::ToolPopup(ToolPopupEvent::PencilClear) => Msg::clear_pencil_drawings(),
+            Self::ToolPopup(ToolPopupEvent::ColorEdit(target, edit)) => {
+                Msg::edit_color(target, edit)
+            }
 
             // Settings events
             Self::Settings(SettingsEvent::DrawerToggle) => Msg::toggle_settings_drawer(),
@@ -817,6 +1327,22 @@ impl ScreenshotEvent {
                 Msg::browse_video_save_location()
             }
             Self::Settings(SettingsEvent::CopyOnSaveToggle) => Msg::toggle_copy_on_save(),
+            Self::Settings(SettingsEvent::BeautifyToggle) => Msg::toggle_beautify(),
+            Self::Settings(SettingsEvent::BeautifyPresetSelect(name)) => {
+                Msg::set_beautify_preset(name)
+            }
+            Self::Settings(SettingsEvent::ColorPickerSetsShapeColorToggle) => {
+                Msg::toggle_color_picker_sets_shape_color()
+            }
+            Self::Settings(SettingsEvent::MeasureInExportToggle) => Msg::toggle_measure_in_export(),
+            Self::Settings(SettingsEvent::OcrEngineSelect(engine)) => Msg::set_ocr_engine(engine),
+            Self::Settings(SettingsEvent::OcrDeskewToggle) => Msg::toggle_ocr_deskew(),
+            Self::Settings(SettingsEvent::OcrModelDownloadToggle) => {
+                Msg::toggle_ocr_model_download()
+            }
+            Self::Settings(SettingsEvent::OcrLanguageToggle(lang)) => {
+                Msg::toggle_ocr_language(lang)
+            }
             Self::Settings(SettingsEvent::CaptureDelaySelect(secs)) => Msg::set_capture_delay(secs),
             Self::Settings(SettingsEvent::TabActivated(entity)) => {
                 Msg::settings_tab_activated(entity)
@@ -846,6 +1372,8 @@ impl ScreenshotEvent {
             Self::Capture(CaptureEvent::SaveToPictures) => Msg::save_to_pictures(),
             Self::Capture(CaptureEvent::DelayedCapture) => Msg::delayed_capture(),
             Self::Capture(CaptureEvent::CycleCaptureDelay) => Msg::cycle_capture_delay(),
+            Self::Capture(CaptureEvent::AutoTrim) => Msg::auto_trim(),
+            Self::Capture(CaptureEvent::ToggleCropMode) => Msg::toggle_crop_mode(),
             Self::Capture(CaptureEvent::RecordRegion) => Msg::record_region(),
             Self::Capture(CaptureEvent::StopRecording) => Msg::stop_recording(),
             Self::Capture(CaptureEvent::ToggleRecordingAnnotation) => {
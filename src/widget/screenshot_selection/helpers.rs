@@ -4,19 +4,21 @@
 //! to improve readability and testability.
 
 use crate::{
-    capture::{ocr::OcrTextOverlay, qr::DetectedQrCode},
+    capture::{ocr::OcrTextOverlay, qr::DetectedQrCode, search::OcrMatch},
     domain::{Choice, Rect},
 };
 
-/// Filter QR codes for a specific output
+/// Filter QR codes and barcodes for a specific output, each with its index
+/// among all detected codes
 pub fn filter_qr_codes_for_output(
     qr_codes: &[DetectedQrCode],
     output_name: &str,
-) -> Vec<(f32, f32, String)> {
+) -> Vec<(usize, DetectedQrCode)> {
     qr_codes
         .iter()
-        .filter(|qr| qr.output_name == output_name)
-        .map(|qr| (qr.center_x, qr.center_y, qr.content.clone()))
+        .enumerate()
+        .filter(|(_, qr)| qr.output_name == output_name)
+        .map(|(index, qr)| (index, qr.clone()))
         .collect()
 }
 
@@ -32,6 +34,18 @@ pub fn filter_ocr_overlays_for_output(
         .collect()
 }
 
+/// Filter find-and-redact matches for a specific output
+pub fn filter_search_matches_for_output(
+    matches: &[OcrMatch],
+    output_name: &str,
+) -> Vec<(f32, f32, f32, f32)> {
+    matches
+        .iter()
+        .filter(|m| m.output_name == output_name)
+        .map(|m| (m.left, m.top, m.width, m.height))
+        .collect()
+}
+
 /// Calculate selection rectangle relative to an output
 ///
 /// Returns (x, y, width, height) in output-local coordinates, or None if no selection.
@@ -16,12 +16,15 @@ use cosmic::{
 use cosmic::widget::segmented_button;
 
 use crate::{
+    annotations::constrain::arrow_end,
     capture::image::ScreenshotImage,
-    config::{ShapeTool, ToolbarPosition},
+    capture::ocr::{OcrSelection, reading_order, word_at},
+    capture::qr::DetectedQrCode,
+    config::{OcrEngine, ShapeTool, ToolbarPosition},
     core::app::OutputState,
-    domain::{Choice, DragState, Rect},
+    domain::{Choice, DragState, MagnifierAnnotation, Rect, stamp_size_from_drag},
     session::{
-        messages::Msg,
+        messages::{ColorTarget, Msg, OcrSearchAction},
         state::{AnnotationState, DetectionState, UiState},
     },
 };
@@ -29,30 +32,42 @@ use crate::{
 use super::events::ScreenshotEvent;
 use super::helpers::{
     calculate_selection_rect, create_output_rect, filter_ocr_overlays_for_output,
-    filter_qr_codes_for_output,
+    filter_qr_codes_for_output, filter_search_matches_for_output,
 };
+use crate::render::contrast::Backdrop;
 use crate::render::mesh::{draw_arrow_preview, draw_arrows};
 use crate::widget::{
-    drawing::{draw_inactive_overlay_with_hint, draw_selection_frame_with_handles},
+    drawing::{
+        draw_dark_overlay_around_regions, draw_inactive_overlay_with_hint,
+        draw_selection_frame_with_handles,
+    },
+    magnifier::{draw_color_picker, pixel_at},
     output_selection::OutputSelection,
     overlays::{
         ShapesOverlay,
+        arrow_overlays::{BEND_HANDLE_RADIUS, draw_arrow_handles},
         magnifier_overlays::{
-            draw_magnifier_handles, draw_magnifier_preview, draw_magnifiers,
+            draw_callout_handles, draw_magnifier_callout, draw_magnifier_handles,
+            draw_magnifier_preview, draw_magnifiers,
         },
+        measure_overlays::{draw_measure_preview, draw_measurements},
         redact_overlays::{
-            PixelationSource, draw_pixelation_preview, draw_redaction_preview,
-            draw_redactions_and_pixelations,
+            PixelationSource, draw_blur_preview, draw_pixelation_preview, draw_redaction_preview,
+            draw_redactions_and_pixelations, draw_spotlight_blur,
         },
+        stamp_overlays::{draw_stamp_preview, draw_stamps},
         status_overlays::{
-            draw_ocr_overlays, draw_ocr_status_indicator, draw_qr_code_overlays,
-            draw_qr_scanning_indicator,
+            QrAction, QrLabel, draw_ocr_overlays, draw_ocr_search_matches, draw_ocr_selection,
+            draw_ocr_status_indicator, draw_qr_code_overlays, draw_qr_scanning_indicator,
+            qr_copy_all_bar, qr_label,
         },
     },
     rectangle_selection::RectangleSelection,
     settings_drawer::build_settings_drawer,
     tool_button::{
-        build_magnifier_popup, build_pencil_popup, build_redact_popup, build_shape_popup,
+        build_color_section, build_magnifier_popup, build_ocr_copy_popup, build_ocr_popup,
+        build_pencil_popup, build_preset_section, build_redact_popup, build_shape_popup,
+        build_stamp_popup, build_text_search_section,
     },
     toolbar::build_toolbar,
 };
@@ -62,13 +77,18 @@ const MAGNIFIER_RING_GRAB: f32 = 12.0;
 /// Zoom change per mouse wheel notch when scrolling over a selected magnifier
 const MAGNIFIER_SCROLL_STEP: f32 = 0.5;
 
-/// Transient (per-frame-persistent) drag state for editing a magnifier.
+/// Transient (per-frame-persistent) drag state for editing a magnifier,
+/// bending an arrow or selecting OCR text.
 ///
 /// Lives in the widget's `Tree` state so it survives view rebuilds without
 /// round-tripping through the application message loop.
 #[derive(Default)]
 struct MagnifierDragState {
     drag: Option<MagnifierDrag>,
+    /// Index (into `arrows`) of the arrow whose bend handle is being dragged
+    arrow_bend: Option<usize>,
+    /// Dragging across OCR words to select them
+    ocr_selecting: bool,
 }
 
 enum MagnifierDrag {
@@ -90,11 +110,6 @@ pub struct OutputContext {
     pub has_mouse_entered: bool,
 }
 
-/// Check if a string looks like a URL
-fn is_url(s: &str) -> bool {
-    s.starts_with("http://") || s.starts_with("https://") || s.starts_with("www.")
-}
-
 /// The refactored ScreenshotSelection widget
 ///
 /// Instead of 96+ individual fields and callbacks, this uses:
@@ -138,8 +153,9 @@ where
     selection_rect: Option<(f32, f32, f32, f32)>,
     image_scale: f32,
     show_qr_overlays: bool,
-    qr_codes_for_output: Vec<(f32, f32, String)>,
+    qr_codes_for_output: Vec<(usize, DetectedQrCode)>,
     ocr_overlays_for_output: Vec<(f32, f32, f32, f32, i32)>,
+    search_matches_for_output: Vec<(f32, f32, f32, f32)>,
 
     // Pre-built child elements
     bg_element: Element<'a, Msg>,
@@ -151,6 +167,8 @@ where
     redact_popup_element: Option<Element<'a, Msg>>,
     pencil_popup_element: Option<Element<'a, Msg>>,
     magnifier_popup_element: Option<Element<'a, Msg>>,
+    stamp_popup_element: Option<Element<'a, Msg>>,
+    ocr_popup_element: Option<Element<'a, Msg>>,
 }
 
 impl<'a, E> ScreenshotSelectionWidget<'a, E>
@@ -190,6 +208,8 @@ where
         let qr_codes_for_output = filter_qr_codes_for_output(&detection.qr_codes, &output.name);
         let ocr_overlays_for_output =
             filter_ocr_overlays_for_output(&detection.ocr_overlays, &output.name);
+        let search_matches_for_output =
+            filter_search_matches_for_output(&detection.search_matches, &output.name);
 
         // Calculate selection rectangle relative to this output
         let selection_rect = calculate_selection_rect(&choice, output_rect, output.logical_size);
@@ -225,17 +245,25 @@ where
                 annotations.arrow_mode,
                 annotations.redact_mode,
                 annotations.pixelate_mode,
+                annotations.blur_mode,
                 annotations.circle_mode,
                 annotations.rect_outline_mode,
                 annotations.magnifier_mode,
+                annotations.spotlight_mode,
+                annotations.color_picker_mode,
+                annotations.measure_mode,
+                annotations.stamp_mode,
                 ui.shape_popup_open
                     || ui.redact_popup_open
                     || ui.magnifier_popup_open
+                    || ui.stamp_popup_open
+                    || ui.ocr_popup_open
                     || ui.settings_drawer_open,
                 ui.magnifier_enabled,
                 ui.is_recording,
                 move_offset,
             )
+            .crop_mode(ui.crop_mode)
             .into(),
             Choice::Output(None) => {
                 // Only show focus highlight after mouse has entered an output
@@ -272,11 +300,13 @@ where
             ShapeTool::Arrow => annotations.arrow_mode,
             ShapeTool::Circle => annotations.circle_mode,
             ShapeTool::Rectangle => annotations.rect_outline_mode,
+            ShapeTool::Spotlight => annotations.spotlight_mode,
         };
 
         let redact_mode_active = match ui.primary_redact_tool {
             crate::config::RedactTool::Redact => annotations.redact_mode,
             crate::config::RedactTool::Pixelate => annotations.pixelate_mode,
+            crate::config::RedactTool::Blur => annotations.blur_mode,
         };
 
         let magnifier_mode_active = annotations.magnifier_mode;
@@ -297,6 +327,13 @@ where
             ui.redact_popup_open,
             magnifier_mode_active,
             ui.magnifier_popup_open,
+            annotations.color_picker_mode,
+            ui.color_picker_format,
+            annotations.measure_mode,
+            ui.measure_kind,
+            annotations.stamp_mode,
+            ui.stamp_popup_open,
+            ui.ocr_popup_open,
             space_s,
             space_xs,
             space_xxs,
@@ -309,6 +346,9 @@ where
             on_event(ScreenshotEvent::delayed_capture()),
             on_event(ScreenshotEvent::cycle_capture_delay()),
             ui.capture_delay_secs,
+            on_event(ScreenshotEvent::auto_trim()),
+            on_event(ScreenshotEvent::toggle_crop_mode()),
+            ui.crop_mode,
             on_event(ScreenshotEvent::record_region()),
             on_event(ScreenshotEvent::stop_recording()),
             on_event(ScreenshotEvent::toggle_recording_annotation()),
@@ -319,7 +359,15 @@ where
             on_event(ScreenshotEvent::redact_popup_toggle()),
             on_event(ScreenshotEvent::magnifier_tool_mode_toggle()),
             on_event(ScreenshotEvent::magnifier_popup_toggle()),
+            on_event(ScreenshotEvent::color_picker_mode_toggle()),
+            on_event(ScreenshotEvent::color_format_cycle()),
+            on_event(ScreenshotEvent::measure_mode_toggle()),
+            on_event(ScreenshotEvent::measure_kind_cycle()),
+            on_event(ScreenshotEvent::stamp_mode_toggle()),
+            on_event(ScreenshotEvent::stamp_popup_toggle()),
             on_event(ScreenshotEvent::ocr_requested()),
+            (ui.ocr_engine == OcrEngine::Tesseract || has_ocr_text)
+                .then(|| on_event(ScreenshotEvent::ocr_popup_toggle())),
             on_event(ScreenshotEvent::ocr_copy_and_close()),
             on_event(ScreenshotEvent::qr_requested()),
             on_event(ScreenshotEvent::qr_copy_and_close()),
@@ -333,7 +381,7 @@ where
             ui.settings_drawer_open || ui.shape_popup_open || ui.redact_popup_open,
             ui.toolbar_unhovered_opacity,
             output_ctx.output_count,
-            ui.tesseract_available,
+            ui.ocr_available(),
             ui.is_video_mode,
             ui.is_recording,
             ui.recording_annotation_mode,
@@ -360,6 +408,7 @@ where
         let on_event_c2 = on_event.clone();
         let on_event_r1 = on_event.clone();
         let on_event_r2 = on_event.clone();
+        let on_event_k = on_event.clone();
         let shapes_element = {
             let program = ShapesOverlay {
                 selection_rect,
@@ -370,6 +419,12 @@ where
                 rect_outline_mode: annotations.rect_outline_mode,
                 circle_drawing: annotations.circle_drawing,
                 rect_outline_drawing: annotations.rect_outline_drawing,
+                arrow_drawing: annotations.arrow_drawing,
+                constrain: annotations.constrain,
+                guides: annotations.guides(&choice),
+                on_constrain: Some(Box::new(move |held| {
+                    on_event_k(ScreenshotEvent::constrain(held))
+                })),
                 on_circle_start: Some(Box::new(move |x, y| {
                     on_event_c1(ScreenshotEvent::circle_start(x, y))
                 })),
@@ -383,7 +438,14 @@ where
                     on_event_r2(ScreenshotEvent::rectangle_end(x, y))
                 })),
                 shape_color: ui.shape_color,
+                shape_width: ui.shape_width,
+                shape_fill: ui.shape_fill,
                 shape_shadow: ui.shape_shadow,
+                backdrop: Backdrop {
+                    image: &screenshot_image.rgba,
+                    scale: image_scale,
+                    origin: (output_rect.left as f32, output_rect.top as f32),
+                },
             };
 
             canvas::Canvas::new(program)
@@ -417,6 +479,31 @@ where
                 on_event(ScreenshotEvent::browse_video_save_location()),
                 ui.copy_to_clipboard_on_save,
                 on_event(ScreenshotEvent::copy_on_save_toggle()),
+                ui.color_picker_sets_shape_color,
+                on_event(ScreenshotEvent::color_picker_sets_shape_color_toggle()),
+                ui.measure_in_export,
+                on_event(ScreenshotEvent::measure_in_export_toggle()),
+                ui.ocr_engine,
+                on_event(ScreenshotEvent::ocr_engine_select(OcrEngine::Tesseract)),
+                on_event(ScreenshotEvent::ocr_engine_select(OcrEngine::Builtin)),
+                ui.ocr_deskew,
+                on_event(ScreenshotEvent::ocr_deskew_toggle()),
+                ui.ocr_model_download,
+                on_event(ScreenshotEvent::ocr_model_download_toggle()),
+                &ui.ocr_installed_languages,
+                &ui.ocr_languages,
+                {
+                    let on_event = on_event.clone();
+                    move |lang| on_event(ScreenshotEvent::ocr_language_toggle(lang))
+                },
+                ui.beautify_enabled,
+                on_event(ScreenshotEvent::beautify_toggle()),
+                &ui.beautify_presets,
+                &ui.beautify_preset,
+                {
+                    let on_event = on_event.clone();
+                    move |name| on_event(ScreenshotEvent::beautify_preset_select(name))
+                },
                 ui.capture_delay_secs,
                 {
                     let on_event = on_event.clone();
@@ -457,17 +544,51 @@ where
 
         // Build shape_popup_element
         let on_event_color = on_event.clone();
+        let on_event_spotlight_dim = on_event.clone();
+        let on_event_shape_width = on_event.clone();
         let shape_popup_element = if ui.shape_popup_open {
+            let on_event_edit = on_event.clone();
+            let on_event_preset = on_event.clone();
             Some(build_shape_popup(
                 ui.primary_shape_tool,
-                ui.shape_color,
+                build_color_section(
+                    ui.shape_color,
+                    &ui.recent_colors,
+                    &ui.color_palettes,
+                    &ui.color_hex_input,
+                    ui.color_hsv,
+                    &move |c| on_event_color(ScreenshotEvent::shape_color_set(c)),
+                    move |edit| {
+                        on_event_edit(ScreenshotEvent::color_edit(ColorTarget::Shape, edit))
+                    },
+                    space_xs,
+                ),
+                build_preset_section(
+                    &ui.annotation_presets,
+                    move |edit| on_event_preset(ScreenshotEvent::preset_edit(edit)),
+                    space_xs,
+                ),
+                ui.shape_width,
                 ui.shape_shadow,
+                ui.shape_auto_contrast,
+                ui.shape_fill,
+                ui.spotlight_dim,
+                ui.spotlight_ellipse,
+                ui.spotlight_blur,
                 has_any_annotations,
                 on_event(ScreenshotEvent::shape_tool_set(ShapeTool::Arrow)),
                 on_event(ScreenshotEvent::shape_tool_set(ShapeTool::Circle)),
                 on_event(ScreenshotEvent::shape_tool_set(ShapeTool::Rectangle)),
-                &move |c| on_event_color(ScreenshotEvent::shape_color_set(c)),
+                on_event(ScreenshotEvent::shape_tool_set(ShapeTool::Spotlight)),
+                move |width| on_event_shape_width(ScreenshotEvent::shape_width_set(width)),
+                on_event(ScreenshotEvent::shape_width_save()),
                 on_event(ScreenshotEvent::shape_shadow_toggle()),
+                on_event(ScreenshotEvent::shape_auto_contrast_toggle()),
+                on_event(ScreenshotEvent::shape_fill_toggle()),
+                move |dim| on_event_spotlight_dim(ScreenshotEvent::spotlight_dim_set(dim)),
+                on_event(ScreenshotEvent::spotlight_dim_save()),
+                on_event(ScreenshotEvent::spotlight_ellipse_toggle()),
+                on_event(ScreenshotEvent::spotlight_blur_toggle()),
                 on_event(ScreenshotEvent::clear_shapes()),
                 space_s,
                 space_xs,
@@ -478,19 +599,49 @@ where
 
         // Build redact_popup_element
         let on_event_size = on_event.clone();
+        let on_event_blur_radius = on_event.clone();
         let redact_popup_element = if ui.redact_popup_open {
             Some(build_redact_popup(
                 ui.primary_redact_tool,
                 has_any_redactions,
                 ui.pixelation_block_size,
+                ui.secure_pixelation,
+                ui.blur_radius,
                 on_event(ScreenshotEvent::redact_tool_set(
                     crate::config::RedactTool::Redact,
                 )),
                 on_event(ScreenshotEvent::redact_tool_set(
                     crate::config::RedactTool::Pixelate,
                 )),
+                on_event(ScreenshotEvent::redact_tool_set(
+                    crate::config::RedactTool::Blur,
+                )),
                 move |size| on_event_size(ScreenshotEvent::pixelation_size_set(size)),
                 on_event(ScreenshotEvent::pixelation_size_save()),
+                on_event(ScreenshotEvent::secure_pixelation_toggle()),
+                move |radius| on_event_blur_radius(ScreenshotEvent::blur_radius_set(radius)),
+                on_event(ScreenshotEvent::blur_radius_save()),
+                ui.ocr_available()
+                    .then(|| on_event(ScreenshotEvent::redact_sensitive())),
+                ui.ocr_available().then(|| {
+                    let on_event_query = on_event.clone();
+                    build_text_search_section(
+                        &ui.ocr_search_query,
+                        ui.ocr_search_regex,
+                        detection.search_matches.len(),
+                        detection.search_invalid,
+                        move |query| on_event_query(ScreenshotEvent::ocr_search_query(query)),
+                        on_event(ScreenshotEvent::ocr_search_submit()),
+                        on_event(ScreenshotEvent::ocr_search_regex_toggle()),
+                        on_event(ScreenshotEvent::ocr_search_apply(OcrSearchAction::Redact)),
+                        on_event(ScreenshotEvent::ocr_search_apply(OcrSearchAction::Pixelate)),
+                        on_event(ScreenshotEvent::ocr_search_apply(
+                            OcrSearchAction::Highlight,
+                        )),
+                        space_s,
+                        space_xs,
+                    )
+                }),
                 on_event(ScreenshotEvent::clear_redactions()),
                 space_s,
                 space_xs,
@@ -501,12 +652,40 @@ where
 
         // Build magnifier_popup_element
         let on_event_magnification = on_event.clone();
+        let on_event_callout_border = on_event.clone();
         let magnifier_popup_element = if ui.magnifier_popup_open {
             Some(build_magnifier_popup(
                 ui.magnifier_magnification,
+                ui.magnifier_callout,
+                ui.magnifier_callout_border,
                 has_any_annotations,
                 move |v| on_event_magnification(ScreenshotEvent::magnification_set(v)),
                 on_event(ScreenshotEvent::magnification_save()),
+                on_event(ScreenshotEvent::magnifier_callout_toggle()),
+                move |v| on_event_callout_border(ScreenshotEvent::callout_border_set(v)),
+                on_event(ScreenshotEvent::callout_border_save()),
+                on_event(ScreenshotEvent::clear_shapes()),
+                space_s,
+                space_xs,
+            ))
+        } else {
+            None
+        };
+
+        // Build stamp_popup_element
+        let on_event_stamp_select = on_event.clone();
+        let on_event_stamp_size = on_event.clone();
+        let stamp_popup_element = if ui.stamp_popup_open {
+            Some(build_stamp_popup(
+                &ui.stamp_library,
+                ui.selected_stamp().map(|stamp| stamp.name.as_str()),
+                ui.stamp_size,
+                has_any_annotations,
+                move |name| on_event_stamp_select(ScreenshotEvent::stamp_select(name)),
+                move |size| on_event_stamp_size(ScreenshotEvent::stamp_size_set(size)),
+                on_event(ScreenshotEvent::stamp_size_save()),
+                on_event(ScreenshotEvent::stamp_folder_open()),
+                on_event(ScreenshotEvent::stamps_reload()),
                 on_event(ScreenshotEvent::clear_shapes()),
                 space_s,
                 space_xs,
@@ -515,6 +694,32 @@ where
             None
         };
 
+        // Build ocr_popup_element: copy layouts once text is recognized,
+        // otherwise the languages for this capture
+        let on_event_ocr_language = on_event.clone();
+        let on_event_ocr_copy = on_event.clone();
+        let ocr_popup_element = if ui.ocr_popup_open && has_ocr_text {
+            Some(build_ocr_copy_popup(
+                move |layout| on_event_ocr_copy(ScreenshotEvent::ocr_copy_as(layout)),
+                space_s,
+            ))
+        } else if ui.ocr_popup_open {
+            Some(build_ocr_popup(
+                &ui.ocr_installed_languages,
+                ui.effective_ocr_languages(),
+                ui.ocr_capture_languages.is_some(),
+                move |lang| {
+                    on_event_ocr_language(ScreenshotEvent::ocr_capture_language_toggle(lang))
+                },
+                on_event(ScreenshotEvent::ocr_capture_languages_reset()),
+                on_event(ScreenshotEvent::ocr_requested()),
+                space_s,
+                space_xs,
+            ))
+        } else {
+            None
+        };
+
         // Build pencil_popup_element (only shown during recording)
         let on_event_pencil_color = on_event.clone();
         let on_event_pencil_duration = on_event.clone();
@@ -524,12 +729,23 @@ where
             // For now, we'll pass false since we don't have direct access to strokes here
             // The clear button will be enabled when there are strokes
             let has_pencil_annotations = true; // Always enable clear button during recording
+            let on_event_edit = on_event.clone();
             Some(build_pencil_popup(
-                ui.pencil_color,
+                build_color_section(
+                    ui.pencil_color,
+                    &ui.recent_colors,
+                    &ui.color_palettes,
+                    &ui.color_hex_input,
+                    ui.color_hsv,
+                    &move |c| on_event_pencil_color(ScreenshotEvent::pencil_color_set(c)),
+                    move |edit| {
+                        on_event_edit(ScreenshotEvent::color_edit(ColorTarget::Pencil, edit))
+                    },
+                    space_xs,
+                ),
                 ui.pencil_fade_duration,
                 ui.pencil_thickness,
                 has_pencil_annotations,
-                &move |c| on_event_pencil_color(ScreenshotEvent::pencil_color_set(c)),
                 move |d| on_event_pencil_duration(ScreenshotEvent::pencil_fade_duration_set(d)),
                 on_event(ScreenshotEvent::pencil_fade_duration_save()),
                 move |t| on_event_pencil_thickness(ScreenshotEvent::pencil_thickness_set(t)),
@@ -565,6 +781,7 @@ where
             show_qr_overlays,
             qr_codes_for_output,
             ocr_overlays_for_output,
+            search_matches_for_output,
             bg_element,
             fg_element,
             menu_element,
@@ -574,6 +791,8 @@ where
             redact_popup_element,
             pencil_popup_element,
             magnifier_popup_element,
+            stamp_popup_element,
+            ocr_popup_element,
         }
     }
 
@@ -603,17 +822,68 @@ where
         self.annotations.pixelate_mode
     }
 
+    fn is_blur_mode(&self) -> bool {
+        self.annotations.blur_mode
+    }
+
     fn is_magnifier_mode(&self) -> bool {
         self.annotations.magnifier_mode
     }
 
+    fn is_spotlight_mode(&self) -> bool {
+        self.annotations.spotlight_mode
+    }
+
+    fn is_color_picker_mode(&self) -> bool {
+        self.annotations.color_picker_mode
+    }
+
+    fn is_measure_mode(&self) -> bool {
+        self.annotations.measure_mode
+    }
+
+    fn is_stamp_mode(&self) -> bool {
+        self.annotations.stamp_mode
+    }
+
     fn is_any_drawing_mode(&self) -> bool {
         self.is_arrow_mode()
             || self.is_circle_mode()
             || self.is_rectangle_mode()
             || self.is_redact_mode()
             || self.is_pixelate_mode()
+            || self.is_blur_mode()
             || self.is_magnifier_mode()
+            || self.is_spotlight_mode()
+            || self.is_color_picker_mode()
+            || self.is_measure_mode()
+            || self.is_stamp_mode()
+    }
+
+    /// Labels of the codes on this output, each with its index among all
+    /// detected codes, then the copy-all bar when there are several
+    fn qr_labels(&self) -> Vec<(Option<usize>, QrLabel)> {
+        let Some(selection_rect) = self.selection_rect.filter(|_| self.show_qr_overlays) else {
+            return Vec::new();
+        };
+        let mut labels: Vec<(Option<usize>, QrLabel)> = self
+            .qr_codes_for_output
+            .iter()
+            .map(|(index, qr)| {
+                let label = qr_label(qr.center_x, qr.center_y, &qr.content, selection_rect);
+                (Some(*index), label)
+            })
+            .collect();
+        let count = self.detection.qr_codes.len();
+        if count > 1 && !labels.is_empty() {
+            labels.push((None, qr_copy_all_bar(count, selection_rect)));
+        }
+        labels
+    }
+
+    /// Whether clicking a code's label picks it, as when several are detected
+    fn picks_qr_codes(&self) -> bool {
+        self.detection.qr_codes.len() > 1 && !self.is_any_drawing_mode()
     }
 }
 
@@ -648,6 +918,12 @@ where
         if let Some(ref popup) = self.magnifier_popup_element {
             children.push(Tree::new(popup));
         }
+        if let Some(ref popup) = self.stamp_popup_element {
+            children.push(Tree::new(popup));
+        }
+        if let Some(ref popup) = self.ocr_popup_element {
+            children.push(Tree::new(popup));
+        }
         children
     }
 
@@ -673,6 +949,12 @@ where
         if let Some(ref mut popup) = self.magnifier_popup_element {
             elements.push(popup);
         }
+        if let Some(ref mut popup) = self.stamp_popup_element {
+            elements.push(popup);
+        }
+        if let Some(ref mut popup) = self.ocr_popup_element {
+            elements.push(popup);
+        }
         tree.diff_children(&mut elements);
     }
 
@@ -1025,6 +1307,145 @@ where
             nodes.push(popup_node);
         }
 
+        // Layout stamp popup if present
+        if let Some(ref mut popup) = self.stamp_popup_element {
+            let mut child_idx = 4;
+            if self.settings_drawer_element.is_some() {
+                child_idx += 1;
+            }
+            if self.shape_popup_element.is_some() {
+                child_idx += 1;
+            }
+            if self.redact_popup_element.is_some() {
+                child_idx += 1;
+            }
+            if self.pencil_popup_element.is_some() {
+                child_idx += 1;
+            }
+            if self.magnifier_popup_element.is_some() {
+                child_idx += 1;
+            }
+            let mut popup_node =
+                popup
+                    .as_widget_mut()
+                    .layout(&mut children[child_idx], renderer, limits);
+            let popup_bounds = popup_node.bounds();
+            let popup_margin = 4.0_f32;
+            let stamp_btn_fraction = 0.72_f32;
+
+            let popup_pos = match self.ui.toolbar_position {
+                ToolbarPosition::Bottom => {
+                    let btn_x = menu_pos.x + menu_bounds.width * stamp_btn_fraction;
+                    Point {
+                        x: (btn_x - popup_bounds.width / 2.0)
+                            .max(margin)
+                            .min(limits.max().width - popup_bounds.width - margin),
+                        y: menu_pos.y - popup_bounds.height - popup_margin,
+                    }
+                }
+                ToolbarPosition::Top => {
+                    let btn_x = menu_pos.x + menu_bounds.width * stamp_btn_fraction;
+                    Point {
+                        x: (btn_x - popup_bounds.width / 2.0)
+                            .max(margin)
+                            .min(limits.max().width - popup_bounds.width - margin),
+                        y: menu_pos.y + menu_bounds.height + popup_margin,
+                    }
+                }
+                ToolbarPosition::Left => {
+                    let btn_y = menu_pos.y + menu_bounds.height * stamp_btn_fraction;
+                    Point {
+                        x: menu_pos.x + menu_bounds.width + popup_margin,
+                        y: (btn_y - popup_bounds.height / 2.0)
+                            .max(margin)
+                            .min(limits.max().height - popup_bounds.height - margin),
+                    }
+                }
+                ToolbarPosition::Right => {
+                    let btn_y = menu_pos.y + menu_bounds.height * stamp_btn_fraction;
+                    Point {
+                        x: menu_pos.x - popup_bounds.width - popup_margin,
+                        y: (btn_y - popup_bounds.height / 2.0)
+                            .max(margin)
+                            .min(limits.max().height - popup_bounds.height - margin),
+                    }
+                }
+            };
+            popup_node = popup_node.move_to(popup_pos);
+            nodes.push(popup_node);
+        }
+
+        // Layout OCR popup if present
+        if let Some(ref mut popup) = self.ocr_popup_element {
+            let mut child_idx = 4;
+            if self.settings_drawer_element.is_some() {
+                child_idx += 1;
+            }
+            if self.shape_popup_element.is_some() {
+                child_idx += 1;
+            }
+            if self.redact_popup_element.is_some() {
+                child_idx += 1;
+            }
+            if self.pencil_popup_element.is_some() {
+                child_idx += 1;
+            }
+            if self.magnifier_popup_element.is_some() {
+                child_idx += 1;
+            }
+            if self.stamp_popup_element.is_some() {
+                child_idx += 1;
+            }
+            let mut popup_node =
+                popup
+                    .as_widget_mut()
+                    .layout(&mut children[child_idx], renderer, limits);
+            let popup_bounds = popup_node.bounds();
+            let popup_margin = 4.0_f32;
+            let ocr_btn_fraction = 0.80_f32;
+
+            let popup_pos = match self.ui.toolbar_position {
+                ToolbarPosition::Bottom => {
+                    let btn_x = menu_pos.x + menu_bounds.width * ocr_btn_fraction;
+                    Point {
+                        x: (btn_x - popup_bounds.width / 2.0)
+                            .max(margin)
+                            .min(limits.max().width - popup_bounds.width - margin),
+                        y: menu_pos.y - popup_bounds.height - popup_margin,
+                    }
+                }
+                ToolbarPosition::Top => {
+                    let btn_x = menu_pos.x + menu_bounds.width * ocr_btn_fraction;
+                    Point {
+                        x: (btn_x - popup_bounds.width / 2.0)
+                            .max(margin)
+                            .min(limits.max().width - popup_bounds.width - margin),
+                        y: menu_pos.y + menu_bounds.height + popup_margin,
+                    }
+                }
+                ToolbarPosition::Left => {
+                    let btn_y = menu_pos.y + menu_bounds.height * ocr_btn_fraction;
+                    Point {
+                        x: menu_pos.x + menu_bounds.width + popup_margin,
+                        y: (btn_y - popup_bounds.height / 2.0)
+                            .max(margin)
+                            .min(limits.max().height - popup_bounds.height - margin),
+                    }
+                }
+                ToolbarPosition::Right => {
+                    let btn_y = menu_pos.y + menu_bounds.height * ocr_btn_fraction;
+                    Point {
+                        x: menu_pos.x - popup_bounds.width - popup_margin,
+                        y: (btn_y - popup_bounds.height / 2.0)
+                            .max(margin)
+                            .min(limits.max().height - popup_bounds.height - margin),
+                    }
+                }
+            };
+            popup_node = popup_node.move_to(popup_pos);
+            nodes.push(popup_node);
+        }
+
         layout::Node::with_children(
             limits.resolve(Length::Fill, Length::Fill, Size::ZERO),
             nodes,
@@ -1079,7 +1500,7 @@ where
 
         // Note: fg_element is always drawn after annotations (selection handles on top)
 
-        // Draw redactions and pixelations
+        // Draw redactions, pixelations and blurs
         let output_offset = (self.output_rect.left as f32, self.output_rect.top as f32);
         let pixelation_source = PixelationSource::Screenshot {
             image: &self.screenshot_image.rgba,
@@ -1105,6 +1526,22 @@ where
                 (cursor_pos.x, cursor_pos.y),
                 output_offset,
                 self.ui.pixelation_block_size,
+                self.annotations.pixelate_noise_seed,
+                &pixelation_source,
+            );
+        }
+
+        // Draw blur preview
+        if let Some((start_x, start_y)) = self.annotations.blur_drawing
+            && let Some(cursor_pos) = cursor.position()
+        {
+            draw_blur_preview(
+                renderer,
+                viewport,
+                (start_x, start_y),
+                (cursor_pos.x, cursor_pos.y),
+                output_offset,
+                self.ui.blur_radius,
                 &pixelation_source,
             );
         }
@@ -1122,6 +1559,53 @@ where
             );
         }
 
+        // Draw spotlights (dim, and maybe blur, everything in the selection
+        // outside them), combining their settings like the export does
+        if let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect {
+            let to_local = |x1: f32, y1: f32, x2: f32, y2: f32| {
+                cosmic::iced::Rectangle::new(
+                    Point::new(x1.min(x2) - output_offset.0, y1.min(y2) - output_offset.1),
+                    Size::new((x2 - x1).abs(), (y2 - y1).abs()),
+                )
+            };
+            let mut holes: Vec<_> = self
+                .annotations
+                .spotlights
+                .iter()
+                .map(|s| (to_local(s.x, s.y, s.x2, s.y2), s.ellipse))
+                .collect();
+            let mut dim = self
+                .annotations
+                .spotlights
+                .iter()
+                .map(|s| s.dim)
+                .reduce(f32::max);
+            let mut blur = self.annotations.spotlights.iter().any(|s| s.blur);
+            if let Some((start_x, start_y)) = self.annotations.spotlight_drawing
+                && let Some(cursor_pos) = cursor.position()
+            {
+                holes.push((
+                    to_local(
+                        start_x,
+                        start_y,
+                        cursor_pos.x + output_offset.0,
+                        cursor_pos.y + output_offset.1,
+                    ),
+                    self.ui.spotlight_ellipse,
+                ));
+                dim = Some(dim.map_or(self.ui.spotlight_dim, |d| d.max(self.ui.spotlight_dim)));
+                blur |= self.ui.spotlight_blur;
+            }
+            if let Some(dim) = dim {
+                let outer =
+                    cosmic::iced::Rectangle::new(Point::new(sel_x, sel_y), Size::new(sel_w, sel_h));
+                if blur {
+                    draw_spotlight_blur(renderer, viewport, outer, &holes, &pixelation_source);
+                }
+                draw_dark_overlay_around_regions(renderer, outer, &holes, dim);
+            }
+        }
+
         // Draw shapes canvas overlay
         if let Some((i, (layout, child))) = children_iter.next() {
             renderer.with_layer(layout.bounds(), |renderer| {
@@ -1133,7 +1617,18 @@ where
         }
 
         // Draw arrows
-        draw_arrows(renderer, viewport, &self.annotations.arrows, output_offset);
+        let backdrop = Backdrop {
+            image: &self.screenshot_image.rgba,
+            scale: self.image_scale,
+            origin: output_offset,
+        };
+        draw_arrows(
+            renderer,
+            viewport,
+            &self.annotations.arrows,
+            output_offset,
+            &backdrop,
+        );
 
         // Draw arrow preview
         if let Some((start_x, start_y)) = self.annotations.arrow_drawing
@@ -1143,7 +1638,20 @@ where
                 start_x - self.output_rect.left as f32,
                 start_y - self.output_rect.top as f32,
             );
-            let local_end = (cursor_pos.x, cursor_pos.y);
+            let end = arrow_end(
+                (start_x, start_y),
+                (
+                    cursor_pos.x + self.output_rect.left as f32,
+                    cursor_pos.y + self.output_rect.top as f32,
+                ),
+                self.annotations.constrain,
+                &self.annotations.guides(&self.choice),
+            )
+            .point;
+            let local_end = (
+                end.0 - self.output_rect.left as f32,
+                end.1 - self.output_rect.top as f32,
+            );
             let shape_color: cosmic::iced::Color = self.ui.shape_color.into();
             draw_arrow_preview(
                 renderer,
@@ -1151,10 +1659,23 @@ where
                 local_start,
                 local_end,
                 shape_color,
+                self.ui.shape_width,
                 self.ui.shape_shadow,
             );
         }
 
+        // Draw bend handles on existing arrows (when the tool is active)
+        if self.is_arrow_mode() && self.annotations.arrow_drawing.is_none() {
+            let accent: cosmic::iced::Color = theme.cosmic().accent_color().into();
+            draw_arrow_handles(
+                renderer,
+                viewport,
+                &self.annotations.arrows,
+                output_offset,
+                accent,
+            );
+        }
+
         // Draw completed magnifier annotations (zoomed loupes).
         // The background screenshot is drawn to fill the bg child's layout bounds;
         // anchor the zoom to that exact rect so the loupe center stays aligned
@@ -1176,6 +1697,35 @@ where
         // Draw magnifier preview
         if let Some((start_x, start_y)) = self.annotations.magnifier_drawing
             && let Some(cursor_pos) = cursor.position()
+            && self.ui.magnifier_callout
+        {
+            // Place the preview callout exactly as it will be placed on release
+            let (off_x, off_y) = output_offset;
+            let mut preview = MagnifierAnnotation {
+                start_x,
+                start_y,
+                end_x: cursor_pos.x + off_x,
+                end_y: cursor_pos.y + off_y,
+                magnification: self.ui.magnifier_magnification,
+                color: self.ui.shape_color,
+                shadow: self.ui.shape_shadow,
+                callout: None,
+                callout_border: self.ui.magnifier_callout_border,
+            };
+            preview.place_callout(
+                self.selection_rect
+                    .map(|(x, y, w, h)| (x + off_x, y + off_y, x + w + off_x, y + h + off_y)),
+            );
+            draw_magnifier_callout(
+                renderer,
+                viewport,
+                &preview,
+                output_offset,
+                &self.screenshot_image.handle,
+                magnifier_base_rect,
+            );
+        } else if let Some((start_x, start_y)) = self.annotations.magnifier_drawing
+            && let Some(cursor_pos) = cursor.position()
         {
             draw_magnifier_preview(
                 renderer,
@@ -1198,27 +1748,97 @@ where
         {
             let accent: cosmic::iced::Color = theme.cosmic().accent_color().into();
             let (cx, cy) = m.center();
-            draw_magnifier_handles(
-                renderer,
-                viewport,
-                cx - self.output_rect.left as f32,
-                cy - self.output_rect.top as f32,
-                m.radius(),
-                accent,
-            );
+            if let Some((x1, y1, x2, y2)) = m.callout_rect() {
+                let bounds = cosmic::iced::Rectangle::new(
+                    Point::new(
+                        x1 - self.output_rect.left as f32,
+                        y1 - self.output_rect.top as f32,
+                    ),
+                    Size::new(x2 - x1, y2 - y1),
+                );
+                draw_callout_handles(renderer, viewport, bounds, accent);
+            } else {
+                draw_magnifier_handles(
+                    renderer,
+                    viewport,
+                    cx - self.output_rect.left as f32,
+                    cy - self.output_rect.top as f32,
+                    m.radius(),
+                    accent,
+                );
+            }
         }
 
-        // Draw fg_element (selection UI above annotations)
-        if let Some((i, (layout, child))) = fg_info {
-            renderer.with_layer(layout.bounds(), |renderer| {
-                let tree = &tree.children[i];
-                child
-                    .as_widget()
-                    .draw(tree, renderer, theme, style, layout, cursor, viewport);
-            });
+        // Draw stamps, with a preview of the one being placed under the cursor
+        draw_stamps(
+            renderer,
+            viewport,
+            &self.annotations.stamps,
+            &self.ui.stamp_library,
+            output_offset,
+        );
+        if self.is_stamp_mode()
+            && let Some(stamp) = self.ui.selected_stamp()
+            && let Some(cursor_pos) = cursor.position()
+            && let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect
+            && (self.annotations.stamp_drawing.is_some()
+                || cosmic::iced::Rectangle::new(Point::new(sel_x, sel_y), Size::new(sel_w, sel_h))
+                    .contains(cursor_pos))
+        {
+            let cursor_global = (
+                cursor_pos.x + output_offset.0,
+                cursor_pos.y + output_offset.1,
+            );
+            let (center, size) = match self.annotations.stamp_drawing {
+                Some(center) => (
+                    center,
+                    stamp_size_from_drag(center, cursor_global, self.ui.stamp_size),
+                ),
+                None => (cursor_global, self.ui.stamp_size),
+            };
+            draw_stamp_preview(renderer, viewport, stamp, center, size, output_offset);
         }
 
-        let cosmic_theme = theme.cosmic();
+        // Draw measurements, labelled with this output's physical scale
+        draw_measurements(
+            renderer,
+            viewport,
+            &self.annotations.measurements,
+            output_offset,
+            self.image_scale,
+        );
+        if let Some(start) = self.annotations.measure_drawing
+            && let Some(cursor_pos) = cursor.position()
+            && let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect
+        {
+            // Clamped like the end point on release, so the preview reads the same
+            let end = (
+                cursor_pos.x.clamp(sel_x, sel_x + sel_w),
+                cursor_pos.y.clamp(sel_y, sel_y + sel_h),
+            );
+            draw_measure_preview(
+                renderer,
+                viewport,
+                self.ui.measure_kind,
+                start,
+                end,
+                output_offset,
+                self.ui.shape_color,
+                self.image_scale,
+            );
+        }
+
+        // Draw fg_element (selection UI above annotations)
+        if let Some((i, (layout, child))) = fg_info {
+            renderer.with_layer(layout.bounds(), |renderer| {
+                let tree = &tree.children[i];
+                child
+                    .as_widget()
+                    .draw(tree, renderer, theme, style, layout, cursor, viewport);
+            });
+        }
+
+        let cosmic_theme = theme.cosmic();
         let accent_color: cosmic::iced::Color = cosmic_theme.accent_color().into();
         let corner_radius: f32 = cosmic_theme.corner_radii.radius_s[0];
 
@@ -1233,6 +1853,7 @@ where
                     renderer,
                     viewport,
                     &self.qr_codes_for_output,
+                    self.detection.qr_codes.len(),
                     (sel_x, sel_y, sel_w, sel_h),
                     accent_color,
                     corner_radius,
@@ -1253,6 +1874,17 @@ where
         // Draw OCR overlays
         if self.show_qr_overlays {
             draw_ocr_overlays(renderer, viewport, &self.ocr_overlays_for_output);
+            draw_ocr_search_matches(renderer, viewport, &self.search_matches_for_output);
+            if let Some(selection) = &self.detection.ocr_selection
+                && selection.output_name == self.output.name
+            {
+                draw_ocr_selection(
+                    renderer,
+                    viewport,
+                    &selection.line_boxes(&self.detection.ocr_overlays),
+                    accent_color,
+                );
+            }
         }
 
         // Draw selection frame (hide while recording)
@@ -1270,6 +1902,24 @@ where
             }
         }
 
+        // Draw color picker loupe next to the cursor (inside the selection only)
+        if self.is_color_picker_mode()
+            && !self.ui.is_recording
+            && let Some(cursor_pos) = cursor.position()
+            && let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect
+            && cosmic::iced::Rectangle::new(Point::new(sel_x, sel_y), Size::new(sel_w, sel_h))
+                .contains(cursor_pos)
+        {
+            draw_color_picker(
+                renderer,
+                viewport,
+                &self.screenshot_image.rgba,
+                self.image_scale,
+                cursor_pos,
+                accent_color,
+            );
+        }
+
         // Draw menu
         let _ = children_iter;
         if let Some((i, (layout, child))) = children_iter.next() {
@@ -1415,6 +2065,81 @@ where
                 });
             }
         }
+
+        // Draw stamp popup
+        if let Some(ref popup) = self.stamp_popup_element {
+            let layout_children: Vec<_> = layout.children().collect();
+            let mut popup_idx = 4;
+            if self.settings_drawer_element.is_some() {
+                popup_idx += 1;
+            }
+            if self.shape_popup_element.is_some() {
+                popup_idx += 1;
+            }
+            if self.redact_popup_element.is_some() {
+                popup_idx += 1;
+            }
+            if self.pencil_popup_element.is_some() {
+                popup_idx += 1;
+            }
+            if self.magnifier_popup_element.is_some() {
+                popup_idx += 1;
+            }
+            if layout_children.len() > popup_idx {
+                let popup_layout = layout_children[popup_idx];
+                renderer.with_layer(popup_layout.bounds(), |renderer| {
+                    let popup_tree = &tree.children[popup_idx];
+                    popup.as_widget().draw(
+                        popup_tree,
+                        renderer,
+                        theme,
+                        style,
+                        popup_layout,
+                        cursor,
+                        viewport,
+                    );
+                });
+            }
+        }
+
+        // Draw OCR popup
+        if let Some(ref popup) = self.ocr_popup_element {
+            let layout_children: Vec<_> = layout.children().collect();
+            let mut popup_idx = 4;
+            if self.settings_drawer_element.is_some() {
+                popup_idx += 1;
+            }
+            if self.shape_popup_element.is_some() {
+                popup_idx += 1;
+            }
+            if self.redact_popup_element.is_some() {
+                popup_idx += 1;
+            }
+            if self.pencil_popup_element.is_some() {
+                popup_idx += 1;
+            }
+            if self.magnifier_popup_element.is_some() {
+                popup_idx += 1;
+            }
+            if self.stamp_popup_element.is_some() {
+                popup_idx += 1;
+            }
+            if layout_children.len() > popup_idx {
+                let popup_layout = layout_children[popup_idx];
+                renderer.with_layer(popup_layout.bounds(), |renderer| {
+                    let popup_tree = &tree.children[popup_idx];
+                    popup.as_widget().draw(
+                        popup_tree,
+                        renderer,
+                        theme,
+                        style,
+                        popup_layout,
+                        cursor,
+                        viewport,
+                    );
+                });
+            }
+        }
     }
 
     fn update(
@@ -1600,6 +2325,81 @@ where
                 }
             }
 
+            // Handle stamp popup click-outside
+            if self.ui.stamp_popup_open {
+                let mut popup_idx = 4;
+                if self.settings_drawer_element.is_some() {
+                    popup_idx += 1;
+                }
+                if self.shape_popup_element.is_some() {
+                    popup_idx += 1;
+                }
+                if self.redact_popup_element.is_some() {
+                    popup_idx += 1;
+                }
+                if self.pencil_popup_element.is_some() {
+                    popup_idx += 1;
+                }
+                if self.magnifier_popup_element.is_some() {
+                    popup_idx += 1;
+                }
+                let inside_popup = if layout_children.len() > popup_idx {
+                    layout_children[popup_idx].bounds().contains(pos)
+                } else {
+                    false
+                };
+                let inside_toolbar = if layout_children.len() > 3 {
+                    layout_children[3].bounds().contains(pos)
+                } else {
+                    false
+                };
+
+                if !inside_popup && !inside_toolbar {
+                    shell.publish(self.emit(ScreenshotEvent::stamp_popup_close()));
+                    shell.capture_event();
+                    return;
+                }
+            }
+
+            // Handle OCR popup click-outside
+            if self.ui.ocr_popup_open {
+                let mut popup_idx = 4;
+                if self.settings_drawer_element.is_some() {
+                    popup_idx += 1;
+                }
+                if self.shape_popup_element.is_some() {
+                    popup_idx += 1;
+                }
+                if self.redact_popup_element.is_some() {
+                    popup_idx += 1;
+                }
+                if self.pencil_popup_element.is_some() {
+                    popup_idx += 1;
+                }
+                if self.magnifier_popup_element.is_some() {
+                    popup_idx += 1;
+                }
+                if self.stamp_popup_element.is_some() {
+                    popup_idx += 1;
+                }
+                let inside_popup = if layout_children.len() > popup_idx {
+                    layout_children[popup_idx].bounds().contains(pos)
+                } else {
+                    false
+                };
+                let inside_toolbar = if layout_children.len() > 3 {
+                    layout_children[3].bounds().contains(pos)
+                } else {
+                    false
+                };
+
+                if !inside_popup && !inside_toolbar {
+                    shell.publish(self.emit(ScreenshotEvent::ocr_popup_close()));
+                    shell.capture_event();
+                    return;
+                }
+            }
+
             // Handle settings drawer click-outside
             if self.ui.settings_drawer_open {
                 let inside_drawer = if layout_children.len() > 4 {
@@ -1629,50 +2429,34 @@ where
             }
         }
 
-        // Handle clicks on QR code URL open buttons
+        // Handle clicks on QR code action buttons, and on the codes themselves
+        // to pick one of several
         if let Event::Mouse(mouse_event) = &event
             && let Some(pos) = cursor.position()
             && matches!(mouse_event, MouseEvent::ButtonPressed(Button::Left))
-            && let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect
         {
-            let button_size = 28.0_f32;
-            let padding = 8.0;
-
-            for (x, y, content) in &self.qr_codes_for_output {
-                if !is_url(content) {
-                    continue;
-                }
-
-                let font_size = 14.0_f32;
-                let button_space = button_size + padding;
-                let max_label_width = (sel_w - padding * 4.0 - button_space).clamp(80.0, 400.0);
-                let chars_per_line = (max_label_width / (font_size * 0.55)).max(10.0) as usize;
-                let num_lines = ((content.len() / chars_per_line).max(1) + 1).min(6);
-                let text_height = (num_lines as f32 * font_size * 1.3).min(sel_h * 0.6);
-                let bg_width = max_label_width + padding * 2.0 + button_space;
-                let bg_height = text_height.max(button_size) + padding * 2.0;
-
-                let mut label_x = *x - bg_width / 2.0;
-                let mut label_y = *y - bg_height / 2.0;
-                label_x = label_x
-                    .max(sel_x + padding)
-                    .min(sel_x + sel_w - bg_width - padding);
-                label_y = label_y
-                    .max(sel_y + padding)
-                    .min(sel_y + sel_h - bg_height - padding);
-
-                let button_x = label_x + bg_width - padding - button_size;
-                let button_y = label_y + (bg_height - button_size) / 2.0;
-
-                if pos.x >= button_x
-                    && pos.x <= button_x + button_size
-                    && pos.y >= button_y
-                    && pos.y <= button_y + button_size
-                {
-                    shell.publish(self.emit(ScreenshotEvent::open_url(content.clone())));
-                    shell.capture_event();
-                    return;
-                }
+            for (index, label) in self.qr_labels().into_iter().rev() {
+                let action = label
+                    .buttons
+                    .iter()
+                    .find(|(rect, _, _)| rect.contains(pos))
+                    .map(|(_, _, action)| action.clone());
+                let event = match (action, index) {
+                    (Some(QrAction::Open(uri)), _) => ScreenshotEvent::open_url(uri),
+                    (Some(QrAction::Copy(text)), _) => ScreenshotEvent::qr_copy_text(text),
+                    (Some(QrAction::CopyAll(format)), _) => {
+                        ScreenshotEvent::qr_copy_all_and_close(format)
+                    }
+                    (None, Some(index))
+                        if self.picks_qr_codes() && label.background.contains(pos) =>
+                    {
+                        ScreenshotEvent::qr_copy_one_and_close(index)
+                    }
+                    _ => continue,
+                };
+                shell.publish(self.emit(event));
+                shell.capture_event();
+                return;
             }
         }
 
@@ -1691,6 +2475,56 @@ where
             false
         };
 
+        // Select OCR text: press on a recognized word and drag across others.
+        // Runs before the children so the press doesn't move the selection.
+        if let Event::Mouse(mouse_event) = &event
+            && let Some(pos) = cursor.position()
+            && self.show_qr_overlays
+            && !self.is_any_drawing_mode()
+        {
+            let drag_state = tree.state.downcast_mut::<MagnifierDragState>();
+            let point = (pos.x, pos.y);
+            match mouse_event {
+                MouseEvent::ButtonPressed(Button::Left)
+                    if !layout_children
+                        .iter()
+                        .skip(3)
+                        .any(|l| l.bounds().contains(pos)) =>
+                {
+                    let words = reading_order(&self.detection.ocr_overlays, &self.output.name);
+                    if word_at(&words, point).is_some() {
+                        drag_state.ocr_selecting = true;
+                        shell.publish(self.emit(ScreenshotEvent::ocr_select(Some(OcrSelection {
+                            output_name: self.output.name.clone(),
+                            anchor: point,
+                            focus: point,
+                        }))));
+                        shell.capture_event();
+                        return;
+                    }
+                    if self.detection.ocr_selection.is_some() {
+                        shell.publish(self.emit(ScreenshotEvent::ocr_select(None)));
+                    }
+                }
+                MouseEvent::CursorMoved { .. } if drag_state.ocr_selecting => {
+                    if let Some(selection) = &self.detection.ocr_selection {
+                        shell.publish(self.emit(ScreenshotEvent::ocr_select(Some(OcrSelection {
+                            focus: point,
+                            ..selection.clone()
+                        }))));
+                    }
+                    shell.capture_event();
+                    return;
+                }
+                MouseEvent::ButtonReleased(Button::Left) if drag_state.ocr_selecting => {
+                    drag_state.ocr_selecting = false;
+                    shell.capture_event();
+                    return;
+                }
+                _ => {}
+            }
+        }
+
         // Let child widgets handle the event
         let mut children: Vec<&mut Element<'_, Msg>> = vec![
             &mut self.bg_element,
@@ -1713,6 +2547,12 @@ where
         if let Some(ref mut popup) = self.magnifier_popup_element {
             children.push(popup);
         }
+        if let Some(ref mut popup) = self.stamp_popup_element {
+            children.push(popup);
+        }
+        if let Some(ref mut popup) = self.ocr_popup_element {
+            children.push(popup);
+        }
 
         for (i, (child_layout, child)) in layout_children
             .into_iter()
@@ -1833,6 +2673,61 @@ where
                     }
                 }
 
+                // Check stamp popup
+                if self.ui.stamp_popup_open {
+                    let mut popup_idx = 4;
+                    if self.settings_drawer_element.is_some() {
+                        popup_idx += 1;
+                    }
+                    if self.shape_popup_element.is_some() {
+                        popup_idx += 1;
+                    }
+                    if self.redact_popup_element.is_some() {
+                        popup_idx += 1;
+                    }
+                    if self.pencil_popup_element.is_some() {
+                        popup_idx += 1;
+                    }
+                    if self.magnifier_popup_element.is_some() {
+                        popup_idx += 1;
+                    }
+                    if layout_children.len() > popup_idx
+                        && layout_children[popup_idx].bounds().contains(pos)
+                    {
+                        shell.capture_event();
+                        return;
+                    }
+                }
+
+                // Check OCR popup
+                if self.ui.ocr_popup_open {
+                    let mut popup_idx = 4;
+                    if self.settings_drawer_element.is_some() {
+                        popup_idx += 1;
+                    }
+                    if self.shape_popup_element.is_some() {
+                        popup_idx += 1;
+                    }
+                    if self.redact_popup_element.is_some() {
+                        popup_idx += 1;
+                    }
+                    if self.pencil_popup_element.is_some() {
+                        popup_idx += 1;
+                    }
+                    if self.magnifier_popup_element.is_some() {
+                        popup_idx += 1;
+                    }
+                    if self.stamp_popup_element.is_some() {
+                        popup_idx += 1;
+                    }
+                    if layout_children.len() > popup_idx
+                        && layout_children[popup_idx].bounds().contains(pos)
+                    {
+                        shell.capture_event();
+                        return;
+                    }
+                }
+
                 // Check settings drawer
                 if self.ui.settings_drawer_open {
                     if layout_children.len() > 4 && layout_children[4].bounds().contains(pos) {
@@ -1865,8 +2760,12 @@ where
                     && pos.y <= sel_y + sel_h - ANNOTATION_MARGIN
             };
 
-            // Handle arrow drawing
+            // Handle arrow tool: bend existing arrows by their handles, or draw new ones
             if self.is_arrow_mode() {
+                let drag_state = tree.state.downcast_mut::<MagnifierDragState>();
+                let cursor_gx = pos.x + self.output_rect.left as f32;
+                let cursor_gy = pos.y + self.output_rect.top as f32;
+
                 let inside_selection =
                     if let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect {
                         inside_inner_selection(sel_x, sel_y, sel_w, sel_h)
@@ -1874,6 +2773,39 @@ where
                         false
                     };
 
+                match mouse_event {
+                    MouseEvent::ButtonPressed(Button::Left)
+                        if self.annotations.arrow_drawing.is_none() =>
+                    {
+                        // Topmost arrow whose bend handle is under the cursor
+                        let hit = self.annotations.arrows.iter().rposition(|arrow| {
+                            let (hx, hy) = arrow.bend_point();
+                            (cursor_gx - hx).hypot(cursor_gy - hy) <= BEND_HANDLE_RADIUS + 4.0
+                        });
+                        if let Some(index) = hit {
+                            drag_state.arrow_bend = Some(index);
+                            shell.capture_event();
+                            return;
+                        }
+                    }
+                    MouseEvent::CursorMoved { .. } => {
+                        if let Some(index) = drag_state.arrow_bend {
+                            shell.publish(
+                                self.emit(ScreenshotEvent::arrow_bend(index, cursor_gx, cursor_gy)),
+                            );
+                            shell.capture_event();
+                            return;
+                        }
+                    }
+                    MouseEvent::ButtonReleased(Button::Left) => {
+                        if drag_state.arrow_bend.take().is_some() {
+                            shell.capture_event();
+                            return;
+                        }
+                    }
+                    _ => {}
+                }
+
                 match mouse_event {
                     MouseEvent::ButtonPressed(Button::Left) if inside_selection => {
                         if let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect {
@@ -1990,6 +2922,175 @@ where
                 }
             }
 
+            // Handle blur drawing
+            if self.is_blur_mode() {
+                let inside_selection =
+                    if let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect {
+                        inside_inner_selection(sel_x, sel_y, sel_w, sel_h)
+                    } else {
+                        false
+                    };
+
+                match mouse_event {
+                    MouseEvent::ButtonPressed(Button::Left) if inside_selection => {
+                        if let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect {
+                            let (clamped_x, clamped_y) =
+                                clamp_to_selection(pos.x, pos.y, sel_x, sel_y, sel_w, sel_h);
+                            let global_x = clamped_x + self.output_rect.left as f32;
+                            let global_y = clamped_y + self.output_rect.top as f32;
+                            shell.publish(
+                                self.emit(ScreenshotEvent::blur_start(global_x, global_y)),
+                            );
+                        }
+                        shell.capture_event();
+                        return;
+                    }
+                    MouseEvent::ButtonReleased(Button::Left)
+                        if self.annotations.blur_drawing.is_some() =>
+                    {
+                        if let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect {
+                            let (clamped_x, clamped_y) =
+                                clamp_to_selection(pos.x, pos.y, sel_x, sel_y, sel_w, sel_h);
+                            let global_x = clamped_x + self.output_rect.left as f32;
+                            let global_y = clamped_y + self.output_rect.top as f32;
+                            shell.publish(self.emit(ScreenshotEvent::blur_end(global_x, global_y)));
+                        }
+                        shell.capture_event();
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+
+            // Handle spotlight drawing
+            if self.is_spotlight_mode() {
+                let inside_selection =
+                    if let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect {
+                        inside_inner_selection(sel_x, sel_y, sel_w, sel_h)
+                    } else {
+                        false
+                    };
+
+                match mouse_event {
+                    MouseEvent::ButtonPressed(Button::Left) if inside_selection => {
+                        if let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect {
+                            let (clamped_x, clamped_y) =
+                                clamp_to_selection(pos.x, pos.y, sel_x, sel_y, sel_w, sel_h);
+                            let global_x = clamped_x + self.output_rect.left as f32;
+                            let global_y = clamped_y + self.output_rect.top as f32;
+                            shell.publish(
+                                self.emit(ScreenshotEvent::spotlight_start(global_x, global_y)),
+                            );
+                        }
+                        shell.capture_event();
+                        return;
+                    }
+                    MouseEvent::ButtonReleased(Button::Left)
+                        if self.annotations.spotlight_drawing.is_some() =>
+                    {
+                        if let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect {
+                            let (clamped_x, clamped_y) =
+                                clamp_to_selection(pos.x, pos.y, sel_x, sel_y, sel_w, sel_h);
+                            let global_x = clamped_x + self.output_rect.left as f32;
+                            let global_y = clamped_y + self.output_rect.top as f32;
+                            shell.publish(
+                                self.emit(ScreenshotEvent::spotlight_end(global_x, global_y)),
+                            );
+                        }
+                        shell.capture_event();
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+
+            // Handle measuring (dimension line or box)
+            if self.is_measure_mode() {
+                let inside_selection =
+                    if let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect {
+                        inside_inner_selection(sel_x, sel_y, sel_w, sel_h)
+                    } else {
+                        false
+                    };
+
+                match mouse_event {
+                    MouseEvent::ButtonPressed(Button::Left) if inside_selection => {
+                        if let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect {
+                            let (clamped_x, clamped_y) =
+                                clamp_to_selection(pos.x, pos.y, sel_x, sel_y, sel_w, sel_h);
+                            let global_x = clamped_x + self.output_rect.left as f32;
+                            let global_y = clamped_y + self.output_rect.top as f32;
+                            shell.publish(
+                                self.emit(ScreenshotEvent::measure_start(global_x, global_y)),
+                            );
+                        }
+                        shell.capture_event();
+                        return;
+                    }
+                    MouseEvent::ButtonReleased(Button::Left)
+                        if self.annotations.measure_drawing.is_some() =>
+                    {
+                        if let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect {
+                            let (clamped_x, clamped_y) =
+                                clamp_to_selection(pos.x, pos.y, sel_x, sel_y, sel_w, sel_h);
+                            let global_x = clamped_x + self.output_rect.left as f32;
+                            let global_y = clamped_y + self.output_rect.top as f32;
+                            shell.publish(
+                                self.emit(ScreenshotEvent::measure_end(global_x, global_y)),
+                            );
+                        }
+                        shell.capture_event();
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+
+            // Handle stamp placement: press at the center, drag out to size it
+            if self.is_stamp_mode() {
+                let inside_selection =
+                    if let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect {
+                        inside_inner_selection(sel_x, sel_y, sel_w, sel_h)
+                    } else {
+                        false
+                    };
+
+                match mouse_event {
+                    MouseEvent::ButtonPressed(Button::Left) if inside_selection => {
+                        let global_x = pos.x + self.output_rect.left as f32;
+                        let global_y = pos.y + self.output_rect.top as f32;
+                        shell.publish(self.emit(ScreenshotEvent::stamp_start(global_x, global_y)));
+                        shell.capture_event();
+                        return;
+                    }
+                    MouseEvent::ButtonReleased(Button::Left)
+                        if self.annotations.stamp_drawing.is_some() =>
+                    {
+                        let global_x = pos.x + self.output_rect.left as f32;
+                        let global_y = pos.y + self.output_rect.top as f32;
+                        shell.publish(self.emit(ScreenshotEvent::stamp_end(global_x, global_y)));
+                        shell.capture_event();
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+
+            // Handle color picker: a click copies the color of the pixel under the cursor
+            if self.is_color_picker_mode()
+                && let MouseEvent::ButtonPressed(Button::Left) = mouse_event
+                && let Some((sel_x, sel_y, sel_w, sel_h)) = self.selection_rect
+                && inside_inner_selection(sel_x, sel_y, sel_w, sel_h)
+            {
+                if let Some(rgb) =
+                    pixel_at(&self.screenshot_image.rgba, self.image_scale, pos.x, pos.y)
+                {
+                    shell.publish(self.emit(ScreenshotEvent::color_picked(rgb)));
+                }
+                shell.capture_event();
+                return;
+            }
+
             // Handle magnifier tool: create new, or select / move / resize existing
             if self.is_magnifier_mode() {
                 let drag_state = tree.state.downcast_mut::<MagnifierDragState>();
@@ -2011,9 +3112,11 @@ where
 
                 match mouse_event {
                     MouseEvent::ButtonPressed(Button::Left) => {
-                        // 1. Grabbing the ring of the selected magnifier -> resize
+                        // 1. Grabbing the ring of the selected loupe -> resize
+                        // (callouts are sized by their source rectangle and zoom)
                         if let Some(sel) = self.annotations.selected_magnifier
                             && let Some(m) = self.annotations.magnifiers.get(sel)
+                            && !m.is_callout()
                         {
                             let (cx, cy) = m.center();
                             if (dist_to(cx, cy) - m.radius()).abs() <= MAGNIFIER_RING_GRAB {
@@ -2023,12 +3126,13 @@ where
                             }
                         }
 
-                        // 2. Clicking inside an existing loupe -> select + move (topmost first)
+                        // 2. Clicking inside an existing loupe or callout -> select + move
+                        // (topmost first)
                         let mut hit = None;
                         for (i, m) in self.annotations.magnifiers.iter().enumerate().rev() {
-                            let (cx, cy) = m.center();
-                            if dist_to(cx, cy) <= m.radius() {
-                                hit = Some((i, cx, cy));
+                            if m.contains(cursor_gx, cursor_gy) {
+                                let (ax, ay) = m.anchor();
+                                hit = Some((i, ax, ay));
                                 break;
                             }
                         }
@@ -2107,8 +3211,7 @@ where
                         if let Some(sel) = self.annotations.selected_magnifier
                             && let Some(m) = self.annotations.magnifiers.get(sel)
                         {
-                            let (cx, cy) = m.center();
-                            if dist_to(cx, cy) <= m.radius() {
+                            if m.contains(cursor_gx, cursor_gy) {
                                 let step = match delta {
                                     cosmic::iced::core::mouse::ScrollDelta::Lines { y, .. } => *y,
                                     cosmic::iced::core::mouse::ScrollDelta::Pixels { y, .. } => {
@@ -2182,6 +3285,12 @@ where
         if let Some(ref popup) = self.magnifier_popup_element {
             children.push(popup);
         }
+        if let Some(ref popup) = self.stamp_popup_element {
+            children.push(popup);
+        }
+        if let Some(ref popup) = self.ocr_popup_element {
+            children.push(popup);
+        }
 
         let layout_children = layout.children().collect::<Vec<_>>();
 
@@ -2247,6 +3356,26 @@ where
             }
         }
 
+        // Pointer over QR code buttons, and over codes that can be picked
+        if let Some(pos) = cursor.position()
+            && self.qr_labels().iter().any(|(index, label)| {
+                label.buttons.iter().any(|(rect, _, _)| rect.contains(pos))
+                    || (index.is_some() && self.picks_qr_codes() && label.background.contains(pos))
+            })
+        {
+            return cosmic::iced::mouse::Interaction::Pointer;
+        }
+
+        // Text cursor over recognized words, which can be selected
+        if self.show_qr_overlays
+            && let Some(pos) = cursor.position()
+        {
+            let words = reading_order(&self.detection.ocr_overlays, &self.output.name);
+            if word_at(&words, (pos.x, pos.y)).is_some() {
+                return cosmic::iced::mouse::Interaction::Text;
+            }
+        }
+
         // Then check fg_element (RectangleSelection) for move/resize cursors
         // This is index 1
         if layout_children.len() > 1 {
@@ -2295,6 +3424,12 @@ where
         if let Some(ref mut popup) = self.magnifier_popup_element {
             elements.push(popup);
         }
+        if let Some(ref mut popup) = self.stamp_popup_element {
+            elements.push(popup);
+        }
+        if let Some(ref mut popup) = self.ocr_popup_element {
+            elements.push(popup);
+        }
 
         let children = elements
             .into_iter()
@@ -2339,6 +3474,12 @@ where
         if let Some(ref mut popup) = self.magnifier_popup_element {
             children.push(popup);
         }
+        if let Some(ref mut popup) = self.stamp_popup_element {
+            children.push(popup);
+        }
+        if let Some(ref mut popup) = self.ocr_popup_element {
+            children.push(popup);
+        }
         for (i, (layout, child)) in layout
             .into_iter()
             .zip(children.into_iter())
@@ -2392,6 +3533,12 @@ where
         if let Some(ref popup) = self.magnifier_popup_element {
             children.push(popup);
         }
+        if let Some(ref popup) = self.stamp_popup_element {
+            children.push(popup);
+        }
+        if let Some(ref popup) = self.ocr_popup_element {
+            children.push(popup);
+        }
         for (i, (layout, child)) in layout.children().zip(children).enumerate() {
             let state = &state.children[i];
             child
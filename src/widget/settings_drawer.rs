@@ -6,8 +6,12 @@ use cosmic::iced::core::Border;
 use cosmic::iced::widget::{column, row, toggler};
 use cosmic::widget::{container, dropdown, radio, segmented_button, tab_bar, text};
 
+use super::tool_button::build_ocr_language_chips;
 use super::toolbar::HoverOpacity;
-use crate::config::{Container, SaveLocationChoice, ToolbarPosition, VideoSaveLocationChoice};
+use crate::config::{
+    BeautifyPreset, Container, OcrEngine, SaveLocationChoice, ToolbarPosition,
+    VideoSaveLocationChoice,
+};
 use crate::fl;
 use crate::session::state::SettingsTab;
 
@@ -26,7 +30,7 @@ const CONTAINER_OPTIONS: &[Container] = &[Container::Mp4, Container::Mkv];
 
 /// Build the settings drawer element
 #[allow(clippy::too_many_arguments)]
-pub fn build_settings_drawer<'a, Msg: Clone + 'static, F, G, H, I>(
+pub fn build_settings_drawer<'a, Msg: Clone + 'static, F, G, H, I, J, K>(
     _toolbar_position: ToolbarPosition,
     magnifier_enabled: bool,
     on_magnifier_toggle: Msg,
@@ -44,6 +48,25 @@ pub fn build_settings_drawer<'a, Msg: Clone + 'static, F, G, H, I>(
     on_browse_video_save_location: Msg,
     copy_to_clipboard_on_save: bool,
     on_copy_on_save_toggle: Msg,
+    color_picker_sets_shape_color: bool,
+    on_color_picker_sets_shape_color_toggle: Msg,
+    measure_in_export: bool,
+    on_measure_in_export_toggle: Msg,
+    ocr_engine: OcrEngine,
+    on_ocr_engine_tesseract: Msg,
+    on_ocr_engine_builtin: Msg,
+    ocr_deskew: bool,
+    on_ocr_deskew_toggle: Msg,
+    ocr_model_download: bool,
+    on_ocr_model_download_toggle: Msg,
+    installed_ocr_languages: &[String],
+    ocr_languages: &[String],
+    on_ocr_language_toggle: K,
+    beautify_enabled: bool,
+    on_beautify_toggle: Msg,
+    beautify_presets: &'a [BeautifyPreset],
+    beautify_preset: &str,
+    on_beautify_preset_select: J,
     capture_delay_secs: u32,
     on_capture_delay_select: I,
     on_github_click: Msg,
@@ -74,6 +97,8 @@ where
     G: Fn(Container) -> Msg + Clone + Send + Sync + 'static,
     H: Fn(u32) -> Msg + Clone + Send + Sync + 'static,
     I: Fn(u32) -> Msg + Clone + Send + Sync + 'static,
+    J: Fn(String) -> Msg + Clone + Send + Sync + 'static,
+    K: Fn(String) -> Msg + Clone + Send + Sync + 'static,
 {
     // Build tab row using tab_bar style (looks like tabs instead of segmented control)
     // The callback receives the Entity, and the handler will look up the SettingsTab data
@@ -152,6 +177,137 @@ where
     .align_y(cosmic::iced::core::Alignment::Center)
     .width(Length::Fill);
 
+    // Eyedropper: also adopt the picked color for new annotations
+    let color_picker_row = row![
+        text::body(fl!("color-picker-sets-shape-color")),
+        cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
+        toggler(color_picker_sets_shape_color)
+            .on_toggle(move |_| on_color_picker_sets_shape_color_toggle.clone())
+            .size(24.0),
+    ]
+    .spacing(space_s)
+    .align_y(cosmic::iced::core::Alignment::Center)
+    .width(Length::Fill);
+
+    // Measure tool: keep dimension annotations in saved images
+    let measure_in_export_row = row![
+        text::body(fl!("measure-in-export")),
+        cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
+        toggler(measure_in_export)
+            .on_toggle(move |_| on_measure_in_export_toggle.clone())
+            .size(24.0),
+    ]
+    .spacing(space_s)
+    .align_y(cosmic::iced::core::Alignment::Center)
+    .width(Length::Fill);
+
+    // OCR engine; tesseract languages are combined when several are picked
+    let ocr_engine_row = cosmic::widget::flex_row(vec![
+        radio(
+            text::body(fl!("ocr-engine-tesseract")),
+            OcrEngine::Tesseract,
+            Some(ocr_engine),
+            move |_| on_ocr_engine_tesseract.clone(),
+        )
+        .into(),
+        radio(
+            text::body(fl!("ocr-engine-builtin")),
+            OcrEngine::Builtin,
+            Some(ocr_engine),
+            move |_| on_ocr_engine_builtin.clone(),
+        )
+        .into(),
+    ])
+    .row_spacing(space_xs)
+    .column_spacing(space_s);
+
+    let ocr_engine_detail: Element<'_, Msg> = match ocr_engine {
+        OcrEngine::Tesseract => column![
+            text::body(fl!("ocr-languages")),
+            build_ocr_language_chips(
+                installed_ocr_languages,
+                ocr_languages,
+                on_ocr_language_toggle,
+                space_xs
+            ),
+        ]
+        .spacing(space_xs)
+        .into(),
+        OcrEngine::Builtin => column![
+            text::caption(fl!("ocr-engine-builtin-hint")),
+            row![
+                text::body(fl!("ocr-model-download")),
+                cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
+                toggler(ocr_model_download)
+                    .on_toggle(move |_| on_ocr_model_download_toggle.clone())
+                    .size(24.0),
+            ]
+            .spacing(space_s)
+            .align_y(cosmic::iced::core::Alignment::Center)
+            .width(Length::Fill),
+        ]
+        .spacing(space_xs)
+        .into(),
+    };
+
+    let ocr_deskew_row = row![
+        text::body(fl!("ocr-deskew")),
+        cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
+        toggler(ocr_deskew)
+            .on_toggle(move |_| on_ocr_deskew_toggle.clone())
+            .size(24.0),
+    ]
+    .spacing(space_s)
+    .align_y(cosmic::iced::core::Alignment::Center)
+    .width(Length::Fill);
+
+    let ocr_section = column![
+        text::body(fl!("ocr-engine")),
+        ocr_engine_row,
+        ocr_engine_detail,
+        ocr_deskew_row,
+    ]
+    .spacing(space_xs)
+    .width(Length::Fill);
+
+    // Beautify frame toggle, with the preset picker when enabled
+    let beautify_toggle_row = row![
+        text::body(fl!("beautify-frame")),
+        cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
+        toggler(beautify_enabled)
+            .on_toggle(move |_| on_beautify_toggle.clone())
+            .size(24.0),
+    ]
+    .spacing(space_s)
+    .align_y(cosmic::iced::core::Alignment::Center)
+    .width(Length::Fill);
+
+    let beautify_section: Element<'_, Msg> = if beautify_enabled && !beautify_presets.is_empty() {
+        let selected_preset_idx = beautify_presets
+            .iter()
+            .position(|p| p.name == beautify_preset);
+        let preset_names: Vec<String> = beautify_presets.iter().map(|p| p.name.clone()).collect();
+        let preset_dropdown = dropdown(beautify_presets, selected_preset_idx, move |idx| {
+            on_beautify_preset_select(preset_names.get(idx).cloned().unwrap_or_default())
+        })
+        .width(Length::Fixed(120.0));
+        column![
+            beautify_toggle_row,
+            row![
+                text::body(fl!("beautify-preset")),
+                cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
+                preset_dropdown,
+            ]
+            .spacing(space_s)
+            .align_y(cosmic::iced::core::Alignment::Center)
+            .width(Length::Fill),
+        ]
+        .spacing(space_xs)
+        .into()
+    } else {
+        beautify_toggle_row.into()
+    };
+
     // Screenshot delay dropdown (for the delayed-capture toolbar button)
     static DELAY_NAMES: &[&str] = &["1s", "2s", "3s", "5s", "10s", "15s"];
     let selected_delay_idx = DELAY_OPTIONS
@@ -351,6 +507,12 @@ where
         custom_path_row,
         cosmic::widget::divider::horizontal::light(),
         copy_on_save_row,
+        color_picker_row,
+        measure_in_export_row,
+        cosmic::widget::divider::horizontal::light(),
+        ocr_section,
+        cosmic::widget::divider::horizontal::light(),
+        beautify_section,
         cosmic::widget::divider::horizontal::light(),
         capture_delay_row,
     ]
@@ -1,13 +1,16 @@
 //! Handlers for settings-related messages
 //!
 //! Handles: ToolbarPositionChange, ToggleSettingsDrawer, ToggleMagnifier,
-//!          SetSaveLocation, ToggleCopyOnSave, SetVideoEncoder, SetVideoContainer,
-//!          SetVideoFramerate
+//!          SetSaveLocation, ToggleCopyOnSave, ToggleBeautify, SetBeautifyPreset,
+//!          ToggleColorPickerSetsShapeColor, ToggleMeasureInExport, SetOcrEngine,
+//!          ToggleOcrDeskew, ToggleOcrModelDownload, ToggleOcrLanguage,
+//!          SetVideoEncoder, SetVideoContainer, SetVideoFramerate
 
 use std::io::Write;
 
 use crate::config::{
-    Container, SaveLocationChoice, SnapPeaConfig, ToolbarPosition, VideoSaveLocationChoice,
+    Container, OcrEngine, SaveLocationChoice, SnapPeaConfig, ToolbarPosition,
+    VideoSaveLocationChoice,
 };
 use crate::screenshot::Args;
 use crate::screenshot::handlers::HandlerResult;
@@ -116,6 +119,78 @@ pub fn handle_toggle_copy_on_save(args: &mut Args) -> HandlerResult {
     cosmic::Task::none()
 }
 
+/// Handle ToggleBeautify message
+pub fn handle_toggle_beautify(args: &mut Args) -> HandlerResult {
+    args.ui.beautify_enabled = !args.ui.beautify_enabled;
+    let mut config = SnapPeaConfig::load();
+    config.beautify_enabled = args.ui.beautify_enabled;
+    config.save();
+    cosmic::Task::none()
+}
+
+/// Handle SetBeautifyPreset message
+pub fn handle_set_beautify_preset(args: &mut Args, name: String) -> HandlerResult {
+    args.ui.beautify_preset = name.clone();
+    let mut config = SnapPeaConfig::load();
+    config.beautify_preset = name;
+    config.save();
+    cosmic::Task::none()
+}
+
+/// Handle ToggleColorPickerSetsShapeColor message
+pub fn handle_toggle_color_picker_sets_shape_color(args: &mut Args) -> HandlerResult {
+    args.ui.color_picker_sets_shape_color = !args.ui.color_picker_sets_shape_color;
+    let mut config = SnapPeaConfig::load();
+    config.color_picker_sets_shape_color = args.ui.color_picker_sets_shape_color;
+    config.save();
+    cosmic::Task::none()
+}
+
+/// Handle ToggleMeasureInExport message
+pub fn handle_toggle_measure_in_export(args: &mut Args) -> HandlerResult {
+    args.ui.measure_in_export = !args.ui.measure_in_export;
+    let mut config = SnapPeaConfig::load();
+    config.measure_in_export = args.ui.measure_in_export;
+    config.save();
+    cosmic::Task::none()
+}
+
+/// Handle SetOcrEngine message
+pub fn handle_set_ocr_engine(args: &mut Args, engine: OcrEngine) -> HandlerResult {
+    args.ui.ocr_engine = engine;
+    let mut config = SnapPeaConfig::load();
+    config.ocr_engine = engine;
+    config.save();
+    cosmic::Task::none()
+}
+
+/// Handle ToggleOcrDeskew message
+pub fn handle_toggle_ocr_deskew(args: &mut Args) -> HandlerResult {
+    args.ui.ocr_deskew = !args.ui.ocr_deskew;
+    let mut config = SnapPeaConfig::load();
+    config.ocr_deskew = args.ui.ocr_deskew;
+    config.save();
+    cosmic::Task::none()
+}
+
+/// Handle ToggleOcrModelDownload message
+pub fn handle_toggle_ocr_model_download(args: &mut Args) -> HandlerResult {
+    args.ui.ocr_model_download = !args.ui.ocr_model_download;
+    let mut config = SnapPeaConfig::load();
+    config.ocr_model_download = args.ui.ocr_model_download;
+    config.save();
+    cosmic::Task::none()
+}
+
+/// Handle ToggleOcrLanguage message
+pub fn handle_toggle_ocr_language(args: &mut Args, lang: String) -> HandlerResult {
+    crate::capture::ocr::toggle_language(&mut args.ui.ocr_languages, lang);
+    let mut config = SnapPeaConfig::load();
+    config.ocr_languages = args.ui.ocr_languages.clone();
+    config.save();
+    cosmic::Task::none()
+}
+
 // Note: SettingsTab activation is handled directly in screenshot/mod.rs
 // because it needs access to app.settings_tab_model
 
@@ -16,8 +16,16 @@ use cosmic::iced::widget::{column, row};
 use cosmic::widget::{button, container, icon, text, toggler, tooltip};
 
 use super::lucide::{self, AppIcon};
-use crate::config::{RedactTool, ShapeColor, ShapeTool};
+use crate::capture::ocr::OcrLayout;
+use crate::config::{
+    AnnotationPreset, ColorPalette, MAX_KEYED_PRESETS, RedactTool, ShapeColor, ShapeTool,
+};
+use crate::domain::color::{hsv_to_rgb, parse_hex, rgb_to_hsv, to_hex};
+use crate::domain::{STAMP_MAX_SIZE, STAMP_MIN_SIZE};
 use crate::fl;
+use crate::render::geometry::shape;
+use crate::render::stamp::Stamp;
+use crate::session::messages::{ColorEdit, PresetEdit};
 
 /// A wrapper widget that detects right-click and long-press events
 pub struct RightClickWrapper<'a, Msg> {
@@ -255,6 +263,379 @@ pub fn color_name(index: usize) -> String {
     }
 }
 
+/// Swatches shown per row in the color section (fits the 230px popups)
+const SWATCHES_PER_ROW: usize = 4;
+
+fn hex_of(color: ShapeColor) -> String {
+    let [r, g, b, _] = color.to_rgba_u8();
+    to_hex([r, g, b])
+}
+
+/// A clickable color swatch; right-click is optional (used to remove palette colors)
+fn color_swatch<'a, Msg: Clone + 'static>(
+    color: ShapeColor,
+    current_color: ShapeColor,
+    name: String,
+    on_press: Msg,
+    on_right_click: Option<Msg>,
+) -> Element<'a, Msg> {
+    let is_selected = (color.r - current_color.r).abs() < 0.05
+        && (color.g - current_color.g).abs() < 0.05
+        && (color.b - current_color.b).abs() < 0.05;
+    let iced_color: cosmic::iced::Color = color.into();
+
+    let swatch = button::custom(
+        container(cosmic::iced::widget::space().width(Length::Fixed(0.0)))
+            .width(Length::Fixed(24.0))
+            .height(Length::Fixed(24.0))
+            .class(cosmic::theme::Container::Custom(Box::new(move |_theme| {
+                cosmic::iced::widget::container::Style {
+                    background: Some(Background::Color(iced_color)),
+                    border: Border {
+                        radius: 4.0.into(),
+                        width: if is_selected { 2.0 } else { 1.0 },
+                        color: if is_selected {
+                            cosmic::iced::Color::WHITE
+                        } else {
+                            cosmic::iced::Color::from_rgba(0.5, 0.5, 0.5, 0.5)
+                        },
+                    },
+                    ..Default::default()
+                }
+            }))),
+    )
+    .class(cosmic::theme::Button::Text)
+    .on_press(on_press)
+    .padding(2);
+
+    tooltip(
+        RightClickWrapper::new(swatch, on_right_click),
+        text::body(name),
+        tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+/// Lay swatches out in centered rows of `SWATCHES_PER_ROW`
+fn swatch_rows<'a, Msg: Clone + 'static>(
+    swatches: Vec<Element<'a, Msg>>,
+    space_xs: u16,
+) -> Element<'a, Msg> {
+    let mut rows: Vec<Element<'a, Msg>> = Vec::new();
+    let mut current: Vec<Element<'a, Msg>> = Vec::with_capacity(SWATCHES_PER_ROW);
+    for swatch in swatches {
+        current.push(swatch);
+        if current.len() == SWATCHES_PER_ROW {
+            rows.push(row(std::mem::take(&mut current)).spacing(space_xs).into());
+        }
+    }
+    if !current.is_empty() {
+        rows.push(row(current).spacing(space_xs).into());
+    }
+    container(column(rows).spacing(space_xs))
+        .width(Length::Fill)
+        .align_x(cosmic::iced::core::alignment::Horizontal::Center)
+        .into()
+}
+
+/// Build the color section of the shape and pencil popups: preset swatches,
+/// recent colors, named palettes and a custom color chooser (hex field and
+/// HSV sliders). Palettes and recent colors are shared by both tools.
+#[allow(clippy::too_many_arguments)]
+pub fn build_color_section<'a, Msg: Clone + 'static>(
+    current_color: ShapeColor,
+    recent_colors: &[ShapeColor],
+    palettes: &[ColorPalette],
+    hex_input: &str,
+    hsv: Option<[f32; 3]>,
+    on_color_change: &(impl Fn(ShapeColor) -> Msg + 'a),
+    on_color_edit: impl Fn(ColorEdit) -> Msg + Clone + 'a,
+    space_xs: u16,
+) -> Element<'a, Msg> {
+    let caption = |label: String| {
+        text::caption(label).class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgba(
+            0.6, 0.6, 0.6, 1.0,
+        )))
+    };
+
+    let mut section = column![text::body(fl!("color"))]
+        .spacing(space_xs)
+        .align_x(cosmic::iced::core::Alignment::Start);
+
+    // Presets
+    let presets = COLOR_PRESETS
+        .iter()
+        .enumerate()
+        .map(|(i, &color)| {
+            color_swatch(
+                color,
+                current_color,
+                color_name(i),
+                on_color_change(color),
+                None,
+            )
+        })
+        .collect();
+    section = section.push(swatch_rows(presets, space_xs));
+
+    // Recently used colors
+    if !recent_colors.is_empty() {
+        let recent = recent_colors
+            .iter()
+            .map(|&color| {
+                color_swatch(
+                    color,
+                    current_color,
+                    hex_of(color),
+                    on_color_change(color),
+                    None,
+                )
+            })
+            .collect();
+        section = section
+            .push(caption(fl!("recent-colors")))
+            .push(swatch_rows(recent, space_xs));
+    }
+
+    // Named palettes: editable name, add button, right-click a color to remove it
+    for (palette_index, palette) in palettes.iter().enumerate() {
+        let on_rename = on_color_edit.clone();
+        let commit = on_color_edit(ColorEdit::CommitNames);
+        let name_input = cosmic::widget::text_input(fl!("color-palette"), palette.name.clone())
+            .on_input(move |name| on_rename(ColorEdit::RenamePalette(palette_index, name)))
+            .on_submit(move |_| commit.clone())
+            .width(Length::Fill);
+        let add_button = tooltip(
+            button::custom(
+                icon::Icon::from(icon::from_name("list-add-symbolic").size(16))
+                    .width(Length::Fixed(16.0))
+                    .height(Length::Fixed(16.0)),
+            )
+            .class(cosmic::theme::Button::Icon)
+            .on_press(on_color_edit(ColorEdit::AddToPalette(Some(palette_index))))
+            .padding(space_xs),
+            text::body(fl!("add-to-palette")),
+            tooltip::Position::Bottom,
+        );
+        let colors = palette
+            .colors
+            .iter()
+            .enumerate()
+            .map(|(color_index, &color)| {
+                color_swatch(
+                    color,
+                    current_color,
+                    hex_of(color),
+                    on_color_change(color),
+                    Some(on_color_edit(ColorEdit::RemoveFromPalette(
+                        palette_index,
+                        color_index,
+                    ))),
+                )
+            })
+            .collect();
+        section = section
+            .push(
+                row![name_input, add_button]
+                    .spacing(space_xs)
+                    .align_y(cosmic::iced::core::Alignment::Center),
+            )
+            .push(swatch_rows(colors, space_xs));
+    }
+    let new_palette = button::custom(
+        row![
+            icon::Icon::from(icon::from_name("list-add-symbolic").size(16))
+                .width(Length::Fixed(16.0))
+                .height(Length::Fixed(16.0)),
+            text::body(fl!("new-palette")),
+        ]
+        .spacing(space_xs)
+        .align_y(cosmic::iced::core::Alignment::Center),
+    )
+    .class(cosmic::theme::Button::Text)
+    .on_press(on_color_edit(ColorEdit::AddToPalette(None)))
+    .padding(space_xs);
+    section = section.push(
+        row![
+            caption(if palettes.is_empty() {
+                String::new()
+            } else {
+                fl!("palette-remove-hint")
+            }),
+            cosmic::iced::widget::space().width(Length::Fill),
+            new_palette,
+        ]
+        .align_y(cosmic::iced::core::Alignment::Center),
+    );
+
+    // Custom color: hex field (applied on Enter) with a preview of the current color
+    let current_hex = hex_of(current_color);
+    let mut hex_field = cosmic::widget::text_input(current_hex, hex_input.to_string())
+        .on_input({
+            let on_edit = on_color_edit.clone();
+            move |text| on_edit(ColorEdit::HexInput(text))
+        })
+        .width(Length::Fill);
+    if let Some(rgb) = parse_hex(hex_input) {
+        let msg = on_color_change(ShapeColor::from_rgb_u8(rgb));
+        hex_field = hex_field.on_submit(move |_| msg.clone());
+    }
+    let preview_color: cosmic::iced::Color = current_color.into();
+    let preview = container(cosmic::iced::widget::space().width(Length::Fixed(0.0)))
+        .width(Length::Fixed(24.0))
+        .height(Length::Fixed(24.0))
+        .class(cosmic::theme::Container::Custom(Box::new(move |_theme| {
+            cosmic::iced::widget::container::Style {
+                background: Some(Background::Color(preview_color)),
+                border: Border {
+                    radius: 4.0.into(),
+                    width: 1.0,
+                    color: cosmic::iced::Color::from_rgba(0.5, 0.5, 0.5, 0.5),
+                },
+                ..Default::default()
+            }
+        })));
+    section = section.push(caption(fl!("custom-color"))).push(
+        row![preview, hex_field]
+            .spacing(space_xs)
+            .align_y(cosmic::iced::core::Alignment::Center),
+    );
+
+    // HSV sliders; keep the dragged HSV while it still describes the color so
+    // hue and saturation don't jump when passing through black or gray
+    let hsv = hsv
+        .filter(|&hsv| hsv_to_rgb(hsv).to_rgba_u8() == current_color.to_rgba_u8())
+        .unwrap_or_else(|| rgb_to_hsv(current_color));
+    let hsv_slider = |label: String, component: usize, max: f32| {
+        let on_edit = on_color_edit.clone();
+        let on_change = move |v: f32| {
+            let mut edited = hsv;
+            edited[component] = v;
+            on_edit(ColorEdit::Hsv(edited))
+        };
+        row![
+            caption(label).width(Length::Fixed(16.0)),
+            cosmic::widget::slider(0.0..=max, hsv[component], on_change)
+                .step(max / 360.0)
+                .on_release(on_color_edit(ColorEdit::Commit))
+                .width(Length::Fill),
+        ]
+        .spacing(space_xs)
+        .align_y(cosmic::iced::core::Alignment::Center)
+    };
+    section = section
+        .push(hsv_slider(fl!("color-hue"), 0, 360.0))
+        .push(hsv_slider(fl!("color-saturation"), 1, 1.0))
+        .push(hsv_slider(fl!("color-value"), 2, 1.0));
+
+    section.into()
+}
+
+/// Build the annotation preset section of the shape popup: one row per preset
+/// with its number key, an editable name and a remove button, plus a button
+/// that saves the current tool and style as a new preset
+pub fn build_preset_section<'a, Msg: Clone + 'static>(
+    presets: &[AnnotationPreset],
+    on_preset_edit: impl Fn(PresetEdit) -> Msg + Clone + 'a,
+    space_xs: u16,
+) -> Element<'a, Msg> {
+    let caption = |label: String| {
+        text::caption(label).class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgba(
+            0.6, 0.6, 0.6, 1.0,
+        )))
+    };
+    let small_icon = |name: &'static str| {
+        icon::Icon::from(icon::from_name(name).size(16))
+            .width(Length::Fixed(16.0))
+            .height(Length::Fixed(16.0))
+    };
+
+    let mut section = column![text::body(fl!("annotation-presets"))]
+        .spacing(space_xs)
+        .align_x(cosmic::iced::core::Alignment::Start);
+
+    for (index, preset) in presets.iter().enumerate() {
+        // Presets beyond the number keys are applied with a check mark
+        let apply_label: Element<'a, Msg> = if index < MAX_KEYED_PRESETS {
+            text::body((index + 1).to_string())
+                .width(Length::Fixed(16.0))
+                .center()
+                .into()
+        } else {
+            small_icon("object-select-symbolic").into()
+        };
+        let apply_button = tooltip(
+            button::custom(apply_label)
+                .class(cosmic::theme::Button::Standard)
+                .on_press(on_preset_edit(PresetEdit::Apply(index)))
+                .padding(space_xs),
+            text::body(fl!("preset-apply")),
+            tooltip::Position::Bottom,
+        );
+        let on_rename = on_preset_edit.clone();
+        let commit = on_preset_edit(PresetEdit::CommitNames);
+        let name_input = cosmic::widget::text_input(fl!("annotation-preset"), preset.name.clone())
+            .on_input(move |name| on_rename(PresetEdit::Rename(index, name)))
+            .on_submit(move |_| commit.clone())
+            .width(Length::Fill);
+        let remove_button = tooltip(
+            button::custom(small_icon("list-remove-symbolic"))
+                .class(cosmic::theme::Button::Icon)
+                .on_press(on_preset_edit(PresetEdit::Remove(index)))
+                .padding(space_xs),
+            text::body(fl!("preset-remove")),
+            tooltip::Position::Bottom,
+        );
+        section = section.push(
+            row![apply_button, name_input, remove_button]
+                .spacing(space_xs)
+                .align_y(cosmic::iced::core::Alignment::Center),
+        );
+    }
+
+    let add_button = button::custom(
+        row![
+            small_icon("list-add-symbolic"),
+            text::body(fl!("preset-add"))
+        ]
+        .spacing(space_xs)
+        .align_y(cosmic::iced::core::Alignment::Center),
+    )
+    .class(cosmic::theme::Button::Text)
+    .on_press(on_preset_edit(PresetEdit::AddCurrent))
+    .padding(space_xs);
+    section = section.push(
+        row![
+            caption(if presets.is_empty() {
+                String::new()
+            } else {
+                fl!("preset-hint")
+            }),
+            cosmic::iced::widget::space().width(Length::Fill),
+            add_button,
+        ]
+        .align_y(cosmic::iced::core::Alignment::Center),
+    );
+
+    section.into()
+}
+
+/// Approximate height the color section adds beyond the preset swatches, for
+/// popups that need a matching input zone (the recording pencil popup)
+pub fn color_section_extra_height(recent_colors: usize, palettes: &[ColorPalette]) -> f32 {
+    const SWATCH_ROW: f32 = 36.0; // Swatch plus spacing
+    const LINE: f32 = 36.0; // Caption, text field, button or slider row plus spacing
+    let rows = |count: usize| count.div_ceil(SWATCHES_PER_ROW) as f32 * SWATCH_ROW;
+    let recent = if recent_colors > 0 {
+        LINE + rows(recent_colors)
+    } else {
+        0.0
+    };
+    let palettes: f32 = palettes.iter().map(|p| LINE + rows(p.colors.len())).sum();
+    // New palette row, custom color caption, hex field and three HSV sliders
+    recent + palettes + 6.0 * LINE
+}
+
 /// Build a generic tool button with indicator dots.
 #[allow(clippy::too_many_arguments)]
 pub fn build_tool_button_with_icon<'a, Msg: Clone + 'static>(
@@ -405,18 +786,20 @@ pub fn build_shape_button<'a, Msg: Clone + 'static>(
         ShapeTool::Arrow => 0,
         ShapeTool::Circle => 1,
         ShapeTool::Rectangle => 2,
+        ShapeTool::Spotlight => 3,
     };
 
     let icon = match current_tool {
         ShapeTool::Arrow => AppIcon::Arrow,
         ShapeTool::Circle => AppIcon::Circle,
         ShapeTool::Rectangle => AppIcon::Square,
+        ShapeTool::Spotlight => AppIcon::Spotlight,
     };
 
     build_tool_button_with_icon(
         lucide::icon_with_opacity(icon, 34.0, content_opacity, is_active || is_popup_open),
         current_tool.tooltip(),
-        3, // 3 shape options
+        4, // 4 shape options
         option_index,
         is_active,
         is_popup_open,
@@ -432,14 +815,29 @@ pub fn build_shape_button<'a, Msg: Clone + 'static>(
 #[allow(clippy::too_many_arguments)]
 pub fn build_shape_popup<'a, Msg: Clone + 'static>(
     current_tool: ShapeTool,
-    current_color: ShapeColor,
+    color_section: Element<'a, Msg>,
+    preset_section: Element<'a, Msg>,
+    shape_width: f32,
     shadow_enabled: bool,
+    auto_contrast: bool,
+    fill_enabled: bool,
+    spotlight_dim: f32,
+    spotlight_ellipse: bool,
+    spotlight_blur: bool,
     has_annotations: bool,
     on_select_arrow: Msg,
     on_select_circle: Msg,
     on_select_rectangle: Msg,
-    on_color_change: &(impl Fn(ShapeColor) -> Msg + 'a),
+    on_select_spotlight: Msg,
+    on_set_width: impl Fn(f32) -> Msg + 'a,
+    on_save_width: Msg,
     on_shadow_toggle: Msg,
+    on_auto_contrast_toggle: Msg,
+    on_fill_toggle: Msg,
+    on_set_spotlight_dim: impl Fn(f32) -> Msg + 'a,
+    on_save_spotlight_dim: Msg,
+    on_toggle_spotlight_ellipse: Msg,
+    on_toggle_spotlight_blur: Msg,
     on_clear: Msg,
     space_s: u16,
     space_xs: u16,
@@ -501,9 +899,27 @@ pub fn build_shape_popup<'a, Msg: Clone + 'static>(
         tooltip::Position::Bottom,
     );
 
+    let btn_spotlight = tooltip(
+        button::custom(lucide::icon_with_opacity(
+            AppIcon::Spotlight,
+            icon_size,
+            1.0,
+            current_tool == ShapeTool::Spotlight,
+        ))
+        .class(if current_tool == ShapeTool::Spotlight {
+            cosmic::theme::Button::Suggested
+        } else {
+            cosmic::theme::Button::Icon
+        })
+        .on_press(on_select_spotlight)
+        .padding(space_xs),
+        text::body(fl!("spotlight")),
+        tooltip::Position::Bottom,
+    );
+
     // Center the shape buttons in the popup
     let shape_buttons = container(
-        row![btn_arrow, btn_circle, btn_rectangle]
+        row![btn_arrow, btn_circle, btn_rectangle, btn_spotlight]
             .spacing(space_xs)
             .align_y(cosmic::iced::core::Alignment::Center),
     )
@@ -517,91 +933,101 @@ pub fn build_shape_popup<'a, Msg: Clone + 'static>(
     .width(Length::Fill)
     .align_x(cosmic::iced::core::alignment::Horizontal::Center);
 
-    // Color picker - 2 rows of 4 color swatches each to avoid clipping
-    let make_color_swatch = |color: &ShapeColor, name: String| {
-        let is_selected = (color.r - current_color.r).abs() < 0.05
-            && (color.g - current_color.g).abs() < 0.05
-            && (color.b - current_color.b).abs() < 0.05;
-        let color_val = *color;
-        let iced_color: cosmic::iced::Color = color_val.into();
-
-        tooltip(
-            button::custom(
-                container(cosmic::iced::widget::space().width(Length::Fixed(0.0)))
-                    .width(Length::Fixed(24.0))
-                    .height(Length::Fixed(24.0))
-                    .class(cosmic::theme::Container::Custom(Box::new(move |_theme| {
-                        cosmic::iced::widget::container::Style {
-                            background: Some(Background::Color(iced_color)),
-                            border: Border {
-                                radius: 4.0.into(),
-                                width: if is_selected { 2.0 } else { 1.0 },
-                                color: if is_selected {
-                                    cosmic::iced::Color::WHITE
-                                } else {
-                                    cosmic::iced::Color::from_rgba(0.5, 0.5, 0.5, 0.5)
-                                },
-                            },
-                            ..Default::default()
-                        }
-                    }))),
-            )
-            .class(cosmic::theme::Button::Text)
-            .on_press(on_color_change(color_val))
-            .padding(2),
-            text::body(name),
-            tooltip::Position::Bottom,
-        )
-    };
+    // Stroke width slider - updates during drag, saves on release
+    let width_label = text::body(fl!("shape-width", width = (shape_width.round() as u32)));
+    let width_slider =
+        cosmic::widget::slider(shape::MIN_WIDTH..=shape::MAX_WIDTH, shape_width, move |v| {
+            on_set_width(v)
+        })
+        .step(1.0)
+        .on_release(on_save_width)
+        .width(Length::Fill);
 
-    // First row: Red, Green, Blue, Yellow
-    let color_row1 = row![
-        make_color_swatch(&COLOR_PRESETS[0], color_name(0)),
-        make_color_swatch(&COLOR_PRESETS[1], color_name(1)),
-        make_color_swatch(&COLOR_PRESETS[2], color_name(2)),
-        make_color_swatch(&COLOR_PRESETS[3], color_name(3)),
-    ]
-    .spacing(space_xs)
-    .align_y(cosmic::iced::core::Alignment::Center);
-
-    // Second row: Orange, Purple, White, Black
-    let color_row2 = row![
-        make_color_swatch(&COLOR_PRESETS[4], color_name(4)),
-        make_color_swatch(&COLOR_PRESETS[5], color_name(5)),
-        make_color_swatch(&COLOR_PRESETS[6], color_name(6)),
-        make_color_swatch(&COLOR_PRESETS[7], color_name(7)),
+    // Shadow toggle
+    let shadow_row = row![
+        text::body(fl!("shadow")),
+        cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
+        toggler(shadow_enabled)
+            .on_toggle(move |_| on_shadow_toggle.clone())
+            .size(20.0),
     ]
-    .spacing(space_xs)
-    .align_y(cosmic::iced::core::Alignment::Center);
+    .spacing(space_s)
+    .align_y(cosmic::iced::core::Alignment::Center)
+    .width(Length::Fill);
 
-    // Center the color rows
-    let color_row1_centered = container(color_row1)
-        .width(Length::Fill)
-        .align_x(cosmic::iced::core::alignment::Horizontal::Center);
+    // Auto-contrast: the shadow color follows the image below the shape
+    let auto_contrast_row = row![
+        text::body(fl!("shape-auto-contrast")),
+        cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
+        toggler(auto_contrast)
+            .on_toggle(move |_| on_auto_contrast_toggle.clone())
+            .size(20.0),
+    ]
+    .spacing(space_s)
+    .align_y(cosmic::iced::core::Alignment::Center)
+    .width(Length::Fill);
 
-    let color_row2_centered = container(color_row2)
-        .width(Length::Fill)
-        .align_x(cosmic::iced::core::alignment::Horizontal::Center);
+    // Fill toggle (circles and rectangles)
+    let fill_row = row![
+        text::body(fl!("shape-fill")),
+        cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
+        toggler(fill_enabled)
+            .on_toggle(move |_| on_fill_toggle.clone())
+            .size(20.0),
+    ]
+    .spacing(space_s)
+    .align_y(cosmic::iced::core::Alignment::Center)
+    .width(Length::Fill);
 
-    let color_section = column![
-        text::body(fl!("color")),
-        color_row1_centered,
-        color_row2_centered
+    let style_section = column![
+        width_label,
+        width_slider,
+        shadow_row,
+        auto_contrast_row,
+        fill_row
     ]
     .spacing(space_xs)
-    .align_x(cosmic::iced::core::Alignment::Start);
+    .width(Length::Fill);
 
-    // Shadow toggle
-    let shadow_row = row![
-        text::body(fl!("shadow")),
+    // Spotlight settings: dim strength, ellipse and blur toggles
+    let spotlight_label = text::body(fl!(
+        "spotlight-dim",
+        percent = (spotlight_dim * 100.0).round() as i32
+    ));
+    let spotlight_slider =
+        cosmic::widget::slider(0.1..=0.9, spotlight_dim, move |v| on_set_spotlight_dim(v))
+            .step(0.05)
+            .on_release(on_save_spotlight_dim)
+            .width(Length::Fill);
+    let spotlight_ellipse_row = row![
+        text::body(fl!("spotlight-ellipse")),
         cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
-        toggler(shadow_enabled)
-            .on_toggle(move |_| on_shadow_toggle.clone())
+        toggler(spotlight_ellipse)
+            .on_toggle(move |_| on_toggle_spotlight_ellipse.clone())
             .size(20.0),
     ]
     .spacing(space_s)
     .align_y(cosmic::iced::core::Alignment::Center)
     .width(Length::Fill);
+    let spotlight_blur_row = row![
+        text::body(fl!("spotlight-blur")),
+        cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
+        toggler(spotlight_blur)
+            .on_toggle(move |_| on_toggle_spotlight_blur.clone())
+            .size(20.0),
+    ]
+    .spacing(space_s)
+    .align_y(cosmic::iced::core::Alignment::Center)
+    .width(Length::Fill);
+
+    let spotlight_section = column![
+        spotlight_label,
+        spotlight_slider,
+        spotlight_ellipse_row,
+        spotlight_blur_row,
+    ]
+    .spacing(space_xs)
+    .width(Length::Fill);
 
     // Clear button (full width)
     let clear_button = button::custom(
@@ -633,7 +1059,11 @@ pub fn build_shape_popup<'a, Msg: Clone + 'static>(
         cosmic::widget::divider::horizontal::light(),
         color_section,
         cosmic::widget::divider::horizontal::light(),
-        shadow_row,
+        style_section,
+        cosmic::widget::divider::horizontal::light(),
+        preset_section,
+        cosmic::widget::divider::horizontal::light(),
+        spotlight_section,
         cosmic::widget::divider::horizontal::light(),
         clear_row,
     ]
@@ -660,16 +1090,24 @@ pub fn build_shape_popup<'a, Msg: Clone + 'static>(
         .into()
 }
 
-/// Build the redact/pixelate tool popup
+/// Build the redact/pixelate/blur tool popup
 #[allow(clippy::too_many_arguments)]
 pub fn build_redact_popup<'a, Msg: Clone + 'static>(
     current_tool: RedactTool,
     has_redactions: bool,
     pixelation_block_size: u32,
+    secure_pixelation: bool,
+    blur_radius: u32,
     on_select_redact: Msg,
     on_select_pixelate: Msg,
+    on_select_blur: Msg,
     on_set_pixelation_size: impl Fn(u32) -> Msg + 'a,
     on_save_pixelation_size: Msg,
+    on_toggle_secure_pixelation: Msg,
+    on_set_blur_radius: impl Fn(u32) -> Msg + 'a,
+    on_save_blur_radius: Msg,
+    on_redact_sensitive: Option<Msg>,
+    search_section: Option<Element<'a, Msg>>,
     on_clear: Msg,
     space_s: u16,
     space_xs: u16,
@@ -713,9 +1151,27 @@ pub fn build_redact_popup<'a, Msg: Clone + 'static>(
         tooltip::Position::Bottom,
     );
 
+    let btn_blur = tooltip(
+        button::custom(lucide::icon_with_opacity(
+            AppIcon::Blur,
+            icon_size,
+            1.0,
+            current_tool == RedactTool::Blur,
+        ))
+        .class(if current_tool == RedactTool::Blur {
+            cosmic::theme::Button::Suggested
+        } else {
+            cosmic::theme::Button::Icon
+        })
+        .on_press(on_select_blur)
+        .padding(space_xs),
+        text::body(fl!("gaussian-blur")),
+        tooltip::Position::Bottom,
+    );
+
     // Center the tool buttons in the popup
     let tool_buttons = container(
-        row![btn_redact, btn_pixelate]
+        row![btn_redact, btn_pixelate, btn_blur]
             .spacing(space_xs)
             .align_y(cosmic::iced::core::Alignment::Center),
     )
@@ -739,7 +1195,32 @@ pub fn build_redact_popup<'a, Msg: Clone + 'static>(
         .on_release(on_save_pixelation_size)
         .width(Length::Fill);
 
-    let pixelation_section = column![pixelation_label, pixelation_slider,]
+    // Secure pixelation toggle
+    let secure_row = row![
+        text::body(fl!("secure-pixelation")),
+        cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
+        toggler(secure_pixelation)
+            .on_toggle(move |_| on_toggle_secure_pixelation.clone())
+            .size(20.0),
+    ]
+    .spacing(space_s)
+    .align_y(cosmic::iced::core::Alignment::Center)
+    .width(Length::Fill);
+
+    let pixelation_section = column![pixelation_label, pixelation_slider, secure_row,]
+        .spacing(space_xs)
+        .width(Length::Fill);
+
+    // Blur radius slider
+    let blur_label = text::body(fl!("blur-radius", radius = blur_radius));
+    let blur_slider = cosmic::widget::slider(2..=50, blur_radius as i32, move |v| {
+        on_set_blur_radius(v as u32)
+    })
+    .step(2i32)
+    .on_release(on_save_blur_radius)
+    .width(Length::Fill);
+
+    let blur_section = column![blur_label, blur_slider,]
         .spacing(space_xs)
         .width(Length::Fill);
 
@@ -765,18 +1246,50 @@ pub fn build_redact_popup<'a, Msg: Clone + 'static>(
 
     let clear_row = container(clear_button).width(Length::Fill);
 
+    // Auto-redact button (needs OCR, so hidden without tesseract)
+    let sensitive_row = on_redact_sensitive.map(|on_redact_sensitive| {
+        button::custom(
+            container(
+                row![
+                    icon::Icon::from(icon::from_name("system-search-symbolic").size(16))
+                        .width(Length::Fixed(16.0))
+                        .height(Length::Fixed(16.0)),
+                    text::body(fl!("redact-sensitive")),
+                ]
+                .spacing(space_xs)
+                .align_y(cosmic::iced::core::Alignment::Center),
+            )
+            .width(Length::Fill)
+            .align_x(cosmic::iced::core::alignment::Horizontal::Center),
+        )
+        .class(cosmic::theme::Button::Standard)
+        .on_press(on_redact_sensitive)
+        .padding([space_xs, space_s])
+        .width(Length::Fill)
+    });
+
     // Assemble popup content
-    let popup_content = column![
+    let mut popup_content = column![
         tool_buttons,
         redact_subtitle,
         cosmic::widget::divider::horizontal::light(),
         pixelation_section,
+        blur_section,
         cosmic::widget::divider::horizontal::light(),
-        clear_row,
-    ]
-    .spacing(space_s)
-    .padding(space_s)
-    .width(Length::Fixed(230.0)); // Same width as shapes popup
+    ];
+    if let Some(search_section) = search_section {
+        popup_content = popup_content
+            .push(search_section)
+            .push(cosmic::widget::divider::horizontal::light());
+    }
+    if let Some(sensitive_row) = sensitive_row {
+        popup_content = popup_content.push(sensitive_row);
+    }
+    let popup_content = popup_content
+        .push(clear_row)
+        .spacing(space_s)
+        .padding(space_s)
+        .width(Length::Fixed(230.0)); // Same width as shapes popup
 
     container(popup_content)
         .class(cosmic::theme::Container::Custom(Box::new(|theme| {
@@ -797,13 +1310,88 @@ pub fn build_redact_popup<'a, Msg: Clone + 'static>(
         .into()
 }
 
-/// Build the magnifier tool popup (magnification slider + clear)
+/// Build the find-and-redact section (search field, regex toggle, bulk actions)
+#[allow(clippy::too_many_arguments)]
+pub fn build_text_search_section<'a, Msg: Clone + 'static>(
+    query: &'a str,
+    regex: bool,
+    match_count: usize,
+    invalid: bool,
+    on_query: impl Fn(String) -> Msg + 'a,
+    on_submit: Msg,
+    on_toggle_regex: Msg,
+    on_redact: Msg,
+    on_pixelate: Msg,
+    on_highlight: Msg,
+    space_s: u16,
+    space_xs: u16,
+) -> Element<'a, Msg> {
+    let search_input = cosmic::widget::text_input(fl!("find-text"), query)
+        .on_input(on_query)
+        .on_submit(move |_| on_submit.clone())
+        .width(Length::Fill);
+
+    let regex_row = row![
+        text::body(fl!("search-regex")),
+        cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
+        toggler(regex)
+            .on_toggle(move |_| on_toggle_regex.clone())
+            .size(20.0),
+    ]
+    .spacing(space_s)
+    .align_y(cosmic::iced::core::Alignment::Center)
+    .width(Length::Fill);
+
+    let status = if invalid {
+        fl!("search-invalid")
+    } else {
+        fl!("search-matches", count = match_count)
+    };
+    let status_text = text::caption(status).class(cosmic::theme::Text::Color(
+        cosmic::iced::Color::from_rgba(0.6, 0.6, 0.6, 1.0),
+    ));
+
+    // Bulk actions, enabled once there is something to act on
+    let has_matches = match_count > 0;
+    let action_button = |app_icon: AppIcon, tip: String, msg: Msg| {
+        tooltip(
+            button::custom(lucide::icon(app_icon, 20.0))
+                .class(cosmic::theme::Button::Icon)
+                .on_press_maybe(has_matches.then_some(msg))
+                .padding(space_xs),
+            text::body(tip),
+            tooltip::Position::Bottom,
+        )
+    };
+    let actions = row![
+        status_text,
+        cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
+        action_button(AppIcon::Redact, fl!("search-redact-all"), on_redact),
+        action_button(AppIcon::Pixelate, fl!("search-pixelate-all"), on_pixelate),
+        action_button(AppIcon::Square, fl!("search-highlight-all"), on_highlight),
+    ]
+    .spacing(space_xs)
+    .align_y(cosmic::iced::core::Alignment::Center)
+    .width(Length::Fill);
+
+    column![search_input, regex_row, actions]
+        .spacing(space_xs)
+        .width(Length::Fill)
+        .into()
+}
+
+/// Build the magnifier tool popup (magnification slider, callout settings + clear)
 #[allow(clippy::too_many_arguments)]
 pub fn build_magnifier_popup<'a, Msg: Clone + 'static>(
     magnification: f32,
+    callout: bool,
+    callout_border: f32,
     has_magnifiers: bool,
     on_set_magnification: impl Fn(f32) -> Msg + 'a,
     on_save_magnification: Msg,
+    on_toggle_callout: Msg,
+    on_set_callout_border: impl Fn(f32) -> Msg + 'a,
+    on_save_callout_border: Msg,
     on_clear: Msg,
     space_s: u16,
     space_xs: u16,
@@ -820,6 +1408,30 @@ pub fn build_magnifier_popup<'a, Msg: Clone + 'static>(
         .spacing(space_xs)
         .width(Length::Fill);
 
+    // Callout toggle: rectangular enlarged copy placed beside the source
+    let callout_row = row![
+        text::body(fl!("magnifier-callout")),
+        cosmic::iced::widget::space().width(cosmic::iced::Length::Fill),
+        toggler(callout)
+            .on_toggle(move |_| on_toggle_callout.clone())
+            .size(20.0),
+    ]
+    .spacing(space_s)
+    .align_y(cosmic::iced::core::Alignment::Center)
+    .width(Length::Fill);
+
+    let mut callout_section = column![callout_row].spacing(space_xs).width(Length::Fill);
+    if callout {
+        let border_label = text::body(fl!("callout-border", width = callout_border.round() as i32));
+        let border_slider = cosmic::widget::slider(1.0..=10.0, callout_border, move |v| {
+            on_set_callout_border(v)
+        })
+        .step(1.0)
+        .on_release(on_save_callout_border)
+        .width(Length::Fill);
+        callout_section = callout_section.push(border_label).push(border_slider);
+    }
+
     // Clear button (full width)
     let clear_button = button::custom(
         container(
@@ -844,6 +1456,7 @@ pub fn build_magnifier_popup<'a, Msg: Clone + 'static>(
 
     let popup_content = column![
         magnification_section,
+        callout_section,
         cosmic::widget::divider::horizontal::light(),
         clear_row,
     ]
@@ -870,96 +1483,290 @@ pub fn build_magnifier_popup<'a, Msg: Clone + 'static>(
         .into()
 }
 
-/// Build the pencil settings popup element for recording annotations
+/// Build the stamp popup: a thumbnail grid of the stamp library, the size of
+/// clicked stamps and buttons to manage the stamp folder
 #[allow(clippy::too_many_arguments)]
-pub fn build_pencil_popup<'a, Msg: Clone + 'static>(
-    current_color: ShapeColor,
-    fade_duration: f32,
-    thickness: f32,
+pub fn build_stamp_popup<'a, Msg: Clone + 'static>(
+    stamps: &[Stamp],
+    selected: Option<&str>,
+    size: f32,
     has_annotations: bool,
-    on_color_change: &(impl Fn(ShapeColor) -> Msg + 'a),
-    on_duration_change: impl Fn(f32) -> Msg + 'a,
-    on_duration_save: Msg,
-    on_thickness_change: impl Fn(f32) -> Msg + 'a,
-    on_thickness_save: Msg,
+    on_select: impl Fn(String) -> Msg + 'a,
+    on_set_size: impl Fn(f32) -> Msg + 'a,
+    on_save_size: Msg,
+    on_open_folder: Msg,
+    on_reload: Msg,
     on_clear: Msg,
     space_s: u16,
     space_xs: u16,
 ) -> Element<'a, Msg> {
-    // Color picker - 2 rows of 4 color swatches each
-    let make_color_swatch = |color: &ShapeColor, name: String| {
-        let is_selected = (color.r - current_color.r).abs() < 0.05
-            && (color.g - current_color.g).abs() < 0.05
-            && (color.b - current_color.b).abs() < 0.05;
-        let color_val = *color;
-        let iced_color: cosmic::iced::Color = color_val.into();
-
-        tooltip(
-            button::custom(
-                container(cosmic::iced::widget::space().width(Length::Fixed(0.0)))
-                    .width(Length::Fixed(24.0))
-                    .height(Length::Fixed(24.0))
-                    .class(cosmic::theme::Container::Custom(Box::new(move |_theme| {
-                        cosmic::iced::widget::container::Style {
-                            background: Some(Background::Color(iced_color)),
-                            border: Border {
-                                radius: 4.0.into(),
-                                width: if is_selected { 2.0 } else { 1.0 },
-                                color: if is_selected {
-                                    cosmic::iced::Color::WHITE
-                                } else {
-                                    cosmic::iced::Color::from_rgba(0.5, 0.5, 0.5, 0.5)
-                                },
-                            },
-                            ..Default::default()
-                        }
-                    }))),
+    let thumbnails: Vec<Element<'a, Msg>> = stamps
+        .iter()
+        .map(|stamp| {
+            let is_selected = selected == Some(stamp.name.as_str());
+            let thumbnail = button::custom(
+                cosmic::widget::image(stamp.handle.clone())
+                    .width(Length::Fixed(36.0))
+                    .height(Length::Fixed(36.0))
+                    .content_fit(cosmic::iced::ContentFit::Contain),
             )
-            .class(cosmic::theme::Button::Text)
-            .on_press(on_color_change(color_val))
-            .padding(2),
-            text::body(name),
-            tooltip::Position::Bottom,
-        )
-    };
+            .class(if is_selected {
+                cosmic::theme::Button::Suggested
+            } else {
+                cosmic::theme::Button::Icon
+            })
+            .on_press(on_select(stamp.name.clone()))
+            .padding(4);
+            tooltip(
+                thumbnail,
+                text::body(stamp.name.clone()),
+                tooltip::Position::Top,
+            )
+            .into()
+        })
+        .collect();
 
-    // First row: Red, Green, Blue, Yellow
-    let color_row1 = row![
-        make_color_swatch(&COLOR_PRESETS[0], color_name(0)),
-        make_color_swatch(&COLOR_PRESETS[1], color_name(1)),
-        make_color_swatch(&COLOR_PRESETS[2], color_name(2)),
-        make_color_swatch(&COLOR_PRESETS[3], color_name(3)),
+    let library_section = column![
+        swatch_rows(thumbnails, space_xs),
+        text::caption(fl!("stamp-folder-hint")),
     ]
     .spacing(space_xs)
-    .align_y(cosmic::iced::core::Alignment::Center);
-
-    // Second row: Orange, Purple, White, Black
-    let color_row2 = row![
-        make_color_swatch(&COLOR_PRESETS[4], color_name(4)),
-        make_color_swatch(&COLOR_PRESETS[5], color_name(5)),
-        make_color_swatch(&COLOR_PRESETS[6], color_name(6)),
-        make_color_swatch(&COLOR_PRESETS[7], color_name(7)),
+    .width(Length::Fill);
+
+    // Size slider - updates during drag, saves on release; dragging out a stamp
+    // on the screenshot sizes it directly
+    let size_label = text::body(fl!("stamp-size", size = (size.round() as u32)));
+    let size_slider = cosmic::widget::slider(STAMP_MIN_SIZE..=STAMP_MAX_SIZE, size, move |v| {
+        on_set_size(v)
+    })
+    .step(4.0)
+    .on_release(on_save_size)
+    .width(Length::Fill);
+
+    let size_section = column![size_label, size_slider]
+        .spacing(space_xs)
+        .width(Length::Fill);
+
+    let folder_button = |icon_name: &'static str, label: String, on_press: Msg| {
+        button::custom(
+            container(
+                row![
+                    icon::Icon::from(icon::from_name(icon_name).size(16))
+                        .width(Length::Fixed(16.0))
+                        .height(Length::Fixed(16.0)),
+                    text::body(label),
+                ]
+                .spacing(space_xs)
+                .align_y(cosmic::iced::core::Alignment::Center),
+            )
+            .width(Length::Fill)
+            .align_x(cosmic::iced::core::alignment::Horizontal::Center),
+        )
+        .class(cosmic::theme::Button::Standard)
+        .on_press(on_press)
+        .padding([space_xs, space_s])
+        .width(Length::Fill)
+    };
+    let folder_row = row![
+        folder_button(
+            "folder-open-symbolic",
+            fl!("stamp-open-folder"),
+            on_open_folder
+        ),
+        folder_button("view-refresh-symbolic", fl!("stamp-reload"), on_reload),
     ]
     .spacing(space_xs)
-    .align_y(cosmic::iced::core::Alignment::Center);
+    .width(Length::Fill);
 
-    // Center the color rows
-    let color_row1_centered = container(color_row1)
+    // Clear button (full width)
+    let clear_button = button::custom(
+        container(
+            row![
+                icon::Icon::from(icon::from_name("edit-delete-symbolic").size(16))
+                    .width(Length::Fixed(16.0))
+                    .height(Length::Fixed(16.0)),
+                text::body(fl!("clear-annotations")),
+            ]
+            .spacing(space_xs)
+            .align_y(cosmic::iced::core::Alignment::Center),
+        )
         .width(Length::Fill)
-        .align_x(cosmic::iced::core::alignment::Horizontal::Center);
+        .align_x(cosmic::iced::core::alignment::Horizontal::Center),
+    )
+    .class(cosmic::theme::Button::Destructive)
+    .on_press_maybe(has_annotations.then_some(on_clear))
+    .padding([space_xs, space_s])
+    .width(Length::Fill);
 
-    let color_row2_centered = container(color_row2)
-        .width(Length::Fill)
-        .align_x(cosmic::iced::core::alignment::Horizontal::Center);
+    let clear_row = container(clear_button).width(Length::Fill);
 
-    let color_section = column![
-        text::body(fl!("color")),
-        color_row1_centered,
-        color_row2_centered
+    let popup_content = column![
+        library_section,
+        size_section,
+        folder_row,
+        cosmic::widget::divider::horizontal::light(),
+        clear_row,
+    ]
+    .spacing(space_s)
+    .padding(space_s)
+    .width(Length::Fixed(230.0));
+
+    container(popup_content)
+        .class(cosmic::theme::Container::Custom(Box::new(|theme| {
+            let cosmic_theme = theme.cosmic();
+            cosmic::iced::widget::container::Style {
+                background: Some(Background::Color(
+                    cosmic_theme.background.component.base.into(),
+                )),
+                text_color: Some(cosmic_theme.background.component.on.into()),
+                border: Border {
+                    radius: cosmic_theme.corner_radii.radius_s.into(),
+                    width: 1.0,
+                    color: cosmic::iced::Color::from_rgba(0.5, 0.5, 0.5, 0.3),
+                },
+                ..Default::default()
+            }
+        })))
+        .into()
+}
+
+/// Build toggle chips for the installed OCR languages, highlighting `selected`
+pub fn build_ocr_language_chips<'a, Msg: Clone + 'static>(
+    installed: &[String],
+    selected: &[String],
+    on_toggle: impl Fn(String) -> Msg + 'a,
+    space_xs: u16,
+) -> Element<'a, Msg> {
+    if installed.is_empty() {
+        return text::caption(fl!("ocr-no-languages")).into();
+    }
+    let chips: Vec<Element<'a, Msg>> = installed
+        .iter()
+        .map(|lang| {
+            button::custom(text::body(lang.clone()))
+                .class(if selected.contains(lang) {
+                    cosmic::theme::Button::Suggested
+                } else {
+                    cosmic::theme::Button::Standard
+                })
+                .on_press(on_toggle(lang.clone()))
+                .padding([2, space_xs])
+                .into()
+        })
+        .collect();
+    cosmic::widget::flex_row(chips)
+        .row_spacing(space_xs)
+        .column_spacing(space_xs)
+        .into()
+}
+
+/// Build the OCR popup: languages for this capture only, and a button to run OCR
+#[allow(clippy::too_many_arguments)]
+pub fn build_ocr_popup<'a, Msg: Clone + 'static>(
+    installed: &[String],
+    selected: &[String],
+    is_override: bool,
+    on_toggle: impl Fn(String) -> Msg + 'a,
+    on_reset: Msg,
+    on_recognize: Msg,
+    space_s: u16,
+    space_xs: u16,
+) -> Element<'a, Msg> {
+    let language_section = column![
+        text::body(fl!("ocr-capture-languages")),
+        build_ocr_language_chips(installed, selected, on_toggle, space_xs),
     ]
     .spacing(space_xs)
-    .align_x(cosmic::iced::core::Alignment::Start);
+    .width(Length::Fill);
+
+    let reset_button = button::standard(fl!("ocr-languages-default"))
+        .on_press_maybe(is_override.then_some(on_reset))
+        .width(Length::Fill);
+    let recognize_button = button::suggested(fl!("recognize-text"))
+        .on_press(on_recognize)
+        .width(Length::Fill);
+
+    let popup_content = column![language_section, reset_button, recognize_button]
+        .spacing(space_s)
+        .padding(space_s)
+        .width(Length::Fixed(230.0));
+
+    container(popup_content)
+        .class(cosmic::theme::Container::Custom(Box::new(|theme| {
+            let cosmic_theme = theme.cosmic();
+            cosmic::iced::widget::container::Style {
+                background: Some(Background::Color(
+                    cosmic_theme.background.component.base.into(),
+                )),
+                text_color: Some(cosmic_theme.background.component.on.into()),
+                border: Border {
+                    radius: cosmic_theme.corner_radii.radius_s.into(),
+                    width: 1.0,
+                    color: cosmic::iced::Color::from_rgba(0.5, 0.5, 0.5, 0.3),
+                },
+                ..Default::default()
+            }
+        })))
+        .into()
+}
+
+/// Build the popup offering layouts to copy recognized text with
+pub fn build_ocr_copy_popup<'a, Msg: Clone + 'static>(
+    on_copy: impl Fn(OcrLayout) -> Msg + 'a,
+    space_s: u16,
+) -> Element<'a, Msg> {
+    let layouts = [
+        (OcrLayout::Paragraphs, fl!("ocr-layout-paragraphs")),
+        (OcrLayout::Code, fl!("ocr-layout-code")),
+        (OcrLayout::Tsv, fl!("ocr-layout-tsv")),
+        (OcrLayout::Markdown, fl!("ocr-layout-markdown")),
+    ];
+    let mut popup_content = column![text::body(fl!("ocr-copy-as"))]
+        .spacing(space_s)
+        .padding(space_s)
+        .width(Length::Fixed(230.0));
+    for (layout, label) in layouts {
+        popup_content = popup_content.push(
+            button::standard(label)
+                .on_press(on_copy(layout))
+                .width(Length::Fill),
+        );
+    }
+
+    container(popup_content)
+        .class(cosmic::theme::Container::Custom(Box::new(|theme| {
+            let cosmic_theme = theme.cosmic();
+            cosmic::iced::widget::container::Style {
+                background: Some(Background::Color(
+                    cosmic_theme.background.component.base.into(),
+                )),
+                text_color: Some(cosmic_theme.background.component.on.into()),
+                border: Border {
+                    radius: cosmic_theme.corner_radii.radius_s.into(),
+                    width: 1.0,
+                    color: cosmic::iced::Color::from_rgba(0.5, 0.5, 0.5, 0.3),
+                },
+                ..Default::default()
+            }
+        })))
+        .into()
+}
 
+/// Build the pencil settings popup element for recording annotations
+#[allow(clippy::too_many_arguments)]
+pub fn build_pencil_popup<'a, Msg: Clone + 'static>(
+    color_section: Element<'a, Msg>,
+    fade_duration: f32,
+    thickness: f32,
+    has_annotations: bool,
+    on_duration_change: impl Fn(f32) -> Msg + 'a,
+    on_duration_save: Msg,
+    on_thickness_change: impl Fn(f32) -> Msg + 'a,
+    on_thickness_save: Msg,
+    on_clear: Msg,
+    space_s: u16,
+    space_xs: u16,
+) -> Element<'a, Msg> {
     // Thickness slider (1-10 pixels) - updates during drag, saves on release
     let thickness_label = text::body(fl!("thickness", size = (thickness as u32)));
     let thickness_slider =
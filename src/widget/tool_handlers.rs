@@ -2,9 +2,16 @@
 //!
 //! Handles ToolMsg for popup state, tool selection, colors, and config persistence.
 
-use crate::config::{RedactTool, ShapeTool, SnapPeaConfig};
+use crate::config::{
+    AnnotationPreset, ColorPalette, PresetTool, RedactTool, ShapeColor, ShapeTool, SnapPeaConfig,
+};
+use crate::domain::color::{hsv_to_rgb, remember_color};
+use crate::domain::{STAMP_MAX_SIZE, STAMP_MIN_SIZE};
+use crate::fl;
+use crate::render::geometry::shape;
+use crate::render::stamp::stamp_dir;
 use crate::screenshot::Args;
-use crate::session::messages::{ToolMsg, ToolPopupAction};
+use crate::session::messages::{ColorEdit, ColorTarget, PresetEdit, ToolMsg, ToolPopupAction};
 
 /// Handle a ToolMsg, modifying Args state
 ///
@@ -28,13 +35,44 @@ pub fn handle_tool_msg(args: &mut Args, msg: ToolMsg) -> bool {
             false
         }
         ToolMsg::SetShapeColor(color) => {
-            args.ui.shape_color = color;
+            set_tool_color(args, ColorTarget::Shape, color);
             true // needs config save
         }
         ToolMsg::ToggleShapeShadow => {
             args.ui.shape_shadow = !args.ui.shape_shadow;
             true // needs config save
         }
+        ToolMsg::SetShapeWidth(width) => {
+            args.ui.shape_width = width.clamp(shape::MIN_WIDTH, shape::MAX_WIDTH);
+            false // saved on release, not during drag
+        }
+        ToolMsg::SaveShapeWidth => {
+            true // needs config save
+        }
+        ToolMsg::ToggleShapeAutoContrast => {
+            args.ui.shape_auto_contrast = !args.ui.shape_auto_contrast;
+            true // needs config save
+        }
+        ToolMsg::ToggleShapeFill => {
+            args.ui.shape_fill = !args.ui.shape_fill;
+            true // needs config save
+        }
+        ToolMsg::EditPreset(edit) => handle_preset_edit(args, edit),
+        ToolMsg::SetSpotlightDim(dim) => {
+            args.ui.spotlight_dim = dim;
+            false // saved on release, not during drag
+        }
+        ToolMsg::SaveSpotlightDim => {
+            true // needs config save
+        }
+        ToolMsg::ToggleSpotlightEllipse => {
+            args.ui.spotlight_ellipse = !args.ui.spotlight_ellipse;
+            true // needs config save
+        }
+        ToolMsg::ToggleSpotlightBlur => {
+            args.ui.spotlight_blur = !args.ui.spotlight_blur;
+            true // needs config save
+        }
         ToolMsg::SetRedactTool(tool) => {
             set_primary_redact_tool(args, tool);
             true // needs config save
@@ -58,6 +96,17 @@ pub fn handle_tool_msg(args: &mut Args, msg: ToolMsg) -> bool {
         ToolMsg::SavePixelationBlockSize => {
             true // needs config save
         }
+        ToolMsg::ToggleSecurePixelation => {
+            args.ui.secure_pixelation = !args.ui.secure_pixelation;
+            true // needs config save
+        }
+        ToolMsg::SetBlurRadius(radius) => {
+            args.ui.blur_radius = radius;
+            false // saved on release, not during drag
+        }
+        ToolMsg::SaveBlurRadius => {
+            true // needs config save
+        }
         ToolMsg::MagnifierModeToggle => {
             handle_magnifier_mode_toggle(args);
             false
@@ -78,12 +127,93 @@ pub fn handle_tool_msg(args: &mut Args, msg: ToolMsg) -> bool {
         ToolMsg::SaveMagnification => {
             true // needs config save
         }
+        ToolMsg::ToggleMagnifierCallout => {
+            args.ui.magnifier_callout = !args.ui.magnifier_callout;
+            true // needs config save
+        }
+        ToolMsg::SetCalloutBorder(width) => {
+            args.ui.magnifier_callout_border = width;
+            if args.annotations.selected_magnifier.is_some() {
+                args.annotations
+                    .edit_selected_magnifier(|m| m.callout_border = width);
+            }
+            false // saved on release, not during drag
+        }
+        ToolMsg::SaveCalloutBorder => {
+            true // needs config save
+        }
+        ToolMsg::ColorPickerModeToggle => {
+            args.annotations.color_picker_mode = !args.annotations.color_picker_mode;
+            if args.annotations.color_picker_mode {
+                disable_other_modes_except(args, Mode::ColorPicker);
+            }
+            args.close_all_popups();
+            false
+        }
+        ToolMsg::CycleColorFormat => {
+            args.ui.color_picker_format = args.ui.color_picker_format.next();
+            true // needs config save
+        }
+        ToolMsg::MeasureModeToggle => {
+            args.annotations.measure_mode = !args.annotations.measure_mode;
+            if args.annotations.measure_mode {
+                disable_other_modes_except(args, Mode::Measure);
+            } else {
+                args.annotations.measure_drawing = None;
+            }
+            args.close_all_popups();
+            false
+        }
+        ToolMsg::CycleMeasureKind => {
+            args.ui.measure_kind = args.ui.measure_kind.next();
+            args.annotations.measure_drawing = None;
+            true // needs config save
+        }
+        ToolMsg::StampModeToggle => {
+            handle_stamp_mode_toggle(args);
+            false
+        }
+        ToolMsg::StampPopup(action) => {
+            handle_stamp_popup(args, action);
+            false
+        }
+        ToolMsg::SelectStamp(name) => {
+            args.ui.stamp_name = name;
+            true // needs config save
+        }
+        ToolMsg::SetStampSize(size) => {
+            args.ui.stamp_size = size.clamp(STAMP_MIN_SIZE, STAMP_MAX_SIZE);
+            false // saved on release, not during drag
+        }
+        ToolMsg::SaveStampSize => {
+            true // needs config save
+        }
+        ToolMsg::OpenStampFolder => {
+            open_stamp_folder();
+            false
+        }
+        ToolMsg::ReloadStamps => {
+            args.ui.stamp_library.clear();
+            args.ui.ensure_stamps_loaded();
+            false
+        }
+        ToolMsg::OcrPopup(action) => {
+            handle_ocr_popup(args, action);
+            false
+        }
+        ToolMsg::ColorPicked(rgb) => {
+            // Copying to the clipboard is done in screenshot/mod.rs (needs a Task)
+            if args.ui.color_picker_sets_shape_color {
+                set_tool_color(args, ColorTarget::Shape, ShapeColor::from_rgb_u8(rgb));
+            }
+            args.ui.color_picker_sets_shape_color
+        }
         ToolMsg::PencilPopup(action) => {
             handle_pencil_popup(args, action);
             false
         }
         ToolMsg::SetPencilColor(color) => {
-            args.ui.pencil_color = color;
+            set_tool_color(args, ColorTarget::Pencil, color);
             true // needs config save
         }
         ToolMsg::SetPencilFadeDuration(duration) => {
@@ -104,6 +234,7 @@ pub fn handle_tool_msg(args: &mut Args, msg: ToolMsg) -> bool {
             // This is handled in screenshot/mod.rs because it needs access to app.recording_indicator
             false
         }
+        ToolMsg::EditColor(target, edit) => handle_color_edit(args, target, edit),
     }
 }
 
@@ -113,12 +244,29 @@ pub fn save_tool_config(args: &Args) {
     config.primary_shape_tool = args.ui.primary_shape_tool;
     config.shape_color = args.ui.shape_color;
     config.shape_shadow = args.ui.shape_shadow;
+    config.shape_width = args.ui.shape_width;
+    config.shape_fill = args.ui.shape_fill;
+    config.shape_auto_contrast = args.ui.shape_auto_contrast;
+    config.annotation_presets = args.ui.annotation_presets.clone();
+    config.spotlight_dim = args.ui.spotlight_dim;
+    config.spotlight_ellipse = args.ui.spotlight_ellipse;
+    config.spotlight_blur = args.ui.spotlight_blur;
     config.primary_redact_tool = args.ui.primary_redact_tool;
     config.pixelation_block_size = args.ui.pixelation_block_size;
+    config.secure_pixelation = args.ui.secure_pixelation;
+    config.blur_radius = args.ui.blur_radius;
     config.magnifier_magnification = args.ui.magnifier_magnification;
+    config.magnifier_callout = args.ui.magnifier_callout;
+    config.magnifier_callout_border = args.ui.magnifier_callout_border;
+    config.color_picker_format = args.ui.color_picker_format;
+    config.measure_kind = args.ui.measure_kind;
+    config.stamp_name = args.ui.stamp_name.clone();
+    config.stamp_size = args.ui.stamp_size;
     config.pencil_color = args.ui.pencil_color;
     config.pencil_fade_duration = args.ui.pencil_fade_duration;
     config.pencil_thickness = args.ui.pencil_thickness;
+    config.recent_colors = args.ui.recent_colors.clone();
+    config.color_palettes = args.ui.color_palettes.clone();
     config.save();
 }
 
@@ -152,6 +300,14 @@ fn handle_shape_mode_toggle(args: &mut Args) {
                 args.annotations.rect_outline_drawing = None;
             }
         }
+        ShapeTool::Spotlight => {
+            args.annotations.spotlight_mode = !args.annotations.spotlight_mode;
+            if args.annotations.spotlight_mode {
+                disable_other_modes_except(args, Mode::Spotlight);
+            } else {
+                args.annotations.spotlight_drawing = None;
+            }
+        }
     }
     // Close popups
     args.close_all_popups();
@@ -174,6 +330,10 @@ fn set_primary_shape_tool(args: &mut Args, tool: ShapeTool) {
             args.annotations.rect_outline_mode = true;
             disable_other_modes_except(args, Mode::Rectangle);
         }
+        ShapeTool::Spotlight => {
+            args.annotations.spotlight_mode = true;
+            disable_other_modes_except(args, Mode::Spotlight);
+        }
     }
     args.close_all_popups();
 }
@@ -189,6 +349,8 @@ fn handle_shape_popup(args: &mut Args, action: ToolPopupAction) {
             args.ui.shape_popup_open = !args.ui.shape_popup_open;
             if args.ui.shape_popup_open {
                 args.ui.redact_popup_open = false;
+                args.ui.stamp_popup_open = false;
+                args.ui.ocr_popup_open = false;
                 args.ui.settings_drawer_open = false;
                 args.disable_all_modes();
             } else {
@@ -199,6 +361,8 @@ fn handle_shape_popup(args: &mut Args, action: ToolPopupAction) {
         ToolPopupAction::Open => {
             args.ui.shape_popup_open = true;
             args.ui.redact_popup_open = false;
+            args.ui.stamp_popup_open = false;
+            args.ui.ocr_popup_open = false;
             args.ui.settings_drawer_open = false;
             args.disable_all_modes();
         }
@@ -226,6 +390,10 @@ fn set_primary_redact_tool(args: &mut Args, tool: RedactTool) {
             args.annotations.pixelate_mode = true;
             disable_other_modes_except(args, Mode::Pixelate);
         }
+        RedactTool::Blur => {
+            args.annotations.blur_mode = true;
+            disable_other_modes_except(args, Mode::Blur);
+        }
     }
     args.close_all_popups();
 }
@@ -241,6 +409,8 @@ fn handle_redact_popup(args: &mut Args, action: ToolPopupAction) {
             args.ui.redact_popup_open = !args.ui.redact_popup_open;
             if args.ui.redact_popup_open {
                 args.ui.shape_popup_open = false;
+                args.ui.stamp_popup_open = false;
+                args.ui.ocr_popup_open = false;
                 args.ui.settings_drawer_open = false;
                 args.disable_all_modes();
             } else {
@@ -251,6 +421,8 @@ fn handle_redact_popup(args: &mut Args, action: ToolPopupAction) {
         ToolPopupAction::Open => {
             args.ui.redact_popup_open = true;
             args.ui.shape_popup_open = false;
+            args.ui.stamp_popup_open = false;
+            args.ui.ocr_popup_open = false;
             args.ui.settings_drawer_open = false;
             args.disable_all_modes();
         }
@@ -280,6 +452,14 @@ fn handle_redact_mode_toggle(args: &mut Args) {
                 args.annotations.pixelate_drawing = None;
             }
         }
+        RedactTool::Blur => {
+            args.annotations.blur_mode = !args.annotations.blur_mode;
+            if args.annotations.blur_mode {
+                disable_other_modes_except(args, Mode::Blur);
+            } else {
+                args.annotations.blur_drawing = None;
+            }
+        }
     }
     // Close popups
     args.close_all_popups();
@@ -308,6 +488,8 @@ fn handle_magnifier_popup(args: &mut Args, action: ToolPopupAction) {
             if args.ui.magnifier_popup_open {
                 args.ui.shape_popup_open = false;
                 args.ui.redact_popup_open = false;
+                args.ui.stamp_popup_open = false;
+                args.ui.ocr_popup_open = false;
                 args.ui.settings_drawer_open = false;
                 // Show the selected magnifier's zoom in the slider, if any
                 if let Some(zoom) = args.annotations.selected_magnifier_zoom() {
@@ -324,6 +506,8 @@ fn handle_magnifier_popup(args: &mut Args, action: ToolPopupAction) {
             args.ui.magnifier_popup_open = true;
             args.ui.shape_popup_open = false;
             args.ui.redact_popup_open = false;
+            args.ui.stamp_popup_open = false;
+            args.ui.ocr_popup_open = false;
             args.ui.settings_drawer_open = false;
             if let Some(zoom) = args.annotations.selected_magnifier_zoom() {
                 args.ui.magnifier_magnification = zoom;
@@ -339,6 +523,224 @@ fn handle_magnifier_popup(args: &mut Args, action: ToolPopupAction) {
     }
 }
 
+// ============================================================================
+// Stamp tool handlers
+// ============================================================================
+
+fn handle_stamp_mode_toggle(args: &mut Args) {
+    args.annotations.stamp_mode = !args.annotations.stamp_mode;
+    if args.annotations.stamp_mode {
+        disable_other_modes_except(args, Mode::Stamp);
+        args.ui.ensure_stamps_loaded();
+    } else {
+        args.annotations.stamp_drawing = None;
+    }
+    args.close_all_popups();
+}
+
+fn handle_stamp_popup(args: &mut Args, action: ToolPopupAction) {
+    let open = match action {
+        ToolPopupAction::Toggle => !args.ui.stamp_popup_open,
+        ToolPopupAction::Open => true,
+        ToolPopupAction::Close => false,
+    };
+    if open {
+        args.close_all_popups();
+        args.ui.stamp_popup_open = true;
+        args.ui.ensure_stamps_loaded();
+        args.disable_all_modes();
+    } else {
+        args.ui.stamp_popup_open = false;
+        // Closing the popup leaves the stamp tool ready to place the selection
+        args.annotations.stamp_mode = true;
+        disable_other_modes_except(args, Mode::Stamp);
+    }
+}
+
+fn handle_ocr_popup(args: &mut Args, action: ToolPopupAction) {
+    let open = match action {
+        ToolPopupAction::Toggle => !args.ui.ocr_popup_open,
+        ToolPopupAction::Open => true,
+        ToolPopupAction::Close => false,
+    };
+    if open {
+        args.close_all_popups();
+        args.ui.ocr_popup_open = true;
+        args.ui.ensure_ocr_languages_loaded();
+        args.disable_all_modes();
+    } else {
+        args.ui.ocr_popup_open = false;
+    }
+}
+
+/// Show the stamp directory in the file manager, creating it first so users
+/// have somewhere to drop their files
+fn open_stamp_folder() {
+    let Some(dir) = stamp_dir() else {
+        log::error!("No data directory for stamps");
+        return;
+    };
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        log::error!("Failed to create stamp directory {}: {}", dir.display(), e);
+        return;
+    }
+    if let Err(e) = std::process::Command::new("xdg-open").arg(&dir).spawn() {
+        log::error!("Failed to open stamp directory: {}", e);
+    }
+}
+
+// ============================================================================
+// Custom color handlers (shared by the shape and pencil colors)
+// ============================================================================
+
+fn tool_color(args: &mut Args, target: ColorTarget) -> &mut ShapeColor {
+    match target {
+        ColorTarget::Shape => &mut args.ui.shape_color,
+        ColorTarget::Pencil => &mut args.ui.pencil_color,
+    }
+}
+
+/// Set a tool color from a swatch, the hex field or the eyedropper and remember it
+fn set_tool_color(args: &mut Args, target: ColorTarget, color: ShapeColor) {
+    *tool_color(args, target) = color;
+    remember_color(&mut args.ui.recent_colors, color);
+    args.ui.color_hex_input.clear();
+    args.ui.color_hsv = None;
+}
+
+/// Returns true if config needs saving
+fn handle_color_edit(args: &mut Args, target: ColorTarget, edit: ColorEdit) -> bool {
+    match edit {
+        ColorEdit::Hsv(hsv) => {
+            *tool_color(args, target) = hsv_to_rgb(hsv);
+            args.ui.color_hsv = Some(hsv);
+            args.ui.color_hex_input.clear();
+            false // saved on release, not during drag
+        }
+        ColorEdit::Commit => {
+            let color = *tool_color(args, target);
+            remember_color(&mut args.ui.recent_colors, color);
+            true
+        }
+        ColorEdit::HexInput(text) => {
+            args.ui.color_hex_input = text;
+            false
+        }
+        ColorEdit::AddToPalette(index) => {
+            let color = *tool_color(args, target);
+            match index.and_then(|i| args.ui.color_palettes.get_mut(i)) {
+                Some(palette) => {
+                    if !palette
+                        .colors
+                        .iter()
+                        .any(|c| c.to_rgba_u8() == color.to_rgba_u8())
+                    {
+                        palette.colors.push(color);
+                    }
+                }
+                None => {
+                    let number = args.ui.color_palettes.len() + 1;
+                    args.ui.color_palettes.push(ColorPalette {
+                        name: fl!("color-palette-name", number = number),
+                        colors: vec![color],
+                    });
+                }
+            }
+            true
+        }
+        ColorEdit::RemoveFromPalette(palette_index, color_index) => {
+            let Some(palette) = args.ui.color_palettes.get_mut(palette_index) else {
+                return false;
+            };
+            if color_index < palette.colors.len() {
+                palette.colors.remove(color_index);
+            }
+            // A palette without colors is gone
+            if palette.colors.is_empty() {
+                args.ui.color_palettes.remove(palette_index);
+            }
+            true
+        }
+        ColorEdit::RenamePalette(index, name) => {
+            if let Some(palette) = args.ui.color_palettes.get_mut(index) {
+                palette.name = name;
+                args.ui.unsaved_names = true;
+            }
+            false // saved on submit or when the popup closes
+        }
+        ColorEdit::CommitNames => std::mem::take(&mut args.ui.unsaved_names),
+    }
+}
+
+// ============================================================================
+// Annotation preset handlers
+// ============================================================================
+
+/// Returns true if config needs saving
+fn handle_preset_edit(args: &mut Args, edit: PresetEdit) -> bool {
+    match edit {
+        PresetEdit::Apply(index) => {
+            let Some(preset) = args.ui.annotation_presets.get(index).cloned() else {
+                return false;
+            };
+            set_tool_color(args, ColorTarget::Shape, preset.color);
+            args.ui.shape_width = preset.width.clamp(shape::MIN_WIDTH, shape::MAX_WIDTH);
+            args.ui.shape_shadow = preset.shadow;
+            args.ui.shape_auto_contrast = preset.auto_contrast;
+            args.ui.shape_fill = preset.fill;
+            match preset.tool {
+                PresetTool::Shape(tool) => set_primary_shape_tool(args, tool),
+                PresetTool::Redact(tool) => set_primary_redact_tool(args, tool),
+            }
+            true
+        }
+        PresetEdit::AddCurrent => {
+            let number = args.ui.annotation_presets.len() + 1;
+            // The redact tool in use, otherwise the current shape tool
+            let annotations = &args.annotations;
+            let redacting =
+                annotations.redact_mode || annotations.pixelate_mode || annotations.blur_mode;
+            let tool = if redacting {
+                PresetTool::Redact(args.ui.primary_redact_tool)
+            } else {
+                PresetTool::Shape(args.ui.primary_shape_tool)
+            };
+            // Only circles and rectangles are filled
+            let fill = args.ui.shape_fill
+                && matches!(
+                    tool,
+                    PresetTool::Shape(ShapeTool::Circle | ShapeTool::Rectangle)
+                );
+            args.ui.annotation_presets.push(AnnotationPreset {
+                name: fl!("preset-name", number = number),
+                tool,
+                color: args.ui.shape_color,
+                width: args.ui.shape_width,
+                shadow: args.ui.shape_shadow,
+                auto_contrast: args.ui.shape_auto_contrast,
+                fill,
+            });
+            true
+        }
+        PresetEdit::Rename(index, name) => {
+            if let Some(preset) = args.ui.annotation_presets.get_mut(index) {
+                preset.name = name;
+                args.ui.unsaved_names = true;
+            }
+            false // saved on submit or when the popup closes
+        }
+        PresetEdit::CommitNames => std::mem::take(&mut args.ui.unsaved_names),
+        PresetEdit::Remove(index) => {
+            if index < args.ui.annotation_presets.len() {
+                args.ui.annotation_presets.remove(index);
+                true
+            } else {
+                false
+            }
+        }
+    }
+}
+
 // ============================================================================
 // Pencil tool handlers (for recording annotations)
 // ============================================================================
@@ -375,8 +777,13 @@ enum Mode {
     Circle,
     Rectangle,
     Magnifier,
+    Spotlight,
+    Measure,
+    Stamp,
+    ColorPicker,
     Redact,
     Pixelate,
+    Blur,
 }
 
 fn disable_other_modes_except(args: &mut Args, keep: Mode) {
@@ -397,6 +804,21 @@ fn disable_other_modes_except(args: &mut Args, keep: Mode) {
         args.annotations.magnifier_drawing = None;
         args.annotations.selected_magnifier = None;
     }
+    if keep != Mode::Spotlight {
+        args.annotations.spotlight_mode = false;
+        args.annotations.spotlight_drawing = None;
+    }
+    if keep != Mode::Measure {
+        args.annotations.measure_mode = false;
+        args.annotations.measure_drawing = None;
+    }
+    if keep != Mode::Stamp {
+        args.annotations.stamp_mode = false;
+        args.annotations.stamp_drawing = None;
+    }
+    if keep != Mode::ColorPicker {
+        args.annotations.color_picker_mode = false;
+    }
     if keep != Mode::Redact {
         args.annotations.redact_mode = false;
         args.annotations.redact_drawing = None;
@@ -405,6 +827,10 @@ fn disable_other_modes_except(args: &mut Args, keep: Mode) {
         args.annotations.pixelate_mode = false;
         args.annotations.pixelate_drawing = None;
     }
+    if keep != Mode::Blur {
+        args.annotations.blur_mode = false;
+        args.annotations.blur_drawing = None;
+    }
     // Clear OCR/QR when switching modes
     args.detection.clear();
 }
@@ -133,7 +133,7 @@ use super::lucide::{self, AppIcon};
 use super::tool_button::{build_shape_button, build_tool_button, build_tool_button_with_icon};
 use super::toolbar_position_selector::ToolbarPositionSelector;
 use crate::capture::qr::DetectedQrCode;
-use crate::config::{RedactTool, ShapeTool, ToolbarPosition};
+use crate::config::{ColorFormat, MeasureKind, RedactTool, ShapeTool, ToolbarPosition};
 use crate::domain::{Choice, DragState, Rect};
 use crate::fl;
 
@@ -1191,6 +1191,13 @@ pub fn build_toolbar<'a, Msg: Clone + 'static>(
     redact_popup_open: bool,
     magnifier_mode_active: bool,
     magnifier_popup_open: bool,
+    color_picker_mode_active: bool,
+    color_picker_format: ColorFormat,
+    measure_mode_active: bool,
+    measure_kind: MeasureKind,
+    stamp_mode_active: bool,
+    stamp_popup_open: bool,
+    ocr_popup_open: bool,
     space_s: u16,
     space_xs: u16,
     space_xxs: u16,
@@ -1201,6 +1208,9 @@ pub fn build_toolbar<'a, Msg: Clone + 'static>(
     on_delayed_capture: Msg,
     on_cycle_capture_delay: Msg,
     capture_delay_secs: u32,
+    on_auto_trim: Msg,
+    on_toggle_crop_mode: Msg,
+    crop_mode: bool,
     on_record_region: Msg,
     on_stop_recording: Msg,
     on_toggle_recording_annotation: Msg,
@@ -1211,7 +1221,14 @@ pub fn build_toolbar<'a, Msg: Clone + 'static>(
     on_redact_right_click: Msg,
     on_magnifier_press: Msg,
     on_magnifier_right_click: Msg,
+    on_color_picker_press: Msg,
+    on_color_picker_right_click: Msg,
+    on_measure_press: Msg,
+    on_measure_right_click: Msg,
+    on_stamp_press: Msg,
+    on_stamp_right_click: Msg,
     on_ocr: Msg,
+    on_ocr_right_click: Option<Msg>,
     on_ocr_copy: Msg,
     on_qr: Msg,
     on_qr_copy: Msg,
@@ -1222,7 +1239,7 @@ pub fn build_toolbar<'a, Msg: Clone + 'static>(
     force_toolbar_opaque: bool,
     toolbar_unhovered_opacity: f32,
     output_count: usize,
-    tesseract_available: bool,
+    ocr_available: bool,
     is_video_mode: bool,
     is_recording: bool,
     recording_annotation_mode: bool,
@@ -1380,6 +1397,34 @@ pub fn build_toolbar<'a, Msg: Clone + 'static>(
         super::tool_button::RightClickWrapper::new(delay_btn, Some(on_cycle_capture_delay)).into()
     };
 
+    // Auto-trim button - left-click strips uniform margins, right-click toggles
+    // re-crop mode. Highlighted while re-cropping.
+    let btn_trim: Element<'_, Msg> = {
+        let trim_btn = tooltip(
+            button::custom(lucide::icon_with_opacity(
+                AppIcon::Crop,
+                34.0,
+                content_opacity,
+                crop_mode,
+            ))
+            .selected(crop_mode)
+            .class(if crop_mode {
+                suggested_button_class_with_opacity(content_opacity)
+            } else {
+                cosmic::theme::Button::Icon
+            })
+            .on_press(on_auto_trim)
+            .padding(space_xs),
+            text::body(if crop_mode {
+                fl!("crop-mode")
+            } else {
+                fl!("auto-trim")
+            }),
+            tooltip::Position::Bottom,
+        );
+        super::tool_button::RightClickWrapper::new(trim_btn, Some(on_toggle_crop_mode)).into()
+    };
+
     // Record button - enabled only when region is selected
     // Custom red circular button with themed border
     let record_icon = container(
@@ -1516,19 +1561,20 @@ pub fn build_toolbar<'a, Msg: Clone + 'static>(
         content_opacity,
     );
 
-    // Redact/Pixelate tool button (combined)
+    // Redact/Pixelate/Blur tool button (combined)
     let btn_redact = build_tool_button_with_icon(
         lucide::icon_with_opacity(
             match primary_redact_tool {
                 RedactTool::Redact => AppIcon::Redact,
                 RedactTool::Pixelate => AppIcon::Pixelate,
+                RedactTool::Blur => AppIcon::Blur,
             },
             34.0,
             content_opacity,
             redact_mode_active || redact_popup_open,
         ),
         primary_redact_tool.tooltip(),
-        2, // 2 options: Redact and Pixelate
+        3, // 3 options: Redact, Pixelate and Blur
         primary_redact_tool.index(),
         redact_mode_active,
         redact_popup_open,
@@ -1559,9 +1605,65 @@ pub fn build_toolbar<'a, Msg: Clone + 'static>(
         content_opacity,
     );
 
-    // OCR button
-    let btn_ocr = if has_ocr_text {
-        tooltip(
+    // Color picker button - right-click cycles the copied format (shown as dots)
+    let btn_color_picker = build_tool_button_with_icon(
+        lucide::icon_with_opacity(
+            AppIcon::ColorPicker,
+            34.0,
+            content_opacity,
+            color_picker_mode_active,
+        ),
+        fl!("color-picker", format = color_picker_format.label()),
+        3,
+        color_picker_format.index(),
+        color_picker_mode_active,
+        false,
+        has_selection,
+        has_selection.then_some(on_color_picker_press.clone()),
+        has_selection.then_some(on_color_picker_right_click.clone()),
+        space_xs,
+        content_opacity,
+    );
+
+    // Measure button - right-click switches between lines and boxes (shown as dots)
+    let btn_measure = build_tool_button_with_icon(
+        lucide::icon_with_opacity(AppIcon::Ruler, 34.0, content_opacity, measure_mode_active),
+        fl!("measure-tool"),
+        2,
+        measure_kind.index(),
+        measure_mode_active,
+        false,
+        has_selection,
+        has_selection.then_some(on_measure_press.clone()),
+        has_selection.then_some(on_measure_right_click.clone()),
+        space_xs,
+        content_opacity,
+    );
+
+    // Stamp tool button - right-click opens the stamp library
+    let btn_stamp = build_tool_button_with_icon(
+        lucide::icon_with_opacity(
+            AppIcon::Stamp,
+            34.0,
+            content_opacity,
+            stamp_mode_active || stamp_popup_open,
+        ),
+        fl!("stamp-tool"),
+        0, // single tool: no option-indicator dots
+        0,
+        stamp_mode_active,
+        stamp_popup_open,
+        has_selection,
+        has_selection.then_some(on_stamp_press.clone()),
+        has_selection.then_some(on_stamp_right_click.clone()),
+        space_xs,
+        content_opacity,
+    );
+
+    // OCR button - right-click picks the tesseract languages for this capture,
+    // or once text is recognized, a layout to copy it with
+    let btn_ocr: Element<'_, Msg> = if has_ocr_text {
+        let copy_btn = tooltip(
             button::custom(
                 icon_with_opacity("edit-copy-symbolic", 64, content_opacity)
                     .width(Length::Fixed(40.0))
@@ -1572,21 +1674,31 @@ pub fn build_toolbar<'a, Msg: Clone + 'static>(
             .padding(space_xs),
             text::body(fl!("copy-ocr-text")),
             tooltip::Position::Bottom,
+        );
+        super::tool_button::RightClickWrapper::new(
+            copy_btn,
+            on_ocr_right_click.filter(|_| has_selection),
         )
-    } else if tesseract_available {
-        tooltip(
+        .into()
+    } else if ocr_available {
+        let ocr_btn = tooltip(
             button::custom(lucide::icon_with_opacity(
                 AppIcon::Ocr,
                 34.0,
                 content_opacity,
-                false,
+                ocr_popup_open,
             ))
             .class(cosmic::theme::Button::Icon)
             .on_press_maybe(has_selection.then_some(on_ocr.clone()))
             .padding(space_xs),
             text::body(fl!("recognize-text")),
             tooltip::Position::Bottom,
+        );
+        super::tool_button::RightClickWrapper::new(
+            ocr_btn,
+            on_ocr_right_click.filter(|_| has_selection),
         )
+        .into()
     } else {
         tooltip(
             button::custom(lucide::icon_with_opacity(
@@ -1601,6 +1713,7 @@ pub fn build_toolbar<'a, Msg: Clone + 'static>(
             text::body(fl!("install-tesseract")),
             tooltip::Position::Bottom,
         )
+        .into()
     };
 
     // QR button
@@ -1709,14 +1822,23 @@ pub fn build_toolbar<'a, Msg: Clone + 'static>(
             .padding([space_s, space_xxs, space_s, space_xxs])
             .into()
         } else if has_selection {
-            let tool_buttons = column![btn_shapes, btn_redact, btn_magnifier, btn_ocr, btn_qr]
-                .spacing(space_s)
-                .align_x(cosmic::iced::core::Alignment::Center);
+            let tool_buttons = column![
+                btn_shapes,
+                btn_redact,
+                btn_magnifier,
+                btn_color_picker,
+                btn_measure,
+                btn_stamp,
+                btn_ocr,
+                btn_qr
+            ]
+            .spacing(space_s)
+            .align_x(cosmic::iced::core::Alignment::Center);
 
             column![
                 position_selector,
                 horizontal::light().width(Length::Fixed(64.0)),
-                column![btn_region, btn_screen]
+                column![btn_region, btn_screen, btn_trim]
                     .spacing(space_s)
                     .align_x(cosmic::iced::core::Alignment::Center),
                 horizontal::light().width(Length::Fixed(64.0)),
@@ -1795,14 +1917,23 @@ pub fn build_toolbar<'a, Msg: Clone + 'static>(
             .padding([space_xxs, space_s, space_xxs, space_s])
             .into()
         } else if has_selection {
-            let tool_buttons = row![btn_shapes, btn_redact, btn_magnifier, btn_ocr, btn_qr]
-                .spacing(space_s)
-                .align_y(cosmic::iced::core::Alignment::Center);
+            let tool_buttons = row![
+                btn_shapes,
+                btn_redact,
+                btn_magnifier,
+                btn_color_picker,
+                btn_measure,
+                btn_stamp,
+                btn_ocr,
+                btn_qr
+            ]
+            .spacing(space_s)
+            .align_y(cosmic::iced::core::Alignment::Center);
 
             row![
                 position_selector,
                 vertical::light().height(Length::Fixed(64.0)),
-                row![btn_region, btn_screen]
+                row![btn_region, btn_screen, btn_trim]
                     .spacing(space_s)
                     .align_y(cosmic::iced::core::Alignment::Center),
                 vertical::light().height(Length::Fixed(64.0)),